@@ -0,0 +1,323 @@
+use serde_json::{json, Value};
+
+use reqvire::error::ReqvireError;
+use reqvire::graph_registry::{ElementNode, GraphRegistry};
+use reqvire::lint;
+use reqvire::matrix_generator::{self, MatrixConfig, MatrixDefinition, MatrixFormat};
+use reqvire::model_json;
+use reqvire::patch;
+use reqvire::project_config::ProjectConfig;
+use reqvire::relation::LinkType;
+use reqvire::reports;
+use reqvire::verification_trace;
+
+/// Prefix of the read-only JSON API served next to the HTML export
+pub const API_PREFIX: &str = "/api/";
+
+/// Answers a request to the JSON API from the in-memory model.
+///
+/// Returns the HTTP status and the JSON body. Unknown routes and elements
+/// answer 404, invalid query parameters 400.
+pub fn respond(registry: &GraphRegistry, project_config: &ProjectConfig, url: &str) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let route = path.strip_prefix(API_PREFIX).unwrap_or(path).trim_end_matches('/');
+
+    let result = match route.split_once('/') {
+        None => match route {
+            "elements" => elements(registry, query),
+            "traces" => traces(registry, query),
+            "coverage" => coverage(registry, project_config, query),
+            "matrix" => matrix(registry, project_config, query),
+            "lint" => lint_report(registry, query),
+            _ => return not_found(&format!("Unknown API route '{}'", path)),
+        },
+        Some(("elements", id)) => element(registry, &percent_decode(id)),
+        Some(("impact", id)) => impact(registry, &percent_decode(id)),
+        _ => return not_found(&format!("Unknown API route '{}'", path)),
+    };
+
+    match result {
+        Ok(value) => (200, value),
+        Err(ApiError::NotFound(message)) => not_found(&message),
+        Err(ApiError::BadRequest(message)) => (400, json!({ "error": message })),
+    }
+}
+
+fn not_found(message: &str) -> (u16, Value) {
+    (404, json!({ "error": message }))
+}
+
+pub(crate) enum ApiError {
+    NotFound(String),
+    BadRequest(String),
+}
+
+impl From<ReqvireError> for ApiError {
+    fn from(error: ReqvireError) -> Self {
+        ApiError::BadRequest(error.to_string())
+    }
+}
+
+/// `/api/elements`: summary of the elements matching the summary filters
+fn elements(registry: &GraphRegistry, query: &str) -> Result<Value, ApiError> {
+    let query = Query::parse(query, &[
+        "filter_file",
+        "filter_name",
+        "filter_section",
+        "filter_type",
+        "filter_content",
+        "filter_is_not_verified",
+        "filter_is_not_satisfied",
+    ])?;
+    let filters = reports::Filters::new(
+        query.get("filter_file"),
+        query.get("filter_name"),
+        query.get("filter_section"),
+        query.get("filter_type"),
+        query.get("filter_content"),
+        query.flag("filter_is_not_verified"),
+        query.flag("filter_is_not_satisfied"),
+    )?;
+    Ok(reports::registry_summary_json(registry, &filters))
+}
+
+/// `/api/elements/{id}`: an element with its outgoing and incoming relations
+fn element(registry: &GraphRegistry, id: &str) -> Result<Value, ApiError> {
+    let identifier = patch::resolve_element(registry, id).map_err(|e| ApiError::NotFound(e.to_string()))?;
+    let element = registry.get_element(&identifier).expect("resolved elements exist");
+    let exported = model_json::export_element(element);
+
+    let mut incoming: Vec<Value> = registry
+        .get_all_elements()
+        .into_iter()
+        .flat_map(|source| {
+            source
+                .relations
+                .iter()
+                .filter(|relation| {
+                    relation.user_created
+                        && matches!(&relation.target.link, LinkType::Identifier(target) if *target == identifier)
+                })
+                .map(move |relation| {
+                    json!({
+                        "type": relation.relation_type.name,
+                        "source": source.identifier,
+                        "source_name": source.name
+                    })
+                })
+        })
+        .collect();
+    incoming.sort_by(|a, b| a["source"].as_str().cmp(&b["source"].as_str()).then(a["type"].as_str().cmp(&b["type"].as_str())));
+
+    Ok(json!({
+        "identifier": element.identifier,
+        "name": element.name,
+        "type": exported.element_type,
+        "file": element.file_path,
+        "section": element.section,
+        "content": exported.content,
+        "metadata": exported.metadata,
+        "outgoing": exported.relations,
+        "incoming": incoming
+    }))
+}
+
+/// `/api/impact/{id}`: the tree of elements affected by a change of an element
+fn impact(registry: &GraphRegistry, id: &str) -> Result<Value, ApiError> {
+    let identifier = patch::resolve_element(registry, id).map_err(|e| ApiError::NotFound(e.to_string()))?;
+    Ok(impact_tree_json(&registry.get_impact_tree(&identifier)))
+}
+
+/// `/api/traces`: verification traces, optionally filtered
+fn traces(registry: &GraphRegistry, query: &str) -> Result<Value, ApiError> {
+    let query = Query::parse(query, &["filter_id", "filter_name", "filter_type"])?;
+    let generator = verification_trace::VerificationTraceGenerator::new(registry, false, None);
+    let report = verification_trace::apply_filters(
+        generator.generate(),
+        query.get("filter_id"),
+        query.get("filter_name"),
+        query.get("filter_type"),
+    )?;
+    to_json(&report)
+}
+
+/// `/api/coverage`: verification coverage, or satisfaction coverage with `satisfaction`
+fn coverage(registry: &GraphRegistry, project_config: &ProjectConfig, query: &str) -> Result<Value, ApiError> {
+    let query = Query::parse(query, &["satisfaction"])?;
+    if query.flag("satisfaction") {
+        to_json(&reports::generate_satisfaction_coverage_report(registry))
+    } else {
+        to_json(&reports::generate_coverage_report(registry, &project_config.verification_rules))
+    }
+}
+
+/// `/api/matrix`: traceability matrix, selected like the `matrix` command options
+fn matrix(registry: &GraphRegistry, project_config: &ProjectConfig, query: &str) -> Result<Value, ApiError> {
+    let query = Query::parse(query, &[
+        "definition",
+        "satisfaction",
+        "source_type",
+        "target_type",
+        "relation",
+        "filter_file",
+        "filter_section",
+    ])?;
+    let config = if let Some(name) = query.get("definition") {
+        project_config
+            .matrix(name)
+            .ok_or_else(|| ApiError::NotFound(format!("Matrix definition '{}' not found", name)))?
+            .to_config(registry)?
+    } else if query.flag("satisfaction") {
+        MatrixConfig::satisfaction(registry)
+    } else {
+        MatrixDefinition {
+            source_types: query.all("source_type"),
+            target_types: query.all("target_type"),
+            relations: query.all("relation"),
+            ..Default::default()
+        }
+        .to_config(registry)?
+    };
+    let config = config.with_filters(query.get("filter_file"), query.get("filter_section"))?;
+    let matrix = matrix_generator::generate_matrix(registry, &config, MatrixFormat::Json);
+    serde_json::from_str(&matrix).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// `/api/lint`: lint issues, optionally only `fixable` or `auditable` ones
+fn lint_report(registry: &GraphRegistry, query: &str) -> Result<Value, ApiError> {
+    let query = Query::parse(query, &["fixable", "auditable"])?;
+    to_json(&lint::analyze_model(registry).filtered(query.flag("fixable"), query.flag("auditable")))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// Compact form of an impact tree with the relation that propagates the change
+pub fn impact_tree_json(node: &ElementNode) -> Value {
+    let relations: Vec<Value> = node
+        .relations
+        .iter()
+        .map(|relation| {
+            json!({
+                "relation": relation.relation_trigger,
+                "element": impact_tree_json(&relation.element_node)
+            })
+        })
+        .collect();
+    json!({
+        "identifier": node.element.identifier,
+        "name": node.element.name,
+        "type": node.element.element_type.as_str(),
+        "file": node.element.file_path,
+        "relations": relations
+    })
+}
+
+/// Decoded query parameters of a request
+pub(crate) struct Query {
+    parameters: Vec<(String, String)>,
+}
+
+impl Query {
+    /// Parses a query string, rejecting parameters the route does not accept
+    pub(crate) fn parse(query: &str, accepted: &[&str]) -> Result<Self, ApiError> {
+        let mut parameters = Vec::new();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let name = percent_decode(&name.replace('+', " "));
+            if !accepted.contains(&name.as_str()) {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown query parameter '{}', expected one of: {}",
+                    name,
+                    accepted.join(", ")
+                )));
+            }
+            parameters.push((name, percent_decode(&value.replace('+', " "))));
+        }
+        Ok(Self { parameters })
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&str> {
+        self.parameters.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// A flag is set when present without a value or with any value but `false` or `0`
+    fn flag(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|value| value != "false" && value != "0")
+    }
+
+    /// Values of a repeated parameter in order
+    pub(crate) fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.parameters.iter().filter(move |(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Values of a repeated parameter, also accepting comma separated lists
+    fn all(&self, name: &str) -> Vec<String> {
+        self.values(name)
+            .flat_map(|value| value.split(','))
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Decodes `%XX` escapes in a URL component
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode_escapes() {
+        assert_eq!(
+            percent_decode("specifications%2FRequirements.md%23remote-start"),
+            "specifications/Requirements.md#remote-start"
+        );
+        assert_eq!(percent_decode("a+b"), "a+b");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%E2%9C%85"), "✅");
+    }
+
+    #[test]
+    fn test_query_rejects_unknown_parameters() {
+        let query = Query::parse(
+            "relation=satisfiedBy,verifiedBy&relation=trace&fixable&filter_name=Remote+Start%2B",
+            &["relation", "fixable", "filter_name"],
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(query.all("relation"), vec!["satisfiedBy", "verifiedBy", "trace"]);
+        assert_eq!(query.get("filter_name"), Some("Remote Start+"));
+        assert!(query.flag("fixable"));
+        let query = Query::parse("fixable=false", &["fixable"]).ok().unwrap();
+        assert_eq!(query.get("fixable"), Some("false"));
+        assert!(!query.flag("fixable"));
+        assert!(matches!(Query::parse("filter=x", &["fixable"]), Err(ApiError::BadRequest(_))));
+    }
+}
@@ -4,17 +4,31 @@ use anyhow::Result;
 use log::{info};
 use serde::Serialize;
 use reqvire::error::ReqvireError;
-use reqvire::ModelManager;
+use reqvire::{ModelContext, ModelManager};
 use globset::GlobSet;
 use reqvire::reports;
+use reqvire::coverage_history;
+use reqvire::tabular::TableFormat;
 use reqvire::diagrams;
 use reqvire::export;
+use reqvire::reqif;
+use reqvire::import;
+use reqvire::reqif_import::{self, ReqifMapping};
+use reqvire::bulk_import::{self, BulkImportMapping};
+use reqvire::model_json;
+use reqvire::single_file;
+use reqvire::patch;
+use reqvire::source_map;
 use reqvire::change_impact;
 use reqvire::git_commands;
 use reqvire::matrix_generator;
 use reqvire::sections_summary;
 use reqvire::verification_trace;
 use crate::serve;
+use crate::lsp;
+use crate::mcp;
+use crate::watch;
+use crate::config::load_project_config;
 use reqvire::lint;
 use reqvire::GraphRegistry;
 use reqvire::graph_registry::{Page, Section};
@@ -44,13 +58,27 @@ pub enum Commands {
     /// Export model to browsable HTML documentation with complete traceability
     
     Export {
-        /// Output directory for HTML files
-        #[clap(long, short = 'o', default_value = "html", help_heading = "EXPORT OPTIONS")]
-        output: String,
+        /// Output directory for HTML files, the .reqifz/.reqif file for ReqIF, or the .json file (- for stdout) for JSON
+        #[clap(long, short = 'o', help_heading = "EXPORT OPTIONS")]
+        output: Option<String>,
+
+        /// Export format: html, reqif or json
+        #[clap(long, default_value = "html", value_parser = ["html", "reqif", "json"], help_heading = "EXPORT OPTIONS")]
+        format: String,
+
+        /// Write the HTML export as one self-contained document to this file
+        #[clap(long, value_name = "FILE", conflicts_with = "output", help_heading = "EXPORT OPTIONS")]
+        single_file: Option<String>,
+    },
+
+    /// Import requirements from other tools into structured markdown files
+    Import {
+        #[clap(subcommand)]
+        source: ImportSource,
     },
 
     /// Serve model as browsable HTML documentation via HTTP server
-    #[clap(override_help = "Serve model as browsable HTML documentation via HTTP server\n\nSERVE OPTIONS:\n      --host <HOST>          Bind address (default: localhost)\n      --port <PORT>          Server port (default: 8080)")]
+    #[clap(override_help = "Serve model as browsable HTML documentation via HTTP server\n\nRead-only JSON endpoints for elements, impact, traces, coverage, matrix and\nlint are served under /api/ next to the pages.\n\nModels at other git refs are served on demand under /ref/<commit>/ and picked\nwith the ref switcher on each page; /compare shows the change impact between refs.\n\nSERVE OPTIONS:\n      --host <HOST>          Bind address (default: localhost)\n      --port <PORT>          Server port (default: 8080)\n      --watch                Rebuild on model changes and reload open pages\n      --ref <REF>            Serve the model at a git branch, tag or commit\n      --edit                 Edit elements through forms in the served pages (implies --watch)")]
     Serve {
         /// Bind address
         #[clap(long, default_value = "localhost", help_heading = "SERVE OPTIONS")]
@@ -59,6 +87,34 @@ pub enum Commands {
         /// Server port
         #[clap(long, default_value = "8080", help_heading = "SERVE OPTIONS")]
         port: u16,
+
+        /// Rebuild on model changes and reload open pages
+        #[clap(long, help_heading = "SERVE OPTIONS")]
+        watch: bool,
+
+        /// Serve the model at a git branch, tag or commit
+        #[clap(long = "ref", value_name = "REF", conflicts_with = "watch", help_heading = "SERVE OPTIONS")]
+        git_ref: Option<String>,
+
+        /// Edit elements through forms in the served pages (implies --watch)
+        #[clap(long, conflicts_with = "git_ref", help_heading = "SERVE OPTIONS")]
+        edit: bool,
+    },
+
+    /// Run a language server for editors over stdio
+    #[clap(override_help = "Run a language server for editors over stdio\n\nProvides diagnostics on save, completion of relations, go to definition,\nfind references, rename and hover for elements.")]
+    Lsp,
+
+    /// Run a Model Context Protocol server for AI agents over stdio
+    #[clap(override_help = "Run a Model Context Protocol server for AI agents over stdio\n\nProvides tools for summaries, element lookup, impact trees, traces, coverage,\nlint and validated edits (add element, add relation, move) with JSON results.")]
+    Mcp,
+
+    /// Watch model files and re-validate incrementally on every change
+    #[clap(override_help = "Watch model files and re-validate incrementally on every change\n\nOnly changed files are parsed again. After each change, prints validation\nerrors or, for a valid model, the lint or coverage report.\n\nArguments:\n  [MODE]  What to print on change: validate, lint or coverage (default: validate)")]
+    Watch {
+        /// What to print on change
+        #[clap(default_value = "validate", value_parser = ["validate", "lint", "coverage"])]
+        mode: String,
     },
 
     /// Format and normalize requirements files. By default, shows preview without applying changes
@@ -73,6 +129,62 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Apply a batch of model edits from a JSON patch. By default, shows preview without applying changes
+    #[clap(override_help = "Apply a batch of model edits from a JSON patch. By default, shows preview without applying changes\n\nAPPLY OPTIONS:\n      [PATCH]    JSON patch file with the operations to apply; reads stdin when omitted or `-`\n      --fix      Apply the changes to files\n      --json     Output results in JSON format")]
+    Apply {
+        /// JSON patch file with the operations to apply; reads stdin when omitted or `-`
+        #[clap(value_name = "PATCH")]
+        patch: Option<PathBuf>,
+
+        /// Apply the changes to files
+        #[clap(long, help_heading = "APPLY OPTIONS")]
+        fix: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "APPLY OPTIONS")]
+        json: bool,
+    },
+
+    /// Move an element to another file or section, updating all relations to it
+    #[clap(override_help = "Move an element to another file or section, updating all relations to it\n\nMV OPTIONS:\n      <ELEMENT>                   Element identifier or unique element name\n      --to <FILE[#SECTION]>       Target file, optionally with section (default: current section)\n      --dry-run                   Show the changes without writing files\n      --json                      Output results in JSON format")]
+    Mv {
+        /// Element identifier or unique element name
+        #[clap(value_name = "ELEMENT")]
+        element: String,
+
+        /// Target file, optionally with section (default: current section)
+        #[clap(long, value_name = "FILE[#SECTION]", help_heading = "MV OPTIONS")]
+        to: String,
+
+        /// Show the changes without writing files
+        #[clap(long, help_heading = "MV OPTIONS")]
+        dry_run: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "MV OPTIONS")]
+        json: bool,
+    },
+
+    /// Rename an element, updating all relations to it
+    #[clap(override_help = "Rename an element, updating all relations to it\n\nRENAME OPTIONS:\n      <ELEMENT>                   Element identifier or unique element name\n      <NEW-TITLE>                 New element name\n      --dry-run                   Show the changes without writing files\n      --json                      Output results in JSON format")]
+    Rename {
+        /// Element identifier or unique element name
+        #[clap(value_name = "ELEMENT")]
+        element: String,
+
+        /// New element name
+        #[clap(value_name = "NEW-TITLE")]
+        new_title: String,
+
+        /// Show the changes without writing files
+        #[clap(long, help_heading = "RENAME OPTIONS")]
+        dry_run: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "RENAME OPTIONS")]
+        json: bool,
+    },
+
     /// Validate model
     #[clap(override_help = "Validate model\n\nVALIDATION OPTIONS:\n      --json     Output results in JSON format")]
     Validate {
@@ -94,7 +206,7 @@ pub enum Commands {
     RemoveDiagrams,
 
     /// Output model registry and summary
-    #[clap(override_help = "Output model registry and summary\n\nSUMMARY OPTIONS:\n      --json                        Output results in JSON format\n      --filter-file <GLOB>          Only include files whose path matches this glob pattern e.g. `src/**/*Reqs.md`\n      --filter-name <REGEX>         Only include elements whose name matches this regular expression\n      --filter-section <GLOB>       Only include sections whose name matches this glob pattern e.g. `System requirement*`\n      --filter-type <TYPE>          Only include elements of the given type e.g. `user-requirement`, `system-requirement`, `verification`, `file`, or other custom type\n      --filter-content <REGEX>      Only include elements whose content matches this regular expression\n      --filter-is-not-verified      Only include requirements that do NOT have any \"verifiedBy\" relations\n      --filter-is-not-satisfied     Only include requirements that do NOT have any \"satisfiedBy\" relations")]
+    #[clap(override_help = "Output model registry and summary\n\nSUMMARY OPTIONS:\n      --json                        Output results in JSON format\n      --filter-file <GLOB>          Only include files whose path matches this glob pattern e.g. `src/**/*Reqs.md`\n      --filter-name <REGEX>         Only include elements whose name matches this regular expression\n      --filter-section <GLOB>       Only include sections whose name matches this glob pattern e.g. `System requirement*`\n      --filter-type <TYPE>          Only include elements of the given type e.g. `user-requirement`, `system-requirement`, `verification`, `file`, or other custom type\n      --filter-content <REGEX>      Only include elements whose content matches this regular expression\n      --filter-is-not-verified      Only include requirements that do NOT have any \"verifiedBy\" relations\n      --filter-is-not-satisfied     Only include requirements that do NOT have any \"satisfiedBy\" relations\n      --format <FORMAT>             Output one row per element as a table: csv or tsv\n      --metadata-key <KEY>          Metadata keys included as table columns (repeatable, comma-separated; default: all)")]
     Summary {
         /// Output results in JSON format
         #[clap(long, help_heading = "SUMMARY OPTIONS")]
@@ -128,6 +240,14 @@ pub enum Commands {
         #[clap(long, help_heading = "SUMMARY OPTIONS")]
         filter_is_not_satisfied: bool,
 
+        /// Output one row per element as a table: csv or tsv
+        #[clap(long, value_name = "FORMAT", value_parser = ["csv", "tsv"], conflicts_with_all = &["json", "cypher"], help_heading = "SUMMARY OPTIONS")]
+        format: Option<String>,
+
+        /// Metadata keys included as table columns (default: all)
+        #[clap(long, value_name = "KEY", value_delimiter = ',', requires = "format", help_heading = "SUMMARY OPTIONS")]
+        metadata_key: Vec<String>,
+
         /// Output model as Cypher queries for graph database import. Cannot be used with --json
         #[clap(long, hide = true, conflicts_with_all = &["json"], help_heading = "SUMMARY OPTIONS")]
         cypher: bool,
@@ -166,7 +286,7 @@ pub enum Commands {
     },
 
     /// Generate verification traceability matrix showing requirements and their verification status
-    #[clap(override_help = "Generate verification traceability matrix showing requirements and their verification status\n\nMATRIX OPTIONS:\n      --svg                       Output traceability matrix as SVG (cannot be used with --json)\n      --json                      Output results in JSON format")]
+    #[clap(override_help = "Generate verification traceability matrix showing requirements and their verification status\n\nMATRIX OPTIONS:\n      --svg                       Output traceability matrix as SVG (cannot be used with --json)\n      --json                      Output results in JSON format\n      --satisfaction              Trace requirements to implementations via satisfiedBy instead of verifications\n      --source-type <TYPE>        Element types used as matrix rows e.g. `requirement` (repeatable, comma-separated)\n      --target-type <TYPE>        Element types used as matrix columns e.g. `test-verification`, `file` (repeatable, comma-separated)\n      --relation <RELATION>       Relation types traced from rows to columns e.g. `verifiedBy` (repeatable, comma-separated)\n      --filter-file <GLOB>        Only include rows from files whose path matches this glob pattern\n      --filter-section <GLOB>     Only include rows from sections whose name matches this glob pattern\n      --definition <NAME>         Use a matrix definition saved under `matrices` in reqvire.yaml\n      --format <FORMAT>           Output traceability matrix as a table: csv or tsv")]
     Matrix {
         /// Output traceability matrix as SVG without hyperlinks and with full element names Cannot be used with --json
         #[clap(long, conflicts_with = "json", help_heading = "MATRIX OPTIONS")]
@@ -175,10 +295,42 @@ pub enum Commands {
         /// Output results in JSON format
         #[clap(long, help_heading = "MATRIX OPTIONS")]
         json: bool,
+
+        /// Trace requirements to implementations via satisfiedBy instead of verifications
+        #[clap(long, conflicts_with_all = &["source_type", "target_type", "relation"], help_heading = "MATRIX OPTIONS")]
+        satisfaction: bool,
+
+        /// Element types used as matrix rows e.g. `requirement`
+        #[clap(long, value_name = "TYPE", value_delimiter = ',', help_heading = "MATRIX OPTIONS")]
+        source_type: Vec<String>,
+
+        /// Element types used as matrix columns e.g. `test-verification`, `file`
+        #[clap(long, value_name = "TYPE", value_delimiter = ',', help_heading = "MATRIX OPTIONS")]
+        target_type: Vec<String>,
+
+        /// Relation types traced from rows to columns e.g. `verifiedBy`
+        #[clap(long, value_name = "RELATION", value_delimiter = ',', help_heading = "MATRIX OPTIONS")]
+        relation: Vec<String>,
+
+        /// Only include rows from files whose path matches this glob pattern
+        #[clap(long, value_name = "GLOB", help_heading = "MATRIX OPTIONS")]
+        filter_file: Option<String>,
+
+        /// Only include rows from sections whose name matches this glob pattern
+        #[clap(long, value_name = "GLOB", help_heading = "MATRIX OPTIONS")]
+        filter_section: Option<String>,
+
+        /// Use a matrix definition saved under `matrices` in reqvire.yaml
+        #[clap(long, value_name = "NAME", conflicts_with_all = &["satisfaction", "source_type", "target_type", "relation"], help_heading = "MATRIX OPTIONS")]
+        definition: Option<String>,
+
+        /// Output traceability matrix as a table: csv or tsv
+        #[clap(long, value_name = "FORMAT", value_parser = ["csv", "tsv"], conflicts_with_all = &["json", "svg"], help_heading = "MATRIX OPTIONS")]
+        format: Option<String>,
     },
 
     /// Generate verification traces showing upward paths from verifications to root requirements
-    #[clap(override_help = "Generate verification traces showing upward paths from verifications to root requirements\n\nTRACES OPTIONS:\n      --json                      Output results in JSON format\n      --from-folder <PATH>        Generate links relative to this folder path\n      --links-with-blobs          Use GitHub blob URLs in diagram links instead of relative paths\n      --filter-id <ID>            Only include verification with this specific identifier\n      --filter-name <REGEX>       Only include verifications whose name matches this regular expression\n      --filter-type <TYPE>        Only include verifications of the given type e.g. `test-verification`, `analysis-verification`")]
+    #[clap(override_help = "Generate verification traces showing upward paths from verifications to root requirements\n\nTRACES OPTIONS:\n      --json                      Output results in JSON format\n      --from-folder <PATH>        Generate links relative to this folder path\n      --links-with-blobs          Use GitHub blob URLs in diagram links instead of relative paths\n      --filter-id <ID>            Only include verification with this specific identifier\n      --filter-name <REGEX>       Only include verifications whose name matches this regular expression\n      --filter-type <TYPE>        Only include verifications of the given type e.g. `test-verification`, `analysis-verification`\n      --format <FORMAT>           Output one row per verification as a table: csv or tsv")]
     Traces {
         /// Output results in JSON format
         #[clap(long, help_heading = "TRACES OPTIONS")]
@@ -203,14 +355,34 @@ pub enum Commands {
         /// Only include verifications of the given type e.g. `test-verification`, `analysis-verification`
         #[clap(long, value_name = "TYPE", help_heading = "TRACES OPTIONS")]
         filter_type: Option<String>,
+
+        /// Output one row per verification as a table: csv or tsv
+        #[clap(long, value_name = "FORMAT", value_parser = ["csv", "tsv"], conflicts_with = "json", help_heading = "TRACES OPTIONS")]
+        format: Option<String>,
     },
 
     /// Generate verification coverage report for leaf requirements
-    #[clap(override_help = "Generate verification coverage report for leaf requirements\n\nCOVERAGE OPTIONS:\n      --json                      Output results in JSON format")]
+    #[clap(override_help = "Generate verification coverage report for leaf requirements\n\nCOVERAGE OPTIONS:\n      --json                      Output results in JSON format\n      --satisfaction              Report implementation coverage (satisfiedBy) instead of verification coverage\n      --history <REV_RANGE>       Report coverage at each commit in a git revision range as CSV (JSON with --json)\n      --svg                       Render the coverage history as an SVG trend chart (requires --history)\n      --format <FORMAT>           Output one row per element (or commit with --history) as a table: csv or tsv")]
     Coverage {
         /// Output results in JSON format
         #[clap(long, help_heading = "COVERAGE OPTIONS")]
         json: bool,
+
+        /// Report implementation coverage (satisfiedBy) instead of verification coverage
+        #[clap(long, help_heading = "COVERAGE OPTIONS")]
+        satisfaction: bool,
+
+        /// Report coverage at each commit in a git revision range (e.g. v1.0..HEAD)
+        #[clap(long, value_name = "REV_RANGE", conflicts_with = "satisfaction", help_heading = "COVERAGE OPTIONS")]
+        history: Option<String>,
+
+        /// Render the coverage history as an SVG trend chart
+        #[clap(long, requires = "history", conflicts_with = "json", help_heading = "COVERAGE OPTIONS")]
+        svg: bool,
+
+        /// Output one row per element (or commit with --history) as a table: csv or tsv
+        #[clap(long, value_name = "FORMAT", value_parser = ["csv", "tsv"], conflicts_with_all = &["json", "svg"], help_heading = "COVERAGE OPTIONS")]
+        format: Option<String>,
     },
 
     /// Generate model structure diagram with optional filtering
@@ -254,6 +426,98 @@ pub enum Commands {
     Sout,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ImportSource {
+    /// Import a ReqIF (.reqif or .reqifz) document
+    Reqif {
+        /// ReqIF file to import
+        #[clap(value_name = "FILE", help_heading = "IMPORT REQIF OPTIONS")]
+        file: PathBuf,
+
+        /// YAML file mapping ReqIF attributes, types and relations onto the model
+        #[clap(long, value_name = "FILE", help_heading = "IMPORT REQIF OPTIONS")]
+        mapping: Option<PathBuf>,
+
+        /// Directory the markdown files are written to
+        #[clap(long, short = 'o', value_name = "DIR", default_value = ".", help_heading = "IMPORT REQIF OPTIONS")]
+        output: PathBuf,
+
+        /// Show the planned files without writing them
+        #[clap(long, help_heading = "IMPORT REQIF OPTIONS")]
+        dry_run: bool,
+
+        /// Replace existing files
+        #[clap(long, help_heading = "IMPORT REQIF OPTIONS")]
+        overwrite: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "IMPORT REQIF OPTIONS")]
+        json: bool,
+    },
+
+    /// Import requirements from a CSV (or .tsv) file with one row per element
+    Csv {
+        /// CSV file to import
+        #[clap(value_name = "FILE", help_heading = "IMPORT CSV OPTIONS")]
+        file: PathBuf,
+
+        /// YAML file mapping columns onto names, content, types, metadata, parents, files and sections
+        #[clap(long, value_name = "FILE", help_heading = "IMPORT CSV OPTIONS")]
+        mapping: Option<PathBuf>,
+
+        /// Directory the markdown files are written to
+        #[clap(long, short = 'o', value_name = "DIR", default_value = ".", help_heading = "IMPORT CSV OPTIONS")]
+        output: PathBuf,
+
+        /// Show the planned files without writing them
+        #[clap(long, help_heading = "IMPORT CSV OPTIONS")]
+        dry_run: bool,
+
+        /// Replace existing files
+        #[clap(long, help_heading = "IMPORT CSV OPTIONS")]
+        overwrite: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "IMPORT CSV OPTIONS")]
+        json: bool,
+    },
+
+    /// Import requirements from a JSON array of records or a model exported with `export --format json`
+    Json {
+        /// JSON file to import
+        #[clap(value_name = "FILE", help_heading = "IMPORT JSON OPTIONS")]
+        file: PathBuf,
+
+        /// YAML file mapping fields onto names, content, types, metadata, parents, files and sections
+        #[clap(long, value_name = "FILE", help_heading = "IMPORT JSON OPTIONS")]
+        mapping: Option<PathBuf>,
+
+        /// Directory the markdown files are written to
+        #[clap(long, short = 'o', value_name = "DIR", default_value = ".", help_heading = "IMPORT JSON OPTIONS")]
+        output: PathBuf,
+
+        /// Show the planned files without writing them
+        #[clap(long, help_heading = "IMPORT JSON OPTIONS")]
+        dry_run: bool,
+
+        /// Replace existing files, e.g. to write back a transformed model
+        #[clap(long, help_heading = "IMPORT JSON OPTIONS")]
+        overwrite: bool,
+
+        /// Output results in JSON format
+        #[clap(long, help_heading = "IMPORT JSON OPTIONS")]
+        json: bool,
+    },
+}
+
+impl ImportSource {
+    pub fn json(&self) -> bool {
+        match self {
+            ImportSource::Reqif { json, .. } | ImportSource::Csv { json, .. } | ImportSource::Json { json, .. } => *json,
+        }
+    }
+}
+
 impl Args {
     pub fn parse_args() -> Self {
         // Check if help was requested before parsing
@@ -351,7 +615,13 @@ fn print_custom_help(cmd: &clap::Command) {
                     if !arg.is_global_set() {
                         has_options = true;
                         let long = arg.get_long().map(|l| format!("--{}", l)).unwrap_or_default();
-                        let value_name = if arg.get_action().takes_values() {
+                        let value_name = if arg.is_positional() {
+                            let value = arg.get_value_names()
+                                .and_then(|v| v.first())
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| arg.get_id().to_string().to_uppercase());
+                            format!("<{}>", value)
+                        } else if arg.get_action().takes_values() {
                             let value = arg.get_value_names()
                                 .and_then(|v| v.get(0))
                                 .map(|s| s.to_string())
@@ -385,7 +655,13 @@ fn print_custom_help(cmd: &clap::Command) {
                 if !arg.is_global_set() {
                     has_options = true;
                     let long = arg.get_long().map(|l| format!("--{}", l)).unwrap_or_default();
-                    let value_name = if arg.get_action().takes_values() {
+                    let value_name = if arg.is_positional() {
+                        let value = arg.get_value_names()
+                            .and_then(|v| v.first())
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| arg.get_id().to_string().to_uppercase());
+                        if arg.is_required_set() { format!("<{}>", value) } else { format!("[{}]", value) }
+                    } else if arg.get_action().takes_values() {
                         let value = arg.get_value_names()
                             .and_then(|v| v.get(0))
                             .map(|s| s.to_string())
@@ -420,7 +696,7 @@ struct ValidationResult {
 }
 
 /// Helper function to print validation results
-fn print_validation_results(errors: &[ReqvireError], json_output: bool) {
+pub(crate) fn print_validation_results(errors: &[ReqvireError], json_output: bool) {
     if json_output {
         let json_result = ValidationResult {
             errors: errors.iter().map(|e| e.to_string()).collect(),
@@ -437,18 +713,35 @@ fn print_validation_results(errors: &[ReqvireError], json_output: bool) {
     }
 }
 
+/// Output directories given on the command line are relative to the working directory
+fn output_path(output: &Path) -> Result<PathBuf, ReqvireError> {
+    let current_dir = std::env::current_dir()?;
+    Ok(current_dir.canonicalize().unwrap_or(current_dir).join(output))
+}
+
+fn load_bulk_import_mapping(path: Option<&Path>) -> Result<BulkImportMapping, ReqvireError> {
+    match path {
+        Some(path) => BulkImportMapping::load(path),
+        None => Ok(BulkImportMapping::default()),
+    }
+}
+
 fn wants_json(args: &Args) -> bool {
     match &args.command {
         Some(Commands::Format { json, .. }) => *json,
+        Some(Commands::Apply { json, .. }) => *json,
+        Some(Commands::Mv { json, .. }) => *json,
+        Some(Commands::Rename { json, .. }) => *json,
         Some(Commands::Validate { json }) => *json,
         Some(Commands::ChangeImpact { json, .. }) => *json,
         Some(Commands::Summary { json, .. }) => *json,
         Some(Commands::SectionSummary { json, .. }) => *json,
         Some(Commands::Matrix { json, .. }) => *json,
         Some(Commands::Traces { json, .. }) => *json,
-        Some(Commands::Coverage { json }) => *json,
+        Some(Commands::Coverage { json, .. }) => *json,
         Some(Commands::Model { json, .. }) => *json,
         Some(Commands::Lint { json, .. }) => *json,
+        Some(Commands::Import { source }) => source.json(),
         _ => false,
     }
 }
@@ -464,6 +757,88 @@ pub fn handle_command(
         return Ok(0);
     }
 
+    // Import validates the new files together with the existing model
+    if let Some(Commands::Import { source }) = &args.command {
+        let context = ModelContext::from_current_dir();
+        let result = match source {
+            ImportSource::Reqif { file, mapping, output, dry_run, overwrite, .. } => {
+                let mapping = match mapping {
+                    Some(path) => ReqifMapping::load(path)?,
+                    None => ReqifMapping::default(),
+                };
+                let model = reqif_import::import_reqif(file, &mapping)?;
+                import::write_imported_model(&context, &model, &output_path(output)?, excluded_filename_patterns, *dry_run, *overwrite)
+            }
+            ImportSource::Csv { file, mapping, output, dry_run, overwrite, .. } => {
+                let mapping = load_bulk_import_mapping(mapping.as_deref())?;
+                let records = bulk_import::read_csv_records(file)?;
+                let model = bulk_import::import_records(&records, &mapping)?;
+                import::write_imported_model(&context, &model, &output_path(output)?, excluded_filename_patterns, *dry_run, *overwrite)
+            }
+            ImportSource::Json { file, mapping, output, dry_run, overwrite, .. } => {
+                let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(file)?)
+                    .map_err(|e| ReqvireError::ProcessError(format!("Failed to read {}: {}", file.display(), e)))?;
+                if model_json::is_model_document(&document) {
+                    if mapping.is_some() {
+                        return Err(ReqvireError::ProcessError(
+                            "--mapping does not apply to models exported with `export --format json`".to_string()
+                        ));
+                    }
+                    let document = model_json::ModelDocument::from_value(document)?;
+                    model_json::import_model(&context, &document, &output_path(output)?, excluded_filename_patterns, *dry_run, *overwrite)
+                } else {
+                    let mapping = load_bulk_import_mapping(mapping.as_deref())?;
+                    let records = bulk_import::json_records(document, &mapping)
+                        .map_err(|e| ReqvireError::ProcessError(format!("{}: {}", file.display(), e)))?;
+                    let model = bulk_import::import_records(&records, &mapping)?;
+                    import::write_imported_model(&context, &model, &output_path(output)?, excluded_filename_patterns, *dry_run, *overwrite)
+                }
+            }
+        };
+        match result {
+            Ok(summary) => summary.print(source.json()),
+            Err(ReqvireError::ValidationError(errors)) => {
+                print_validation_results(&errors, source.json());
+                return Ok(1);
+            }
+            Err(e) => return Err(e),
+        }
+        return Ok(0);
+    }
+
+    // The language server parses the model itself on every save
+    if let Some(Commands::Lsp) = &args.command {
+        lsp::run_language_server(excluded_filename_patterns)?;
+        return Ok(0);
+    }
+
+    // The MCP server parses the model itself for every tool call
+    if let Some(Commands::Mcp) = &args.command {
+        mcp::run_mcp_server(excluded_filename_patterns)?;
+        return Ok(0);
+    }
+
+    // Watch mode keeps its own model and patches it on every change
+    if let Some(Commands::Watch { mode }) = &args.command {
+        watch::run_watch(mode, excluded_filename_patterns)?;
+        return Ok(0);
+    }
+
+    // Live serving keeps running when the model becomes invalid
+    if let Some(Commands::Serve { host, port, watch, edit, .. }) = &args.command {
+        if *watch || *edit {
+            reqvire::utils::enable_quiet_mode();
+            serve::serve_watch(host, *port, excluded_filename_patterns, *edit)?;
+            return Ok(0);
+        }
+    }
+
+    if let Some(Commands::Serve { host, port, git_ref: Some(git_ref), .. }) = &args.command {
+        reqvire::utils::enable_quiet_mode();
+        serve::serve_ref(git_ref, host, *port, excluded_filename_patterns)?;
+        return Ok(0);
+    }
+
     let mut model_manager = ModelManager::new();
     let parse_result = model_manager.parse_and_validate(
         None,
@@ -531,7 +906,9 @@ pub fn handle_command(
             filter_type,
             filter_content,
             filter_is_not_verified,
-            filter_is_not_satisfied
+            filter_is_not_satisfied,
+            format,
+            metadata_key
         }) => {
             let filters = reports::Filters::new(
                 filter_file.as_deref(),
@@ -545,7 +922,12 @@ pub fn handle_command(
                 ReqvireError::ProcessError(format!("❌ Failed to construct filters: {}", e))
             })?;
 
-            let output_format = if cypher {
+            let output_format = if let Some(format) = format {
+                reports::SummaryOutputFormat::Table {
+                    format: TableFormat::from_name(&format)?,
+                    metadata_keys: metadata_key,
+                }
+            } else if cypher {
                 reports::SummaryOutputFormat::Cypher
             } else if json {
                 reports::SummaryOutputFormat::Json
@@ -608,21 +990,102 @@ pub fn handle_command(
             }
             return Ok(0);
         },
-        Some(Commands::Matrix { json, svg }) => {
+        Some(Commands::Apply { patch, fix, json }) => {
+            let document = match patch.as_deref() {
+                Some(path) if path != Path::new("-") => std::fs::read_to_string(path)?,
+                _ => std::io::read_to_string(std::io::stdin())?,
+            };
+            let entries = patch::parse_patch(&document)?;
+            // Default is dry-run mode (preview only), --fix flag writes the files
+            let result = patch::apply_patch(
+                &mut model_manager.graph_registry,
+                &entries,
+                excluded_filename_patterns,
+                !fix,
+            )?;
+            result.print(json);
+            Ok(if result.succeeded() { 0 } else { 1 })
+        },
+        Some(Commands::Mv { element, to, dry_run, json }) => {
+            let (file, section) = match to.split_once('#') {
+                Some((file, section)) => (file, Some(section)),
+                None => (to.as_str(), None),
+            };
+            let change = patch::move_element(
+                &mut model_manager.graph_registry,
+                &element,
+                file,
+                section,
+                excluded_filename_patterns,
+                dry_run,
+            )?;
+            change.print(json);
+            Ok(if change.result.succeeded() { 0 } else { 1 })
+        },
+        Some(Commands::Rename { element, new_title, dry_run, json }) => {
+            let change = patch::rename_element(
+                &mut model_manager.graph_registry,
+                &element,
+                &new_title,
+                excluded_filename_patterns,
+                dry_run,
+            )?;
+            change.print(json);
+            Ok(if change.result.succeeded() { 0 } else { 1 })
+        },
+        Some(Commands::Matrix {
+            json,
+            svg,
+            satisfaction,
+            source_type,
+            target_type,
+            relation,
+            filter_file,
+            filter_section,
+            definition,
+            format
+        }) => {
             // Generate traceability matrix with verification roll-up strategy
-            let matrix_config = matrix_generator::MatrixConfig::default();
+            let matrix_config = if let Some(name) = definition {
+                let project_config = load_project_config()?;
+                project_config
+                    .matrix(&name)
+                    .ok_or_else(|| ReqvireError::ProcessError(format!(
+                        "❌ Matrix definition '{}' not found in reqvire.yaml", name
+                    )))?
+                    .to_config(&model_manager.graph_registry)?
+            } else if satisfaction {
+                matrix_generator::MatrixConfig::satisfaction(&model_manager.graph_registry)
+            } else {
+                matrix_generator::MatrixDefinition {
+                    source_types: source_type,
+                    target_types: target_type,
+                    relations: relation,
+                    ..Default::default()
+                }.to_config(&model_manager.graph_registry)?
+            };
+            let matrix_config = matrix_config.with_filters(filter_file.as_deref(), filter_section.as_deref())?;
+            let matrix_format = if let Some(format) = format {
+                matrix_generator::MatrixFormat::Table(TableFormat::from_name(&format)?)
+            } else if json {
+                matrix_generator::MatrixFormat::Json
+            } else if svg {
+                matrix_generator::MatrixFormat::Svg
+            } else {
+                matrix_generator::MatrixFormat::Markdown
+            };
+            let is_table = matches!(matrix_format, matrix_generator::MatrixFormat::Table(_));
             let matrix_output = reqvire::matrix_generator::generate_matrix(
                 &model_manager.graph_registry,
                 &matrix_config,
-                if json {
-                    matrix_generator::MatrixFormat::Json
-                } else if svg {
-                    matrix_generator::MatrixFormat::Svg
-                } else {
-                    matrix_generator::MatrixFormat::Markdown
-                },
+                matrix_format,
             );
-            println!("{}", matrix_output);
+            // Tables already end with a record terminator
+            if is_table {
+                print!("{}", matrix_output);
+            } else {
+                println!("{}", matrix_output);
+            }
             return Ok(0);
         },
         Some(Commands::Traces {
@@ -631,7 +1094,8 @@ pub fn handle_command(
             links_with_blobs,
             filter_id,
             filter_name,
-            filter_type
+            filter_type,
+            format
         }) => {
             // Generate verification traces report (upward paths from verifications to requirements)
             let generator = verification_trace::VerificationTraceGenerator::new(
@@ -653,7 +1117,9 @@ pub fn handle_command(
             }
 
             // Output the report
-            if json {
+            if let Some(format) = format {
+                print!("{}", verification_trace::format_traces_table(&report, TableFormat::from_name(&format)?));
+            } else if json {
                 let json_output = serde_json::to_string_pretty(&report)
                     .map_err(|e| ReqvireError::ProcessError(format!("Failed to serialize report: {}", e)))?;
                 println!("{}", json_output);
@@ -664,9 +1130,44 @@ pub fn handle_command(
 
             return Ok(0);
         },
-        Some(Commands::Coverage { json }) => {
-            let coverage_report = reports::generate_coverage_report(&model_manager.graph_registry);
-            coverage_report.print(json);
+        Some(Commands::Coverage { json, satisfaction, history, svg, format }) => {
+            let table_format = format.as_deref().map(TableFormat::from_name).transpose()?;
+            if let Some(rev_range) = history {
+                let project_config = load_project_config()?;
+                let coverage_history = coverage_history::compute_coverage_history(
+                    &model_manager.graph_registry.context,
+                    &rev_range,
+                    excluded_filename_patterns,
+                    &project_config.verification_rules
+                )?;
+                if svg {
+                    print!("{}", coverage_history.to_svg());
+                } else if json {
+                    println!("{}", coverage_history.to_json());
+                } else {
+                    print!("{}", coverage_history.to_table(table_format.unwrap_or(TableFormat::Csv)));
+                }
+                return Ok(0);
+            }
+
+            if satisfaction {
+                let satisfaction_report = reports::generate_satisfaction_coverage_report(&model_manager.graph_registry);
+                match table_format {
+                    Some(table_format) => print!("{}", satisfaction_report.format_table(&model_manager.graph_registry, table_format)),
+                    None => satisfaction_report.print(json),
+                }
+                return Ok(0);
+            }
+
+            let project_config = load_project_config()?;
+            let coverage_report = reports::generate_coverage_report(
+                &model_manager.graph_registry,
+                &project_config.verification_rules
+            );
+            match table_format {
+                Some(table_format) => print!("{}", coverage_report.format_table(&model_manager.graph_registry, table_format)),
+                None => coverage_report.print(json),
+            }
             return Ok(0);
         },
         Some(Commands::Model { root_id, json }) => {
@@ -687,15 +1188,17 @@ pub fn handle_command(
 
             if fix {
                 // Apply automatic fixes
+                let baseline = source_map::Baseline::capture(&model_manager.graph_registry);
                 match lint_report.apply_fixes(&mut model_manager.graph_registry) {
                     Ok(relations_removed) => {
                         if relations_removed > 0 {
-                            // Rewrite all files with updated relations
-                            let format_result = format_files(&model_manager.graph_registry, false)?;
+                            // Remove only the fixed relation lines, leaving the rest of the files as written
+                            let root = model_manager.graph_registry.context.root().to_path_buf();
+                            let files_changed = source_map::write_changes(&model_manager.graph_registry, &baseline, &root)?;
 
                             if !json {
                                 println!("✅ Fixed {} redundant verify relation(s)\n", relations_removed);
-                                println!("Formatted {} file(s) with removed relations.\n", format_result.files_changed);
+                                println!("Updated {} file(s) with removed relations.\n", files_changed);
                             }
 
                             // Show remaining issues that need manual review
@@ -721,34 +1224,76 @@ pub fn handle_command(
 
             return Ok(0);
         },
-        Some(Commands::Export { output }) => {
+        Some(Commands::Export { single_file: Some(file), format, .. }) => {
+            if format != "html" {
+                return Err(ReqvireError::ProcessError(
+                    "--single-file is only available for the html format".to_string()
+                ));
+            }
+            info!("Exporting model to single HTML file: {}", &file);
+            let project_config = load_project_config()?;
+            single_file::export_single_file(&model_manager.graph_registry, Path::new(&file), &project_config)?;
+            info!("✅ Export completed successfully");
+            Ok(0)
+        },
+        Some(Commands::Export { output, format, .. }) if format == "reqif" => {
+            let Some(output) = output else {
+                return Err(ReqvireError::ProcessError(
+                    "ReqIF export requires an output file, e.g. -o model.reqifz".to_string()
+                ));
+            };
+            info!("Exporting model to ReqIF: {}", &output);
+            reqif::export_reqif(&model_manager.graph_registry, Path::new(&output))?;
+            info!("✅ Export completed successfully");
+            Ok(0)
+        },
+        Some(Commands::Export { output, format, .. }) if format == "json" => {
+            let Some(output) = output else {
+                return Err(ReqvireError::ProcessError(
+                    "JSON export requires an output file, e.g. -o model.json, or - for stdout".to_string()
+                ));
+            };
+            info!("Exporting model to JSON: {}", &output);
+            model_json::export_model_json(&model_manager.graph_registry, Path::new(&output))?;
+            Ok(0)
+        },
+        Some(Commands::Export { output, .. }) => {
+            let output = output.unwrap_or_else(|| "html".to_string());
             info!("Exporting model to HTML folder: {}", &output);
             let output_path = PathBuf::from(&output);
+            let project_config = load_project_config()?;
             export::export_model_with_artifacts(
                 &model_manager.graph_registry,
                 &output_path,
                 excluded_filename_patterns,
-                false // always generate links without blobs for Export
+                false, // always generate links without blobs for Export
+                &project_config
             )?;
             info!("✅ Export completed successfully");
             return Ok(0);
         },
-        Some(Commands::Serve { host, port }) => {
+        Some(Commands::Serve { host, port, .. }) => {
             // Enable quiet mode for serve command (suppress verbose export output)
             reqvire::utils::enable_quiet_mode();
 
-            let temp_dir = export::generate_artifacts_in_temp(
+            let project_config = load_project_config()?;
+            // Moved to a directory of its own, removed when the server stops
+            let site = serve::ExportDir::new("reqvire-serve-", || export::generate_artifacts_in_temp(
                 &model_manager.graph_registry,
-                excluded_filename_patterns,
-                false // always generate links without blobs for Serve
-            )?;
+                false, // always generate links without blobs for Serve
+                &project_config
+            ))?;
 
             // Start HTTP server (runs until Ctrl-C)
             info!("Starting HTTP server at http://{}:{}/", host, port);
-            serve::serve_directory(&temp_dir, &host, port)?;
-
-            // Cleanup temporary directory after server stops
-            std::fs::remove_dir_all(&temp_dir)?;
+            serve::serve_directory(
+                site.path(),
+                &host,
+                port,
+                &model_manager.graph_registry,
+                &project_config,
+                excluded_filename_patterns
+            )?;
 
             return Ok(0);
         },
@@ -760,9 +1305,9 @@ pub fn handle_command(
             run_sout(&model_manager.graph_registry)?;
             return Ok(0);
         },
-        None => {
-            // This case is handled at the beginning of handle_command
-            unreachable!("Command is None but should have been handled earlier");
+        Some(Commands::Import { .. }) | Some(Commands::Lsp) | Some(Commands::Mcp) | Some(Commands::Watch { .. }) | None => {
+            // Help and commands managing their own model return before the model is parsed
+            unreachable!("help, import, lsp, mcp and watch return before the model is parsed");
         }
     }
 }
@@ -1230,14 +1775,14 @@ mod tests {
     #[test]
     fn test_cli_parsing_subcommand() {
         let args = Args::parse_from(&["reqvire", "export"]);
-        assert!(matches!(args.command, Some(Commands::Export { output: _ })));
+        assert!(matches!(args.command, Some(Commands::Export { output: _, .. })));
     }
 
     #[test]
     fn test_handle_command() {
         // Mock CLI arguments
         let args = Args {
-            command: Some(Commands::Export { output: "html".to_string() }),
+            command: Some(Commands::Export { output: None, format: "html".to_string(), single_file: None }),
         };
 
         // Define test input paths
@@ -3,6 +3,8 @@ use log::{debug, warn};
 use std::path::PathBuf;
 use std::env;
 use globset::{Glob, GlobSet, GlobSetBuilder};
+use reqvire::error::ReqvireError;
+use reqvire::project_config::{ProjectConfig, PROJECT_CONFIG_FILENAME};
 
 /// Finds the root of the git repository
 fn find_git_root() -> Option<PathBuf> {
@@ -191,6 +193,18 @@ pub fn get_excluded_filename_patterns_glob_set() -> GlobSet {
         builder.build().expect("Failed to build glob set")
 }
 
+/// Loads project configuration from reqvire.yaml at the repository root
+pub fn load_project_config() -> Result<ProjectConfig, ReqvireError> {
+        let root = match find_git_root() {
+            Some(root) => root,
+            None => env::current_dir()?,
+        };
+
+        let config_path = root.join(PROJECT_CONFIG_FILENAME);
+        debug!("Loading project configuration from {}", config_path.display());
+        ProjectConfig::load(&config_path)
+}
+
 #[cfg(test)]
 mod config_tests {
     use crate::config::get_excluded_filename_patterns_glob_set;
@@ -0,0 +1,674 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use globset::GlobSet;
+use tiny_http::{Method, Request, Response};
+
+use reqvire::element::Element;
+use reqvire::error::ReqvireError;
+use reqvire::graph_registry::GraphRegistry;
+use reqvire::model_json::{self, TargetKind};
+use reqvire::patch::{self, PatchEntry, PatchOperation};
+use reqvire::relation::RELATION_TYPES;
+use reqvire::utils;
+use reqvire::{ModelContext, ModelManager};
+
+use crate::api::{percent_decode, ApiError, Query};
+use crate::refs;
+use crate::serve::{append_to_body, EXPORT_LOCK};
+use crate::static_files;
+
+/// Path of the element editor, followed by `?element=<identifier>`
+pub const EDIT_PATH: &str = "/edit";
+
+/// Path of the form creating elements, optionally followed by `?file=<path>&section=<name>`
+pub const NEW_PATH: &str = "/edit/new";
+
+/// Largest form submission accepted
+const MAX_FORM_SIZE: u64 = 1024 * 1024;
+
+/// Empty rows offered for new metadata entries and relations
+const EMPTY_ROWS: usize = 2;
+
+/// Element types offered by the type field in addition to the ones in use
+const ELEMENT_TYPES: [&str; 6] = [
+    "requirement",
+    "user-requirement",
+    "test-verification",
+    "analysis-verification",
+    "inspection-verification",
+    "demonstration-verification",
+];
+
+const EDITOR_STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222;max-width:60em}\
+label{display:block;margin:.8em 0}label>input,label>textarea{display:block;width:100%;box-sizing:border-box;margin-top:.2em;padding:.3em}\
+textarea{font-family:monospace}fieldset{margin:1em 0;border:1px solid #ddd}\
+table{border-collapse:collapse;width:100%}td{padding:.2em .4em}td input,td select{width:100%;box-sizing:border-box;padding:.2em}\
+.errors{color:#b31d28}button{padding:.4em 1.2em;margin-right:1em}";
+
+/// Forms editing model elements, applied as validated patches like `reqvire patch`.
+///
+/// Each request parses the model from disk, so forms always show and edit
+/// the files as they are.
+pub(crate) struct Editor {
+    excluded_filename_patterns: GlobSet,
+}
+
+impl Editor {
+    pub(crate) fn new(excluded_filename_patterns: &GlobSet) -> Self {
+        Self { excluded_filename_patterns: excluded_filename_patterns.clone() }
+    }
+
+    /// Answers editor requests, or returns `None` for other requests.
+    ///
+    /// `on_write` is called with the written files while [`EXPORT_LOCK`] is
+    /// still held, so the served site is updated before the next edit.
+    pub(crate) fn respond(
+        &self,
+        request: &mut Request,
+        on_write: impl FnOnce(&[PathBuf]),
+    ) -> Option<Response<Cursor<Vec<u8>>>> {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let new = match path {
+            EDIT_PATH => false,
+            NEW_PATH => true,
+            _ => return None,
+        };
+
+        Some(match request.method() {
+            Method::Get | Method::Head => self.form_response(new, query),
+            Method::Post => self.submit_response(request, new, on_write),
+            _ => static_files::page_response(405, String::from("405 Method Not Allowed"))
+                .with_header(static_files::header("Allow", "GET, HEAD, POST")),
+        })
+    }
+
+    fn form_response(&self, new: bool, query: &str) -> Response<Cursor<Vec<u8>>> {
+        let query = match Query::parse(query, &["element", "file", "section"]) {
+            Ok(query) => query,
+            Err(ApiError::BadRequest(message) | ApiError::NotFound(message)) => {
+                return error_page(400, "Invalid request", &[message]);
+            }
+        };
+
+        let _export = EXPORT_LOCK.lock().unwrap();
+        let model_manager = match self.load() {
+            Ok(model_manager) => model_manager,
+            Err(errors) => return error_page(503, "The model has validation errors", &errors),
+        };
+        let registry = &model_manager.graph_registry;
+
+        let form = if new {
+            ElementForm {
+                file: query.get("file").unwrap_or_default().to_string(),
+                section: query.get("section").unwrap_or_default().to_string(),
+                element_type: "requirement".to_string(),
+                ..Default::default()
+            }
+        } else {
+            let reference = query.get("element").unwrap_or_default();
+            match patch::resolve_element(registry, reference) {
+                Ok(identifier) => ElementForm::of(registry, registry.get_element(&identifier).expect("resolved elements exist")),
+                Err(e) => return error_page(404, "Element not found", &[e.to_string()]),
+            }
+        };
+        static_files::page_response(200, render_page(&form, &[], &datalists(registry)))
+    }
+
+    fn submit_response(
+        &self,
+        request: &mut Request,
+        new: bool,
+        on_write: impl FnOnce(&[PathBuf]),
+    ) -> Response<Cursor<Vec<u8>>> {
+        // Pages of other sites must not be able to edit the model through the browser
+        if !is_same_origin(request) {
+            return error_page(403, "Forbidden", &["Edits are only accepted from pages of this server".to_string()]);
+        }
+        let mut body = String::new();
+        if request.as_reader().take(MAX_FORM_SIZE + 1).read_to_string(&mut body).is_err() {
+            return error_page(400, "Invalid request", &["The form could not be read".to_string()]);
+        }
+        if body.len() as u64 > MAX_FORM_SIZE {
+            return error_page(413, "Invalid request", &["The form is too large".to_string()]);
+        }
+        let form = match ElementForm::parse(&body) {
+            Ok(form) => form,
+            Err(ApiError::BadRequest(message) | ApiError::NotFound(message)) => {
+                return error_page(400, "Invalid request", &[message]);
+            }
+        };
+
+        let _export = EXPORT_LOCK.lock().unwrap();
+        let mut model_manager = match self.load() {
+            Ok(model_manager) => model_manager,
+            Err(errors) => return error_page(503, "The model has validation errors", &errors),
+        };
+        let registry = &mut model_manager.graph_registry;
+        let suggestions = datalists(registry);
+
+        let operations = if new {
+            form.create_operations(registry)
+        } else {
+            let Some(element) = registry.get_element(&form.element).cloned() else {
+                return error_page(404, "Element not found", &[format!("Element '{}' not found", form.element)]);
+            };
+            // Saving would overwrite what changed in the file since the form was loaded
+            let conflict = match file_version(&registry.context, &element.file_path) {
+                Ok(version) if !form.version.is_empty() && version == form.version => None,
+                Ok(_) => Some(format!(
+                    "'{}' changed since this form was loaded. Copy your changes, reload the editor and apply them again.",
+                    element.file_path
+                )),
+                Err(e) => Some(format!("'{}' could not be read: {}", element.file_path, e)),
+            };
+            if let Some(error) = conflict {
+                return static_files::page_response(409, render_page(&form, &[error], &suggestions));
+            }
+            form.edit_operations(registry, &element)
+        };
+        let operations = match operations {
+            Ok(operations) => operations,
+            Err(errors) => return static_files::page_response(422, render_page(&form, &errors, &suggestions)),
+        };
+
+        let entries: Vec<PatchEntry> = operations
+            .into_iter()
+            .map(|operation| PatchEntry { op: operation_name(&operation).to_string(), operation: Ok(operation) })
+            .collect();
+        let result = match patch::apply_patch(registry, &entries, &self.excluded_filename_patterns, false) {
+            Ok(result) => result,
+            Err(e) => return static_files::page_response(422, render_page(&form, &[e.to_string()], &suggestions)),
+        };
+        if !result.succeeded() {
+            let errors: Vec<String> = result
+                .operations
+                .iter()
+                .filter_map(|operation| operation.error.clone())
+                .chain(result.errors.iter().cloned())
+                .collect();
+            return static_files::page_response(422, render_page(&form, &errors, &suggestions));
+        }
+
+        let written: Vec<PathBuf> = result
+            .patches
+            .iter()
+            .map(|patch| registry.context.resolve(&patch.file_path))
+            .collect();
+        if !written.is_empty() {
+            println!("✏️  Saved: {}", result.patches.iter().map(|patch| patch.file_path.as_str()).collect::<Vec<_>>().join(", "));
+            on_write(&written);
+        }
+
+        let identifier = result
+            .operations
+            .iter()
+            .find(|operation| operation.op == "update-element" || operation.op == "add-element")
+            .and_then(|operation| operation.element.clone())
+            .unwrap_or(form.element);
+        static_files::redirect(303, &refs::element_href(&identifier, "/"))
+    }
+
+    /// Parses the model from disk; the caller holds [`EXPORT_LOCK`]
+    fn load(&self) -> Result<ModelManager, Vec<String>> {
+        let mut model_manager = ModelManager::new();
+        let errors = match model_manager.parse_and_validate(None, &self.excluded_filename_patterns) {
+            Ok(errors) => errors,
+            Err(ReqvireError::ValidationError(errors)) => errors,
+            Err(e) => vec![e],
+        };
+        // Like the CLI, nothing is edited on an invalid model
+        if errors.is_empty() {
+            Ok(model_manager)
+        } else {
+            Err(errors.iter().map(|e| e.to_string()).collect())
+        }
+    }
+
+    /// Adds a link to the new element form and, on pages of model files, an
+    /// edit link to every element heading
+    pub(crate) fn with_edit_links(&self, page: &str, file: Option<&str>) -> String {
+        let new_element = match file {
+            Some(file) => format!("{}?file={}", NEW_PATH, refs::percent_encode(file)),
+            None => NEW_PATH.to_string(),
+        };
+        let mut addition = format!(
+            "<nav id=\"reqvire-edit\" style=\"position:fixed;left:1em;bottom:1em;z-index:9999;padding:.4em .6em;\
+             background:#fff;border:1px solid #ccc;border-radius:4px;box-shadow:0 1px 4px rgba(0,0,0,.2);font:13px sans-serif\">\
+             <a href=\"{}\">✚ New element</a></nav>",
+            utils::escape_html(&new_element)
+        );
+        if let Some(file) = file {
+            // Fragments of element identifiers are normalized like `utils::normalize_fragment`
+            addition.push_str(&format!(
+                "<script>document.querySelectorAll(\"h3[id]\").forEach(heading => {{\
+                 const fragment = heading.textContent.trim().toLowerCase().replace(/ /g, \"-\").replace(/[(),:]/g, \"\");\
+                 const link = document.createElement(\"a\");\
+                 link.href = \"{}?element=\" + encodeURIComponent({} + \"#\" + fragment);\
+                 link.className = \"reqvire-edit-link\"; link.title = \"Edit\"; link.textContent = \"✎\";\
+                 link.style.marginLeft = \".5em\"; link.style.textDecoration = \"none\";\
+                 heading.append(link);\
+                 }});</script>",
+                EDIT_PATH,
+                utils::escape_html(&serde_json::to_string(file).unwrap())
+            ));
+        }
+        append_to_body(page, &addition)
+    }
+}
+
+/// Model file shown by an exported page, e.g. `specifications/Requirements.md`
+/// for `/specifications/Requirements.html`
+pub(crate) fn page_file(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = percent_decode(path);
+    let stem = path.strip_prefix('/')?.strip_suffix(".html")?;
+    Some(format!("{}.md", stem))
+}
+
+/// Values of the element form, loaded from an element or submitted
+#[derive(Debug, Default)]
+struct ElementForm {
+    /// Identifier of the edited element, empty for new elements
+    element: String,
+    /// Version of the element's file when the form was loaded
+    version: String,
+    file: String,
+    section: String,
+    name: String,
+    element_type: String,
+    content: String,
+    metadata: Vec<(String, String)>,
+    /// Relations of the edited element as type and target
+    relations: Vec<(String, String)>,
+    /// Relations to remove, as `<type> <target>`
+    removed: Vec<String>,
+    /// Relations to add as type and target
+    added: Vec<(String, String)>,
+}
+
+impl ElementForm {
+    fn of(registry: &GraphRegistry, element: &Element) -> Self {
+        let exported = model_json::export_element(element);
+        Self {
+            element: element.identifier.clone(),
+            // An unreadable file has no version, so saving reports a conflict
+            version: file_version(&registry.context, &element.file_path).unwrap_or_default(),
+            file: element.file_path.clone(),
+            section: element.section.clone(),
+            name: element.name.clone(),
+            element_type: exported.element_type,
+            content: exported.content,
+            metadata: exported.metadata.into_iter().collect(),
+            relations: element
+                .relations
+                .iter()
+                .filter(|relation| relation.user_created)
+                .map(|relation| (relation.relation_type.name.to_string(), relation.target.link.as_str().to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses a submitted form; empty metadata and relation rows are dropped
+    fn parse(body: &str) -> Result<Self, ApiError> {
+        let query = Query::parse(body, &[
+            "element",
+            "version",
+            "file",
+            "section",
+            "name",
+            "type",
+            "content",
+            "meta_key",
+            "meta_value",
+            "relation",
+            "remove",
+            "relation_type",
+            "relation_target",
+        ])?;
+        let field = |name: &str| query.get(name).unwrap_or_default().trim().to_string();
+        let pairs = |first: &str, second: &str| -> Vec<(String, String)> {
+            query
+                .values(first)
+                .zip(query.values(second))
+                .map(|(a, b)| (a.trim().to_string(), b.trim().to_string()))
+                .filter(|(a, b)| !a.is_empty() || !b.is_empty())
+                .collect()
+        };
+        Ok(Self {
+            element: field("element"),
+            version: field("version"),
+            file: field("file"),
+            section: field("section"),
+            name: field("name"),
+            element_type: field("type"),
+            // Browsers submit text areas with CRLF line breaks
+            content: query.get("content").unwrap_or_default().replace("\r\n", "\n").trim_end().to_string(),
+            metadata: pairs("meta_key", "meta_value"),
+            relations: query
+                .values("relation")
+                .filter_map(|relation| relation.split_once(' '))
+                .map(|(relation_type, target)| (relation_type.to_string(), target.to_string()))
+                .collect(),
+            removed: query.values("remove").map(str::to_string).collect(),
+            added: pairs("relation_type", "relation_target"),
+        })
+    }
+
+    /// Operations creating the element with its metadata and relations
+    fn create_operations(&self, registry: &GraphRegistry) -> Result<Vec<PatchOperation>, Vec<String>> {
+        let mut errors = Vec::new();
+        let metadata = self.metadata_entries(&mut errors);
+        let identifier = format!("{}#{}", self.file, utils::normalize_fragment(&self.name));
+        let relations = self.added_relations(registry, &identifier, &mut errors);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut operations = vec![PatchOperation::AddElement {
+            name: self.name.clone(),
+            file: self.file.clone(),
+            section: self.section.clone(),
+            element_type: Some(self.element_type.clone()).filter(|element_type| !element_type.is_empty()),
+            content: self.content.clone(),
+            metadata: metadata.into_iter().collect(),
+        }];
+        operations.extend(relations);
+        Ok(operations)
+    }
+
+    /// Operations turning the element into the submitted one. Relations are
+    /// edited first, while the element still has its original identifier.
+    fn edit_operations(&self, registry: &GraphRegistry, element: &Element) -> Result<Vec<PatchOperation>, Vec<String>> {
+        let mut errors = Vec::new();
+        let metadata = self.metadata_entries(&mut errors);
+        let mut operations = self.added_relations(registry, &element.identifier, &mut errors);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for removed in &self.removed {
+            if let Some((relation_type, target)) = removed.split_once(' ') {
+                operations.insert(0, PatchOperation::RemoveRelation {
+                    source: element.identifier.clone(),
+                    relation_type: relation_type.to_string(),
+                    target: target.to_string(),
+                });
+            }
+        }
+
+        let exported = model_json::export_element(element);
+        let mut metadata_changes: BTreeMap<String, Option<String>> = exported
+            .metadata
+            .keys()
+            .filter(|key| !metadata.iter().any(|(submitted, _)| submitted == *key))
+            .map(|key| (key.clone(), None))
+            .collect();
+        for (key, value) in metadata {
+            if exported.metadata.get(&key) != Some(&value) {
+                metadata_changes.insert(key, Some(value));
+            }
+        }
+        operations.push(PatchOperation::UpdateElement {
+            element: element.identifier.clone(),
+            name: Some(self.name.clone()).filter(|name| *name != element.name),
+            element_type: Some(self.element_type.clone()).filter(|element_type| *element_type != exported.element_type),
+            content: Some(self.content.clone()).filter(|content| content.as_str() != exported.content.trim_end()),
+            metadata: metadata_changes,
+        });
+        Ok(operations)
+    }
+
+    fn metadata_entries(&self, errors: &mut Vec<String>) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        for (key, value) in &self.metadata {
+            if key.is_empty() {
+                errors.push(format!("Metadata value '{}' has no key", value));
+            } else {
+                entries.push((key.clone(), value.clone()));
+            }
+        }
+        entries
+    }
+
+    /// Operations adding the new relations, with targets resolved to element
+    /// identifiers, URLs or files
+    fn added_relations(&self, registry: &GraphRegistry, source: &str, errors: &mut Vec<String>) -> Vec<PatchOperation> {
+        let mut operations = Vec::new();
+        for (relation_type, target) in &self.added {
+            if relation_type.is_empty() || target.is_empty() {
+                errors.push(format!("Relation '{} {}' needs both a type and a target", relation_type, target));
+                continue;
+            }
+            let (target, kind) = if target.starts_with("http://") || target.starts_with("https://") {
+                (target.clone(), TargetKind::Url)
+            } else if let Ok(identifier) = patch::resolve_element(registry, target) {
+                (identifier, TargetKind::Element)
+            } else {
+                (target.clone(), TargetKind::File)
+            };
+            operations.push(PatchOperation::AddRelation {
+                source: source.to_string(),
+                relation_type: relation_type.clone(),
+                target,
+                kind,
+                text: None,
+            });
+        }
+        operations
+    }
+}
+
+fn operation_name(operation: &PatchOperation) -> &'static str {
+    match operation {
+        PatchOperation::AddElement { .. } => "add-element",
+        PatchOperation::UpdateElement { .. } => "update-element",
+        PatchOperation::RemoveElement { .. } => "remove-element",
+        PatchOperation::MoveElement { .. } => "move-element",
+        PatchOperation::AddRelation { .. } => "add-relation",
+        PatchOperation::RemoveRelation { .. } => "remove-relation",
+    }
+}
+
+/// Fingerprint of a model file's content, empty files included
+fn file_version(context: &ModelContext, file_path: &str) -> std::io::Result<String> {
+    let content = std::fs::read(context.resolve(file_path))?;
+    Ok(static_files::etag(&content).trim_matches('"').to_string())
+}
+
+/// Requests from browsers carry the origin of the submitting page
+fn is_same_origin(request: &Request) -> bool {
+    match static_files::header_value(request, "Origin") {
+        None => true,
+        Some(origin) => static_files::header_value(request, "Host")
+            .is_some_and(|host| origin.strip_prefix("http://") == Some(host)),
+    }
+}
+
+fn render_page(form: &ElementForm, errors: &[String], suggestions: &str) -> String {
+    let new = form.element.is_empty();
+    let title = if new { "New Element".to_string() } else { format!("Edit {}", form.name) };
+
+    let mut body = String::new();
+    if !errors.is_empty() {
+        body.push_str("<ul class=\"errors\">");
+        for error in errors {
+            body.push_str(&format!("<li>{}</li>", utils::escape_html(error)));
+        }
+        body.push_str("</ul>");
+    }
+
+    body.push_str(&format!("<form method=\"post\" action=\"{}\">", if new { NEW_PATH } else { EDIT_PATH }));
+    if new {
+        body.push_str(&format!(
+            "<label>File<input name=\"file\" list=\"reqvire-files\" required value=\"{}\"></label>\
+             <label>Section<input name=\"section\" list=\"reqvire-sections\" required value=\"{}\"></label>",
+            utils::escape_html(&form.file),
+            utils::escape_html(&form.section)
+        ));
+    } else {
+        body.push_str(&format!(
+            "<input type=\"hidden\" name=\"element\" value=\"{}\"><input type=\"hidden\" name=\"version\" value=\"{}\">\
+             <p><code>{}</code> in section {}</p>",
+            utils::escape_html(&form.element),
+            utils::escape_html(&form.version),
+            utils::escape_html(&form.element),
+            utils::escape_html(&form.section)
+        ));
+    }
+    // A newline after the opening tag keeps leading newlines of the content
+    body.push_str(&format!(
+        "<label>Name<input name=\"name\" required value=\"{}\"></label>\
+         <label>Type<input name=\"type\" list=\"reqvire-types\" value=\"{}\"></label>\
+         <label>Content<textarea name=\"content\" rows=\"12\">\n{}</textarea></label>",
+        utils::escape_html(&form.name),
+        utils::escape_html(&form.element_type),
+        utils::escape_html(&form.content)
+    ));
+
+    body.push_str("<fieldset><legend>Metadata</legend><table>");
+    let empty = (String::new(), String::new());
+    for (key, value) in form.metadata.iter().chain(std::iter::repeat_n(&empty, EMPTY_ROWS)) {
+        body.push_str(&format!(
+            "<tr><td><input name=\"meta_key\" placeholder=\"Key\" value=\"{}\"></td>\
+             <td><input name=\"meta_value\" placeholder=\"Value\" value=\"{}\"></td></tr>",
+            utils::escape_html(key),
+            utils::escape_html(value)
+        ));
+    }
+    body.push_str("</table></fieldset>");
+
+    body.push_str("<fieldset><legend>Relations</legend><table>");
+    for (relation_type, target) in &form.relations {
+        let relation = format!("{} {}", relation_type, target);
+        body.push_str(&format!(
+            "<tr><td>{}</td><td><code>{}</code></td><td><input type=\"hidden\" name=\"relation\" value=\"{2}\">\
+             <label><input type=\"checkbox\" name=\"remove\" value=\"{2}\"{3}> Remove</label></td></tr>",
+            utils::escape_html(relation_type),
+            utils::escape_html(target),
+            utils::escape_html(&relation),
+            if form.removed.contains(&relation) { " checked" } else { "" }
+        ));
+    }
+    let mut relation_types: Vec<&str> = RELATION_TYPES.keys().copied().collect();
+    relation_types.sort();
+    for (relation_type, target) in form.added.iter().chain(std::iter::repeat_n(&empty, EMPTY_ROWS)) {
+        let options: String = std::iter::once("")
+            .chain(relation_types.iter().copied())
+            .map(|name| {
+                format!(
+                    "<option value=\"{0}\"{1}>{0}</option>",
+                    name,
+                    if name == relation_type { " selected" } else { "" }
+                )
+            })
+            .collect();
+        body.push_str(&format!(
+            "<tr><td><select name=\"relation_type\" aria-label=\"Relation type\">{}</select></td>\
+             <td colspan=\"2\"><input name=\"relation_target\" list=\"reqvire-elements\" placeholder=\"Element, file or URL\" value=\"{}\"></td></tr>",
+            options,
+            utils::escape_html(target)
+        ));
+    }
+    body.push_str("</table></fieldset>");
+
+    let cancel = if new { "/".to_string() } else { refs::element_href(&form.element, "/") };
+    body.push_str(&format!(
+        "<button type=\"submit\">Save</button><a href=\"{}\">Cancel</a></form>",
+        utils::escape_html(&cancel)
+    ));
+    body.push_str(suggestions);
+
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title><style>{1}</style></head>\
+         <body><h1>{0}</h1>{2}</body></html>",
+        utils::escape_html(&title),
+        EDITOR_STYLE,
+        body
+    )
+}
+
+/// Suggestions for element identifiers, types, files and sections
+fn datalists(registry: &GraphRegistry) -> String {
+    let elements: Vec<&Element> = {
+        let mut elements: Vec<&Element> = registry
+            .get_all_elements()
+            .into_iter()
+            .filter(|element| !element.identifier.starts_with("__virtual__"))
+            .collect();
+        elements.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        elements
+    };
+    let types: BTreeSet<&str> = ELEMENT_TYPES
+        .into_iter()
+        .chain(elements.iter().map(|element| element.element_type.as_str()))
+        .collect();
+    let files: BTreeSet<&str> = registry.sections.keys().map(|key| key.file_path.as_str()).collect();
+    let sections: BTreeSet<&str> = registry.sections.keys().map(|key| key.section_name.as_str()).collect();
+
+    let mut html = String::from("<datalist id=\"reqvire-elements\">");
+    for element in &elements {
+        html.push_str(&format!("<option value=\"{}\">{}</option>", utils::escape_html(&element.identifier), utils::escape_html(&element.name)));
+    }
+    for (id, values) in [("reqvire-types", &types), ("reqvire-files", &files), ("reqvire-sections", &sections)] {
+        html.push_str(&format!("</datalist><datalist id=\"{}\">", id));
+        for value in values {
+            html.push_str(&format!("<option value=\"{}\">", utils::escape_html(value)));
+        }
+    }
+    html.push_str("</datalist>");
+    html
+}
+
+fn error_page(status: u16, title: &str, messages: &[String]) -> Response<Cursor<Vec<u8>>> {
+    let items: String = messages
+        .iter()
+        .map(|message| format!("<li>{}</li>", utils::escape_html(message)))
+        .collect();
+    let page = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title><style>{1}</style></head>\
+         <body><h1>{0}</h1><ul class=\"errors\">{2}</ul><p><a href=\"/\">Back</a></p></body></html>",
+        utils::escape_html(title),
+        EDITOR_STYLE,
+        items
+    );
+    static_files::page_response(status, page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_file_maps_pages_to_model_files() {
+        assert_eq!(
+            page_file("/specifications/Requirements.html#remote-start").as_deref(),
+            Some("specifications/Requirements.md")
+        );
+        assert_eq!(page_file("/docs/My%20Notes.html?x=1").as_deref(), Some("docs/My Notes.md"));
+        assert_eq!(page_file("/"), None);
+        assert_eq!(page_file("/styles.css"), None);
+    }
+
+    #[test]
+    fn test_parse_form_pairs_rows_and_drops_empty_ones() {
+        let form = ElementForm::parse(
+            "element=a.md%23x&name=+X+&content=Line+1%0D%0ALine+2%0D%0A\
+             &meta_key=priority&meta_value=high&meta_key=&meta_value=\
+             &relation=verifiedBy+b.md%23y&remove=verifiedBy+b.md%23y\
+             &relation_type=trace&relation_target=c.md%23z&relation_type=&relation_target=",
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(form.element, "a.md#x");
+        assert_eq!(form.name, "X");
+        assert_eq!(form.content, "Line 1\nLine 2");
+        assert_eq!(form.metadata, vec![("priority".to_string(), "high".to_string())]);
+        assert_eq!(form.relations, vec![("verifiedBy".to_string(), "b.md#y".to_string())]);
+        assert_eq!(form.removed, vec!["verifiedBy b.md#y"]);
+        assert_eq!(form.added, vec![("trace".to_string(), "c.md#z".to_string())]);
+    }
+}
@@ -0,0 +1,723 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::path::Path;
+
+use globset::GlobSet;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, GotoDefinition, HoverRequest, PrepareRenameRequest, References, Rename,
+    Request as _,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    CompletionTextEdit, Diagnostic, DiagnosticSeverity, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, Location,
+    MarkupContent, MarkupKind, OneOf, Position, PrepareRenameResponse, PublishDiagnosticsParams,
+    ReferenceParams, RenameOptions, RenameParams, SaveOptions, ServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use reqvire::element::{Element, ElementType, SubSection};
+use reqvire::error::ReqvireError;
+use reqvire::patch;
+use reqvire::relation::{LinkType, RELATION_TYPES};
+use reqvire::source_map::{self, ElementSpan, SourceMap};
+use reqvire::utils;
+use reqvire::ModelManager;
+
+/// Runs a language server for the specification files over stdio until the
+/// client shuts it down
+pub fn run_language_server(excluded_filename_patterns: &GlobSet) -> Result<(), ReqvireError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(server_capabilities())
+        .map_err(|e| ReqvireError::ProcessError(format!("Invalid server capabilities: {}", e)))?;
+    connection
+        .initialize(capabilities)
+        .map_err(|e| ReqvireError::ProcessError(format!("Language server initialization failed: {}", e)))?;
+
+    let mut server = LanguageServer {
+        connection,
+        excluded_filename_patterns: excluded_filename_patterns.clone(),
+        model: ModelManager::new(),
+        documents: HashMap::new(),
+        unsaved: HashSet::new(),
+        published: HashSet::new(),
+    };
+    server.reload()?;
+    server.run()?;
+    drop(server);
+
+    io_threads
+        .join()
+        .map_err(|e| ReqvireError::ProcessError(format!("Language server I/O failed: {}", e)))
+}
+
+fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions { include_text: Some(false) })),
+            ..Default::default()
+        })),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["*".to_string(), ":".to_string(), "[".to_string()]),
+            ..Default::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    }
+}
+
+/// What the cursor points at: an element header or a relation target
+#[derive(Debug, PartialEq)]
+enum Target {
+    Element(String),
+    File(String),
+}
+
+struct LanguageServer {
+    connection: Connection,
+    excluded_filename_patterns: GlobSet,
+    /// Model as of the last save
+    model: ModelManager,
+    /// Text of the open documents by model path
+    documents: HashMap<String, String>,
+    /// Open documents with changes that are not saved yet
+    unsaved: HashSet<String>,
+    /// Documents diagnostics were last published for
+    published: HashSet<Url>,
+}
+
+impl LanguageServer {
+    fn run(&mut self) -> Result<(), ReqvireError> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    let shutdown = self
+                        .connection
+                        .handle_shutdown(&request)
+                        .map_err(|e| ReqvireError::ProcessError(format!("Language server protocol error: {}", e)))?;
+                    if shutdown {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.send(Message::Response(response));
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&self, message: Message) {
+        // The client is gone when sending fails, which ends the receive loop
+        let _ = self.connection.sender.send(message);
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            Completion::METHOD => params(request).map(|params| to_value(self.completion(params))),
+            GotoDefinition::METHOD => params(request).map(|params| to_value(self.definition(params))),
+            References::METHOD => params(request).map(|params| to_value(self.references(params))),
+            HoverRequest::METHOD => params(request).map(|params| to_value(self.hover(params))),
+            PrepareRenameRequest::METHOD => params(request).map(|params| to_value(self.prepare_rename(params))),
+            Rename::METHOD => params(request).and_then(|params| self.rename(params).map(to_value)),
+            method => Err((ErrorCode::MethodNotFound, format!("Unsupported request '{}'", method))),
+        };
+        response(id, result)
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ReqvireError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<lsp_types::DidOpenTextDocumentParams>(notification) {
+                    if let Some(file_path) = self.file_path(&params.text_document.uri) {
+                        self.documents.insert(file_path, params.text_document.text);
+                    }
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<lsp_types::DidChangeTextDocumentParams>(notification) {
+                    if let (Some(file_path), Some(change)) =
+                        (self.file_path(&params.text_document.uri), params.content_changes.into_iter().last())
+                    {
+                        self.unsaved.insert(file_path.clone());
+                        self.documents.insert(file_path, change.text);
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<lsp_types::DidCloseTextDocumentParams>(notification) {
+                    if let Some(file_path) = self.file_path(&params.text_document.uri) {
+                        self.documents.remove(&file_path);
+                        self.unsaved.remove(&file_path);
+                    }
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<lsp_types::DidSaveTextDocumentParams>(notification) {
+                    if let Some(file_path) = self.file_path(&params.text_document.uri) {
+                        self.unsaved.remove(&file_path);
+                    }
+                }
+                self.reload()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Parses the model from disk and publishes the diagnostics of both parse passes
+    fn reload(&mut self) -> Result<(), ReqvireError> {
+        let mut model = ModelManager::new();
+        let errors = match model.parse_and_validate(None, &self.excluded_filename_patterns) {
+            Ok(errors) => errors,
+            Err(ReqvireError::ValidationError(errors)) => errors,
+            Err(e) => vec![e],
+        };
+        self.model = model;
+
+        let mut diagnostics: BTreeMap<String, Vec<Diagnostic>> = BTreeMap::new();
+        for error in &errors {
+            let message = error.to_string();
+            match self.locate_error(error) {
+                Some((file_path, range)) => diagnostics.entry(file_path).or_default().push(Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("reqvire".to_string()),
+                    message,
+                    ..Default::default()
+                }),
+                None => log::error!("{}", message),
+            }
+        }
+
+        let mut published = HashSet::new();
+        for (file_path, diagnostics) in diagnostics {
+            if let Some(uri) = self.uri(&file_path) {
+                self.publish_diagnostics(uri.clone(), diagnostics);
+                published.insert(uri);
+            }
+        }
+        for uri in std::mem::take(&mut self.published) {
+            if !published.contains(&uri) {
+                self.publish_diagnostics(uri, Vec::new());
+            }
+        }
+        self.published = published;
+        Ok(())
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) {
+        let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+        self.send(Message::Notification(Notification::new(PublishDiagnostics::METHOD.to_string(), params)));
+    }
+
+    /// File and range a validation error refers to: the line of parse
+    /// errors, the element header or the relation line of relation errors
+    fn locate_error(&self, error: &ReqvireError) -> Option<(String, lsp_types::Range)> {
+        let location = error.location()?;
+        let text = self.text(&location.file)?;
+        if let Some(line) = location.line {
+            let start = line_offset(&text, line.saturating_sub(1));
+            let end = line_end(&text, start);
+            return Some((location.file.clone(), range_at(&text, start..end)));
+        }
+
+        let element = self.model.graph_registry.get_element(location.element.as_deref()?)?;
+        let map = SourceMap::parse(&text);
+        let span = map.element(&element.name)?;
+
+        // Point at the relation line of a missing or incompatible target
+        let target_line = location.target.as_deref().and_then(|target| {
+            relation_lines(&text, span).into_iter().find(|line| {
+                source_map::parse_relation_key(&text[line.clone()], &location.file, self.root())
+                    .is_some_and(|(_, line_target)| line_target == target)
+            })
+        });
+        let range = target_line.unwrap_or_else(|| span.header.clone());
+        Some((location.file.clone(), range_at(&text, trim_range(&text, range))))
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let file_path = self.file_path(&position.text_document.uri)?;
+        let text = self.text(&file_path)?;
+        let offset = offset_at(&text, position.position);
+
+        let map = SourceMap::parse(&text);
+        let span = map.elements.iter().find(|span| span.body.start <= offset && offset <= span.body.end)?;
+        let relations = span.subsection(&SubSection::Relations)?;
+        if offset < relations.body.start || offset > relations.body.end {
+            return None;
+        }
+
+        let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let prefix = &text[line_start..offset];
+        let item = prefix.trim_start();
+        if !item.starts_with('*') && !item.starts_with('-') {
+            return None;
+        }
+
+        let Some(colon) = item.find(':') else {
+            let mut relation_types: Vec<_> = RELATION_TYPES.values().collect();
+            relation_types.sort_by_key(|info| info.name);
+            let items = relation_types
+                .into_iter()
+                .map(|info| CompletionItem {
+                    label: info.name.to_string(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    detail: Some(info.description.to_string()),
+                    insert_text: Some(format!("{}: ", info.name)),
+                    ..Default::default()
+                })
+                .collect();
+            return Some(CompletionResponse::Array(items));
+        };
+
+        // Replace whatever was typed after the relation type
+        let after_colon = &item[colon + 1..];
+        let target_start = offset - after_colon.trim_start().len();
+        let edit_range = range_at(&text, target_start..offset);
+        let folder = self.root().join(&file_path).parent().map(Path::to_path_buf)?;
+
+        let mut elements: Vec<&Element> = self.elements().collect();
+        elements.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        let items = elements
+            .into_iter()
+            .filter(|element| !(element.file_path == file_path && element.name == span.name))
+            .map(|element| {
+                let link = if element.file_path == file_path {
+                    format!("#{}", element.identifier.split_once('#').map_or("", |(_, fragment)| fragment))
+                } else {
+                    utils::to_relative_identifier(self.model.context(), &element.identifier, &folder, false)
+                        .unwrap_or_else(|_| element.identifier.clone())
+                };
+                let new_text = format!("[{}]({})", element.name, link);
+                CompletionItem {
+                    label: element.name.clone(),
+                    kind: Some(CompletionItemKind::REFERENCE),
+                    detail: Some(element.identifier.clone()),
+                    filter_text: Some(new_text.clone()),
+                    text_edit: Some(CompletionTextEdit::Edit(TextEdit { range: edit_range, new_text })),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let (target, _) = self.target_at(&params.text_document_position_params)?;
+        let location = match target {
+            Target::Element(identifier) => self.element_location(&identifier)?,
+            Target::File(file_path) => Location {
+                uri: self.uri(&file_path)?,
+                range: lsp_types::Range::default(),
+            },
+        };
+        Some(GotoDefinitionResponse::Scalar(location))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let (Target::Element(identifier), _) = self.target_at(&params.text_document_position)? else {
+            return None;
+        };
+
+        let mut locations = Vec::new();
+        if params.context.include_declaration {
+            locations.extend(self.element_location(&identifier));
+        }
+        let mut sources: Vec<&Element> = self
+            .elements()
+            .filter(|element| {
+                element.relations.iter().any(|relation| {
+                    relation.user_created && matches!(&relation.target.link, LinkType::Identifier(target) if *target == identifier)
+                })
+            })
+            .collect();
+        sources.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+        for source in sources {
+            let Some(text) = self.text(&source.file_path) else { continue };
+            let map = SourceMap::parse(&text);
+            let Some(span) = map.element(&source.name) else { continue };
+            let Some(uri) = self.uri(&source.file_path) else { continue };
+            for line in relation_lines(&text, span) {
+                let key = source_map::parse_relation_key(&text[line.clone()], &source.file_path, self.root());
+                if key.is_some_and(|(_, target)| target == identifier) {
+                    locations.push(Location { uri: uri.clone(), range: range_at(&text, trim_range(&text, line)) });
+                }
+            }
+        }
+        Some(locations)
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let (Target::Element(identifier), range) = self.target_at(&params.text_document_position_params)? else {
+            return None;
+        };
+        let element = self.model.graph_registry.get_element(&identifier)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: self.describe(element),
+            }),
+            range: Some(range),
+        })
+    }
+
+    /// Hover text with the element content and its verification status
+    fn describe(&self, element: &Element) -> String {
+        let mut text = format!("**{}** · {}\n\n`{}`\n", element.name, element.element_type.as_str(), element.identifier);
+        if !element.content.trim().is_empty() {
+            text.push_str(&format!("\n{}\n", element.content.trim()));
+        }
+        text.push_str("\n---\n\n");
+
+        let related = |relation_types: &[&str]| -> Vec<String> {
+            element
+                .relations
+                .iter()
+                .filter(|relation| relation_types.contains(&relation.relation_type.name))
+                .map(|relation| match &relation.target.link {
+                    LinkType::Identifier(target) => self
+                        .model
+                        .graph_registry
+                        .get_element(target)
+                        .map_or_else(|| target.clone(), |target| target.name.clone()),
+                    link => link.as_str().to_string(),
+                })
+                .collect()
+        };
+        match element.element_type {
+            ElementType::Verification(_) => {
+                let verified = related(&["verify"]);
+                let satisfied_by = related(&["satisfiedBy"]);
+                if verified.is_empty() {
+                    text.push_str("⚠️ Verifies no requirement\n");
+                } else {
+                    text.push_str(&format!("Verifies: {}\n", verified.join(", ")));
+                }
+                if !satisfied_by.is_empty() {
+                    text.push_str(&format!("\nSatisfied by: {}\n", satisfied_by.join(", ")));
+                }
+            }
+            ElementType::Requirement(_) => {
+                let verified_by = related(&["verifiedBy"]);
+                if verified_by.is_empty() {
+                    text.push_str("⚠️ Not verified\n");
+                } else {
+                    text.push_str(&format!("✅ Verified by: {}\n", verified_by.join(", ")));
+                }
+            }
+            _ => {}
+        }
+        text
+    }
+
+    fn prepare_rename(&self, params: TextDocumentPositionParams) -> Option<PrepareRenameResponse> {
+        let (Target::Element(identifier), range) = self.target_at(&params)? else {
+            return None;
+        };
+        let element = self.model.graph_registry.get_element(&identifier)?;
+        Some(PrepareRenameResponse::RangeWithPlaceholder { range, placeholder: element.name.clone() })
+    }
+
+    /// Renames the element like `reqvire rename` and returns the file changes as a workspace edit
+    fn rename(&self, params: RenameParams) -> Result<WorkspaceEdit, (ErrorCode, String)> {
+        let (target, _) = self
+            .target_at(&params.text_document_position)
+            .ok_or((ErrorCode::InvalidParams, "No element at this position".to_string()))?;
+        let Target::Element(identifier) = target else {
+            return Err((ErrorCode::InvalidParams, "Only elements can be renamed".to_string()));
+        };
+        if !self.unsaved.is_empty() {
+            return Err((ErrorCode::RequestFailed, "Save all files before renaming".to_string()));
+        }
+
+        let mut model = ModelManager::new();
+        model
+            .parse_and_validate(None, &self.excluded_filename_patterns)
+            .map_err(|_| (ErrorCode::RequestFailed, "Fix the validation errors before renaming".to_string()))?;
+        let change = patch::rename_element(
+            &mut model.graph_registry,
+            &identifier,
+            &params.new_name,
+            &self.excluded_filename_patterns,
+            true,
+        )
+        .map_err(|e| (ErrorCode::RequestFailed, e.to_string()))?;
+        if !change.result.succeeded() {
+            let errors: Vec<String> = change
+                .result
+                .operations
+                .iter()
+                .filter_map(|operation| operation.error.clone())
+                .chain(change.result.errors.iter().cloned())
+                .collect();
+            return Err((ErrorCode::RequestFailed, errors.join("; ")));
+        }
+
+        let mut changes = HashMap::new();
+        for file_patch in &change.result.patches {
+            let uri = self
+                .uri(&file_patch.file_path)
+                .ok_or((ErrorCode::InternalError, format!("Invalid path '{}'", file_patch.file_path)))?;
+            changes.insert(uri, text_edits(&file_patch.current, &file_patch.patched));
+        }
+        Ok(WorkspaceEdit { changes: Some(changes), ..Default::default() })
+    }
+
+    /// The element or file at a position, with the range of the header or relation line
+    fn target_at(&self, position: &TextDocumentPositionParams) -> Option<(Target, lsp_types::Range)> {
+        let file_path = self.file_path(&position.text_document.uri)?;
+        let text = self.text(&file_path)?;
+        let offset = offset_at(&text, position.position);
+        let map = SourceMap::parse(&text);
+        let span = map.elements.iter().find(|span| span.body.start <= offset && offset <= span.body.end)?;
+
+        if offset <= span.header.end {
+            let element = self.elements().find(|element| element.file_path == file_path && element.name == span.name)?;
+            return Some((Target::Element(element.identifier.clone()), range_at(&text, span.header.clone())));
+        }
+
+        let line = relation_lines(&text, span)
+            .into_iter()
+            .find(|line| line.start <= offset && offset < line.end.max(line.start + 1))?;
+        let (_, target) = source_map::parse_relation_key(&text[line.clone()], &file_path, self.root())?;
+        let range = range_at(&text, trim_range(&text, line));
+        if self.model.graph_registry.get_element(&target).is_some() {
+            Some((Target::Element(target), range))
+        } else if !target.contains("://") && !target.contains('#') {
+            Some((Target::File(target), range))
+        } else {
+            None
+        }
+    }
+
+    fn element_location(&self, identifier: &str) -> Option<Location> {
+        let element = self.model.graph_registry.get_element(identifier)?;
+        let text = self.text(&element.file_path)?;
+        let map = SourceMap::parse(&text);
+        let span = map.element(&element.name)?;
+        Some(Location { uri: self.uri(&element.file_path)?, range: range_at(&text, span.header.clone()) })
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.model
+            .graph_registry
+            .get_all_elements()
+            .into_iter()
+            .filter(|element| !element.identifier.starts_with("__virtual__"))
+    }
+
+    /// Text of a model file, preferring the open document over the file on disk
+    fn text(&self, file_path: &str) -> Option<String> {
+        match self.documents.get(file_path) {
+            Some(text) => Some(text.clone()),
+            None => std::fs::read_to_string(self.root().join(file_path)).ok(),
+        }
+    }
+
+    /// Model root all model paths are relative to
+    fn root(&self) -> &Path {
+        self.model.graph_registry.context.root()
+    }
+
+    /// Model path of a document, relative to the model root
+    fn file_path(&self, uri: &Url) -> Option<String> {
+        let path = uri.to_file_path().ok()?;
+        let relative = match path.strip_prefix(self.root()) {
+            Ok(relative) => relative.to_path_buf(),
+            // The client may have resolved symlinks the root was reached through
+            Err(_) => path.canonicalize().ok()?.strip_prefix(self.root().canonicalize().ok()?).ok()?.to_path_buf(),
+        };
+        Some(relative.to_string_lossy().to_string())
+    }
+
+    fn uri(&self, file_path: &str) -> Option<Url> {
+        Url::from_file_path(self.root().join(file_path)).ok()
+    }
+}
+
+fn params<P: DeserializeOwned>(request: Request) -> Result<P, (ErrorCode, String)> {
+    serde_json::from_value(request.params).map_err(|e| (ErrorCode::InvalidParams, e.to_string()))
+}
+
+fn notification_params<P: DeserializeOwned>(notification: Notification) -> Result<P, serde_json::Error> {
+    serde_json::from_value(notification.params)
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn response(id: RequestId, result: Result<Value, (ErrorCode, String)>) -> Response {
+    match result {
+        Ok(value) => Response::new_ok(id, value),
+        Err((code, message)) => Response::new_err(id, code as i32, message),
+    }
+}
+
+/// Ranges of the relation lines of an element, line breaks included
+fn relation_lines(text: &str, span: &ElementSpan) -> Vec<Range<usize>> {
+    let Some(relations) = span.subsection(&SubSection::Relations) else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    let mut offset = relations.body.start;
+    for line in text[relations.body.clone()].split_inclusive('\n') {
+        if line.trim_start().starts_with("* ") {
+            lines.push(offset..offset + line.len());
+        }
+        offset += line.len();
+    }
+    lines
+}
+
+/// The range without leading and trailing whitespace
+fn trim_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    start..end.max(start)
+}
+
+fn line_offset(text: &str, line: usize) -> usize {
+    let mut offset = 0;
+    for _ in 0..line {
+        match text[offset..].find('\n') {
+            Some(index) => offset += index + 1,
+            None => return text.len(),
+        }
+    }
+    offset
+}
+
+fn line_end(text: &str, offset: usize) -> usize {
+    text[offset..].find('\n').map_or(text.len(), |index| offset + index)
+}
+
+/// LSP position of a byte offset; characters are counted in UTF-16 code units
+fn position_at(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = text[line_start..offset].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+/// Byte offset of an LSP position, clamped to the end of its line
+fn offset_at(text: &str, position: Position) -> usize {
+    let line_start = line_offset(text, position.line as usize);
+    let end = line_end(text, line_start);
+    let mut units = 0;
+    for (index, character) in text[line_start..end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += character.len_utf16();
+    }
+    end
+}
+
+fn range_at(text: &str, range: Range<usize>) -> lsp_types::Range {
+    lsp_types::Range::new(position_at(text, range.start), position_at(text, range.end))
+}
+
+/// Edits turning `current` into `patched`: one per changed line when the line
+/// structure is kept, as renames do, otherwise a single edit
+fn text_edits(current: &str, patched: &str) -> Vec<TextEdit> {
+    let current_lines: Vec<&str> = current.split_inclusive('\n').collect();
+    let patched_lines: Vec<&str> = patched.split_inclusive('\n').collect();
+    if current_lines.len() != patched_lines.len() {
+        return vec![minimal_edit(current, patched)];
+    }
+
+    let mut edits = Vec::new();
+    let mut offset = 0;
+    for (current_line, patched_line) in current_lines.into_iter().zip(patched_lines) {
+        if current_line != patched_line {
+            let mut edit = minimal_edit(current_line, patched_line);
+            let line_start = position_at(current, offset);
+            for position in [&mut edit.range.start, &mut edit.range.end] {
+                position.line += line_start.line;
+            }
+            edits.push(edit);
+        }
+        offset += current_line.len();
+    }
+    edits
+}
+
+/// A single edit replacing the part of `current` that differs from `patched`
+fn minimal_edit(current: &str, patched: &str) -> TextEdit {
+    let prefix = current
+        .char_indices()
+        .zip(patched.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(current.len().min(patched.len()), |((index, _), _)| index);
+    let suffix = current[prefix..]
+        .chars()
+        .rev()
+        .zip(patched[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum::<usize>();
+    TextEdit {
+        range: range_at(current, prefix..current.len() - suffix),
+        new_text: patched[prefix..patched.len() - suffix].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_count_utf16_code_units() {
+        let text = "# Title\n\nÄ 𝔸 text\n";
+        let offset = text.find("text").unwrap();
+        let position = position_at(text, offset);
+        assert_eq!(position, Position::new(2, 5));
+        assert_eq!(offset_at(text, position), offset);
+        // Positions past the end of a line are clamped to it
+        assert_eq!(offset_at(text, Position::new(0, 99)), 7);
+    }
+
+    #[test]
+    fn test_minimal_edit_replaces_changed_text_only() {
+        let edit = minimal_edit("### Remote Start\n\nText\n", "### Remote Pump Start\n\nText\n");
+        assert_eq!(edit.range, lsp_types::Range::new(Position::new(0, 11), Position::new(0, 11)));
+        assert_eq!(edit.new_text, "Pump ");
+    }
+
+    #[test]
+    fn test_text_edits_per_changed_line() {
+        let current = "### A\n\n* derivedFrom: [A](#a)\n* verifiedBy: [B](#b)\n";
+        let patched = "### AA\n\n* derivedFrom: [AA](#aa)\n* verifiedBy: [B](#b)\n";
+        let edits = text_edits(current, patched);
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].range, lsp_types::Range::new(Position::new(0, 5), Position::new(0, 5)));
+        assert_eq!(edits[1].range.start.line, 2);
+        assert_eq!(edits[1].new_text, "A](#a");
+    }
+}
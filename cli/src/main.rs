@@ -1,6 +1,13 @@
 pub mod cli;
 pub mod config;
+mod api;
+mod refs;
+mod editor;
 mod serve;
+mod static_files;
+mod lsp;
+mod mcp;
+mod watch;
 
 use log::error;
 use crate::cli::handle_command;
@@ -18,11 +25,15 @@ fn main() {
     let uses_json = match &args.command {
         Some(cli::Commands::ChangeImpact { json, .. }) => *json,
         Some(cli::Commands::Format { json, .. }) => *json,
+        Some(cli::Commands::Apply { json, .. }) => *json,
+        Some(cli::Commands::Mv { json, .. }) => *json,
+        Some(cli::Commands::Rename { json, .. }) => *json,
         Some(cli::Commands::Summary { json, .. }) => *json,
         Some(cli::Commands::SectionSummary { json, .. }) => *json,
         Some(cli::Commands::Matrix { json, .. }) => *json,
         Some(cli::Commands::Traces { json, .. }) => *json,
-        Some(cli::Commands::Coverage { json }) => *json,
+        Some(cli::Commands::Coverage { json, .. }) => *json,
+        Some(cli::Commands::Import { source }) => source.json(),
         _ => false,
     };
 
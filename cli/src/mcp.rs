@@ -0,0 +1,459 @@
+use std::io::{BufRead, Write};
+
+use globset::GlobSet;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use reqvire::error::ReqvireError;
+use reqvire::lint;
+use reqvire::model_json;
+use reqvire::patch;
+use reqvire::reports;
+use reqvire::verification_trace;
+use reqvire::ModelManager;
+
+use crate::api::impact_tree_json;
+use crate::config::load_project_config;
+
+/// Protocol versions the server can speak, newest first
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Runs a Model Context Protocol server over stdio until stdin is closed.
+///
+/// Messages are newline-delimited JSON-RPC. The model is parsed again for
+/// every tool call, so edits made by other tools are always seen.
+pub fn run_mcp_server(excluded_filename_patterns: &GlobSet) -> Result<(), ReqvireError> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&message, excluded_filename_patterns),
+            Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &format!("Invalid JSON: {}", e))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Response to a message; notifications and client responses get none
+fn handle_message(message: &Value, excluded_filename_patterns: &GlobSet) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return id
+            .filter(|_| message.get("result").is_none() && message.get("error").is_none())
+            .map(|id| error_response(id, INVALID_REQUEST, "Missing method"));
+    };
+    let id = id?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => call_tool(&params, excluded_filename_patterns),
+        _ => Err((METHOD_NOT_FOUND, format!("Unsupported method '{}'", method))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let protocol_version = requested
+        .filter(|version| PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": { "name": "reqvire", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Query and edit the Reqvire model of this repository. Elements are addressed by identifier (path#fragment) or unique name; edits are validated before any file is written."
+    })
+}
+
+fn tools() -> Vec<Value> {
+    let element = json!({ "type": "string", "description": "Element identifier (path#fragment) or unique element name" });
+    let dry_run = json!({ "type": "boolean", "description": "Validate and return the diffs without writing files" });
+    vec![
+        json!({
+            "name": "validate",
+            "description": "Validate the model and list the errors",
+            "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false }
+        }),
+        json!({
+            "name": "summary",
+            "description": "Summary of the model elements grouped by file and section, in the `summary --json` format",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "filter_file": { "type": "string", "description": "Glob pattern for file paths" },
+                    "filter_name": { "type": "string", "description": "Regular expression for element names" },
+                    "filter_section": { "type": "string", "description": "Glob pattern for section names" },
+                    "filter_type": { "type": "string", "description": "Element type, e.g. system-requirement" },
+                    "filter_content": { "type": "string", "description": "Regular expression for element content" },
+                    "filter_is_not_verified": { "type": "boolean", "description": "Only requirements without verifiedBy relations" },
+                    "filter_is_not_satisfied": { "type": "boolean", "description": "Only requirements without satisfiedBy relations" }
+                },
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "element",
+            "description": "Look up an element with its content, metadata, written relations and the opposite relations added from other elements",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "element": element },
+                "required": ["element"],
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "impact_tree",
+            "description": "Elements affected by a change of an element, as a tree of relations",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "element": element },
+                "required": ["element"],
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "traces",
+            "description": "Upward traces from verifications to the requirements they cover, in the `traces --json` format",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "filter_id": { "type": "string", "description": "Verification identifier" },
+                    "filter_name": { "type": "string", "description": "Regular expression for verification names" },
+                    "filter_type": { "type": "string", "description": "Verification type, e.g. test-verification" },
+                    "from_folder": { "type": "string", "description": "Folder links are made relative to" }
+                },
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "coverage",
+            "description": "Verification coverage report, or satisfaction coverage of requirements by implementations",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "satisfaction": { "type": "boolean", "description": "Report satisfiedBy coverage instead of verification coverage" }
+                },
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "lint",
+            "description": "Model quality issues, split into auto-fixable issues and issues that need manual review",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "fixable": { "type": "boolean", "description": "Only auto-fixable issues" },
+                    "auditable": { "type": "boolean", "description": "Only issues that need manual review" }
+                },
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "add_element",
+            "description": "Add an element to a file and section. The file is only written when the edited model is valid.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "file": { "type": "string", "description": "File path relative to the project root" },
+                    "section": { "type": "string", "description": "Section name, default Requirements" },
+                    "type": { "type": "string", "description": "Element type, e.g. system-requirement" },
+                    "content": { "type": "string" },
+                    "metadata": { "type": "object", "additionalProperties": { "type": "string" } },
+                    "dry_run": dry_run
+                },
+                "required": ["name", "file"],
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "add_relation",
+            "description": "Add a relation to an element. Files are only written when the edited model is valid.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "source": { "type": "string", "description": "Element identifier (path#fragment)" },
+                    "type": { "type": "string", "description": "Relation type, e.g. derivedFrom" },
+                    "target": { "type": "string", "description": "Element identifier, file path or URL" },
+                    "kind": { "type": "string", "enum": ["element", "file", "url"] },
+                    "text": { "type": "string", "description": "Link text of URLs" },
+                    "dry_run": dry_run
+                },
+                "required": ["source", "type", "target"],
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "move_element",
+            "description": "Move an element to another file and section and rewrite the relations referring to it. Files are only written when the edited model is valid.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "element": element,
+                    "file": { "type": "string", "description": "File path relative to the project root" },
+                    "section": { "type": "string", "description": "Section name, default the current section" },
+                    "dry_run": dry_run
+                },
+                "required": ["element", "file"],
+                "additionalProperties": false
+            }
+        }),
+    ]
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SummaryArgs {
+    filter_file: Option<String>,
+    filter_name: Option<String>,
+    filter_section: Option<String>,
+    filter_type: Option<String>,
+    filter_content: Option<String>,
+    filter_is_not_verified: bool,
+    filter_is_not_satisfied: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ElementArgs {
+    element: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TracesArgs {
+    filter_id: Option<String>,
+    filter_name: Option<String>,
+    filter_type: Option<String>,
+    from_folder: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CoverageArgs {
+    satisfaction: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LintArgs {
+    fixable: bool,
+    auditable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveArgs {
+    element: String,
+    file: String,
+    #[serde(default)]
+    section: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+fn call_tool(params: &Value, excluded_filename_patterns: &GlobSet) -> Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let arguments = match params.get("arguments") {
+        None | Some(Value::Null) => json!({}),
+        Some(arguments) => arguments.clone(),
+    };
+    if !tools().iter().any(|tool| tool["name"] == name) {
+        return Err((INVALID_PARAMS, format!("Unknown tool '{}'", name)));
+    }
+
+    let result = run_tool(name, arguments, excluded_filename_patterns);
+    let (value, is_error) = match result {
+        Ok(outcome) => outcome,
+        Err(e) => (json!({ "errors": [e.to_string()] }), true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": serde_json::to_string_pretty(&value).unwrap() }],
+        "structuredContent": value,
+        "isError": is_error
+    }))
+}
+
+/// Result of a tool and whether it failed
+fn run_tool(name: &str, arguments: Value, excluded_filename_patterns: &GlobSet) -> Result<(Value, bool), ReqvireError> {
+    let mut model_manager = ModelManager::new();
+    let errors = match model_manager.parse_and_validate(None, excluded_filename_patterns) {
+        Ok(errors) => errors,
+        Err(ReqvireError::ValidationError(errors)) => errors,
+        Err(e) => vec![e],
+    };
+    let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    if name == "validate" {
+        return Ok((json!({ "valid": errors.is_empty(), "errors": errors }), false));
+    }
+    // Like the CLI, nothing is reported or edited on an invalid model
+    if !errors.is_empty() {
+        return Ok((json!({ "errors": errors }), true));
+    }
+    let registry = &mut model_manager.graph_registry;
+
+    let value = match name {
+        "summary" => {
+            let args: SummaryArgs = arguments_of(arguments)?;
+            let filters = reports::Filters::new(
+                args.filter_file.as_deref(),
+                args.filter_name.as_deref(),
+                args.filter_section.as_deref(),
+                args.filter_type.as_deref(),
+                args.filter_content.as_deref(),
+                args.filter_is_not_verified,
+                args.filter_is_not_satisfied,
+            )?;
+            reports::registry_summary_json(registry, &filters)
+        }
+        "element" => {
+            let args: ElementArgs = arguments_of(arguments)?;
+            let identifier = patch::resolve_element(registry, &args.element)?;
+            let element = registry.get_element(&identifier).expect("resolved elements exist");
+            let exported = model_json::export_element(element);
+            let opposite_relations: Vec<model_json::ModelRelation> = element
+                .relations
+                .iter()
+                .filter(|relation| !relation.user_created)
+                .map(model_json::export_relation)
+                .collect();
+            json!({
+                "identifier": element.identifier,
+                "name": element.name,
+                "type": exported.element_type,
+                "file": element.file_path,
+                "section": element.section,
+                "content": exported.content,
+                "metadata": exported.metadata,
+                "relations": exported.relations,
+                "opposite_relations": opposite_relations
+            })
+        }
+        "impact_tree" => {
+            let args: ElementArgs = arguments_of(arguments)?;
+            let identifier = patch::resolve_element(registry, &args.element)?;
+            impact_tree_json(&registry.get_impact_tree(&identifier))
+        }
+        "traces" => {
+            let args: TracesArgs = arguments_of(arguments)?;
+            let generator = verification_trace::VerificationTraceGenerator::new(registry, false, args.from_folder);
+            let report = verification_trace::apply_filters(
+                generator.generate(),
+                args.filter_id.as_deref(),
+                args.filter_name.as_deref(),
+                args.filter_type.as_deref(),
+            )?;
+            to_json(&report)?
+        }
+        "coverage" => {
+            let args: CoverageArgs = arguments_of(arguments)?;
+            if args.satisfaction {
+                to_json(&reports::generate_satisfaction_coverage_report(registry))?
+            } else {
+                let project_config = load_project_config()?;
+                to_json(&reports::generate_coverage_report(registry, &project_config.verification_rules))?
+            }
+        }
+        "lint" => {
+            let args: LintArgs = arguments_of(arguments)?;
+            to_json(&lint::analyze_model(registry).filtered(args.fixable, args.auditable))?
+        }
+        "add_element" | "add_relation" => {
+            let Value::Object(mut operation) = arguments else {
+                return Err(ReqvireError::ProcessError("Tool arguments must be an object".to_string()));
+            };
+            let dry_run = match operation.remove("dry_run") {
+                Some(value) => value.as_bool().ok_or_else(|| {
+                    ReqvireError::ProcessError("'dry_run' must be a boolean".to_string())
+                })?,
+                None => false,
+            };
+            operation.insert("op".to_string(), json!(name.replace('_', "-")));
+            let entries = patch::parse_patch(&Value::Array(vec![Value::Object(operation)]).to_string())?;
+            let result = patch::apply_patch(registry, &entries, excluded_filename_patterns, dry_run)?;
+            return Ok((result.to_json(), !result.succeeded()));
+        }
+        "move_element" => {
+            let args: MoveArgs = arguments_of(arguments)?;
+            let change = patch::move_element(
+                registry,
+                &args.element,
+                &args.file,
+                args.section.as_deref(),
+                excluded_filename_patterns,
+                args.dry_run,
+            )?;
+            return Ok((change.to_json(), !change.result.succeeded()));
+        }
+        _ => unreachable!("tool names are checked before running"),
+    };
+    Ok((value, false))
+}
+
+fn arguments_of<T: DeserializeOwned>(arguments: Value) -> Result<T, ReqvireError> {
+    serde_json::from_value(arguments).map_err(|e| ReqvireError::ProcessError(format!("Invalid arguments: {}", e)))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, ReqvireError> {
+    serde_json::to_value(value).map_err(|e| ReqvireError::SerializationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_negotiates_protocol_version() {
+        let patterns = GlobSet::empty();
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "protocolVersion": "2024-11-05" } });
+        let response = handle_message(&request, &patterns).unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+
+        let request = json!({ "jsonrpc": "2.0", "id": 2, "method": "initialize", "params": { "protocolVersion": "1999-01-01" } });
+        let response = handle_message(&request, &patterns).unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+    }
+
+    #[test]
+    fn test_notifications_and_unknown_methods() {
+        let patterns = GlobSet::empty();
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle_message(&notification, &patterns).is_none());
+
+        let request = json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" });
+        let response = handle_message(&request, &patterns).unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let request = json!({ "jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": { "name": "delete_everything" } });
+        let response = handle_message(&request, &patterns).unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,544 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use globset::GlobSet;
+use tiny_http::{Request, Response};
+
+use reqvire::change_impact::{self, ChangeImpactReport, RelationSummary};
+use reqvire::error::ReqvireError;
+use reqvire::export;
+use reqvire::git_commands;
+use reqvire::graph_registry::{ElementNode, GraphRegistry};
+use reqvire::project_config::ProjectConfig;
+use reqvire::utils;
+use reqvire::{ModelContext, ModelManager};
+
+use crate::api::{percent_decode, ApiError, Query};
+use crate::serve::{append_to_body, ExportDir};
+use crate::static_files;
+
+/// URL prefix of the exports of git refs, followed by the full commit hash
+pub const REF_PREFIX: &str = "/ref/";
+
+/// Path of the page comparing the models of two refs
+pub const COMPARE_PATH: &str = "/compare";
+
+/// Number of refs whose models and exports are kept at a time
+const MAX_REF_SITES: usize = 8;
+
+const COMPARE_STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+form{margin-bottom:1.5em}input{margin:0 .5em;padding:.2em}\
+table{border-collapse:collapse;width:100%;table-layout:fixed;margin-bottom:2em}\
+th,td{border:1px solid #ddd;padding:.5em;vertical-align:top;text-align:left}\
+thead th{background:#f4f4f4}tbody th{background:#fafafa}\
+pre{white-space:pre-wrap;margin:0}.none{color:#999}\
+.added{color:#176f2c}.removed{color:#b31d28}.changed{color:#9a6700}";
+
+/// Model served at the root of the site, which refs are compared against by default
+pub(crate) trait RootModel {
+    fn with_registry<R>(&self, f: impl FnOnce(&GraphRegistry) -> R) -> R;
+}
+
+impl RootModel for GraphRegistry {
+    fn with_registry<R>(&self, f: impl FnOnce(&GraphRegistry) -> R) -> R {
+        f(self)
+    }
+}
+
+impl RootModel for Mutex<ModelManager> {
+    fn with_registry<R>(&self, f: impl FnOnce(&GraphRegistry) -> R) -> R {
+        f(&self.lock().unwrap().graph_registry)
+    }
+}
+
+/// Models and exports of git refs, built on first request.
+///
+/// Commits never change, so both are kept until more recently requested
+/// commits evict them; the commit of `serve --ref` is never evicted.
+pub struct RefSites {
+    /// Model the refs are taken from
+    context: ModelContext,
+    excluded_filename_patterns: GlobSet,
+    project_config: ProjectConfig,
+    /// Commit served in place of the working tree with `serve --ref`
+    root_commit: Option<String>,
+    /// Sites of the commits, from least to most recently used
+    sites: Mutex<Vec<Arc<RefSite>>>,
+}
+
+/// Model of a commit and its export
+pub struct RefSite {
+    pub commit: String,
+    pub model_manager: ModelManager,
+    /// Validation errors of the model at the commit
+    errors: Vec<String>,
+    /// Export, or the errors that prevented it
+    export: OnceLock<Result<ExportDir, Vec<String>>>,
+}
+
+impl RefSites {
+    pub fn new(
+        context: ModelContext,
+        excluded_filename_patterns: &GlobSet,
+        project_config: &ProjectConfig,
+        root_commit: Option<String>,
+    ) -> Self {
+        Self {
+            context,
+            excluded_filename_patterns: excluded_filename_patterns.clone(),
+            project_config: project_config.clone(),
+            root_commit,
+            sites: Mutex::default(),
+        }
+    }
+
+    /// Returns the model of a commit, parsing it on first use
+    pub fn site(&self, commit: &str) -> Arc<RefSite> {
+        {
+            let mut sites = self.sites.lock().unwrap();
+            if let Some(position) = sites.iter().position(|site| site.commit == commit) {
+                let site = sites.remove(position);
+                sites.push(Arc::clone(&site));
+                return site;
+            }
+        }
+
+        let mut model_manager = ModelManager::with_context(self.context.clone());
+        let result = model_manager.parse_and_validate(Some(commit), &self.excluded_filename_patterns);
+        let errors = match result {
+            Ok(_) => Vec::new(),
+            Err(ReqvireError::ValidationError(errors)) => errors.iter().map(|e| e.to_string()).collect(),
+            Err(e) => vec![e.to_string()],
+        };
+
+        let site = Arc::new(RefSite {
+            commit: commit.to_string(),
+            model_manager,
+            errors,
+            export: OnceLock::new(),
+        });
+        let mut sites = self.sites.lock().unwrap();
+        sites.push(Arc::clone(&site));
+        // Exports are removed once no request uses their site anymore
+        while sites.len() > MAX_REF_SITES {
+            match sites.iter().position(|site| self.root_commit.as_ref() != Some(&site.commit)) {
+                Some(position) => sites.remove(position),
+                None => break,
+            };
+        }
+        drop(sites);
+        site
+    }
+
+    /// Returns the export directory of a commit, exporting it on first use
+    pub fn export<'a>(&self, site: &'a RefSite) -> &'a Result<ExportDir, Vec<String>> {
+        site.export.get_or_init(|| {
+            if !site.errors.is_empty() {
+                return Err(site.errors.clone());
+            }
+            ExportDir::new("reqvire-ref-", || {
+                export::generate_artifacts_at_commit(
+                    &site.model_manager.graph_registry,
+                    &site.commit,
+                    false, // always generate links without blobs for Serve
+                    &self.project_config,
+                )
+            })
+            .map_err(|e| vec![e.to_string()])
+        })
+    }
+
+    /// Answers requests for ref exports and the compare page, or returns
+    /// `None` for other requests
+    pub(crate) fn respond<M: RootModel>(&self, request: &Request, root: &M) -> Option<Response<Cursor<Vec<u8>>>> {
+        let url = request.url();
+        if let Some(rest) = url.strip_prefix(REF_PREFIX) {
+            return Some(self.ref_response(request, rest));
+        }
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        if path == COMPARE_PATH {
+            return Some(self.compare_response(request, query, root));
+        }
+        None
+    }
+
+    fn ref_response(&self, request: &Request, rest: &str) -> Response<Cursor<Vec<u8>>> {
+        let (name, path) = match rest.find(['/', '?']) {
+            Some(position) => (&rest[..position], &rest[position..]),
+            None => (rest, ""),
+        };
+        let name = percent_decode(name);
+        let commit = if self.sites.lock().unwrap().iter().any(|site| site.commit == name) {
+            name.clone()
+        } else {
+            match git_commands::resolve_commit(self.context.root(), &name) {
+                Ok(commit) => commit,
+                Err(e) => return error_page(404, "Unknown git reference", &[e.to_string()]),
+            }
+        };
+
+        // Branch and tag names move, so they redirect to the commit they point to
+        if name != commit || !path.starts_with('/') {
+            let path = if path.starts_with('/') { path.to_string() } else { format!("/{}", path) };
+            return static_files::redirect(302, &format!("{}{}{}", REF_PREFIX, commit, path));
+        }
+
+        let site = self.site(&commit);
+        match self.export(&site) {
+            Ok(directory) => static_files::response(request, path, directory.path(), |page| {
+                self.with_switcher(&page, Some(&commit))
+            }),
+            Err(errors) => error_page(500, &format!("The model at {} cannot be exported", short(&commit)), errors),
+        }
+    }
+
+    fn compare_response<M: RootModel>(&self, request: &Request, query: &str, root: &M) -> Response<Cursor<Vec<u8>>> {
+        let query = match Query::parse(query, &["from", "to"]) {
+            Ok(query) => query,
+            Err(ApiError::BadRequest(message) | ApiError::NotFound(message)) => {
+                return error_page(400, "Invalid comparison", &[message]);
+            }
+        };
+        let from = query.get("from").filter(|reference| !reference.is_empty());
+        let to = query.get("to").filter(|reference| !reference.is_empty());
+
+        let mut body = compare_form(from, to, &self.ref_names());
+        let mut current = self.root_commit.clone();
+        if let Some(from) = from {
+            match self.compare(from, to, root) {
+                Ok((report, to_commit)) => {
+                    let to_label = match to {
+                        Some(to) => to.to_string(),
+                        None => self.root_label(),
+                    };
+                    let to_prefix = match &to_commit {
+                        Some(commit) => format!("{}{}/", REF_PREFIX, commit),
+                        None => "/".to_string(),
+                    };
+                    let from_prefix = format!("{}{}/", REF_PREFIX, percent_encode(from));
+                    body.push_str(&render_report(&report, (from, &from_prefix), (&to_label, &to_prefix)));
+                    current = to_commit.or(current);
+                }
+                Err(e) => return error_page(404, "Cannot compare", &[e.to_string()]),
+            }
+        }
+
+        let page = format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Change Impact</title><style>{}</style></head>\
+             <body><h1>Change Impact</h1>{}</body></html>",
+            COMPARE_STYLE, body
+        );
+        let page = self.with_switcher(&page, current.as_deref());
+        static_files::content_response(request, "text/html; charset=utf-8", page.into_bytes())
+    }
+
+    /// Computes the change impact from one ref to another, or to the root model.
+    ///
+    /// Returns the report and the commit compared against, `None` for the root model.
+    fn compare<M: RootModel>(
+        &self,
+        from: &str,
+        to: Option<&str>,
+        root: &M,
+    ) -> Result<(ChangeImpactReport, Option<String>), ReqvireError> {
+        let reference = self.site(&git_commands::resolve_commit(self.context.root(), from)?);
+        match to {
+            Some(to) => {
+                let current = self.site(&git_commands::resolve_commit(self.context.root(), to)?);
+                let report = change_impact::compute_change_impact(
+                    &current.model_manager.graph_registry,
+                    &reference.model_manager.graph_registry,
+                )?;
+                Ok((report, Some(current.commit.clone())))
+            }
+            None => {
+                let report = root.with_registry(|registry| {
+                    change_impact::compute_change_impact(registry, &reference.model_manager.graph_registry)
+                })?;
+                Ok((report, None))
+            }
+        }
+    }
+
+    fn root_label(&self) -> String {
+        match &self.root_commit {
+            Some(commit) => short(commit).to_string(),
+            None => "Working tree".to_string(),
+        }
+    }
+
+    fn ref_names(&self) -> Vec<(String, String)> {
+        git_commands::list_refs(self.context.root()).unwrap_or_default()
+    }
+
+    /// Adds the ref switcher to a page of the given commit, `None` for the working tree
+    pub fn with_switcher(&self, page: &str, current: Option<&str>) -> String {
+        let mut options = String::new();
+        if self.root_commit.is_none() {
+            options.push_str(&option("/", "Working tree", current.is_none()));
+        }
+        let mut selected = false;
+        for (name, commit) in self.ref_names() {
+            let is_current = !selected && current == Some(commit.as_str());
+            selected |= is_current;
+            let label = format!("{} · {}", name, short(&commit));
+            options.push_str(&option(&format!("{}{}/", REF_PREFIX, commit), &label, is_current));
+        }
+        if let Some(commit) = current.filter(|_| !selected) {
+            options.push_str(&option(&format!("{}{}/", REF_PREFIX, commit), short(commit), true));
+        }
+
+        let compare = match current {
+            Some(commit) if self.root_commit.as_deref() != Some(commit) => format!("{}?from={}", COMPARE_PATH, commit),
+            _ => COMPARE_PATH.to_string(),
+        };
+        let switcher = format!(
+            "<nav id=\"reqvire-refs\" style=\"position:fixed;right:1em;bottom:1em;z-index:9999;padding:.4em .6em;\
+             background:#fff;border:1px solid #ccc;border-radius:4px;box-shadow:0 1px 4px rgba(0,0,0,.2);font:13px sans-serif\">\
+             <select aria-label=\"Git ref\" onchange=\"location.href=this.value+location.pathname.replace(/^\\/(ref\\/[0-9a-f]+\\/)?/,'')+location.hash\">{}</select> \
+             <a href=\"{}\">Compare</a></nav>",
+            options, compare
+        );
+        append_to_body(page, &switcher)
+    }
+
+    /// Drops the models of all refs, removing their exports
+    pub fn cleanup(&self) {
+        self.sites.lock().unwrap().clear();
+    }
+}
+
+fn option(value: &str, label: &str, selected: bool) -> String {
+    format!(
+        "<option value=\"{}\"{}>{}</option>",
+        utils::escape_html(value),
+        if selected { " selected" } else { "" },
+        utils::escape_html(label)
+    )
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+/// Encodes a ref name for use as a single path segment
+pub(crate) fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn compare_form(from: Option<&str>, to: Option<&str>, refs: &[(String, String)]) -> String {
+    let names: String = refs
+        .iter()
+        .map(|(name, _)| format!("<option value=\"{}\">", utils::escape_html(name)))
+        .collect();
+    format!(
+        "<form method=\"get\" action=\"{}\"><label>From<input name=\"from\" list=\"reqvire-ref-names\" required value=\"{}\"></label>\
+         <label>To<input name=\"to\" list=\"reqvire-ref-names\" placeholder=\"Working tree\" value=\"{}\"></label>\
+         <button type=\"submit\">Compare</button><datalist id=\"reqvire-ref-names\">{}</datalist></form>",
+        COMPARE_PATH,
+        utils::escape_html(from.unwrap_or("")),
+        utils::escape_html(to.unwrap_or("")),
+        names
+    )
+}
+
+/// Renders a change impact report with the `from` and `to` side of each element
+/// next to each other. Sides are given as label and URL prefix of their export.
+fn render_report(report: &ChangeImpactReport, from: (&str, &str), to: (&str, &str)) -> String {
+    let mut html = format!(
+        "<p class=\"summary\">{} changed, {} added, {} removed, {} invalidated verification(s)</p>",
+        report.changed.len(),
+        report.added.len(),
+        report.removed.len(),
+        report.invalidated_verifications.len()
+    );
+    if report.changed.is_empty() && report.added.is_empty() && report.removed.is_empty() {
+        html.push_str("<p>Nothing to report.</p>");
+        return html;
+    }
+
+    let header = format!(
+        "<table><thead><tr><th>{}</th><th>{}</th></tr></thead><tbody>",
+        utils::escape_html(from.0),
+        utils::escape_html(to.0)
+    );
+    let none = "<p class=\"none\">—</p>";
+
+    if !report.changed.is_empty() {
+        html.push_str("<h2>Changed Elements</h2>");
+        html.push_str(&header);
+        for element in &report.changed {
+            html.push_str(&element_heading(&element.name, &element.element_id, to.1));
+            html.push_str(&format!(
+                "<tr><td><pre>{}</pre>{}</td><td><pre>{}</pre>{}</td></tr>",
+                utils::escape_html(&element.old_content),
+                relation_list(&element.removed_relations, "removed", "−"),
+                utils::escape_html(&element.new_content),
+                relation_list(&element.added_relations, "added", "+")
+            ));
+            html.push_str(&impact_row(&element.change_impact_tree, to.1));
+        }
+        html.push_str("</tbody></table>");
+    }
+
+    if !report.added.is_empty() {
+        html.push_str("<h2>New Elements</h2>");
+        html.push_str(&header);
+        for element in &report.added {
+            html.push_str(&element_heading(&element.name, &element.element_id, to.1));
+            html.push_str(&format!(
+                "<tr><td>{}</td><td><pre>{}</pre>{}</td></tr>",
+                none,
+                utils::escape_html(&element.new_content),
+                relation_list(&element.added_relations, "added", "+")
+            ));
+            html.push_str(&impact_row(&element.change_impact_tree, to.1));
+        }
+        html.push_str("</tbody></table>");
+    }
+
+    if !report.removed.is_empty() {
+        html.push_str("<h2>Removed Elements</h2>");
+        html.push_str(&header);
+        for element in &report.removed {
+            html.push_str(&element_heading(&element.name, &element.element_id, from.1));
+            html.push_str(&format!(
+                "<tr><td><pre>{}</pre>{}</td><td>{}</td></tr>",
+                utils::escape_html(&element.old_content),
+                relation_list(&element.removed_relations, "removed", "−"),
+                none
+            ));
+        }
+        html.push_str("</tbody></table>");
+    }
+
+    if !report.invalidated_verifications.is_empty() {
+        html.push_str("<h2>Invalidated Verifications</h2><ul>");
+        for verification in &report.invalidated_verifications {
+            html.push_str(&format!(
+                "<li>{}</li>",
+                element_link(&verification.name, &verification.element_id, to.1)
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+fn element_heading(name: &str, identifier: &str, prefix: &str) -> String {
+    format!(
+        "<tr><th colspan=\"2\">{} <code>{}</code></th></tr>",
+        element_link(name, identifier, prefix),
+        utils::escape_html(identifier)
+    )
+}
+
+/// Links an element to its page in the export under `prefix`
+fn element_link(name: &str, identifier: &str, prefix: &str) -> String {
+    format!("<a href=\"{}\">{}</a>", utils::escape_html(&element_href(identifier, prefix)), utils::escape_html(name))
+}
+
+/// URL of an element's page in the export under `prefix`
+pub(crate) fn element_href(identifier: &str, prefix: &str) -> String {
+    let (file, fragment) = identifier.split_once('#').unwrap_or((identifier, ""));
+    let page = match file.strip_suffix(".md") {
+        Some(stem) => format!("{}.html", stem),
+        None => file.to_string(),
+    };
+    if fragment.is_empty() {
+        format!("{}{}", prefix, page)
+    } else {
+        format!("{}{}#{}", prefix, page, fragment)
+    }
+}
+
+fn relation_list(relations: &[RelationSummary], class: &str, sign: &str) -> String {
+    if relations.is_empty() {
+        return String::new();
+    }
+    let items: String = relations
+        .iter()
+        .map(|relation| {
+            format!(
+                "<li class=\"{}\">{} {}: {}</li>",
+                class,
+                sign,
+                utils::escape_html(&relation.relation_type),
+                utils::escape_html(relation.target.link.as_str())
+            )
+        })
+        .collect();
+    format!("<ul>{}</ul>", items)
+}
+
+fn impact_row(tree: &ElementNode, prefix: &str) -> String {
+    if tree.relations.is_empty() {
+        return String::new();
+    }
+    format!("<tr><td colspan=\"2\">Impact:{}</td></tr>", impact_tree(tree, prefix))
+}
+
+/// Nested list of the elements affected through each relation
+fn impact_tree(node: &ElementNode, prefix: &str) -> String {
+    if node.relations.is_empty() {
+        return String::new();
+    }
+    let items: String = node
+        .relations
+        .iter()
+        .map(|relation| {
+            let child = &relation.element_node;
+            format!(
+                "<li>{} {}{}{}</li>",
+                utils::escape_html(&relation.relation_trigger),
+                element_link(&child.element.name, &child.element.identifier, prefix),
+                if child.element.changed_since_commit { " <span class=\"changed\">⚠️ changed</span>" } else { "" },
+                impact_tree(child, prefix)
+            )
+        })
+        .collect();
+    format!("<ul>{}</ul>", items)
+}
+
+fn error_page(status: u16, title: &str, messages: &[String]) -> Response<Cursor<Vec<u8>>> {
+    let items: String = messages
+        .iter()
+        .map(|message| format!("<li>{}</li>", utils::escape_html(message)))
+        .collect();
+    let page = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title><style>{1}</style></head>\
+         <body><h1>{0}</h1><ul>{2}</ul><p><a href=\"/\">Back</a></p></body></html>",
+        utils::escape_html(title),
+        COMPARE_STYLE,
+        items
+    );
+    static_files::page_response(status, page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_link_points_to_exported_page() {
+        assert_eq!(
+            element_link("Remote Start", "specifications/Requirements.md#remote-start", "/ref/abc/"),
+            "<a href=\"/ref/abc/specifications/Requirements.html#remote-start\">Remote Start</a>"
+        );
+        assert_eq!(
+            element_link("A & B", "docs/Notes.md", "/"),
+            "<a href=\"/docs/Notes.html\">A &amp; B</a>"
+        );
+    }
+
+    #[test]
+    fn test_percent_encode_ref_names() {
+        assert_eq!(percent_encode("release/v1.0"), "release%2Fv1.0");
+        assert_eq!(percent_decode(&percent_encode("feature/ä b")), "feature/ä b");
+    }
+}
@@ -1,14 +1,181 @@
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use globset::GlobSet;
+use tempfile::TempDir;
+use tiny_http::{Request, Response, Server};
+
 use reqvire::error::ReqvireError;
+use reqvire::export;
+use reqvire::filesystem;
+use reqvire::git_commands;
+use reqvire::graph_registry::GraphRegistry;
+use reqvire::project_config::ProjectConfig;
+use reqvire::utils;
+use reqvire::{ModelContext, ModelManager};
+
+use crate::api;
+use crate::config::load_project_config;
+use crate::editor::{self, Editor};
+use crate::refs::{self, RefSites};
+use crate::static_files;
+use crate::watch;
+
+/// Path of the Server-Sent Events stream announcing rebuilds
+const EVENTS_PATH: &str = "/__reqvire/events";
+
+/// Script injected into served pages to reload them after a rebuild
+const RELOAD_SCRIPT: &str = "<script>new EventSource(\"/__reqvire/events\").addEventListener(\"reload\", () => location.reload());</script>";
+
+/// Page served before the first successful export
+const EMPTY_PAGE: &str = "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Reqvire</title></head><body></body></html>";
+
+/// Serializes model edits with the rebuilds of the served site, so every
+/// edit starts from the site the previous one produced
+pub(crate) static EXPORT_LOCK: Mutex<()> = Mutex::new(());
 
-/// Starts an HTTP server serving static files from the given directory
+/// Starts an HTTP server serving static files from the given directory,
+/// the read-only JSON API from the given model and exports of git refs
 pub fn serve_directory(
     directory: &Path,
     host: &str,
     port: u16,
+    registry: &GraphRegistry,
+    project_config: &ProjectConfig,
+    excluded_filename_patterns: &GlobSet,
 ) -> Result<(), ReqvireError> {
-    use tiny_http::{Server, Response, Header};
+    let refs = RefSites::new(registry.context.clone(), excluded_filename_patterns, project_config, None);
+    let server = start_server(host, port)?;
+
+    // Serve requests
+    for request in server.incoming_requests() {
+        let response = if request.url().starts_with(api::API_PREFIX) {
+            let (status, body) = api::respond(registry, project_config, request.url());
+            json_response(status, &body)
+        } else if let Some(response) = refs.respond(&request, registry) {
+            response
+        } else {
+            static_files::response(&request, request.url(), directory, |page| refs.with_switcher(&page, None))
+        };
+        let _ = request.respond(response);
+    }
+
+    refs.cleanup();
+    Ok(())
+}
+
+/// Serves the model at a git ref in place of the working tree.
+///
+/// The ref is resolved to its commit once and served under `/ref/<commit>/`;
+/// other refs stay reachable through the ref switcher.
+pub fn serve_ref(
+    reference: &str,
+    host: &str,
+    port: u16,
+    excluded_filename_patterns: &GlobSet,
+) -> Result<(), ReqvireError> {
+    let context = ModelContext::from_current_dir();
+    let commit = git_commands::resolve_commit(context.root(), reference)?;
+    let project_config = load_project_config()?;
+    let refs = RefSites::new(context, excluded_filename_patterns, &project_config, Some(commit.clone()));
+
+    // Fail before starting the server if the ref cannot be exported
+    let site = refs.site(&commit);
+    if let Err(errors) = refs.export(&site) {
+        return Err(ReqvireError::ProcessError(format!(
+            "Cannot export the model at {}:\n{}",
+            reference,
+            errors.join("\n")
+        )));
+    }
+    let registry = &site.model_manager.graph_registry;
+
+    let server = start_server(host, port)?;
+    for request in server.incoming_requests() {
+        let response = if request.url().starts_with(api::API_PREFIX) {
+            let (status, body) = api::respond(registry, &project_config, request.url());
+            json_response(status, &body)
+        } else if let Some(response) = refs.respond(&request, registry) {
+            response
+        } else {
+            static_files::redirect(302, &format!("{}{}{}", refs::REF_PREFIX, commit, request.url()))
+        };
+        let _ = request.respond(response);
+    }
 
+    refs.cleanup();
+    Ok(())
+}
+
+/// Serves the exported model and rebuilds it whenever model files change.
+///
+/// Open pages reload through Server-Sent Events after each rebuild. While the
+/// model has validation errors, the last valid export is served with an
+/// overlay listing the errors. With `edit`, pages link to forms editing the
+/// model files.
+pub fn serve_watch(
+    host: &str,
+    port: u16,
+    excluded_filename_patterns: &GlobSet,
+    edit: bool,
+) -> Result<(), ReqvireError> {
+    let context = ModelContext::from_current_dir();
+    let root = context.scan_root().to_path_buf();
+    let receiver = watch::spawn_watcher(&root)?;
+    let project_config = load_project_config()?;
+
+    let refs = RefSites::new(context, excluded_filename_patterns, &project_config, None);
+    let editor = edit.then(|| Editor::new(excluded_filename_patterns));
+    let site = Arc::new(LiveSite::new(project_config, excluded_filename_patterns, refs, editor));
+    {
+        let _export = EXPORT_LOCK.lock().unwrap();
+        let mut model_manager = site.model.lock().unwrap();
+        let result = model_manager.parse_and_validate(None, excluded_filename_patterns);
+        site.rebuild(&model_manager, result);
+    }
+
+    let server = start_server(host, port)?;
+    if edit {
+        println!("✏️  Editing enabled, new elements at {}\n", editor::NEW_PATH);
+    }
+    println!("👀 Watching {} for changes\n", root.display());
+
+    let server_site = Arc::clone(&site);
+    std::thread::spawn(move || {
+        // Quiet mode is per thread, and edits and refs are exported on this one
+        utils::enable_quiet_mode();
+        for request in server.incoming_requests() {
+            server_site.respond(request);
+        }
+    });
+
+    // The model is locked only while it is updated, not while waiting for changes
+    while let Some(batch) = watch::next_batch(&receiver) {
+        let export = EXPORT_LOCK.lock().unwrap();
+        let mut model_manager = site.model.lock().unwrap();
+        let paths = watch::relevant_changes(batch, &model_manager);
+        if paths.is_empty() {
+            continue;
+        }
+
+        let started = Instant::now();
+        println!("🔄 Changed: {}", watch::describe_changes(&paths, &model_manager));
+        let result = model_manager.update_files(&paths, excluded_filename_patterns);
+        site.rebuild(&model_manager, result);
+        drop(model_manager);
+        drop(export);
+        site.reload();
+        println!("   Rebuilt in {} ms\n", started.elapsed().as_millis());
+    }
+
+    site.cleanup();
+    Ok(())
+}
+
+fn start_server(host: &str, port: u16) -> Result<Server, ReqvireError> {
     let addr = format!("{}:{}", host, port);
     let server = Server::http(&addr)
         .map_err(|e| ReqvireError::ProcessError(format!("Failed to start server: {}", e)))?;
@@ -21,36 +188,260 @@ pub fn serve_directory(
     println!("  • Press Ctrl-C to stop server");
     println!();
 
-    // Serve requests
-    for request in server.incoming_requests() {
-        let url_path = request.url().trim_start_matches('/');
-        let file_path = if url_path.is_empty() {
-            directory.join("index.html")
-        } else {
-            directory.join(url_path)
+    Ok(server)
+}
+
+fn json_response(status: u16, body: &serde_json::Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(serde_json::to_string_pretty(body).unwrap())
+        .with_status_code(status)
+        .with_header(static_files::header("Content-Type", "application/json"))
+}
+
+/// Export served in watch mode, replaced after every successful rebuild.
+///
+/// Locks are taken in the order [`EXPORT_LOCK`], `model`, then `state`.
+struct LiveSite {
+    model: Mutex<ModelManager>,
+    project_config: ProjectConfig,
+    excluded_filename_patterns: GlobSet,
+    refs: RefSites,
+    /// Element editor, enabled with `serve --edit`
+    editor: Option<Editor>,
+    state: Mutex<SiteState>,
+    clients: Mutex<Vec<Box<dyn Write + Send>>>,
+}
+
+#[derive(Default)]
+struct SiteState {
+    /// Last successful export
+    directory: Option<ExportDir>,
+    /// Validation errors of the current model
+    errors: Vec<String>,
+    /// Model files of the exported elements, whose pages get edit links
+    element_files: BTreeSet<String>,
+}
+
+impl LiveSite {
+    fn new(
+        project_config: ProjectConfig,
+        excluded_filename_patterns: &GlobSet,
+        refs: RefSites,
+        editor: Option<Editor>,
+    ) -> Self {
+        Self {
+            model: Mutex::new(ModelManager::new()),
+            project_config,
+            excluded_filename_patterns: excluded_filename_patterns.clone(),
+            refs,
+            editor,
+            state: Mutex::default(),
+            clients: Mutex::default(),
+        }
+    }
+
+    /// Exports a valid model, or records the errors of an invalid one
+    fn rebuild(&self, model_manager: &ModelManager, result: Result<Vec<ReqvireError>, ReqvireError>) {
+        let errors = match result {
+            Ok(_) => match self.export(model_manager) {
+                Ok(()) => Vec::new(),
+                Err(e) => vec![e.to_string()],
+            },
+            Err(ReqvireError::ValidationError(errors)) => errors.iter().map(|e| e.to_string()).collect(),
+            Err(e) => vec![e.to_string()],
         };
 
-        if file_path.exists() && file_path.is_file() {
-            if let Ok(content) = std::fs::read(&file_path) {
-                let content_type = match file_path.extension().and_then(|s| s.to_str()) {
-                    Some("html") => "text/html; charset=utf-8",
-                    Some("svg") => "image/svg+xml",
-                    Some("css") => "text/css",
-                    Some("js") => "application/javascript",
-                    _ => "text/plain",
-                };
+        if errors.is_empty() {
+            println!("✅ Site rebuilt");
+        } else {
+            println!("❌ {} validation error(s), showing them over the last valid export", errors.len());
+        }
+        self.state.lock().unwrap().errors = errors;
+    }
+
+    fn export(&self, model_manager: &ModelManager) -> Result<(), ReqvireError> {
+        let directory = ExportDir::new("reqvire-serve-", || {
+            export::generate_artifacts_in_temp(
+                &model_manager.graph_registry,
+                false, // always generate links without blobs for Serve
+                &self.project_config,
+            )
+        })?;
+        let element_files = model_manager
+            .graph_registry
+            .get_all_elements()
+            .into_iter()
+            .filter(|element| !element.identifier.starts_with("__virtual__"))
+            .map(|element| element.file_path.clone())
+            .collect();
+
+        // Swap the export in whole so requests never see a partial site
+        let mut state = self.state.lock().unwrap();
+        state.element_files = element_files;
+        state.directory = Some(directory);
+        Ok(())
+    }
+
+    /// Tells connected pages to reload
+    fn reload(&self) {
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain_mut(|client| {
+            client
+                .write_all(b"event: reload\ndata: reload\n\n")
+                .and_then(|_| client.flush())
+                .is_ok()
+        });
+    }
 
-                let header = Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
-                    .unwrap();
+    fn respond(&self, mut request: Request) {
+        if request.url() == EVENTS_PATH {
+            let mut writer = request.into_writer();
+            let opened = writer
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n: connected\n\n")
+                .and_then(|_| writer.flush());
+            if opened.is_ok() {
+                self.clients.lock().unwrap().push(writer);
+            }
+            return;
+        }
+
+        if request.url().starts_with(api::API_PREFIX) {
+            let model_manager = self.model.lock().unwrap();
+            let errors = self.state.lock().unwrap().errors.clone();
+            let (status, body) = if errors.is_empty() {
+                api::respond(&model_manager.graph_registry, &self.project_config, request.url())
+            } else {
+                (503, serde_json::json!({ "errors": errors }))
+            };
+            drop(model_manager);
+            let _ = request.respond(json_response(status, &body));
+            return;
+        }
 
-                let response = Response::from_data(content).with_header(header);
+        if let Some(editor) = &self.editor {
+            let mut written = false;
+            let response = editor.respond(&mut request, |paths| {
+                let mut model_manager = self.model.lock().unwrap();
+                let result = model_manager.update_files(paths, &self.excluded_filename_patterns);
+                self.rebuild(&model_manager, result);
+                written = true;
+            });
+            if let Some(response) = response {
                 let _ = request.respond(response);
+                if written {
+                    self.reload();
+                }
+                return;
             }
-        } else {
-            let response = Response::from_string("404 Not Found").with_status_code(404);
+        }
+
+        if let Some(response) = self.refs.respond(&request, &self.model) {
             let _ = request.respond(response);
+            return;
+        }
+
+        let state = self.state.lock().unwrap();
+        let response = match &state.directory {
+            Some(directory) => static_files::response(&request, request.url(), directory.path(), |page| {
+                let mut page = inject(&page, &state.errors);
+                if let Some(editor) = &self.editor {
+                    let file = editor::page_file(request.url()).filter(|file| state.element_files.contains(file));
+                    page = editor.with_edit_links(&page, file.as_deref());
+                }
+                self.refs.with_switcher(&page, None)
+            }),
+            None => static_files::content_response(
+                &request,
+                "text/html; charset=utf-8",
+                inject(EMPTY_PAGE, &state.errors).into_bytes(),
+            ),
+        };
+        drop(state);
+        let _ = request.respond(response);
+    }
+
+    /// Removes the served export
+    fn cleanup(&self) {
+        self.state.lock().unwrap().directory = None;
+        self.refs.cleanup();
+    }
+}
+
+/// Adds the reload script and, if there are errors, the error overlay to a page
+fn inject(page: &str, errors: &[String]) -> String {
+    let mut addition = String::from(RELOAD_SCRIPT);
+    if !errors.is_empty() {
+        addition.push_str("<div id=\"reqvire-errors\" style=\"position:fixed;inset:0;z-index:10000;overflow:auto;padding:2em;background:rgba(24,24,24,0.94);color:#f8f8f8;font:14px/1.5 monospace\">");
+        addition.push_str(&format!("<h2 style=\"color:#ff8080\">❌ {} validation error(s)</h2><ol>", errors.len()));
+        for error in errors {
+            addition.push_str(&format!("<li style=\"margin-bottom:1em\">{}</li>", utils::escape_html(error)));
         }
+        addition.push_str("</ol><p>The page reloads when the model is valid again.</p></div>");
+    }
+    append_to_body(page, &addition)
+}
+
+/// Inserts markup at the end of a page's body
+pub(crate) fn append_to_body(page: &str, addition: &str) -> String {
+    match page.rfind("</body>") {
+        Some(position) => format!("{}{}{}", &page[..position], addition, &page[position..]),
+        None => format!("{}{}", page, addition),
+    }
+}
+
+/// Export moved to a temporary directory of its own, removed on drop
+pub(crate) struct ExportDir {
+    /// Kept only to remove the directory on drop
+    _temp_dir: TempDir,
+    path: PathBuf,
+}
+
+impl ExportDir {
+    /// Runs an export and moves its result to a new directory named with `prefix`
+    pub(crate) fn new(
+        prefix: &str,
+        export: impl FnOnce() -> Result<PathBuf, ReqvireError>,
+    ) -> Result<Self, ReqvireError> {
+        let temp_dir = tempfile::Builder::new().prefix(prefix).tempdir()?;
+        let path = temp_dir.path().join("site");
+        export_into(&path, export)?;
+        Ok(Self { _temp_dir: temp_dir, path })
     }
 
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Runs an export and moves its result to `directory`
+fn export_into(
+    directory: &Path,
+    export: impl FnOnce() -> Result<PathBuf, ReqvireError>,
+) -> Result<(), ReqvireError> {
+    let temp_dir = export()?;
+
+    if let Some(parent) = directory.parent() {
+        filesystem::create_dir_all(parent)?;
+    }
+    std::fs::rename(&temp_dir, directory)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inject_adds_reload_script_and_escaped_overlay() {
+        let page = "<html><body><p>Model</p></body></html>";
+
+        let valid = inject(page, &[]);
+        assert!(valid.contains(RELOAD_SCRIPT));
+        assert!(valid.ends_with("</body></html>"));
+        assert!(!valid.contains("reqvire-errors"));
+
+        let invalid = inject(page, &["Missing target <a.md#b>".to_string()]);
+        assert!(invalid.contains("id=\"reqvire-errors\""));
+        assert!(invalid.contains("Missing target &lt;a.md#b&gt;"));
+        assert!(invalid.contains("<p>Model</p>"));
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{Cursor, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::api::percent_decode;
+
+/// Where a request URL points inside the served directory
+#[derive(Debug, PartialEq)]
+enum Resolved {
+    /// A file inside the root
+    File(PathBuf),
+    /// A directory requested without a trailing slash; relative links in its
+    /// index only work from the slash-terminated URL
+    Redirect(String),
+    NotFound,
+    /// A path leaving the root, through `..` or a symlink, or a hidden file
+    Forbidden,
+}
+
+/// Maps a request URL to a file under `root`.
+///
+/// The path is percent-decoded and normalised before it is joined to the
+/// root, then canonicalised so symlinks cannot lead outside of it either.
+/// Hidden files, like the `.git` directory of the export, are not served.
+/// Directories are served through their `index.html`.
+fn resolve(root: &Path, url: &str) -> Resolved {
+    let (url_path, query) = match url.find(['?', '#']) {
+        Some(position) => (&url[..position], &url[position..]),
+        None => (url, ""),
+    };
+
+    let mut relative = PathBuf::new();
+    for component in percent_decode(url_path).split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if !relative.pop() {
+                    return Resolved::Forbidden;
+                }
+            }
+            name => {
+                // Rejects drive prefixes and NUL bytes smuggled in through escapes
+                let mut components = Path::new(name).components();
+                let plain = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+                if !plain || name.starts_with('.') || name.contains('\0') {
+                    return Resolved::Forbidden;
+                }
+                relative.push(name);
+            }
+        }
+    }
+
+    let (Ok(root), Ok(path)) = (root.canonicalize(), root.join(&relative).canonicalize()) else {
+        return Resolved::NotFound;
+    };
+    if !path.starts_with(&root) {
+        return Resolved::Forbidden;
+    }
+
+    if path.is_dir() {
+        if !url_path.ends_with('/') {
+            let query = if query.starts_with('?') { query } else { "" };
+            return Resolved::Redirect(format!("{}/{}", url_path, query));
+        }
+        let index = path.join("index.html");
+        return if index.is_file() { Resolved::File(index) } else { Resolved::NotFound };
+    }
+    if path.is_file() {
+        Resolved::File(path)
+    } else {
+        Resolved::NotFound
+    }
+}
+
+/// Answers a GET or HEAD request for `url` with a file from `root`, passing
+/// HTML pages through `page` before they are sent
+pub(crate) fn response(
+    request: &Request,
+    url: &str,
+    root: &Path,
+    page: impl FnOnce(String) -> String,
+) -> Response<Cursor<Vec<u8>>> {
+    if !matches!(request.method(), Method::Get | Method::Head) {
+        return text_response(405, "405 Method Not Allowed").with_header(header("Allow", "GET, HEAD"));
+    }
+
+    match resolve(root, url) {
+        Resolved::File(path) => match std::fs::read(&path) {
+            Ok(content) => {
+                let content_type = content_type(&path).unwrap_or(if is_text(&content) {
+                    "text/plain; charset=utf-8"
+                } else {
+                    "application/octet-stream"
+                });
+                let content = if content_type.starts_with("text/html") {
+                    page(String::from_utf8_lossy(&content).into_owned()).into_bytes()
+                } else {
+                    content
+                };
+                content_response(request, content_type, content)
+            }
+            Err(_) => not_found(),
+        },
+        Resolved::Redirect(location) => redirect(301, &location),
+        Resolved::NotFound => not_found(),
+        Resolved::Forbidden => text_response(403, "403 Forbidden"),
+    }
+}
+
+/// Response with an ETag, answering 304 when the client already has the
+/// content, and compressed with gzip for clients accepting it
+pub(crate) fn content_response(
+    request: &Request,
+    content_type: &str,
+    content: Vec<u8>,
+) -> Response<Cursor<Vec<u8>>> {
+    let etag = etag(&content);
+    if header_value(request, "If-None-Match").is_some_and(|value| matches_etag(value, &etag)) {
+        return Response::from_data(Vec::new())
+            .with_status_code(304)
+            .with_header(header("ETag", &etag));
+    }
+
+    let mut headers = vec![
+        header("Content-Type", content_type),
+        header("ETag", &etag),
+        // Pages change with the model, so clients revalidate with the ETag
+        header("Cache-Control", "no-cache"),
+    ];
+    let mut content = content;
+    if is_compressible(content_type) {
+        headers.push(header("Vary", "Accept-Encoding"));
+        if header_value(request, "Accept-Encoding").is_some_and(accepts_gzip) {
+            if let Ok(compressed) = gzip(&content) {
+                content = compressed;
+                headers.push(header("Content-Encoding", "gzip"));
+            }
+        }
+    }
+
+    let mut response = Response::from_data(content);
+    for header in headers {
+        response.add_header(header);
+    }
+    response
+}
+
+pub(crate) fn not_found() -> Response<Cursor<Vec<u8>>> {
+    text_response(404, "404 Not Found")
+}
+
+/// Redirect with 301 for moves that browsers may cache, 303 after a form
+/// submission, or 302 otherwise
+pub(crate) fn redirect(status: u16, location: &str) -> Response<Cursor<Vec<u8>>> {
+    let text = match status {
+        301 => "301 Moved Permanently",
+        303 => "303 See Other",
+        _ => "302 Found",
+    };
+    text_response(status, text).with_header(header("Location", location))
+}
+
+/// Generated HTML page, sent without caching headers
+pub(crate) fn page_response(status: u16, page: String) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(page)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "text/html; charset=utf-8"))
+}
+
+fn text_response(status: u16, text: &str) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(text)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "text/plain; charset=utf-8"))
+}
+
+pub(crate) fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap()
+}
+
+pub(crate) fn header_value<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+/// MIME type by file extension, `None` for unknown extensions
+fn content_type(file_path: &Path) -> Option<&'static str> {
+    let extension = file_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(str::to_ascii_lowercase);
+    let content_type = match extension.as_deref()? {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "md" => "text/markdown; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "xml" | "reqif" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "pdf" => "application/pdf",
+        "wasm" => "application/wasm",
+        "zip" => "application/zip",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// Source files linked from the model are shown as text rather than downloaded
+fn is_text(content: &[u8]) -> bool {
+    !content.contains(&0) && std::str::from_utf8(content).is_ok()
+}
+
+/// Images other than SVG, fonts and archives are compressed already
+fn is_compressible(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || matches!(content_type, "application/json" | "application/xml" | "image/svg+xml" | "application/wasm")
+}
+
+pub(crate) fn etag(content: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Checks an `If-None-Match` value, which lists ETags or is `*`
+fn matches_etag(value: &str, etag: &str) -> bool {
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Checks an `Accept-Encoding` value for gzip without `q=0`
+fn accepts_gzip(value: &str) -> bool {
+    value.split(',').any(|coding| {
+        let mut parts = coding.split(';').map(str::trim);
+        parts.next().is_some_and(|name| name.eq_ignore_ascii_case("gzip"))
+            && parts.all(|parameter| {
+                parameter
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_none_or(|q| q > 0.0)
+            })
+    })
+}
+
+fn gzip(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_confines_paths_to_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("site");
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(root.join("index.html"), "index").unwrap();
+        std::fs::write(root.join("docs/a b.html"), "page").unwrap();
+        std::fs::write(temp.path().join("secret.txt"), "secret").unwrap();
+        let root = root.canonicalize().unwrap();
+
+        assert_eq!(resolve(&root, "/"), Resolved::File(root.join("index.html")));
+        assert_eq!(resolve(&root, "/?t=1"), Resolved::File(root.join("index.html")));
+        assert_eq!(resolve(&root, "/docs/a%20b.html?x#y"), Resolved::File(root.join("docs/a b.html")));
+        assert_eq!(resolve(&root, "/docs/../index.html"), Resolved::File(root.join("index.html")));
+
+        assert_eq!(resolve(&root, "/../secret.txt"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/%2e%2e/secret.txt"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/docs/..%2F..%2Fsecret.txt"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/docs/..%5C..%5Csecret.txt"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/index.html%00.png"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/.git/config"), Resolved::Forbidden);
+        assert_eq!(resolve(&root, "/missing.html"), Resolved::NotFound);
+
+        // Directories redirect to their slash-terminated URL and serve an index if present
+        assert_eq!(resolve(&root, "/docs?x=1"), Resolved::Redirect("/docs/?x=1".to_string()));
+        assert_eq!(resolve(&root, "/docs/"), Resolved::NotFound);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_rejects_symlinks_leaving_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("site");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(temp.path().join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(temp.path().join("secret.txt"), root.join("link.txt")).unwrap();
+
+        assert_eq!(resolve(&root, "/link.txt"), Resolved::Forbidden);
+    }
+
+    #[test]
+    fn test_content_negotiation_headers() {
+        assert_eq!(content_type(Path::new("logo.PNG")), Some("image/png"));
+        assert_eq!(content_type(Path::new("font.woff2")), Some("font/woff2"));
+        assert_eq!(content_type(Path::new("model.json")), Some("application/json"));
+        assert_eq!(content_type(Path::new("main.rs")), None);
+        assert!(is_text("fn main() {}".as_bytes()));
+        assert!(!is_text(&[0x89, b'P', b'N', b'G', 0]));
+
+        let tag = etag(b"content");
+        assert!(matches_etag(&tag, &tag));
+        assert!(matches_etag(&format!("\"other\", W/{}", tag), &tag));
+        assert!(matches_etag("*", &tag));
+        assert!(!matches_etag(&etag(b"changed"), &tag));
+
+        assert!(accepts_gzip("gzip, deflate, br"));
+        assert!(accepts_gzip("br;q=1.0, GZIP;q=0.5"));
+        assert!(!accepts_gzip("gzip;q=0"));
+        assert!(!accepts_gzip("deflate"));
+    }
+}
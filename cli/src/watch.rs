@@ -0,0 +1,234 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use globset::GlobSet;
+
+use reqvire::error::ReqvireError;
+use reqvire::lint;
+use reqvire::reports;
+use reqvire::utils;
+use reqvire::ModelManager;
+
+use crate::cli::print_validation_results;
+use crate::config::load_project_config;
+
+/// Quiet period that groups the events of one save into a single update
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Watches the model files and prints updated results whenever they change.
+///
+/// The model is parsed once; afterwards only the changed files are parsed
+/// again and the registry is patched incrementally. Runs until interrupted.
+pub fn run_watch(mode: &str, excluded_filename_patterns: &GlobSet) -> Result<(), ReqvireError> {
+    let mut model_manager = ModelManager::new();
+    let root = model_manager.context().scan_root().to_path_buf();
+    let receiver = spawn_watcher(&root)?;
+
+    let started = Instant::now();
+    let result = model_manager.parse_and_validate(None, excluded_filename_patterns);
+    report(mode, &model_manager, result)?;
+    println!("👀 Watching {} for changes ({} ms)\n", root.display(), started.elapsed().as_millis());
+
+    while let Some(paths) = next_changes(&receiver, &model_manager) {
+        let started = Instant::now();
+        let result = model_manager.update_files(&paths, excluded_filename_patterns);
+        println!("🔄 Changed: {} ({} ms)", describe_changes(&paths, &model_manager), started.elapsed().as_millis());
+        report(mode, &model_manager, result)?;
+    }
+
+    Ok(())
+}
+
+/// Waits for the next change that matters to the model and returns the paths
+/// changed together with it, or `None` once the watcher stops.
+pub(crate) fn next_changes(receiver: &Receiver<PathBuf>, model_manager: &ModelManager) -> Option<Vec<PathBuf>> {
+    loop {
+        let paths = relevant_changes(next_batch(receiver)?, model_manager);
+        if !paths.is_empty() {
+            return Some(paths);
+        }
+    }
+}
+
+/// Waits for the next change and collects the paths changed together with it,
+/// or returns `None` once the watcher stops.
+pub(crate) fn next_batch(receiver: &Receiver<PathBuf>) -> Option<BTreeSet<PathBuf>> {
+    let mut paths = BTreeSet::from([receiver.recv().ok()?]);
+    loop {
+        match receiver.recv_timeout(DEBOUNCE) {
+            Ok(path) => {
+                paths.insert(path);
+            }
+            Err(RecvTimeoutError::Timeout) => return Some(paths),
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+/// Keeps the changed paths that matter to the model
+pub(crate) fn relevant_changes(paths: BTreeSet<PathBuf>, model_manager: &ModelManager) -> Vec<PathBuf> {
+    // Other files only matter when relations point to them
+    let targets = model_manager.graph_registry.get_internal_path_targets();
+    paths
+        .into_iter()
+        .filter(|path| {
+            is_markdown(path)
+                || utils::get_relative_path(model_manager.context(), path).is_ok_and(|relative| targets.contains(&relative))
+        })
+        .collect()
+}
+
+/// Lists changed paths relative to the model root
+pub(crate) fn describe_changes(paths: &[PathBuf], model_manager: &ModelManager) -> String {
+    paths
+        .iter()
+        .map(|path| {
+            utils::get_relative_path(model_manager.context(), path)
+                .unwrap_or_else(|_| path.clone())
+                .display()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Prints validation errors, or the lint or coverage report of a valid model
+fn report(
+    mode: &str,
+    model_manager: &ModelManager,
+    result: Result<Vec<ReqvireError>, ReqvireError>,
+) -> Result<(), ReqvireError> {
+    match result {
+        Err(ReqvireError::ValidationError(errors)) => {
+            print_validation_results(&errors, false);
+            return Ok(());
+        }
+        Err(e) => {
+            println!("❌ Parsing failed: {}\n", e);
+            return Ok(());
+        }
+        Ok(_) => {}
+    }
+
+    match mode {
+        "lint" => {
+            let lint_report = lint::analyze_model(&model_manager.graph_registry);
+            if lint_report.auto_fixable.is_empty() && lint_report.needs_manual_review.is_empty() {
+                println!("✅ No lint issues found\n");
+            } else {
+                lint_report.print(false, false, false);
+            }
+        }
+        "coverage" => {
+            let project_config = load_project_config()?;
+            reports::generate_coverage_report(
+                &model_manager.graph_registry,
+                &project_config.verification_rules,
+            )
+            .print(false);
+        }
+        _ => println!("✅ No validation issues found\n"),
+    }
+    Ok(())
+}
+
+/// Walks the files and directories under `root` that are not ignored by git
+fn walk(root: &Path) -> ignore::Walk {
+    ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
+
+/// Starts a thread sending the paths of changed files under `root`.
+///
+/// Markdown files are reported on every write; other files only when they
+/// are created, removed or renamed, since only their existence matters to
+/// the model. Files in directories ignored by git are skipped.
+pub(crate) fn spawn_watcher(root: &Path) -> Result<Receiver<PathBuf>, ReqvireError> {
+    use notify::event::{AccessKind, AccessMode, ModifyKind};
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    let watch_error = |e: notify::Error| ReqvireError::ProcessError(format!("Failed to start file watcher: {}", e));
+    let (event_sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(event_sender).map_err(watch_error)?;
+    watcher.watch(root, RecursiveMode::Recursive).map_err(watch_error)?;
+
+    let mut directories: HashSet<PathBuf> = walk(root)
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_dir()))
+        .map(|entry| entry.into_path())
+        .collect();
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        // The watcher stops when dropped, so it lives as long as this thread
+        let _watcher = watcher;
+        for event in events {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    log::error!("File watcher error: {}", e);
+                    continue;
+                }
+            };
+            let existence_changed = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+            );
+            let written = existence_changed
+                || matches!(
+                    event.kind,
+                    EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Any)
+                        | EventKind::Access(AccessKind::Close(AccessMode::Write))
+                );
+            if !written {
+                continue;
+            }
+
+            for path in event.paths {
+                if !path.parent().is_some_and(|parent| directories.contains(parent)) {
+                    continue;
+                }
+                if path.is_dir() {
+                    if existence_changed && is_walked(&path) {
+                        // Files moved in with a directory produce no events of their own
+                        for entry in walk(&path).filter_map(Result::ok) {
+                            if entry.file_type().is_some_and(|t| t.is_dir()) {
+                                directories.insert(entry.into_path());
+                            } else if sender.send(entry.into_path()).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                if (is_markdown(&path) || existence_changed) && sender.send(path).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+/// Checks that a new directory is not ignored by git
+fn is_walked(path: &Path) -> bool {
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+    ignore::WalkBuilder::new(parent)
+        .hidden(false)
+        .max_depth(Some(1))
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .filter_map(Result::ok)
+        .any(|entry| entry.path() == path)
+}
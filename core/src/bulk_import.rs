@@ -0,0 +1,408 @@
+//! Bulk import of requirement lists from CSV and JSON
+//!
+//! Every record (CSV row or JSON object) becomes one element. A mapping file
+//! names the fields that provide the element name, content, type, metadata
+//! and parent references, and templates that place elements into files and
+//! sections. Parent references are resolved by external ID first and by
+//! element name second.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::error::ReqvireError;
+use crate::import::{self, ImportedElement, ImportedFile, ImportedModel, ImportedRelation, DEFAULT_SECTION};
+
+/// Placeholder value for template fields that are empty in a record
+const UNASSIGNED: &str = "Unassigned";
+
+/// Maps record fields onto the reqvire model. Fields are CSV column headers or
+/// JSON keys; nested JSON values are addressed with dotted paths such as
+/// `fields.priority.name`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BulkImportMapping {
+    /// JSON path of the record array, e.g. `issues`; defaults to the top-level array
+    pub records: Option<String>,
+    /// Field holding the external ID used to resolve parent references
+    pub id: Option<String>,
+    /// Field holding the element name
+    pub name: String,
+    /// Field holding the element content
+    pub content: Option<String>,
+    /// Field holding the element type
+    #[serde(rename = "type")]
+    pub type_field: Option<String>,
+    /// Type field value to element type
+    pub types: BTreeMap<String, String>,
+    /// Element type used when the record has no type
+    pub default_type: String,
+    /// Field to metadata key
+    pub metadata: BTreeMap<String, String>,
+    /// Field holding references to parent elements by external ID or name
+    pub parent: Option<String>,
+    /// Separator between several parent references in one field
+    pub parent_separator: Option<String>,
+    /// Relation from an element to its parents
+    pub parent_relation: String,
+    /// Target file template, e.g. `specifications/{Component}.md`
+    pub file: String,
+    /// Target section template, e.g. `{Epic}`
+    pub section: String,
+}
+
+impl Default for BulkImportMapping {
+    fn default() -> Self {
+        BulkImportMapping {
+            records: None,
+            id: Some("id".to_string()),
+            name: "name".to_string(),
+            content: Some("content".to_string()),
+            type_field: Some("type".to_string()),
+            types: BTreeMap::new(),
+            default_type: "requirement".to_string(),
+            metadata: BTreeMap::new(),
+            parent: Some("parent".to_string()),
+            parent_separator: None,
+            parent_relation: "derivedFrom".to_string(),
+            file: "Requirements.md".to_string(),
+            section: DEFAULT_SECTION.to_string(),
+        }
+    }
+}
+
+impl BulkImportMapping {
+    /// Loads a mapping file (YAML)
+    pub fn load(path: &Path) -> Result<Self, ReqvireError> {
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yml::from_str(&content).map_err(|e| {
+            ReqvireError::InitializationError(format!("Invalid import mapping {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// Reads CSV records; `.tsv` files are tab separated
+pub fn read_csv_records(path: &Path) -> Result<Vec<Value>, ReqvireError> {
+    let is_tsv = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("tsv"));
+    let csv_error = |e: csv::Error| ReqvireError::ProcessError(format!("Failed to read {}: {}", path.display(), e));
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(if is_tsv { b'\t' } else { b',' })
+        .flexible(true)
+        .from_path(path)
+        .map_err(csv_error)?;
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(|header| header.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+
+    let mut records = Vec::new();
+    for row in reader.records() {
+        let row = row.map_err(csv_error)?;
+        let record: Map<String, Value> = headers
+            .iter()
+            .zip(row.iter())
+            .map(|(header, value)| (header.clone(), Value::String(value.to_string())))
+            .collect();
+        records.push(Value::Object(record));
+    }
+    Ok(records)
+}
+
+/// Reads JSON records from the top-level array or the array at `mapping.records`
+pub fn read_json_records(path: &Path, mapping: &BulkImportMapping) -> Result<Vec<Value>, ReqvireError> {
+    let document: Value = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| ReqvireError::ProcessError(format!("Failed to read {}: {}", path.display(), e)))?;
+    json_records(document, mapping).map_err(|e| ReqvireError::ProcessError(format!("{}: {}", path.display(), e)))
+}
+
+/// Extracts the records from a parsed JSON document
+pub fn json_records(document: Value, mapping: &BulkImportMapping) -> Result<Vec<Value>, String> {
+    let records = match &mapping.records {
+        Some(records_path) => records_path
+            .split('.')
+            .try_fold(document, |mut value, key| value.get_mut(key).map(Value::take)),
+        None => Some(document),
+    };
+    match records {
+        Some(Value::Array(records)) => Ok(records),
+        _ => Err("no array of records found; set `records` in the mapping to its location".to_string()),
+    }
+}
+
+/// Returns all non-empty values of a field
+fn field_values(record: &Value, field: &str) -> Vec<String> {
+    // Keys containing dots (e.g. CSV headers) take precedence over paths
+    if let Some(value) = record.get(field) {
+        return flatten(value);
+    }
+    let mut current = vec![record];
+    for key in field.split('.') {
+        current = current
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(items) => items.iter().filter_map(|item| item.get(key)).collect::<Vec<_>>(),
+                other => other.get(key).into_iter().collect(),
+            })
+            .collect();
+    }
+    current.into_iter().flat_map(flatten).collect()
+}
+
+fn flatten(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) if !text.trim().is_empty() => vec![text.clone()],
+        Value::Number(number) => vec![number.to_string()],
+        Value::Bool(flag) => vec![flag.to_string()],
+        Value::Array(items) => items.iter().flat_map(flatten).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the values of a field as a single line
+fn field_text(record: &Value, field: Option<&String>) -> String {
+    field
+        .map(|field| import::single_line(&field_values(record, field).join(", ")))
+        .unwrap_or_default()
+}
+
+/// Substitutes `{field}` placeholders with record values
+fn render_template(template: &str, record: &Value, sanitize: fn(&str) -> String) -> String {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else { break };
+        rendered.push_str(&rest[..start]);
+        let field = &rest[start + 1..start + length];
+        let value = sanitize(&field_values(record, field).join(", "));
+        rendered.push_str(if value.is_empty() { UNASSIGNED } else { &value });
+        rest = &rest[start + length + 1..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Turns a field value into a single path segment
+fn path_segment(value: &str) -> String {
+    value
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'))
+        .filter(|part| !part.is_empty() && *part != "." && *part != "..")
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn file_path(record: &Value, mapping: &BulkImportMapping) -> Result<String, ReqvireError> {
+    let mut path = render_template(&mapping.file, record, path_segment);
+    if !path.ends_with(".md") {
+        path.push_str(".md");
+    }
+    let is_plain_relative = Path::new(&path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !is_plain_relative {
+        return Err(ReqvireError::ProcessError(format!(
+            "File template '{}' must produce a relative path, got '{}'",
+            mapping.file, path
+        )));
+    }
+    Ok(path)
+}
+
+/// Maps records onto an `ImportedModel`
+pub fn import_records(records: &[Value], mapping: &BulkImportMapping) -> Result<ImportedModel, ReqvireError> {
+    let mut model = ImportedModel::default();
+    let mut file_indices: HashMap<String, usize> = HashMap::new();
+    let mut keys = Vec::new();
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut records_by_id: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (index, record) in records.iter().enumerate() {
+        let name = field_text(record, Some(&mapping.name));
+        if name.is_empty() {
+            return Err(ReqvireError::ProcessError(format!(
+                "Record {} has no value for the name field '{}'",
+                index + 1,
+                mapping.name
+            )));
+        }
+
+        let id = field_text(record, mapping.id.as_ref());
+        let key = if id.is_empty() { format!("record-{}", index + 1) } else { id.clone() };
+        if !id.is_empty() {
+            records_by_id.entry(id.clone()).or_default().push(index + 1);
+            ids.entry(id).or_insert(index);
+        }
+        names.entry(name.to_lowercase()).or_default().push(index);
+
+        let type_value = field_text(record, mapping.type_field.as_ref());
+        let element_type = if type_value.is_empty() {
+            mapping.default_type.clone()
+        } else {
+            mapping.types.get(&type_value).cloned().unwrap_or(type_value)
+        };
+
+        let mut metadata = BTreeMap::new();
+        for (field, metadata_key) in &mapping.metadata {
+            let value = field_text(record, Some(field));
+            if !value.is_empty() {
+                metadata.insert(metadata_key.clone(), value);
+            }
+        }
+
+        let path = file_path(record, mapping)?;
+        let file_index = *file_indices.entry(path.clone()).or_insert_with(|| {
+            model.files.push(ImportedFile {
+                path,
+                title: None,
+                sections: Vec::new(),
+            });
+            model.files.len() - 1
+        });
+        let section = import::single_line(&render_template(&mapping.section, record, import::single_line));
+
+        model.files[file_index]
+            .section_mut(if section.is_empty() { DEFAULT_SECTION } else { &section })
+            .elements
+            .push(ImportedElement {
+                key: key.clone(),
+                name,
+                content: mapping
+                    .content
+                    .as_ref()
+                    .map(|field| field_values(record, field).join("\n\n"))
+                    .unwrap_or_default(),
+                element_type,
+                metadata,
+            });
+        keys.push(key);
+    }
+
+    let duplicates: Vec<String> = records_by_id
+        .iter()
+        .filter(|(_, records)| records.len() > 1)
+        .map(|(id, records)| {
+            let records: Vec<String> = records.iter().map(|record| record.to_string()).collect();
+            format!("'{}' in records {}", id, records.join(", "))
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        return Err(ReqvireError::ProcessError(format!(
+            "Records must have unique values in the ID field '{}', found duplicates: {}",
+            mapping.id.as_deref().unwrap_or_default(),
+            duplicates.join("; ")
+        )));
+    }
+
+    // Parent references are resolved once all records are known
+    let Some(parent_field) = &mapping.parent else {
+        return Ok(model);
+    };
+    for (index, record) in records.iter().enumerate() {
+        let references: Vec<String> = field_values(record, parent_field)
+            .iter()
+            .flat_map(|value| match &mapping.parent_separator {
+                Some(separator) => value.split(separator.as_str()).map(import::single_line).collect(),
+                None => vec![import::single_line(value)],
+            })
+            .filter(|reference| !reference.is_empty())
+            .collect();
+
+        for reference in references {
+            let description = format!("{} {} {}", keys[index], mapping.parent_relation, reference);
+            let target = match (ids.get(&reference), names.get(&reference.to_lowercase())) {
+                (Some(&target), _) => target,
+                (None, Some(matches)) if matches.len() == 1 => matches[0],
+                (None, Some(_)) => {
+                    model.skipped_relations.push(format!("{} (ambiguous parent name)", description));
+                    continue;
+                }
+                (None, None) => {
+                    model.skipped_relations.push(format!("{} (unknown parent)", description));
+                    continue;
+                }
+            };
+            model.relations.push(ImportedRelation {
+                source: keys[index].clone(),
+                relation_type: mapping.parent_relation.clone(),
+                target: keys[target].clone(),
+            });
+        }
+    }
+
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_values_follow_paths() {
+        let record = json!({
+            "key": "PRJ-1",
+            "Issue.Type": "Story",
+            "fields": { "priority": { "name": "High" }, "labels": ["a", "b"], "components": [{ "name": "Brakes" }] }
+        });
+        assert_eq!(field_values(&record, "Issue.Type"), vec!["Story"]);
+        assert_eq!(field_values(&record, "fields.priority.name"), vec!["High"]);
+        assert_eq!(field_values(&record, "fields.labels"), vec!["a", "b"]);
+        assert_eq!(field_values(&record, "fields.components.name"), vec!["Brakes"]);
+        assert!(field_values(&record, "fields.missing").is_empty());
+    }
+
+    #[test]
+    fn test_templates_and_parent_resolution() {
+        let mapping = BulkImportMapping {
+            file: "specs/{component}".to_string(),
+            section: "{epic}".to_string(),
+            ..Default::default()
+        };
+        let records = vec![
+            json!({"id": "R-1", "name": "Braking", "component": "Brake System", "epic": "Safety"}),
+            json!({"id": "R-2", "name": "Emergency Stop", "component": "../Brake System", "parent": "R-1"}),
+            json!({"id": "R-3", "name": "Stop Light", "component": "", "parent": "braking"}),
+            json!({"id": "R-4", "name": "Horn", "parent": "Missing"}),
+        ];
+
+        let model = import_records(&records, &mapping).unwrap();
+        let paths: Vec<&str> = model.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["specs/Brake-System.md", "specs/Unassigned.md"]);
+        assert_eq!(model.files[0].sections[0].name, "Safety");
+        assert_eq!(model.files[0].sections[1].name, "Unassigned");
+
+        let relations: Vec<(&str, &str)> = model
+            .relations
+            .iter()
+            .map(|relation| (relation.source.as_str(), relation.target.as_str()))
+            .collect();
+        assert_eq!(relations, vec![("R-2", "R-1"), ("R-3", "R-1")]);
+        assert_eq!(model.skipped_relations, vec!["R-4 derivedFrom Missing (unknown parent)"]);
+    }
+
+    #[test]
+    fn test_duplicate_ids_are_rejected() {
+        let records = vec![
+            json!({"id": "R-1", "name": "Braking"}),
+            json!({"id": "R-2", "name": "Horn"}),
+            json!({"id": "R-1", "name": "Stop Light"}),
+            json!({"name": "Siren"}),
+            json!({"name": "Beacon"}),
+        ];
+
+        let error = import_records(&records, &BulkImportMapping::default()).unwrap_err().to_string();
+        assert!(error.ends_with("'R-1' in records 1, 3"), "{}", error);
+    }
+}
@@ -0,0 +1,154 @@
+//! Location of a model on disk
+//!
+//! Identifiers and file paths of a model are relative to its root, which is
+//! normally the root of its git repository. Passing the context explicitly
+//! instead of reading the working directory lets a process work with several
+//! models at once, for example exporting one while parsing another.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use log::debug;
+
+use crate::error::ReqvireError;
+use crate::git_commands;
+
+#[derive(Debug, Clone)]
+pub struct ModelContext {
+    /// Folder identifiers and file paths are relative to
+    root: PathBuf,
+    /// Folder scanned for model files, the root or a folder within it
+    scan_root: PathBuf,
+    /// Repository base URL and commit hash, looked up on first use
+    repository: OnceLock<Repository>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Repository {
+    base_url: Option<String>,
+    commit_hash: Option<String>,
+}
+
+impl ModelContext {
+    /// Context of the model in `root`, scanning the whole folder
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            scan_root: root.clone(),
+            root,
+            repository: OnceLock::new(),
+        }
+    }
+
+    /// Context of the current directory.
+    ///
+    /// The root is the git repository root, or the current directory outside
+    /// of a repository. If the current directory is within the root but not
+    /// at the root, only the current directory subtree is scanned.
+    pub fn from_current_dir() -> Self {
+        let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let root = match git_commands::get_git_root_dir() {
+            Ok(dir) => dir,
+            Err(_) => {
+                debug!("Not in a git repository, using current directory");
+                current_dir.clone()
+            }
+        };
+        Self::new(root).with_scan_root(current_dir)
+    }
+
+    /// Restricts scanning to `scan_root` if it is a folder within the root
+    pub fn with_scan_root(mut self, scan_root: impl Into<PathBuf>) -> Self {
+        let scan_root = scan_root.into();
+        if scan_root.starts_with(&self.root) && scan_root != self.root {
+            self.scan_root = scan_root;
+        }
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn scan_root(&self) -> &Path {
+        &self.scan_root
+    }
+
+    /// Folder of the scan root relative to the root, empty when they are the same
+    pub fn scan_prefix(&self) -> &Path {
+        self.scan_root.strip_prefix(&self.root).unwrap_or(Path::new(""))
+    }
+
+    /// Resolves a path relative to the root
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Path of `path` relative to the root
+    pub fn relative_path(&self, path: &Path) -> Result<PathBuf, ReqvireError> {
+        path.strip_prefix(&self.root)
+            .map(Path::to_path_buf)
+            .map_err(|_| ReqvireError::PathError(format!(
+                "Failed to determine relative path: {}",
+                path.display()
+            )))
+    }
+
+    /// HTTPS base URL of the repository whose root is the model root
+    pub fn repository_base_url(&self) -> Option<&str> {
+        self.repository().base_url.as_deref()
+    }
+
+    /// Commit checked out in the repository whose root is the model root
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.repository().commit_hash.as_deref()
+    }
+
+    /// Remote and commit of the repository, if the root is a repository
+    /// root. Folders within other repositories have neither, so models
+    /// copied to a scratch folder link locally.
+    fn repository(&self) -> &Repository {
+        self.repository.get_or_init(|| {
+            let Ok(repository_root) = git_commands::find_git_repo_root(&self.root) else {
+                return Repository::default();
+            };
+            match (Path::new(&repository_root).canonicalize(), self.root.canonicalize()) {
+                (Ok(repository_root), Ok(root)) if repository_root == root => Repository {
+                    base_url: git_commands::get_repository_base_url_in(&self.root).ok(),
+                    commit_hash: git_commands::get_commit_hash_in(&self.root).ok(),
+                },
+                _ => Repository::default(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_root_and_relative_paths() {
+        let context = ModelContext::new("/repo");
+        assert_eq!(context.scan_root(), Path::new("/repo"));
+        assert_eq!(context.scan_prefix(), Path::new(""));
+
+        let context = context.with_scan_root("/repo/sub");
+        assert_eq!(context.scan_root(), Path::new("/repo/sub"));
+        assert_eq!(context.scan_prefix(), Path::new("sub"));
+        assert_eq!(context.relative_path(Path::new("/repo/sub/a.md")).unwrap(), PathBuf::from("sub/a.md"));
+        assert!(context.relative_path(Path::new("/other/a.md")).is_err());
+
+        // Folders outside of the root are ignored
+        let context = ModelContext::new("/repo").with_scan_root("/other");
+        assert_eq!(context.scan_root(), Path::new("/repo"));
+    }
+
+    #[test]
+    fn test_folder_without_repository_has_no_remote() {
+        let folder = tempfile::tempdir().unwrap();
+        let context = ModelContext::new(folder.path());
+        assert_eq!(context.repository_base_url(), None);
+        assert_eq!(context.commit_hash(), None);
+    }
+}
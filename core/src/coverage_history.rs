@@ -0,0 +1,268 @@
+//! Coverage history across a range of git commits
+//!
+//! The model is loaded at each selected commit through the git-backed file
+//! reader and reduced to a `CoverageSummary`. Commits that do not touch any
+//! model file are skipped since their coverage equals that of their parent.
+
+use crate::error::ReqvireError;
+use crate::git_commands;
+use crate::context::ModelContext;
+use crate::model::ModelManager;
+use crate::reports::{self, CoverageSummary};
+use crate::tabular::{TableFormat, TableWriter};
+use crate::utils;
+use crate::verification_rules::VerificationRule;
+use globset::GlobSet;
+use log::debug;
+use serde::Serialize;
+
+const TABLE_HEADER: [&str; 11] = [
+    "commit",
+    "date",
+    "total_leaf_requirements",
+    "verified_leaf_requirements",
+    "leaf_requirements_coverage_percentage",
+    "total_test_verifications",
+    "satisfied_test_verifications",
+    "test_verifications_satisfaction_percentage",
+    "total_verifications",
+    "orphaned_verifications",
+    "valid",
+];
+
+// SVG chart geometry
+const CHART_WIDTH: f64 = 760.0;
+const CHART_HEIGHT: f64 = 340.0;
+const MARGIN_LEFT: f64 = 56.0;
+const MARGIN_RIGHT: f64 = 24.0;
+const MARGIN_TOP: f64 = 64.0;
+const MARGIN_BOTTOM: f64 = 56.0;
+const MAX_X_LABELS: usize = 12;
+const LEAF_COLOR: &str = "#2563eb";
+const TEST_COLOR: &str = "#16a34a";
+
+/// Extracts the charted percentage from a coverage summary
+type SeriesValue = fn(&CoverageSummary) -> f64;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageHistoryPoint {
+    pub commit: String,
+    pub date: String,
+    /// False when the model at this commit failed validation
+    pub valid: bool,
+    pub summary: CoverageSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageHistory {
+    pub rev_range: String,
+    pub points: Vec<CoverageHistoryPoint>,
+    /// Commits in the range that were skipped because they did not touch model files
+    pub skipped_commits: usize,
+}
+
+/// Returns true if any of the given paths (relative to the model root) is a model file
+fn touches_model_files(context: &ModelContext, changed_files: &[String], excluded_filename_patterns: &GlobSet) -> bool {
+    changed_files
+        .iter()
+        .map(|file| context.resolve(file))
+        .any(|path| utils::is_requirements_file_by_path(context, &path, excluded_filename_patterns))
+}
+
+/// Computes the coverage summary at every commit in `rev_range` that changes the model.
+///
+/// The first commit of the range is always included so the series has a starting point.
+pub fn compute_coverage_history(
+    context: &ModelContext,
+    rev_range: &str,
+    excluded_filename_patterns: &GlobSet,
+    rules: &[VerificationRule],
+) -> Result<CoverageHistory, ReqvireError> {
+    let commits = git_commands::list_commits(context.root(), rev_range)?;
+    let mut points = Vec::new();
+    let mut skipped_commits = 0;
+
+    for (index, (commit, date)) in commits.iter().enumerate() {
+        if index > 0 {
+            let changed_files = git_commands::changed_files_in_commit(context.root(), commit)?;
+            if !touches_model_files(context, &changed_files, excluded_filename_patterns) {
+                debug!("Skipping commit {} as it does not change model files", commit);
+                skipped_commits += 1;
+                continue;
+            }
+        }
+
+        let mut model_manager = ModelManager::with_context(context.clone());
+        let valid = match model_manager.parse_and_validate(Some(commit), excluded_filename_patterns) {
+            Ok(_) => true,
+            Err(ReqvireError::ValidationError(errors)) => {
+                debug!("Model at commit {} has {} validation errors", commit, errors.len());
+                false
+            }
+            Err(e) => return Err(e),
+        };
+
+        let report = reports::generate_coverage_report(&model_manager.graph_registry, rules);
+        points.push(CoverageHistoryPoint {
+            commit: commit.clone(),
+            date: date.clone(),
+            valid,
+            summary: report.summary().clone(),
+        });
+    }
+
+    Ok(CoverageHistory {
+        rev_range: rev_range.to_string(),
+        points,
+        skipped_commits,
+    })
+}
+
+impl CoverageHistory {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn to_table(&self, format: TableFormat) -> String {
+        let mut writer = TableWriter::new(format, TABLE_HEADER.to_vec());
+        for point in &self.points {
+            let s = &point.summary;
+            writer.push_row(vec![
+                point.commit.clone(),
+                point.date.clone(),
+                s.total_leaf_requirements.to_string(),
+                s.verified_leaf_requirements.to_string(),
+                format!("{:.2}", s.leaf_requirements_coverage_percentage),
+                s.total_test_verifications.to_string(),
+                s.satisfied_test_verifications.to_string(),
+                format!("{:.2}", s.test_verifications_satisfaction_percentage),
+                s.total_verifications.to_string(),
+                s.orphaned_verifications.to_string(),
+                point.valid.to_string(),
+            ]);
+        }
+        writer.render()
+    }
+
+    /// Renders a self-contained SVG line chart of leaf requirement coverage and
+    /// test verification satisfaction over the recorded commits.
+    pub fn to_svg(&self) -> String {
+        let plot_width = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let x_of = |index: usize| -> f64 {
+            if self.points.len() <= 1 {
+                MARGIN_LEFT + plot_width / 2.0
+            } else {
+                MARGIN_LEFT + plot_width * index as f64 / (self.points.len() - 1) as f64
+            }
+        };
+        let y_of = |percentage: f64| -> f64 { MARGIN_TOP + plot_height * (1.0 - percentage.clamp(0.0, 100.0) / 100.0) };
+
+        let mut svg = String::new();
+        svg.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            w = CHART_WIDTH,
+            h = CHART_HEIGHT
+        ));
+        svg.push_str(&format!("  <rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>\n", CHART_WIDTH, CHART_HEIGHT));
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"24\" font-size=\"16\" font-weight=\"bold\">Coverage History ({})</text>\n",
+            MARGIN_LEFT,
+            utils::escape_html(&self.rev_range)
+        ));
+
+        // Legend
+        for (offset, (color, label)) in [(LEAF_COLOR, "Leaf requirements verified"), (TEST_COLOR, "Test verifications satisfied")]
+            .iter()
+            .enumerate()
+        {
+            let x = MARGIN_LEFT + offset as f64 * 240.0;
+            svg.push_str(&format!(
+                "  <line x1=\"{x}\" y1=\"44\" x2=\"{x2}\" y2=\"44\" stroke=\"{color}\" stroke-width=\"3\"/>\n  <text x=\"{tx}\" y=\"48\">{label}</text>\n",
+                x = x,
+                x2 = x + 24.0,
+                tx = x + 30.0,
+                color = color,
+                label = label
+            ));
+        }
+
+        // Horizontal grid with percentage axis
+        for step in 0..=4 {
+            let percentage = step as f64 * 25.0;
+            let y = y_of(percentage);
+            svg.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#e5e7eb\"/>\n  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}%</text>\n",
+                MARGIN_LEFT,
+                MARGIN_LEFT + plot_width,
+                MARGIN_LEFT - 8.0,
+                y + 4.0,
+                percentage,
+                y = y
+            ));
+        }
+
+        if self.points.is_empty() {
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#6b7280\">No commits in range</text>\n",
+                MARGIN_LEFT + plot_width / 2.0,
+                MARGIN_TOP + plot_height / 2.0
+            ));
+            svg.push_str("</svg>\n");
+            return svg;
+        }
+
+        // Commit labels along the x axis, thinned out for long histories
+        let label_step = self.points.len().div_ceil(MAX_X_LABELS);
+        for (index, point) in self.points.iter().enumerate() {
+            if index % label_step != 0 && index != self.points.len() - 1 {
+                continue;
+            }
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-family=\"monospace\" font-size=\"10\">{}</text>\n",
+                x_of(index),
+                MARGIN_TOP + plot_height + 18.0,
+                short_hash(&point.commit)
+            ));
+        }
+
+        let series: [(&str, &str, SeriesValue); 2] = [
+            (LEAF_COLOR, "Leaf requirements verified", |s| s.leaf_requirements_coverage_percentage),
+            (TEST_COLOR, "Test verifications satisfied", |s| s.test_verifications_satisfaction_percentage),
+        ];
+        for (color, label, value_of) in series.iter() {
+            let coordinates: Vec<String> = self
+                .points
+                .iter()
+                .enumerate()
+                .map(|(index, point)| format!("{:.1},{:.1}", x_of(index), y_of(value_of(&point.summary))))
+                .collect();
+            svg.push_str(&format!(
+                "  <polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" points=\"{}\"/>\n",
+                color,
+                coordinates.join(" ")
+            ));
+            for (index, point) in self.points.iter().enumerate() {
+                let value = value_of(&point.summary);
+                svg.push_str(&format!(
+                    "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"><title>{} {} - {}: {:.1}%</title></circle>\n",
+                    x_of(index),
+                    y_of(value),
+                    color,
+                    short_hash(&point.commit),
+                    utils::escape_html(&point.date),
+                    label,
+                    value
+                ));
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn short_hash(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
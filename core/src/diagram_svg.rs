@@ -0,0 +1,824 @@
+//! Static SVG rendering of Mermaid flowcharts
+//!
+//! Documents that are read offline cannot load the Mermaid script, so the
+//! flowcharts Reqvire generates, and hand-written ones using the same syntax,
+//! are laid out here instead: nodes are ranked along the flow direction,
+//! ordered to reduce crossings and joined by straight edges. Class and style
+//! colors, edge labels and click links are kept; subgraph frames are not
+//! drawn. Other diagram types are not supported.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::utils;
+
+const FONT_SIZE: f64 = 13.0;
+const CHAR_WIDTH: f64 = 7.2;
+const LINE_HEIGHT: f64 = 16.0;
+const NODE_PADDING: f64 = 12.0;
+const NODE_GAP: f64 = 24.0;
+const RANK_GAP: f64 = 80.0;
+const MARGIN: f64 = 10.0;
+const DEFAULT_FILL: &str = "#f5f5f5";
+const DEFAULT_STROKE: &str = "#333333";
+
+lazy_static! {
+    static ref HEADER: Regex = Regex::new(r"^(?:graph|flowchart)(?:\s+(TB|TD|BT|LR|RL))?\s*$").unwrap();
+    static ref NODE_ID: Regex = Regex::new(r"^[A-Za-z0-9_]+(?:-[A-Za-z0-9_]+)*").unwrap();
+    static ref TEXT_EDGE: Regex =
+        Regex::new(r"^(<?)(--|==|-\.)\s*([^-=.|>\s][^>]*?)\s*(-{2,}>|-{3,}|={2,}>|={3,}|\.-+>|\.-+)").unwrap();
+    static ref EDGE: Regex = Regex::new(r"^(<?)(-{2,}[>ox]?|={2,}[>ox]?|-\.+-[>ox]?)(?:\s*\|([^|]*)\|)?").unwrap();
+    static ref CLICK: Regex = Regex::new(r#"^click\s+(\S+)\s+(?:href\s+)?"([^"]*)""#).unwrap();
+    static ref LINE_BREAK: Regex = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref ENTITY: Regex = Regex::new(r"[&#](#?)(x?)([0-9A-Za-z]+);").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    TopDown,
+    BottomUp,
+    LeftRight,
+    RightLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Rectangle,
+    Rounded,
+    Diamond,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    lines: Vec<String>,
+    shape: Shape,
+    class: Option<String>,
+    style: HashMap<String, String>,
+    link: Option<String>,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    dotted: bool,
+    thick: bool,
+    arrow: bool,
+}
+
+/// Parsed flowchart
+#[derive(Debug)]
+struct Flowchart {
+    direction: Direction,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    class_styles: HashMap<String, HashMap<String, String>>,
+}
+
+/// Renders a Mermaid flowchart as an SVG element, or returns `None` for
+/// other diagram types and syntax that is not supported.
+///
+/// `id_prefix` keeps the ids of several diagrams in one document apart and
+/// `link` maps click targets to the links the nodes point to.
+pub fn render_flowchart(source: &str, id_prefix: &str, link: impl Fn(&str) -> String) -> Option<String> {
+    let flowchart = Flowchart::parse(source)?;
+    if flowchart.nodes.is_empty() {
+        return None;
+    }
+    Some(flowchart.render(id_prefix, &link))
+}
+
+impl Flowchart {
+    fn parse(source: &str) -> Option<Self> {
+        let mut statements = source
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("%%"))
+            .flat_map(split_statements)
+            .filter(|statement| !statement.is_empty());
+
+        let direction = match HEADER.captures(statements.next()?)?.get(1).map(|m| m.as_str()) {
+            Some("BT") => Direction::BottomUp,
+            Some("LR") => Direction::LeftRight,
+            Some("RL") => Direction::RightLeft,
+            _ => Direction::TopDown,
+        };
+        let mut flowchart = Flowchart {
+            direction,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            class_styles: HashMap::new(),
+        };
+
+        for statement in statements {
+            let keyword = statement.split_whitespace().next().unwrap_or_default();
+            match keyword {
+                "subgraph" | "end" | "direction" | "linkStyle" => {}
+                "classDef" => {
+                    let mut parts = statement.split_whitespace().skip(1);
+                    let (name, style) = (parts.next()?, parts.collect::<Vec<_>>().join(" "));
+                    flowchart.class_styles.insert(name.to_string(), parse_style(&style));
+                }
+                "class" => {
+                    let mut parts = statement.split_whitespace().skip(1);
+                    let (ids, class) = (parts.next()?, parts.next()?);
+                    for id in ids.split(',') {
+                        let index = flowchart.node(id.trim());
+                        flowchart.nodes[index].class = Some(class.to_string());
+                    }
+                }
+                "style" => {
+                    let mut parts = statement.split_whitespace().skip(1);
+                    let (id, style) = (parts.next()?, parts.collect::<Vec<_>>().join(" "));
+                    let index = flowchart.node(id);
+                    flowchart.nodes[index].style.extend(parse_style(&style));
+                }
+                "click" => {
+                    // Callbacks cannot run in a static image, only links are kept
+                    if let Some(caps) = CLICK.captures(statement) {
+                        let index = flowchart.node(&caps[1]);
+                        flowchart.nodes[index].link = Some(caps[2].to_string());
+                    }
+                }
+                _ => flowchart.parse_chain(statement)?,
+            }
+        }
+        Some(flowchart)
+    }
+
+    /// Parses nodes joined by edges, such as `A["Label"] -->|text| B & C`
+    fn parse_chain(&mut self, statement: &str) -> Option<()> {
+        let mut rest = statement;
+        let mut previous: Vec<usize> = Vec::new();
+        let mut pending: Option<Edge> = None;
+        loop {
+            let mut group = Vec::new();
+            loop {
+                let (index, remainder) = self.parse_node(rest.trim_start())?;
+                group.push(index);
+                rest = remainder.trim_start();
+                match rest.strip_prefix('&') {
+                    Some(remainder) => rest = remainder,
+                    None => break,
+                }
+            }
+            if let Some(edge) = pending.take() {
+                for &from in &previous {
+                    for &to in &group {
+                        self.edges.push(Edge { from, to, label: edge.label.clone(), ..edge });
+                    }
+                }
+            }
+            if rest.is_empty() {
+                return Some(());
+            }
+
+            let (edge, remainder) = if let Some(caps) = TEXT_EDGE.captures(rest) {
+                let operator = format!("{}{}", &caps[2], &caps[4]);
+                (edge_of(&operator, Some(&caps[3])), &rest[caps[0].len()..])
+            } else {
+                let caps = EDGE.captures(rest)?;
+                (edge_of(&caps[2], caps.get(3).map(|m| m.as_str())), &rest[caps[0].len()..])
+            };
+            pending = Some(edge);
+            previous = group;
+            rest = remainder;
+        }
+    }
+
+    /// Parses a node reference with an optional shape, label and `:::class`
+    fn parse_node<'s>(&mut self, text: &'s str) -> Option<(usize, &'s str)> {
+        let id = NODE_ID.find(text)?.as_str();
+        let index = self.node(id);
+        let mut rest = self.parse_shape(index, &text[id.len()..])?;
+
+        if let Some(class) = rest.strip_prefix(":::").and_then(|class| NODE_ID.find(class)) {
+            self.nodes[index].class = Some(class.as_str().to_string());
+            rest = &rest[3 + class.end()..];
+        }
+        Some((index, rest))
+    }
+
+    /// Parses the shape and label following a node id and returns the rest
+    fn parse_shape<'s>(&mut self, index: usize, rest: &'s str) -> Option<&'s str> {
+        let opener: String = rest.chars().take_while(|c| "[({>".contains(*c)).take(2).collect();
+        if opener.is_empty() {
+            return Some(rest);
+        }
+        let closer: String = opener
+            .chars()
+            .rev()
+            .map(|c| match c {
+                '[' | '>' => ']',
+                '(' => ')',
+                _ => '}',
+            })
+            .collect();
+        let inner = &rest[opener.len()..];
+        let (label, remainder) = match inner.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], quoted[end + 1..].strip_prefix(closer.as_str())?)
+            }
+            None => {
+                let end = inner.find(closer.as_str())?;
+                (&inner[..end], &inner[end + closer.len()..])
+            }
+        };
+
+        let node = &mut self.nodes[index];
+        node.lines = label_lines(label);
+        node.shape = match opener.as_str() {
+            "{" | "{{" => Shape::Diamond,
+            "(" | "((" | "([" => Shape::Rounded,
+            _ => Shape::Rectangle,
+        };
+        Some(remainder)
+    }
+
+    /// Index of the node with the given id, adding it on first use
+    fn node(&mut self, id: &str) -> usize {
+        if let Some(index) = self.nodes.iter().position(|node| node.id == id) {
+            return index;
+        }
+        self.nodes.push(Node {
+            id: id.to_string(),
+            lines: vec![id.to_string()],
+            shape: Shape::Rectangle,
+            class: None,
+            style: HashMap::new(),
+            link: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn render(&self, id_prefix: &str, link: &dyn Fn(&str) -> String) -> String {
+        let layout = Layout::compute(self);
+        let marker = format!("{}-arrow", id_prefix);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"reqvire-diagram\" width=\"{w:.0}\" height=\"{h:.0}\" viewBox=\"0 0 {w:.0} {h:.0}\" font-family=\"sans-serif\" font-size=\"{f}\">\n",
+            w = layout.width,
+            h = layout.height,
+            f = FONT_SIZE
+        );
+        svg.push_str(&format!(
+            "<defs><marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"{}\"/></marker></defs>\n",
+            marker, DEFAULT_STROKE
+        ));
+
+        let mut labels = String::new();
+        for edge in &self.edges {
+            if edge.from == edge.to {
+                continue;
+            }
+            let (x1, y1) = layout.boundary(edge.from, layout.center(edge.to));
+            let (x2, y2) = layout.boundary(edge.to, layout.center(edge.from));
+            let mut attributes = format!(
+                "stroke=\"{}\" stroke-width=\"{}\"",
+                DEFAULT_STROKE,
+                if edge.thick { 3 } else { 1 }
+            );
+            if edge.dotted {
+                attributes.push_str(" stroke-dasharray=\"4 3\"");
+            }
+            if edge.arrow {
+                attributes.push_str(&format!(" marker-end=\"url(#{})\"", marker));
+            }
+            svg.push_str(&format!(
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>\n",
+                x1, y1, x2, y2, attributes
+            ));
+            if let Some(label) = edge.label.as_deref().filter(|label| !label.is_empty()) {
+                let (x, y) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+                let width = text_width(label) + 8.0;
+                labels.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#ffffff\" fill-opacity=\"0.85\"/>\n\
+                     <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" font-size=\"11\">{}</text>\n",
+                    x - width / 2.0,
+                    y - LINE_HEIGHT / 2.0,
+                    width,
+                    LINE_HEIGHT,
+                    x,
+                    y,
+                    utils::escape_html(label)
+                ));
+            }
+        }
+        svg.push_str(&labels);
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            svg.push_str(&self.render_node(node, &layout.boxes[index], link));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn render_node(&self, node: &Node, bounds: &Bounds, link: &dyn Fn(&str) -> String) -> String {
+        let default_style = HashMap::new();
+        let class_style = match &node.class {
+            Some(class) => self.class_styles.get(class),
+            None => self.class_styles.get("default"),
+        }
+        .unwrap_or(&default_style);
+        let property = |name: &str, fallback: &str| -> String {
+            node.style
+                .get(name)
+                .or_else(|| class_style.get(name))
+                .map(|value| value.trim_end_matches("px").to_string())
+                .unwrap_or_else(|| fallback.to_string())
+        };
+        let paint = format!(
+            "fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"",
+            utils::escape_html(&property("fill", DEFAULT_FILL)),
+            utils::escape_html(&property("stroke", DEFAULT_STROKE)),
+            utils::escape_html(&property("stroke-width", "1"))
+        );
+
+        let shape = match node.shape {
+            Shape::Diamond => format!(
+                "<polygon points=\"{cx:.1},{y:.1} {r:.1},{cy:.1} {cx:.1},{b:.1} {x:.1},{cy:.1}\" {paint}/>",
+                cx = bounds.x + bounds.width / 2.0,
+                cy = bounds.y + bounds.height / 2.0,
+                x = bounds.x,
+                y = bounds.y,
+                r = bounds.x + bounds.width,
+                b = bounds.y + bounds.height,
+                paint = paint
+            ),
+            shape => format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{}\" {}/>",
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                if shape == Shape::Rounded { 12 } else { 3 },
+                paint
+            ),
+        };
+
+        let cx = bounds.x + bounds.width / 2.0;
+        let first_y = bounds.y + bounds.height / 2.0 - (node.lines.len() as f64 - 1.0) * LINE_HEIGHT / 2.0;
+        let text = node
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                format!(
+                    "<tspan x=\"{:.1}\" y=\"{:.1}\">{}</tspan>",
+                    cx,
+                    first_y + index as f64 * LINE_HEIGHT,
+                    utils::escape_html(line)
+                )
+            })
+            .collect::<String>();
+        let group = format!(
+            "<g>{}<text text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text></g>",
+            shape,
+            utils::escape_html(&property("color", "#000000")),
+            text
+        );
+        match &node.link {
+            Some(target) => format!("<a href=\"{}\">{}</a>\n", utils::escape_html(&link(target)), group),
+            None => format!("{}\n", group),
+        }
+    }
+}
+
+/// Statements of a line, which are separated by semicolons outside quotes
+fn split_statements(line: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                statements.push(line[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(line[start..].trim());
+    statements
+}
+
+/// Edge of a link operator such as `-->`, `-.->` or `==>`
+fn edge_of(operator: &str, label: Option<&str>) -> Edge {
+    Edge {
+        from: 0,
+        to: 0,
+        label: label.map(|label| label_lines(label).join(" ")),
+        dotted: operator.contains('.'),
+        thick: operator.contains('='),
+        arrow: operator.ends_with(['>', 'o', 'x']),
+    }
+}
+
+/// Lines of a label, with line break tags applied and other markup removed
+fn label_lines(label: &str) -> Vec<String> {
+    let label = label.trim().trim_matches('"');
+    LINE_BREAK
+        .split(label)
+        .map(|line| decode_entities(&TAG.replace_all(line, "")).trim().to_string())
+        .collect()
+}
+
+/// Decodes the character references Mermaid labels use, written as `&...;` or `#...;`
+fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures| {
+            let value = &caps[3];
+            let decoded = if caps[1].is_empty() {
+                match value {
+                    "quot" => Some('"'),
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    _ => value.parse::<u32>().ok().and_then(char::from_u32),
+                }
+            } else {
+                let radix = if caps[2].is_empty() { 10 } else { 16 };
+                u32::from_str_radix(value, radix).ok().and_then(char::from_u32)
+            };
+            decoded.map(String::from).unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Properties of a `classDef` or `style` statement, such as `fill:#fff,stroke:#333`
+fn parse_style(style: &str) -> HashMap<String, String> {
+    style
+        .split(',')
+        .filter_map(|property| property.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn text_width(text: &str) -> f64 {
+    text.chars().count() as f64 * CHAR_WIDTH
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Positions of the nodes of a flowchart
+struct Layout {
+    boxes: Vec<Bounds>,
+    width: f64,
+    height: f64,
+}
+
+impl Layout {
+    fn compute(flowchart: &Flowchart) -> Self {
+        let sizes: Vec<(f64, f64)> = flowchart
+            .nodes
+            .iter()
+            .map(|node| {
+                let width = node.lines.iter().map(|line| text_width(line)).fold(0.0, f64::max) + 2.0 * NODE_PADDING;
+                let height = node.lines.len() as f64 * LINE_HEIGHT + NODE_PADDING;
+                match node.shape {
+                    Shape::Diamond => (width * 1.5, height * 1.8),
+                    _ => (width.max(60.0), height),
+                }
+            })
+            .collect();
+        let layers = order_layers(flowchart, &rank_nodes(flowchart));
+
+        // Ranks advance along the main axis, nodes of a rank along the cross axis
+        let horizontal = matches!(flowchart.direction, Direction::LeftRight | Direction::RightLeft);
+        let main = |(width, height): (f64, f64)| if horizontal { width } else { height };
+        let cross = |(width, height): (f64, f64)| if horizontal { height } else { width };
+
+        let rank_sizes: Vec<f64> = layers
+            .iter()
+            .map(|layer| layer.iter().map(|&node| main(sizes[node])).fold(0.0, f64::max))
+            .collect();
+        let layer_extents: Vec<f64> = layers
+            .iter()
+            .map(|layer| layer.iter().map(|&node| cross(sizes[node])).sum::<f64>() + NODE_GAP * (layer.len() as f64 - 1.0))
+            .collect();
+        // Edge labels sit between ranks, so side by side ranks leave room for them
+        let rank_gap = if horizontal {
+            flowchart
+                .edges
+                .iter()
+                .filter_map(|edge| edge.label.as_deref())
+                .map(|label| text_width(label) + 2.0 * NODE_PADDING)
+                .fold(RANK_GAP, f64::max)
+        } else {
+            RANK_GAP
+        };
+        let cross_extent = layer_extents.iter().cloned().fold(0.0, f64::max);
+        let main_extent = rank_sizes.iter().sum::<f64>() + rank_gap * (layers.len() as f64 - 1.0);
+
+        let mut boxes = vec![Bounds::default(); flowchart.nodes.len()];
+        let mut main_position = 0.0;
+        for (rank, layer) in layers.iter().enumerate() {
+            let mut cross_position = (cross_extent - layer_extents[rank]) / 2.0;
+            for &node in layer {
+                let size = sizes[node];
+                // Nodes are centered within their rank
+                let main_start = main_position + (rank_sizes[rank] - main(size)) / 2.0;
+                let main_start = match flowchart.direction {
+                    Direction::RightLeft | Direction::BottomUp => main_extent - main_start - main(size),
+                    _ => main_start,
+                };
+                let (x, y) = if horizontal {
+                    (main_start, cross_position)
+                } else {
+                    (cross_position, main_start)
+                };
+                boxes[node] = Bounds { x: x + MARGIN, y: y + MARGIN, width: size.0, height: size.1 };
+                cross_position += cross(size) + NODE_GAP;
+            }
+            main_position += rank_sizes[rank] + rank_gap;
+        }
+
+        let (width, height) = if horizontal { (main_extent, cross_extent) } else { (cross_extent, main_extent) };
+        Layout { boxes, width: width + 2.0 * MARGIN, height: height + 2.0 * MARGIN }
+    }
+
+    fn center(&self, node: usize) -> (f64, f64) {
+        let bounds = &self.boxes[node];
+        (bounds.x + bounds.width / 2.0, bounds.y + bounds.height / 2.0)
+    }
+
+    /// Point where the line from the center of `node` towards `toward` leaves its box
+    fn boundary(&self, node: usize, toward: (f64, f64)) -> (f64, f64) {
+        let bounds = &self.boxes[node];
+        let (cx, cy) = self.center(node);
+        let (dx, dy) = (toward.0 - cx, toward.1 - cy);
+        if dx == 0.0 && dy == 0.0 {
+            return (cx, cy);
+        }
+        let scale_x = if dx == 0.0 { f64::INFINITY } else { bounds.width / 2.0 / dx.abs() };
+        let scale_y = if dy == 0.0 { f64::INFINITY } else { bounds.height / 2.0 / dy.abs() };
+        let scale = scale_x.min(scale_y);
+        (cx + dx * scale, cy + dy * scale)
+    }
+}
+
+/// Rank of every node: the length of the longest path reaching it, with
+/// edges closing a cycle left out
+fn rank_nodes(flowchart: &Flowchart) -> Vec<usize> {
+    let count = flowchart.nodes.len();
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for edge in &flowchart.edges {
+        if edge.from != edge.to {
+            successors[edge.from].push(edge.to);
+        }
+    }
+
+    // Depth-first search in declaration order yields a topological order
+    // of the graph without its back edges
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        New,
+        Active,
+        Done,
+    }
+    let mut state = vec![State::New; count];
+    let mut forward: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut order = Vec::with_capacity(count);
+    for start in 0..count {
+        if state[start] != State::New {
+            continue;
+        }
+        let mut stack = vec![(start, 0)];
+        state[start] = State::Active;
+        while let Some((node, next)) = stack.pop() {
+            if let Some(&successor) = successors[node].get(next) {
+                stack.push((node, next + 1));
+                match state[successor] {
+                    State::New => {
+                        forward[node].push(successor);
+                        state[successor] = State::Active;
+                        stack.push((successor, 0));
+                    }
+                    State::Done => forward[node].push(successor),
+                    State::Active => {}
+                }
+            } else {
+                state[node] = State::Done;
+                order.push(node);
+            }
+        }
+    }
+
+    let mut ranks = vec![0; count];
+    for &node in order.iter().rev() {
+        for &successor in &forward[node] {
+            ranks[successor] = ranks[successor].max(ranks[node] + 1);
+        }
+    }
+    ranks
+}
+
+/// Nodes of every rank, ordered by the positions of their neighbours to
+/// reduce edge crossings
+fn order_layers(flowchart: &Flowchart, ranks: &[usize]) -> Vec<Vec<usize>> {
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); ranks.iter().max().map_or(0, |max| max + 1)];
+    for (node, &rank) in ranks.iter().enumerate() {
+        layers[rank].push(node);
+    }
+
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); ranks.len()];
+    for edge in &flowchart.edges {
+        if edge.from != edge.to {
+            neighbours[edge.from].push(edge.to);
+            neighbours[edge.to].push(edge.from);
+        }
+    }
+
+    let mut position = vec![0.0; ranks.len()];
+    let update_positions = |layers: &[Vec<usize>], position: &mut Vec<f64>| {
+        for layer in layers {
+            for (index, &node) in layer.iter().enumerate() {
+                position[node] = index as f64;
+            }
+        }
+    };
+    update_positions(&layers, &mut position);
+
+    for sweep in 0..8 {
+        let downward = sweep % 2 == 0;
+        let indices: Vec<usize> = if downward { (1..layers.len()).collect() } else { (0..layers.len().saturating_sub(1)).rev().collect() };
+        for rank in indices {
+            let adjacent = if downward { rank - 1 } else { rank + 1 };
+            let barycenters: HashMap<usize, f64> = layers[rank]
+                .iter()
+                .map(|&node| {
+                    let positions: Vec<f64> = neighbours[node]
+                        .iter()
+                        .filter(|&&neighbour| ranks[neighbour] == adjacent)
+                        .map(|&neighbour| position[neighbour])
+                        .collect();
+                    let barycenter = if positions.is_empty() {
+                        position[node]
+                    } else {
+                        positions.iter().sum::<f64>() / positions.len() as f64
+                    };
+                    (node, barycenter)
+                })
+                .collect();
+            layers[rank].sort_by(|a, b| barycenters[a].total_cmp(&barycenters[b]));
+            update_positions(&layers, &mut position);
+        }
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_generated_flowchart() {
+        let source = "graph LR;\n  %% REQVIRE-AUTOGENERATED-DIAGRAM\n\
+            \x20 classDef verification fill:#d6f9d6,stroke:#5fd75f,stroke-width:1px;\n\
+            \x20 a1[\"Remote &quot;Start&quot;\"];\n  class a1 verification;\n  click a1 \"Requirements.md#remote-start\";\n\
+            \x20 b2[\"Test\"];\n  b2 -.->|verifies| a1;\n  a1 -->|satisfiedBy| c3[\"main.rs\"];\n";
+        let svg = render_flowchart(source, "d1", |target| format!("#{}", target)).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"reqvire-diagram\""));
+        assert!(svg.contains("<a href=\"#Requirements.md#remote-start\"><g><rect"));
+        assert!(svg.contains("fill=\"#d6f9d6\" stroke=\"#5fd75f\" stroke-width=\"1\""));
+        assert!(svg.contains(">Remote &quot;Start&quot;</tspan>"));
+        assert!(svg.contains(">verifies</text>"));
+        assert!(svg.contains("stroke-dasharray=\"4 3\" marker-end=\"url(#d1-arrow)\""));
+        assert_eq!(svg.matches("<line ").count(), 2);
+    }
+
+    #[test]
+    fn test_rank_and_order() {
+        let flowchart = Flowchart::parse("flowchart TD\n A[Start] --> B{Is it?}\n B -- Yes --> C[OK]\n B -- No ----> D[Retry] --> A").unwrap();
+        assert_eq!(flowchart.nodes.len(), 4);
+        assert_eq!(flowchart.edges.len(), 4);
+        assert_eq!(flowchart.edges[1].label.as_deref(), Some("Yes"));
+        assert_eq!(flowchart.nodes[1].shape, Shape::Diamond);
+        assert_eq!(rank_nodes(&flowchart), vec![0, 1, 2, 2]);
+
+        let flowchart = Flowchart::parse("graph LR\n A[Start]:::done --> B:::open").unwrap();
+        assert_eq!(flowchart.nodes[0].class.as_deref(), Some("done"));
+        assert_eq!(flowchart.nodes[1].class.as_deref(), Some("open"));
+    }
+
+    #[test]
+    fn test_edge_syntaxes() {
+        let flowchart = Flowchart::parse(
+            "graph TD\n A --> B\n A --- C\n A -.-> D\n A ==> E\n A --o F\n A --x G\n A <--> H\n\
+             A -- solid text --> B\n A -. dotted text .-> C\n A == thick text ==> D\n A ---->|long| E",
+        )
+        .unwrap();
+        let edges: Vec<(&str, Option<&str>, bool, bool, bool)> = flowchart
+            .edges
+            .iter()
+            .map(|edge| (flowchart.nodes[edge.to].id.as_str(), edge.label.as_deref(), edge.dotted, edge.thick, edge.arrow))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("B", None, false, false, true),
+                ("C", None, false, false, false),
+                ("D", None, true, false, true),
+                ("E", None, false, true, true),
+                ("F", None, false, false, true),
+                ("G", None, false, false, true),
+                ("H", None, false, false, true),
+                ("B", Some("solid text"), false, false, true),
+                ("C", Some("dotted text"), true, false, true),
+                ("D", Some("thick text"), false, true, true),
+                ("E", Some("long"), false, false, true),
+            ]
+        );
+        assert!(flowchart.edges.iter().all(|edge| flowchart.nodes[edge.from].id == "A"));
+
+        // Groups joined by & link every node of one side to every node of the other
+        let flowchart = Flowchart::parse("graph LR\n A & B --> C & D; D --> E").unwrap();
+        let pairs: Vec<(&str, &str)> = flowchart
+            .edges
+            .iter()
+            .map(|edge| (flowchart.nodes[edge.from].id.as_str(), flowchart.nodes[edge.to].id.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("A", "C"), ("A", "D"), ("B", "C"), ("B", "D"), ("D", "E")]);
+
+        let svg = render_flowchart("graph LR\n A --- B\n B ==> C", "d", |t| t.to_string()).unwrap();
+        assert!(svg.contains("stroke-width=\"1\"/>"));
+        assert!(svg.contains("stroke-width=\"3\" marker-end=\"url(#d-arrow)\""));
+    }
+
+    #[test]
+    fn test_node_shapes() {
+        let source = "flowchart TD\n A{Decide} --> B{{Prepare}}\n B --> C(Round) --> D([Stadium]) --> E((Circle))\n E --> F[Box] --> G[[Sub]]";
+        let flowchart = Flowchart::parse(source).unwrap();
+        let shapes: Vec<Shape> = flowchart.nodes.iter().map(|node| node.shape).collect();
+        assert_eq!(
+            shapes,
+            vec![
+                Shape::Diamond,
+                Shape::Diamond,
+                Shape::Rounded,
+                Shape::Rounded,
+                Shape::Rounded,
+                Shape::Rectangle,
+                Shape::Rectangle
+            ]
+        );
+        assert_eq!(flowchart.nodes[1].lines, vec!["Prepare"]);
+        assert_eq!(flowchart.nodes[6].lines, vec!["Sub"]);
+
+        let svg = render_flowchart(source, "d", |t| t.to_string()).unwrap();
+        assert_eq!(svg.matches("<polygon ").count(), 2);
+        assert_eq!(svg.matches("rx=\"12\"").count(), 3);
+        assert!(svg.contains(">Decide</tspan>"));
+
+        // Diamonds are wider and taller than the rectangle of the same label
+        let layout = Layout::compute(&Flowchart::parse("graph TD\n A{Same} --> B[Same]").unwrap());
+        assert!(layout.boxes[0].width > layout.boxes[1].width);
+        assert!(layout.boxes[0].height > layout.boxes[1].height);
+    }
+
+    #[test]
+    fn test_click_links() {
+        let source = "graph TD\n A --> B --> C\n click A \"Requirements.md#a\"\n\
+             click B href \"https://example.com/?x=1&y=2\" \"Tooltip\"\n click C callback";
+        let flowchart = Flowchart::parse(source).unwrap();
+        assert_eq!(flowchart.nodes[0].link.as_deref(), Some("Requirements.md#a"));
+        assert_eq!(flowchart.nodes[1].link.as_deref(), Some("https://example.com/?x=1&y=2"));
+        assert_eq!(flowchart.nodes[2].link, None);
+
+        let svg = render_flowchart(source, "d", |target| format!("../{}", target)).unwrap();
+        assert!(svg.contains("<a href=\"../Requirements.md#a\"><g>"));
+        assert!(svg.contains("<a href=\"../https://example.com/?x=1&amp;y=2\"><g>"));
+        assert_eq!(svg.matches("<a href=").count(), 2);
+    }
+
+    #[test]
+    fn test_unsupported_diagrams() {
+        assert!(render_flowchart("classDiagram\n class A", "d", |t| t.to_string()).is_none());
+        assert!(render_flowchart("sequenceDiagram\n A->>B: hi", "d", |t| t.to_string()).is_none());
+        assert!(render_flowchart("graph TD\n A --> B --- ", "d", |t| t.to_string()).is_none());
+        for source in [
+            "stateDiagram-v2\n [*] --> Idle",
+            "erDiagram\n PUMP ||--o{ VALVE : drives",
+            "gantt\n title Plan",
+            "pie\n \"A\" : 1",
+            "graph TD",
+            "",
+        ] {
+            assert!(render_flowchart(source, "d", |t| t.to_string()).is_none(), "{}", source);
+        }
+    }
+}
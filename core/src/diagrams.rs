@@ -9,7 +9,6 @@ use log::debug;
 use crate::relation;
 use crate::element::ElementType;
 use crate::element::RequirementType;
-use crate::git_commands;
 use crate::filesystem;
 use std::path::Path;
 use serde::Serialize;
@@ -408,20 +407,9 @@ fn generate_section_diagram(
     diagrams_with_blobs: bool
 ) -> Result<String, ReqvireError> {
     // Get Git repository information for creating proper links
-    let repo_root = match git_commands::get_git_root_dir() {
-        Ok(root) => root,
-        Err(_) => PathBuf::from(""),
-    };
-
-    let base_url = match git_commands::get_repository_base_url() {
-        Ok(url) => url,
-        Err(_) => String::from(""),
-    };
-
-    let commit_hash = match git_commands::get_commit_hash() {
-        Ok(hash) => hash,
-        Err(_) => String::from("HEAD"),
-    };
+    let repo_root = registry.context.root();
+    let base_url = registry.context.repository_base_url().unwrap_or("");
+    let commit_hash = registry.context.commit_hash().unwrap_or("HEAD");
 
     // Use default diagram direction constant
     let mut diagram = String::from(format!("```mermaid\ngraph {};\n", DEFAULT_DIAGRAM_DIRECTION));
@@ -447,9 +435,9 @@ fn generate_section_diagram(
             &mut included_elements,
             file_path,
             diagrams_with_blobs,
-            &repo_root,
-            &base_url,
-            &commit_hash,
+            repo_root,
+            base_url,
+            commit_hash,
         )?;
     }
 
@@ -483,9 +471,9 @@ fn generate_section_diagram(
                 &mut included_elements,
                 file_path,
                 diagrams_with_blobs,
-                &repo_root,
-                &base_url,
-                &commit_hash,
+                repo_root,
+                base_url,
+                commit_hash,
             )?;
         }
     }
@@ -503,7 +491,7 @@ fn add_element_to_diagram(
     included_elements: &mut HashSet<String>,
     file_path: &str,
     diagrams_with_blobs: bool,  
-    repo_root: &PathBuf,
+    repo_root: &Path,
     base_url: &str,
     commit_hash: &str,
 ) -> Result<(), ReqvireError> {
@@ -516,6 +504,7 @@ fn add_element_to_diagram(
  
     // Get relative ID for local navigation
     let relative_target = utils::to_relative_identifier(
+        &registry.context,
         &element.identifier.clone(),
         &base_dir,
         false
@@ -527,7 +516,7 @@ fn add_element_to_diagram(
        
     let click_target = if diagrams_with_blobs && has_git_info {
         // Get repository-relative path
-        let relative_id = match utils::get_relative_path(&PathBuf::from(&element.identifier)) {
+        let relative_id = match utils::get_relative_path(&registry.context, Path::new(&element.identifier)) {
             Ok(rel_path) => rel_path.to_string_lossy().to_string(),
             Err(_) => element.identifier.clone(),
         };
@@ -591,6 +580,7 @@ fn add_element_to_diagram(
 
                 // Get relative ID for local navigation
                 let relative_target = utils::to_relative_identifier(
+                    &registry.context,
                     &target,
                     &base_dir,
                     false
@@ -600,7 +590,7 @@ fn add_element_to_diagram(
                 // Get a GitHub link if we have git info
                 let click_target = if diagrams_with_blobs &&  has_git_info {
                     // Get repository-relative path
-                    let relative_id = match utils::get_relative_path(&PathBuf::from(target)) {
+                    let relative_id = match utils::get_relative_path(&registry.context, Path::new(target)) {
                         Ok(rel_path) => rel_path.to_string_lossy().to_string(),
                         Err(_) => target.clone(),
                     };
@@ -646,6 +636,7 @@ fn add_element_to_diagram(
             relation::LinkType::InternalPath(path) => {
                 // Get relative ID for local navigation
                 let relative_target = utils::to_relative_identifier(
+                    &registry.context,
                     &path.to_string_lossy().into_owned(),
                     &base_dir,
                     false
@@ -655,7 +646,7 @@ fn add_element_to_diagram(
                 // Get a GitHub link if we have git info
                 let click_target = if diagrams_with_blobs &&  has_git_info {
                     // Get repository-relative path
-                    let relative_id = match utils::get_relative_path(&path) {
+                    let relative_id = match utils::get_relative_path(&registry.context, path) {
                         Ok(rel_path) => rel_path.to_string_lossy().to_string(),
                         Err(_) => path.to_string_lossy().to_string()
                     };
@@ -723,19 +714,10 @@ pub fn process_diagrams(
             .push((section, new_diagram));
     }
 
-    // Get git root for resolving relative paths
-    let git_root = match git_commands::get_git_root_dir() {
-        Ok(root) => root,
-        Err(_) => {
-            log::error!("Not in a git repository, using current directory");
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-        }
-    };
-
     // Process each file
     for (file_path, section_diagrams) in files_to_update {
-        // Resolve file path relative to git root, not current directory
-        let absolute_file_path = git_root.join(&file_path);
+        // Resolve file path relative to the model root, not current directory
+        let absolute_file_path = registry.context.resolve(&file_path);
 
         // Read file content
         let mut file_content = match filesystem::read_file(&absolute_file_path) {
@@ -769,7 +751,7 @@ pub fn process_diagrams(
 /// - `new_diagram`: The newly generated Mermaid diagram.
 ///
 /// Returns the modified file content as a `String`.
-fn replace_section_diagram(content: &str, section: &str, new_diagram: &str) -> String {
+pub(crate) fn replace_section_diagram(content: &str, section: &str, new_diagram: &str) -> String {
     let section_header = format!("## {}", section);
     let mermaid_block_start = "```mermaid";
     let mermaid_block_end = "```";
@@ -855,19 +837,10 @@ pub fn remove_diagrams(registry: &GraphRegistry) -> Result<(), ReqvireError> {
             .push(section.clone());
     }
 
-    // Get git root for resolving relative paths
-    let git_root = match git_commands::get_git_root_dir() {
-        Ok(root) => root,
-        Err(_) => {
-            log::error!("Not in a git repository, using current directory");
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-        }
-    };
-
     // Process each file
     for (file_path, sections) in files_to_update {
-        // Resolve file path relative to git root, not current directory
-        let absolute_file_path = git_root.join(&file_path);
+        // Resolve file path relative to the model root, not current directory
+        let absolute_file_path = registry.context.resolve(&file_path);
 
         // Read file content
         let mut file_content = match filesystem::read_file(&absolute_file_path) {
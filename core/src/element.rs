@@ -65,6 +65,18 @@ pub enum ElementType {
 
 
 impl ElementType {
+    /// Type names recognized by `from_metadata` without custom types
+    pub const BUILT_IN_NAMES: &'static [&'static str] = &[
+        "requirement",
+        "user-requirement",
+        "verification",
+        "test-verification",
+        "analysis-verification",
+        "inspection-verification",
+        "demonstration-verification",
+        "file",
+    ];
+
     /// Returns the metadata key corresponding to this ElementType,
     /// e.g. "user_requirement", "analysis-verification", or the
     /// raw string for Other.
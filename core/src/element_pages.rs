@@ -0,0 +1,356 @@
+//! Element detail pages of the HTML export
+//!
+//! Every element gets a page of its own with its content, metadata, outgoing
+//! and incoming relations grouped by type, its verification trace, the tree
+//! of elements impacted by a change and links to its source files. Pages are
+//! generated as Markdown and converted to HTML like the model files, so they
+//! share the navigation, styles and theme of the other pages.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::element::{Element, ElementType};
+use crate::error::ReqvireError;
+use crate::filesystem;
+use crate::graph_registry::{ElementNode, GraphRegistry};
+use crate::html;
+use crate::relation::{LinkType, Relation};
+use crate::theme::Theme;
+use crate::utils;
+use crate::verification_trace::{RequirementNode, VerificationTrace, VerificationTraceGenerator};
+
+/// Folder of the element pages in the export
+pub const ELEMENTS_FOLDER: &str = "elements";
+
+/// Path of an element's page relative to the export root, with the given
+/// extension: `elements/<model file without .md>/<fragment>.<extension>`
+pub fn element_page_path(identifier: &str, extension: &str) -> String {
+    let (file, fragment) = utils::extract_path_and_fragment(identifier);
+    let stem = file.strip_suffix(".md").unwrap_or(file);
+    let name: String = fragment
+        .unwrap_or("element")
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    format!("{}/{}/{}.{}", ELEMENTS_FOLDER, stem, name, extension)
+}
+
+/// Relative path from a page to the export root
+fn root_prefix(page_path: &str) -> String {
+    "../".repeat(page_path.matches('/').count())
+}
+
+/// Path of the exported page of a model file relative to the export root
+fn file_page_path(file_path: &str) -> String {
+    match file_path.strip_suffix(".md") {
+        Some(stem) if stem == "SpecificationIndex" || stem.ends_with("/SpecificationIndex") => {
+            format!("{}index.html", &stem[..stem.len() - "SpecificationIndex".len()])
+        }
+        Some(stem) => format!("{}.html", stem),
+        None => file_path.to_string(),
+    }
+}
+
+/// Verifications whose trace passes through an element
+struct Coverage<'a> {
+    traces: HashMap<&'a str, &'a VerificationTrace>,
+    verified_by: HashMap<String, Vec<(&'a VerificationTrace, bool)>>,
+}
+
+impl<'a> Coverage<'a> {
+    fn new(traces: impl Iterator<Item = &'a VerificationTrace>) -> Self {
+        fn collect<'a>(
+            trace: &'a VerificationTrace,
+            nodes: &[RequirementNode],
+            verified_by: &mut HashMap<String, Vec<(&'a VerificationTrace, bool)>>,
+        ) {
+            for node in nodes {
+                let entries = verified_by.entry(node.id.clone()).or_default();
+                if !entries.iter().any(|(existing, _)| existing.identifier == trace.identifier) {
+                    entries.push((trace, node.is_directly_verified));
+                }
+                collect(trace, &node.children, verified_by);
+            }
+        }
+
+        let mut coverage = Coverage { traces: HashMap::new(), verified_by: HashMap::new() };
+        for trace in traces {
+            coverage.traces.insert(trace.identifier.as_str(), trace);
+            collect(trace, &trace.trace_tree.requirements, &mut coverage.verified_by);
+        }
+        for entries in coverage.verified_by.values_mut() {
+            entries.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+        }
+        coverage
+    }
+}
+
+/// Writes the HTML page of every element below `export_dir` and returns
+/// the number of pages
+pub fn generate_element_pages(registry: &GraphRegistry, export_dir: &Path, theme: &Theme) -> Result<usize, ReqvireError> {
+    let trace_generator = VerificationTraceGenerator::new(registry, false, None);
+    let report = trace_generator.generate();
+    let coverage = Coverage::new(
+        report
+            .files
+            .values()
+            .flat_map(|file| file.sections.values())
+            .flat_map(|section| section.verifications.iter()),
+    );
+
+    let mut count = 0;
+    for element in registry.get_all_elements() {
+        if element.identifier.starts_with("__virtual__") {
+            continue;
+        }
+        let page_path = element_page_path(&element.identifier, "md");
+        let markdown = element_page(registry, element, &coverage, &root_prefix(&page_path));
+        let html = html::convert_to_html(
+            &export_dir.join(&page_path),
+            &markdown,
+            &element.name,
+            &export_dir.to_path_buf(),
+            theme,
+        )?;
+        filesystem::write_file(export_dir.join(element_page_path(&element.identifier, "html")), html.as_bytes())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn element_page(registry: &GraphRegistry, element: &Element, coverage: &Coverage, root: &str) -> String {
+    let file_page = format!("{}{}#{}", root, file_page_path(&element.file_path), html::heading_id(&element.name));
+    let mut page = format!("# {}\n\n", element.name);
+    page.push_str(&format!("- **Type**: {}\n", element.element_type.as_str()));
+    page.push_str(&format!("- **Section**: {}\n", element.section));
+    page.push_str(&format!("- **Identifier**: `{}`\n", element.identifier));
+    page.push_str(&format!("- **Page**: [{}]({})\n\n", element.file_path, file_page));
+
+    page.push_str("## Content\n\n");
+    if element.content.trim().is_empty() {
+        page.push_str("No content.\n\n");
+    } else {
+        page.push_str(&rebase_links(element.content.trim(), &element.file_path, root));
+        page.push_str("\n\n");
+    }
+
+    if !element.metadata.is_empty() {
+        page.push_str("## Metadata\n\n");
+        let metadata: BTreeMap<_, _> = element.metadata.iter().collect();
+        for (key, value) in metadata {
+            page.push_str(&format!("- **{}**: {}\n", key, value));
+        }
+        page.push('\n');
+    }
+
+    for (title, user_created) in [("Outgoing Relations", true), ("Incoming Relations", false)] {
+        page.push_str(&format!("## {}\n\n", title));
+        let mut groups: BTreeMap<&str, Vec<&Relation>> = BTreeMap::new();
+        for relation in element.relations.iter().filter(|r| r.user_created == user_created) {
+            groups.entry(relation.relation_type.name).or_default().push(relation);
+        }
+        if groups.is_empty() {
+            page.push_str("None.\n\n");
+        }
+        for (relation_type, relations) in groups {
+            page.push_str(&format!("### {}\n\n", relation_type));
+            for relation in relations {
+                page.push_str(&format!("- {}\n", relation_link(registry, relation, root)));
+            }
+            page.push('\n');
+        }
+    }
+
+    page.push_str("## Verification Trace\n\n");
+    if let Some(trace) = coverage.traces.get(element.identifier.as_str()) {
+        page.push_str("Requirements verified by this element, with the requirements they derive from:\n\n");
+        push_trace_tree(&mut page, &trace.trace_tree.requirements, root, 0);
+        page.push('\n');
+    } else if matches!(element.element_type, ElementType::Verification(_)) {
+        page.push_str("This verification does not verify any requirement.\n\n");
+    } else if let Some(entries) = coverage.verified_by.get(&element.identifier) {
+        page.push_str("Verifications whose trace includes this element:\n\n");
+        for (trace, directly) in entries {
+            page.push_str(&format!(
+                "- [{}]({}{}) ({}){}\n",
+                trace.name,
+                root,
+                element_page_path(&trace.identifier, "html"),
+                trace.verification_type,
+                if *directly { ", verifies it directly" } else { "" }
+            ));
+        }
+        page.push('\n');
+    } else {
+        page.push_str("Not covered by any verification.\n\n");
+    }
+
+    page.push_str("## Change Impact\n\n");
+    let impact = registry.get_impact_tree(&element.identifier);
+    if impact.relations.is_empty() {
+        page.push_str("A change of this element impacts no other element.\n\n");
+    } else {
+        page.push_str("Elements impacted by a change of this element:\n\n");
+        push_impact_tree(&mut page, &impact, root, 0);
+        page.push('\n');
+    }
+
+    page.push_str("## Source Files\n\n");
+    page.push_str(&format!("- [{}]({})\n", element.file_path, file_page));
+    let mut files: Vec<String> = element
+        .relations
+        .iter()
+        .filter_map(|relation| match &relation.target.link {
+            LinkType::InternalPath(path) => Some(path.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    for file in files {
+        page.push_str(&format!("- [{}]({}{})\n", file, root, file));
+    }
+
+    page
+}
+
+/// Markdown link to the target of a relation
+fn relation_link(registry: &GraphRegistry, relation: &Relation, root: &str) -> String {
+    match &relation.target.link {
+        LinkType::Identifier(id) => match registry.get_element(id) {
+            Some(target) => format!(
+                "[{}]({}{}) ({})",
+                target.name,
+                root,
+                element_page_path(id, "html"),
+                target.element_type.as_str()
+            ),
+            None => format!("{} (missing)", id),
+        },
+        LinkType::InternalPath(path) => {
+            let path = path.to_string_lossy();
+            format!("[{}]({}{})", path, root, path)
+        }
+        LinkType::ExternalUrl(url) => format!("[{}]({})", relation.target.text, url),
+    }
+}
+
+fn push_trace_tree(page: &mut String, nodes: &[RequirementNode], root: &str, depth: usize) {
+    for node in nodes {
+        page.push_str(&format!(
+            "{}- [{}]({}{}) ({}){}\n",
+            "  ".repeat(depth),
+            node.name,
+            root,
+            element_page_path(&node.id, "html"),
+            node.element_type,
+            if node.is_directly_verified { ", verified directly" } else { "" }
+        ));
+        push_trace_tree(page, &node.children, root, depth + 1);
+    }
+}
+
+fn push_impact_tree(page: &mut String, node: &ElementNode, root: &str, depth: usize) {
+    for relation in &node.relations {
+        let target = &relation.element_node.element;
+        page.push_str(&format!(
+            "{}- {}: [{}]({}{}) ({})\n",
+            "  ".repeat(depth),
+            relation.relation_trigger,
+            target.name,
+            root,
+            element_page_path(&target.identifier, "html"),
+            target.element_type.as_str()
+        ));
+        push_impact_tree(page, &relation.element_node, root, depth + 1);
+    }
+}
+
+/// Makes relative links of element content, which are relative to the
+/// element's model file, relative to the element page
+fn rebase_links(content: &str, file_path: &str, root: &str) -> String {
+    lazy_static! {
+        static ref LINK_REGEX: Regex = Regex::new(r"\]\(([^)\s]+)\)").unwrap();
+    }
+    let directory = match file_path.rfind('/') {
+        Some(position) => &file_path[..=position],
+        None => "",
+    };
+    LINK_REGEX
+        .replace_all(content, |caps: &Captures| {
+            let target = &caps[1];
+            if target.starts_with('/') || utils::EXTERNAL_SCHEMES.iter().any(|scheme| target.starts_with(scheme)) {
+                caps[0].to_string()
+            } else if target.starts_with('#') {
+                format!("]({}{}{})", root, file_path, target)
+            } else {
+                format!("]({}{}{})", root, directory, target)
+            }
+        })
+        .into_owned()
+}
+
+/// Adds a link to the element page below every element heading of the
+/// exported model file pages in `export_dir`
+pub fn link_element_pages(registry: &GraphRegistry, export_dir: &Path) -> Result<(), ReqvireError> {
+    lazy_static! {
+        static ref HEADING_REGEX: Regex = Regex::new(r#"<h3 id="([^"]*)">[^<]*</h3>"#).unwrap();
+    }
+
+    let mut elements_by_file: BTreeMap<&str, HashMap<String, &Element>> = BTreeMap::new();
+    for element in registry.get_all_elements() {
+        if !element.identifier.starts_with("__virtual__") {
+            elements_by_file
+                .entry(element.file_path.as_str())
+                .or_default()
+                .insert(html::heading_id(&element.name), element);
+        }
+    }
+
+    for (file_path, elements) in elements_by_file {
+        let page_path = file_page_path(file_path);
+        let path = export_dir.join(&page_path);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let root = root_prefix(&page_path);
+        let linked = HEADING_REGEX.replace_all(&content, |caps: &Captures| match elements.get(&caps[1]) {
+            Some(element) => format!(
+                "{}\n<p class=\"element-details\"><a href=\"{}{}\">Element details</a></p>",
+                &caps[0],
+                root,
+                element_page_path(&element.identifier, "html")
+            ),
+            None => caps[0].to_string(),
+        });
+        fs::write(&path, linked.as_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_page_path_and_links() {
+        assert_eq!(
+            element_page_path("specs/Requirements.md#remote-start-v2", "html"),
+            "elements/specs/Requirements/remote-start-v2.html"
+        );
+        assert_eq!(element_page_path("Requirements.md#a.b/c?d", "md"), "elements/Requirements/a-b-c-d.md");
+        assert_eq!(root_prefix("elements/specs/Requirements/remote-start.md"), "../../../");
+        assert_eq!(file_page_path("specs/SpecificationIndex.md"), "specs/index.html");
+
+        let content = "See [A](Other.md#a), [B](#b), [C](https://example.com) and [D](/docs/d.md).";
+        assert_eq!(
+            rebase_links(content, "specs/Requirements.md", "../../../"),
+            "See [A](../../../specs/Other.md#a), [B](../../../specs/Requirements.md#b), \
+             [C](https://example.com) and [D](/docs/d.md)."
+        );
+    }
+}
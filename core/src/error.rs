@@ -109,4 +109,63 @@ pub enum ReqvireError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    /// Model validation error with the place in the model it refers to
+    #[error("{error}")]
+    Located {
+        error: Box<ReqvireError>,
+        location: ErrorLocation,
+    },
+
+}
+
+/// Place in the model a validation error refers to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorLocation {
+    /// File path relative to the model root
+    pub file: String,
+    /// Line number, starting at 1, of errors found while parsing
+    pub line: Option<usize>,
+    /// Identifier of the element the error is about
+    pub element: Option<String>,
+    /// Relation target of the element the error is about
+    pub target: Option<String>,
+}
+
+impl ErrorLocation {
+    /// Line of a file
+    pub fn line(file: &str, line: usize) -> Self {
+        ErrorLocation {
+            file: file.to_string(),
+            line: Some(line),
+            ..Default::default()
+        }
+    }
+
+    /// Element of a file, optionally one of its relation targets
+    pub fn element(file: &str, element: &str, target: Option<&str>) -> Self {
+        ErrorLocation {
+            file: file.to_string(),
+            element: Some(element.to_string()),
+            target: target.map(str::to_string),
+            ..Default::default()
+        }
+    }
+}
+
+impl ReqvireError {
+    /// Attaches the place in the model the error refers to
+    pub fn at(self, location: ErrorLocation) -> Self {
+        ReqvireError::Located {
+            error: Box::new(self),
+            location,
+        }
+    }
+
+    /// Place in the model the error refers to, if known
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            ReqvireError::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
 }
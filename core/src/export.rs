@@ -9,15 +9,18 @@ use crate::error::ReqvireError;
 use crate::html_export;
 use crate::graph_registry::GraphRegistry;
 use crate::filesystem;
+use crate::project_config::ProjectConfig;
+use crate::context::ModelContext;
 
-/// Generates HTML wrapper for matrix.svg with pan/zoom functionality
-fn generate_matrix_html() -> String {
+/// Generates HTML wrapper for a matrix SVG with pan/zoom functionality.
+/// `root` is the relative path from the page to the export root.
+fn generate_matrix_html(title: &str, svg_file: &str, root: &str) -> String {
     r#"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0, maximum-scale=1.0, user-scalable=no">
-    <title>Verification Traceability Matrix</title>
+    <title>{title}</title>
     <style>
         body {
             margin: 0;
@@ -106,6 +109,8 @@ fn generate_matrix_html() -> String {
     <button id="arrow-left" class="arrow-btn">◄</button>
     <button id="arrow-right" class="arrow-btn">►</button>
     <div id="svg-container"></div>
+    <div id="reqvire-search" data-root="{root}" style="position:fixed;top:60px;right:10px;z-index:1000"></div>
+    <script src="{root}search.js"></script>
     <script src="https://cdn.jsdelivr.net/npm/hammerjs@2.0.8/hammer.min.js"></script>
     <script src="https://cdn.jsdelivr.net/npm/svg-pan-zoom@3.5.0/dist/svg-pan-zoom.min.js"></script>
     <script>
@@ -115,7 +120,7 @@ fn generate_matrix_html() -> String {
             console.log('Page loaded');
 
             // Load SVG inline to enable proper touch event handling
-            fetch('matrix.svg')
+            fetch('{svg_file}')
                 .then(function(response) {
                     return response.text();
                 })
@@ -233,7 +238,10 @@ fn generate_matrix_html() -> String {
         };
     </script>
 </body>
-</html>"#.to_string()
+</html>"#
+        .replace("{title}", title)
+        .replace("{svg_file}", svg_file)
+        .replace("{root}", root)
 }
 
 fn prepare_output_folder(output_folder: &Path) -> std::io::Result<()> {
@@ -260,21 +268,42 @@ pub fn copy_model_files_to_temp(
     registry: &GraphRegistry,
     temp_dir: &Path,
 ) -> Result<(), ReqvireError> {
-    let git_root = git_commands::get_git_root_dir()
-        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    copy_model_files(registry, temp_dir, None)
+}
 
-    let current_dir = std::env::current_dir()
-        .unwrap_or_else(|_| PathBuf::from("."));
+/// Writes all model files of a registry parsed at `commit` to temporary directory,
+/// with their content at that commit
+pub fn copy_model_files_at_commit(
+    registry: &GraphRegistry,
+    commit: &str,
+    temp_dir: &Path,
+) -> Result<(), ReqvireError> {
+    copy_model_files(registry, temp_dir, Some(commit))
+}
 
-    // Determine if we're in a subdirectory and get the relative path prefix to strip
-    let subdir_prefix = if current_dir.starts_with(&git_root) && current_dir != git_root {
-        current_dir.strip_prefix(&git_root).ok()
-    } else {
-        None
-    };
+fn copy_model_files(
+    registry: &GraphRegistry,
+    temp_dir: &Path,
+    commit: Option<&str>,
+) -> Result<(), ReqvireError> {
+    let context = &registry.context;
+    let git_root = context.root();
+
+    // Files outside the root folder are copied without the scan root folder
+    let subdir_prefix = Some(context.scan_prefix()).filter(|prefix| !prefix.as_os_str().is_empty());
 
     info!("Copying model files to temporary directory...");
 
+    // Files of the commit, to check relation targets against instead of the working tree
+    let commit_files: Option<HashSet<String>> = match commit {
+        Some(commit) => Some(git_commands::ls_tree_commit(git_root, commit)?.into_iter().collect()),
+        None => None,
+    };
+    let copy = |file_path: &str, src: &Path, dest: &Path| match commit {
+        Some(commit) => filesystem::write_file(dest, git_commands::get_file_bytes_at_commit(git_root, file_path, commit)?),
+        None => filesystem::copy_file_with_structure(src, dest),
+    };
+
     let mut copied_files = HashSet::new();
 
     // Copy all model markdown files from pages
@@ -292,8 +321,12 @@ pub fn copy_model_files_to_temp(
             temp_dir.join(file_path)
         };
 
-        if src.exists() && !copied_files.contains(file_path.as_str()) {
-            filesystem::copy_file_with_structure(&src, &dest_path)?;
+        let exists = match &commit_files {
+            Some(files) => files.contains(file_path.as_str()),
+            None => src.exists(),
+        };
+        if exists && !copied_files.contains(file_path.as_str()) {
+            copy(file_path, &src, &dest_path)?;
             copied_files.insert(file_path.clone());
             debug!("Copied model file: {} -> {}", file_path, dest_path.display());
         }
@@ -306,7 +339,11 @@ pub fn copy_model_files_to_temp(
                 let src = git_root.join(path);
                 let path_str = path.to_string_lossy().to_string();
 
-                if src.is_file() && !copied_files.contains(&path_str) {
+                let exists = match &commit_files {
+                    Some(files) => files.contains(&path_str),
+                    None => src.is_file(),
+                };
+                if exists && !copied_files.contains(&path_str) {
                     // Strip subdirectory prefix from destination path if running from subdirectory
                     let dest = if let Some(prefix) = subdir_prefix {
                         if let Ok(stripped) = path.strip_prefix(prefix) {
@@ -318,7 +355,7 @@ pub fn copy_model_files_to_temp(
                         temp_dir.join(path)
                     };
 
-                    filesystem::copy_file_with_structure(&src, &dest)?;
+                    copy(&path_str, &src, &dest)?;
                     copied_files.insert(path_str);
                     debug!("Copied relation target: {} -> {}", path.display(), dest.display());
                 }
@@ -426,22 +463,72 @@ fn post_process_html_files(temp_dir: &Path) -> Result<(), ReqvireError> {
 /// Returns the path to the temporary directory containing all generated artifacts.
 /// The caller is responsible for cleanup.
 ///
+/// The export reads the model from the registry and its context, and neither
+/// changes the working directory nor parses the model again, so several
+/// exports may run at once.
+///
+/// The coverage history chart is left out, see [`export_model_with_artifacts`].
+///
 /// Steps:
-/// 1. Creates temporary working directory
+/// 1. Creates a temporary directory for this export
 /// 2. Copies all model files to temp
-/// 3. Initializes git repository in temp directory
-/// 4. Changes to temp directory and reloads model
-/// 5. Generates all artifacts (diagrams, index, traces, coverage, matrix)
-/// 6. Converts markdown to HTML
-/// 7. Restores original directory
+/// 3. Relocates the registry to the temp directory
+/// 4. Generates all artifacts (diagrams, index, search index, traces, coverage, matrix)
+/// 5. Converts markdown to HTML with the theme and generates element pages
 ///
 /// Returns: PathBuf to temporary directory with all generated content
 pub fn generate_artifacts_in_temp(
+    registry: &GraphRegistry,
+    diagrams_with_blobs: bool,
+    config: &ProjectConfig,
+) -> Result<PathBuf, ReqvireError> {
+    generate_artifacts(registry, None, diagrams_with_blobs, config, None)
+}
+
+/// Generates all artifacts for a registry parsed at `commit` in temporary directory
+///
+/// Same as [`generate_artifacts_in_temp`], except that model files and relation
+/// targets are taken from the commit instead of the working tree.
+pub fn generate_artifacts_at_commit(
+    registry: &GraphRegistry,
+    commit: &str,
+    diagrams_with_blobs: bool,
+    config: &ProjectConfig,
+) -> Result<PathBuf, ReqvireError> {
+    generate_artifacts(registry, Some(commit), diagrams_with_blobs, config, None)
+}
+
+/// Coverage history chart for `export.coverage_history`, which parses the
+/// model at every commit of the range and is therefore left to `reqvire export`
+fn coverage_history_chart(
     registry: &GraphRegistry,
     excluded_patterns: &globset::GlobSet,
+    config: &ProjectConfig,
+) -> Option<String> {
+    let rev_range = config.export.coverage_history.as_ref()?;
+    info!("Computing coverage history for {}...", rev_range);
+    match crate::coverage_history::compute_coverage_history(&registry.context, rev_range, excluded_patterns, &config.verification_rules) {
+        Ok(history) => Some(history.to_svg()),
+        Err(e) => {
+            warn!("Skipping coverage history chart: {}", e);
+            None
+        }
+    }
+}
+
+fn generate_artifacts(
+    registry: &GraphRegistry,
+    commit: Option<&str>,
     diagrams_with_blobs: bool,
+    config: &ProjectConfig,
+    coverage_history_svg: Option<String>,
 ) -> Result<PathBuf, ReqvireError> {
-    use std::env;
+    // The theme is read from the original repository as well
+    let git_commit = match commit {
+        Some(commit) => commit.to_string(),
+        None => registry.context.commit_hash().unwrap_or_default().to_string(),
+    };
+    let theme = crate::theme::Theme::load(registry.context.root(), &git_commit)?;
 
     // Step 1: Create temp directory
     info!("Creating temporary working directory...");
@@ -449,114 +536,112 @@ pub fn generate_artifacts_in_temp(
     info!("✅ Temporary directory: {}", temp_dir.display());
 
     // Step 2: Copy all model files to temp
-    copy_model_files_to_temp(registry, &temp_dir)?;
-
-    // Step 3: Initialize git repository in temp directory
-    info!("Initializing git repository in temporary directory...");
-    std::process::Command::new("git")
-        .arg("init")
-        .current_dir(&temp_dir)
-        .output()
-        .map_err(|e| ReqvireError::PathError(format!("Failed to initialize git repo in temp: {}", e)))?;
-
-    // Step 4: Change to temp directory and create new model manager
-    let original_dir = env::current_dir()
-        .map_err(|e| ReqvireError::IoError(e))?;
+    copy_model_files(registry, &temp_dir, commit)?;
 
-    env::set_current_dir(&temp_dir)
-        .map_err(|e| ReqvireError::IoError(e))?;
-
-    // Clear git cache so paths resolve to temp directory instead of original repo
-    git_commands::clear_git_cache();
+    // Step 3: Relocate the registry, so diagrams are written to the copied
+    // files and links resolve against them. The temp directory is no
+    // repository, so links stay local.
+    let registry = registry.relocated(ModelContext::new(&temp_dir));
+    let write = |file: &str, content: &[u8]| filesystem::write_file(temp_dir.join(file), content);
 
-    // Create new model manager and parse from temp directory
-    info!("Loading model from temporary directory...");
-    let mut temp_model_manager = crate::ModelManager::new();
-    let parse_result = temp_model_manager.parse_and_validate(
-        None,
-        excluded_patterns
-    );
-
-    // Check for validation errors
-    if let Err(e) = parse_result {
-        // Log the error before returning
-        match &e {
-            ReqvireError::ValidationError(errors) => {
-                eprintln!("\n❌ Validation failed in temporary directory with {} error(s):", errors.len());
-                for (i, error) in errors.iter().enumerate() {
-                    eprintln!("  {}. {}", i + 1, error);
-                }
-            }
-            _ => eprintln!("❌ Parsing failed in temporary directory: {}", e),
-        }
-        // Restore original directory before returning error
-        let _ = env::set_current_dir(&original_dir);
-        return Err(e);
-    }
-
-    // Step 5: Generate all artifacts in temp directory
+    // Step 4: Generate all artifacts in temp directory
     info!("Generating diagrams...");
     crate::diagrams::process_diagrams(
-        &temp_model_manager.graph_registry,
+        &registry,
         diagrams_with_blobs
     )?;
 
     info!("Generating index.md...");
     let index_content = crate::index_generator::generate_readme_index(
-        &temp_model_manager.graph_registry,
-        &PathBuf::from(".")
+        &registry,
+        &temp_dir
     )?;
-    filesystem::write_file("index.md", index_content.as_bytes())?;
+    write("index.md", index_content.as_bytes())?;
+
+    info!("Generating search index...");
+    let search_index = crate::search_index::generate_search_index(&registry);
+    write(crate::search_index::SEARCH_INDEX_FILE, search_index.to_json().as_bytes())?;
+    write(crate::search_index::SEARCH_INDEX_SCRIPT_FILE, search_index.to_script().as_bytes())?;
+    write(crate::search_index::SEARCH_SCRIPT_FILE, crate::search_index::SEARCH_SCRIPT.as_bytes())?;
 
     info!("Generating matrix.svg...");
     let matrix_config = crate::matrix_generator::MatrixConfig::default();
     let matrix_svg = crate::matrix_generator::generate_matrix(
-        &temp_model_manager.graph_registry,
+        &registry,
         &matrix_config,
         crate::matrix_generator::MatrixFormat::Svg
     );
-    filesystem::write_file("matrix.svg", matrix_svg.as_bytes())?;
+    write("matrix.svg", matrix_svg.as_bytes())?;
 
     // Generate HTML wrapper for matrix.svg with pan/zoom functionality
     info!("Generating matrix.html...");
-    let matrix_html = generate_matrix_html();
-    filesystem::write_file("matrix.html", matrix_html.as_bytes())?;
+    let matrix_html = generate_matrix_html("Verification Traceability Matrix", "matrix.svg", "");
+    write("matrix.html", matrix_html.as_bytes())?;
+
+    // Saved matrix definitions are exported next to the default matrix
+    let mut matrix_links = Vec::new();
+    for definition in &config.matrices {
+        let slug = definition.slug();
+        info!("Generating matrices/{}.svg...", slug);
+        let definition_svg = crate::matrix_generator::generate_matrix(
+            &registry,
+            &definition.to_config(&registry)?,
+            crate::matrix_generator::MatrixFormat::Svg
+        );
+        write(&format!("matrices/{}.svg", slug), definition_svg.as_bytes())?;
+        let definition_html = generate_matrix_html(&definition.name, &format!("{}.svg", slug), "../");
+        write(&format!("matrices/{}.html", slug), definition_html.as_bytes())?;
+        matrix_links.push(format!("- [{}](matrices/{}.html)\n", definition.name, slug));
+    }
+    if !matrix_links.is_empty() {
+        let mut index_with_matrices = index_content;
+        index_with_matrices.push_str("\n## Traceability Matrices\n\n");
+        index_with_matrices.push_str(&matrix_links.concat());
+        write("index.md", index_with_matrices.as_bytes())?;
+    }
 
     // Generate model structure diagram with Mermaid
     info!("Generating model.md...");
-    let model_mermaid = crate::diagrams::generate_model_diagram(&temp_model_manager.graph_registry, None)?;
-    filesystem::write_file("model.md", model_mermaid.as_bytes())?;
+    let model_mermaid = crate::diagrams::generate_model_diagram(&registry, None)?;
+    write("model.md", model_mermaid.as_bytes())?;
 
     info!("Generating traces.md...");
     let trace_generator = crate::verification_trace::VerificationTraceGenerator::new(
-        &temp_model_manager.graph_registry,
+        &registry,
         false,  // Always use relative links for traces in HTML export
         None
     );
     let trace_report = trace_generator.generate();
     let traces_markdown = trace_generator.generate_markdown(&trace_report);
-    filesystem::write_file("traces.md", traces_markdown.as_bytes())?;
+    write("traces.md", traces_markdown.as_bytes())?;
 
     info!("Generating coverage.md...");
-    let coverage_report = crate::reports::generate_coverage_report(&temp_model_manager.graph_registry);
-    let coverage_text = coverage_report.format_text();
-    filesystem::write_file("coverage.md", coverage_text.as_bytes())?;
+    let coverage_report = crate::reports::generate_coverage_report(
+        &registry,
+        &config.verification_rules
+    );
+    let mut coverage_text = coverage_report.format_text();
+    if let Some(svg) = &coverage_history_svg {
+        info!("Generating coverage-history.svg...");
+        write("coverage-history.svg", svg.as_bytes())?;
+        coverage_text.push_str("\n## Coverage History\n\n![Coverage history](coverage-history.svg)\n");
+    }
+    write("coverage.md", coverage_text.as_bytes())?;
 
-    // Step 6: Convert markdown to HTML
+    // Step 5: Convert markdown to HTML
     info!("Converting markdown to HTML...");
-    let html_count = html_export::export_markdown_to_html(&temp_dir, &temp_dir)?;
+    let html_count = html_export::export_markdown_to_html(&registry.context, &temp_dir, &theme)?;
     info!("✅ Converted {} markdown files to HTML", html_count);
+    theme.copy_assets(&temp_dir)?;
 
-    // Step 6.5: Post-process HTML files to convert .md references to .html
+    info!("Generating element pages...");
+    let element_page_count = crate::element_pages::generate_element_pages(&registry, &temp_dir, &theme)?;
+    info!("✅ Generated {} element pages", element_page_count);
+
+    // Post-process HTML files to convert .md references to .html
     info!("Post-processing HTML artifacts...");
     post_process_html_files(&temp_dir)?;
-
-    // Step 7: Restore original directory
-    env::set_current_dir(&original_dir)
-        .map_err(|e| ReqvireError::IoError(e))?;
-
-    // Clear git cache again so it refreshes for original directory
-    git_commands::clear_git_cache();
+    crate::element_pages::link_element_pages(&registry, &temp_dir)?;
 
     Ok(temp_dir)
 }
@@ -576,17 +661,22 @@ pub fn finalize_export(temp_dir: &Path, output_dir: &Path, cleanup: bool) -> Res
     Ok(())
 }
 
-/// Exports comprehensive HTML documentation with all model artifacts (complete pipeline)
+/// Exports comprehensive HTML documentation with all model artifacts (complete pipeline),
+/// including the coverage history chart configured in `export.coverage_history`
 pub fn export_model_with_artifacts(
     registry: &GraphRegistry,
     output_dir: &Path,
     excluded_patterns: &globset::GlobSet,
     diagrams_with_blobs: bool,
+    config: &ProjectConfig,
 ) -> Result<(), ReqvireError> {
-    let temp_dir = generate_artifacts_in_temp(
+    let coverage_history_svg = coverage_history_chart(registry, excluded_patterns, config);
+    let temp_dir = generate_artifacts(
         registry,
-        excluded_patterns,
-        diagrams_with_blobs
+        None,
+        diagrams_with_blobs,
+        config,
+        coverage_history_svg
     )?;
 
     finalize_export(&temp_dir, output_dir, true)?;
@@ -600,20 +690,14 @@ pub fn export_model(
     output_folder: &Path,
 ) -> Result<usize, ReqvireError> {
 
-    // Try to get repository root as base directory
-    let base_dir = match git_commands::get_git_root_dir() {
-        Ok(git_root) => git_root,
-        Err(_) => {
-            // If Git repository root can't be found, use the current working directory
-            std::env::current_dir()
-                .map_err(|e| ReqvireError::PathError(format!("Failed to get current directory: {}", e)))?
-        }
-    };
+    let context = &registry.context;
     
     // prepare output folder
     prepare_output_folder(&output_folder)?;
 
-    let count = html_export::export_markdown_to_html(&base_dir, output_folder)?;
+    let theme = crate::theme::Theme::load(context.root(), context.commit_hash().unwrap_or_default())?;
+    let count = html_export::export_markdown_to_html(context, output_folder, &theme)?;
+    theme.copy_assets(output_folder)?;
     
     debug!("{} markdown files converted to HTML", count);
 
@@ -621,14 +705,13 @@ pub fn export_model(
 
     for src in internal_paths {
         // src is e.g. "core/src/linting/newlines.rs"
+        let dst = output_folder.join(&src);
+        let src = context.resolve(&src);
         if !src.is_file() {
             warn!("Skipping missing/non-file path: {:?}", src);
             continue;
         }
 
-        // Build the destination: output_folder/core/src/linting/newlines.rs
-        let dst = output_folder.join(&src);
-
         // Ensure parent dirs exist: output_folder/core/src/linting
         if let Some(parent) = dst.parent() {
             if let Err(e) = fs::create_dir_all(parent) {
@@ -1,17 +1,21 @@
 use crate::error::ReqvireError;
 use std::path::{Path,PathBuf};
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::vec::IntoIter;
 use crate::git_commands;
 
 pub struct FileReaderIterator<'a> {
+    root: PathBuf,
     git_commit_hash: Option<&'a str>,
     files: IntoIter<PathBuf>,
 }
 
 impl<'a> FileReaderIterator<'a> {
-    pub fn new(git_commit_hash: Option<&'a str>, files: Vec<PathBuf>) -> Self {
+    /// Reads `files` from disk, or at `git_commit_hash` from the repository at `root`
+    pub fn new(root: &Path, git_commit_hash: Option<&'a str>, files: Vec<PathBuf>) -> Self {
         Self {
+            root: root.to_path_buf(),
             files: files.into_iter(),
             git_commit_hash: git_commit_hash,            
         }
@@ -30,15 +34,7 @@ impl Iterator for FileReaderIterator<'_>{
                 
             match self.git_commit_hash{
                 Some(commit)=>{
-                    // Get git root directory
-                    let git_root = match git_commands::get_git_root_dir() {
-                        Ok(dir) => dir,
-                        Err(_) => {
-                            return Err(ReqvireError::GitCommandError("Failed to get git root directory".to_string()));
-                        }
-                    };
-                
-                    match git_commands::get_file_at_commit(&file.to_string_lossy(), &git_root, commit) {
+                    match git_commands::get_file_at_commit(&file.to_string_lossy(), &self.root, commit) {
                         Ok(content)=> {
                             Ok((file, filename_str, content))
                         },
@@ -82,11 +78,22 @@ pub fn create_dir_all<P: AsRef<Path>>(path: P) -> Result<(), ReqvireError> {
     })
 }
 
-/// Creates a temporary working directory with a unique name based on process ID
+/// Creates an empty temporary working directory with a unique name based on
+/// process ID and a counter, so exports running at once do not share it
 pub fn create_temp_working_dir() -> Result<PathBuf, ReqvireError> {
-    let temp_base = std::env::temp_dir();
-    let temp_dir = temp_base.join(format!("reqvire-export-{}", std::process::id()));
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
 
+    let temp_base = std::env::temp_dir();
+    let temp_dir = temp_base.join(format!(
+        "reqvire-export-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    // Leftovers of an earlier process with the same ID
+    if temp_dir.exists() {
+        remove_dir_all(&temp_dir)?;
+    }
     fs::create_dir_all(&temp_dir)
         .map_err(|e| ReqvireError::PathError(format!("Failed to create temp directory: {}", e)))?;
 
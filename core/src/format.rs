@@ -35,8 +35,7 @@ pub struct DiffLine {
 
 /// Format all files in the registry, optionally in dry-run mode
 pub fn format_files(registry: &GraphRegistry, dry_run: bool) -> Result<FormatResult, ReqvireError> {
-    let base_dir = std::env::current_dir()
-        .map_err(|e| ReqvireError::PathError(format!("Failed to get current directory: {}", e)))?;
+    let base_dir = registry.context.root();
 
     let grouped_elements = registry.group_elements_by_location();
     let mut files_changed = 0;
@@ -53,7 +52,7 @@ pub fn format_files(registry: &GraphRegistry, dry_run: bool) -> Result<FormatRes
         // Apply linting rules to ensure consistent formatting
         new_content = apply_formatting_rules(&new_content);
 
-        // Construct the full file path relative to current directory
+        // Construct the full file path relative to the model root
         let full_file_path = base_dir.join(&file_path);
 
         // Read current content if file exists
@@ -100,7 +99,7 @@ pub fn format_files(registry: &GraphRegistry, dry_run: bool) -> Result<FormatRes
 }
 
 /// Apply basic formatting rules to generated markdown content
-fn apply_formatting_rules(content: &str) -> String {
+pub fn apply_formatting_rules(content: &str) -> String {
     // Since we're generating from the model, we only need basic cleanup
     // of content that comes from original files (element content, page content, section content)
 
@@ -116,7 +115,7 @@ fn apply_formatting_rules(content: &str) -> String {
 }
 
 /// Generate a diff showing changes between current and new content
-fn generate_file_diff(file_path: &str, current: &str, new: &str) -> FileDiff {
+pub(crate) fn generate_file_diff(file_path: &str, current: &str, new: &str) -> FileDiff {
     use difference::{Difference, Changeset};
 
     let changeset = Changeset::new(current, new, "\n");
@@ -306,7 +305,7 @@ pub fn render_diff(format_result: &FormatResult) {
 }
 
 /// Render file diffs with ANSI colors
-fn render_file_diffs(diffs: &[FileDiff]) {
+pub(crate) fn render_file_diffs(diffs: &[FileDiff]) {
     for file_diff in diffs {
         println!("📄 {}", file_diff.file_path);
         for line in &file_diff.lines {
@@ -353,18 +352,23 @@ pub fn render_diff_json(format_result: &FormatResult) -> String {
     let json_result = serde_json::json!({
         "dry_run": format_result.dry_run,
         "files_changed": format_result.files_changed,
-        "diffs": format_result.diffs.iter().map(|file_diff| {
-            serde_json::json!({
-                "file_path": file_diff.file_path,
-                "lines": file_diff.lines.iter().map(|line| {
-                    serde_json::json!({
-                        "prefix": line.prefix,
-                        "content": line.content,
-                        "color": line.color
-                    })
-                }).collect::<Vec<_>>()
-            })
-        }).collect::<Vec<_>>()
+        "diffs": diffs_to_json(&format_result.diffs)
     });
     serde_json::to_string_pretty(&json_result).unwrap()
+}
+
+/// Convert file diffs to the JSON structure used by `render_diff_json`
+pub(crate) fn diffs_to_json(diffs: &[FileDiff]) -> serde_json::Value {
+    serde_json::Value::Array(diffs.iter().map(|file_diff| {
+        serde_json::json!({
+            "file_path": file_diff.file_path,
+            "lines": file_diff.lines.iter().map(|line| {
+                serde_json::json!({
+                    "prefix": line.prefix,
+                    "content": line.content,
+                    "color": line.color
+                })
+            }).collect::<Vec<_>>()
+        })
+    }).collect())
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::process::Command;
 use anyhow::Result;
 use crate::error::ReqvireError;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
 use std::collections::HashMap;
@@ -26,10 +26,19 @@ pub fn get_repository_base_url() -> Result<String, ReqvireError> {
     if let Some(ref url) = *cached {
         return Ok(url.clone());
     }
-    
+
+    let base_url = get_repository_base_url_in(Path::new("."))?;
+    *cached = Some(base_url.clone());
+
+    Ok(base_url)
+}
+
+/// Retrieves the repository base URL (HTTPS format) of the repository containing `root`.
+pub fn get_repository_base_url_in(root: &Path) -> Result<String, ReqvireError> {
     // Fetch the repository URL from git configuration
     let output = Command::new("git")
         .args(&["config", "--get", "remote.origin.url"])
+        .current_dir(root)
         .output()?;
 
     if !output.status.success() {
@@ -60,8 +69,6 @@ pub fn get_repository_base_url() -> Result<String, ReqvireError> {
         )));
     };
 
-    *cached = Some(base_url.clone());
-
     Ok(base_url)
 }
 
@@ -72,10 +79,19 @@ pub fn get_commit_hash() -> Result<String,ReqvireError> {
     if let Some(ref hash) = *cached {
         return Ok(hash.clone());
     }
-    
+
+    let hash = get_commit_hash_in(Path::new("."))?;
+    *cached = Some(hash.clone());
+
+    Ok(hash)
+}
+
+/// Retrieves the current commit hash of the repository containing `root`.
+pub fn get_commit_hash_in(root: &Path) -> Result<String, ReqvireError> {
     // Run the git command to get the current commit hash
     let output = Command::new("git")
         .args(&["rev-parse", "HEAD"])
+        .current_dir(root)
         .output()?;
     if !output.status.success() {
         let err = String::from_utf8_lossy(&output.stderr);
@@ -88,8 +104,6 @@ pub fn get_commit_hash() -> Result<String,ReqvireError> {
         return Err(ReqvireError::GitCommandError("Commit hash is empty".to_string()));
     }
 
-    *cached = Some(hash.clone());
-
     Ok(hash)
 }
 
@@ -177,13 +191,11 @@ pub fn get_git_root_dir() -> Result<PathBuf, ReqvireError> {
 }
 
 /// Lists all files in a commit by running `git ls-tree --name-only -r <commit>`
-/// from the git repository root. Returns a list of file paths.
-pub fn ls_tree_commit(commit: &str) -> Result<Vec<String>, ReqvireError> {
-    let git_root = get_git_root_dir()?;
-    
+/// from the repository root `root`. Returns a list of file paths.
+pub fn ls_tree_commit(root: &Path, commit: &str) -> Result<Vec<String>, ReqvireError> {
     let output = Command::new("git")
         .args(&["ls-tree", "--name-only", "-r", commit])
-        .current_dir(&git_root)
+        .current_dir(root)
         .output()?;
 
     if !output.status.success() {
@@ -251,3 +263,168 @@ pub fn ls_tree_commit_in_folder(commit: &str, folder: &PathBuf) -> Result<Vec<St
 }
 
 
+
+/// Lists the commits selected by `rev_range` along the first-parent chain,
+/// oldest first, as `(hash, committer date in ISO 8601)` pairs using
+/// `git log --first-parent --reverse` in the repository at `root`. Commits of
+/// merged branches are left out, their changes count towards the merge.
+/// Ranges starting with `-` are rejected so they cannot pass options to git.
+pub fn list_commits(root: &Path, rev_range: &str) -> Result<Vec<(String, String)>, ReqvireError> {
+    if rev_range.is_empty() || rev_range.starts_with('-') {
+        return Err(ReqvireError::GitCommandError(format!("Invalid revision range '{}'", rev_range)));
+    }
+    let output = Command::new("git")
+        .args(["log", "--first-parent", "--reverse", "--format=%H%x09%cI", "--end-of-options", rev_range, "--"])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr_str = String::from_utf8_lossy(&output.stderr);
+        return Err(ReqvireError::GitCommandError(format!(
+            "git log failed (range = {}): {}",
+            rev_range, stderr_str
+        )));
+    }
+
+    let stdout_str = String::from_utf8_lossy(&output.stdout);
+    let commits = stdout_str
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(hash, date)| (hash.to_string(), date.to_string()))
+        .collect::<Vec<(String, String)>>();
+
+    Ok(commits)
+}
+
+/// Lists the files a commit changed relative to its first parent (relative to
+/// the repository root `root`) by running
+/// `git diff-tree --no-commit-id --name-only -r <commit>^1 <commit>`, so merge
+/// commits list the files their merged branch changed. Root commits list all
+/// their files.
+pub fn changed_files_in_commit(root: &Path, commit: &str) -> Result<Vec<String>, ReqvireError> {
+    let first_parent = format!("{}^1", commit);
+    let has_parent = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &first_parent])
+        .current_dir(root)
+        .output()?
+        .status
+        .success();
+    let range: Vec<&str> = if has_parent { vec![&first_parent, commit] } else { vec!["--root", commit] };
+
+    let output = Command::new("git")
+        .args(["diff-tree", "--no-commit-id", "--name-only", "-r"])
+        .args(&range)
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr_str = String::from_utf8_lossy(&output.stderr);
+        return Err(ReqvireError::GitCommandError(format!(
+            "git diff-tree failed (commit = {}): {}",
+            commit, stderr_str
+        )));
+    }
+
+    let stdout_str = String::from_utf8_lossy(&output.stdout);
+    let files = stdout_str
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+
+    Ok(files)
+}
+
+/// Returns the committer date of `commit` in the repository at `root` in ISO 8601 format.
+pub fn get_commit_date(root: &Path, commit: &str) -> Result<String, ReqvireError> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%cI", commit, "--"])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr_str = String::from_utf8_lossy(&output.stderr);
+        return Err(ReqvireError::GitCommandError(format!(
+            "git log failed (commit = {}): {}",
+            commit, stderr_str
+        )));
+    }
+
+    let date = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if date.is_empty() {
+        return Err(ReqvireError::GitCommandError(format!("No commit date for {}", commit)));
+    }
+
+    Ok(date)
+}
+
+/// Resolves a branch, tag or commit name in the repository at `root` to the
+/// full commit hash using `git rev-parse --verify <reference>^{commit}`.
+pub fn resolve_commit(root: &Path, reference: &str) -> Result<String, ReqvireError> {
+    if reference.is_empty() || reference.starts_with('-') {
+        return Err(ReqvireError::GitCommandError(format!("Invalid git reference '{}'", reference)));
+    }
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)])
+        .current_dir(root)
+        .output()?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || hash.is_empty() {
+        return Err(ReqvireError::GitCommandError(format!("Unknown git reference '{}'", reference)));
+    }
+
+    Ok(hash)
+}
+
+/// Lists branches and tags as `(name, commit hash)` pairs, branches first,
+/// using `git for-each-ref` in the repository at `root`. Annotated tags
+/// resolve to the tagged commit.
+pub fn list_refs(root: &Path) -> Result<Vec<(String, String)>, ReqvireError> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--format=%(refname:short)%09%(objectname)%09%(*objectname)",
+            "refs/heads",
+            "refs/tags",
+        ])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr_str = String::from_utf8_lossy(&output.stderr);
+        return Err(ReqvireError::GitCommandError(format!("git for-each-ref failed: {}", stderr_str)));
+    }
+
+    let stdout_str = String::from_utf8_lossy(&output.stdout);
+    let refs = stdout_str
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            let object = fields.next()?;
+            let commit = fields.next().filter(|peeled| !peeled.is_empty()).unwrap_or(object);
+            Some((name.to_string(), commit.to_string()))
+        })
+        .collect::<Vec<(String, String)>>();
+
+    Ok(refs)
+}
+
+/// Reads the raw content of a file (relative to the repository root `root`)
+/// at `commit` using `git show <commit>:<path>`.
+pub fn get_file_bytes_at_commit(root: &Path, file_path: &str, commit: &str) -> Result<Vec<u8>, ReqvireError> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:{}", commit, file_path)])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr_str = String::from_utf8_lossy(&output.stderr);
+        return Err(ReqvireError::GitCommandError(format!(
+            "git show failed for {} at {}: {}",
+            file_path, commit, stderr_str
+        )));
+    }
+
+    Ok(output.stdout)
+}
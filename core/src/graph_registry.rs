@@ -6,8 +6,8 @@ use serde::Serialize;
 
 use crate::relation::{self, LinkType, get_parent_relation_types, IMPACT_PROPAGATION_RELATIONS};
 use crate::element::{Element, ElementType, RequirementType};
-use crate::error::ReqvireError;
-use crate::git_commands;
+use crate::error::{ErrorLocation, ReqvireError};
+use crate::context::ModelContext;
 use globset::GlobSet;
 use regex::Regex;
 
@@ -78,16 +78,63 @@ pub struct GraphRegistry {
     pub nodes: HashMap<String, ElementNode>,
     pub pages: HashMap<String, Page>,
     pub sections: HashMap<SectionKey, Section>,
+    /// Location of the model the paths of the registry are relative to
+    pub context: ModelContext,
 }
 
 impl GraphRegistry {
-    /// Creates a new empty GraphRegistry
+    /// Creates a new empty GraphRegistry for the model of the current directory
     pub fn new() -> Self {
+        Self::with_context(ModelContext::from_current_dir())
+    }
+
+    /// Creates a new empty GraphRegistry for the model at `context`
+    pub fn with_context(context: ModelContext) -> Self {
         Self {
             nodes: HashMap::new(),
             pages: HashMap::new(),
             sections: HashMap::new(),
+            context,
+        }
+    }
+
+    /// Copy of the registry for its model files copied from the scan root to
+    /// the root of `context`. Paths within the scan root lose the scan root
+    /// folder, so the copy matches parsing the copied files.
+    pub fn relocated(&self, context: ModelContext) -> Self {
+        let prefix = self.context.scan_prefix();
+        let relocate = |path: &str| {
+            Path::new(path)
+                .strip_prefix(prefix)
+                .map(|relative| relative.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| path.to_string())
+        };
+
+        let mut registry = Self::with_context(context);
+        for (file_path, page) in &self.pages {
+            registry.pages.insert(relocate(file_path), page.clone());
+        }
+        for (key, section) in &self.sections {
+            let key = SectionKey::new(relocate(&key.file_path), key.section_name.clone());
+            registry.sections.insert(key, section.clone());
+        }
+        for node in self.nodes.values() {
+            let mut element = node.element.clone();
+            element.identifier = relocate(&element.identifier);
+            element.file_path = relocate(&element.file_path);
+            for relation in &mut element.relations {
+                match &mut relation.target.link {
+                    LinkType::Identifier(target) => *target = relocate(target),
+                    LinkType::InternalPath(target) => {
+                        *target = PathBuf::from(relocate(&target.to_string_lossy()));
+                    }
+                    LinkType::ExternalUrl(_) => {}
+                }
+            }
+            registry.nodes.insert(element.identifier.clone(), ElementNode { element, relations: Vec::new() });
         }
+        registry.build_relation_graph();
+        registry
     }
 
     /// Registers a page with content
@@ -112,7 +159,8 @@ impl GraphRegistry {
         let element_id = element.identifier.clone();
 
         if self.nodes.contains_key(&element_id) {
-            return Err(ReqvireError::DuplicateElement(element_id));
+            let location = ErrorLocation::element(&element.file_path, &element_id, None);
+            return Err(ReqvireError::DuplicateElement(element_id).at(location));
         }
 
         self.nodes.insert(element_id, ElementNode {
@@ -127,12 +175,67 @@ impl GraphRegistry {
     pub fn build_relations(&mut self, excluded_filename_patterns: &GlobSet) -> Result<Vec<ReqvireError>, ReqvireError> {
         debug!("GraphRegistry: Building relations and validating graph structure");
 
-        // First build the relation graph
-        self.build_relation_graph();
-
         // Add missing opposites
         self.propagate_missing_opposites(excluded_filename_patterns);
 
+        // Build the relation graph, including the propagated opposites that
+        // carry impact such as derive and verifiedBy
+        self.build_relation_graph();
+
+        self.validate_graph(excluded_filename_patterns)
+    }
+
+    /// Rebuilds relations after the given files were re-registered.
+    ///
+    /// Only the opposites and graph edges touching elements of the changed
+    /// files are recomputed; the graph is then validated as a whole.
+    pub fn rebuild_relations(
+        &mut self,
+        changed_files: &HashSet<String>,
+        excluded_filename_patterns: &GlobSet
+    ) -> Result<Vec<ReqvireError>, ReqvireError> {
+        debug!("GraphRegistry: Rebuilding relations for {} changed files", changed_files.len());
+
+        let in_changed_file = |id: &str| {
+            changed_files.contains(id.split_once('#').map_or(id, |(path, _)| path))
+        };
+
+        // Drop opposites derived from the previous content of the changed files
+        for node in self.nodes.values_mut() {
+            node.element.relations.retain(|r| {
+                r.user_created || !matches!(&r.target.link, LinkType::Identifier(id) if in_changed_file(id))
+            });
+        }
+
+        let mut affected: HashSet<String> = self.nodes.iter()
+            .filter(|(id, node)| {
+                in_changed_file(id)
+                    || node.element.relations.iter().any(|r| {
+                        matches!(&r.target.link, LinkType::Identifier(target) if in_changed_file(target))
+                    })
+                    || node.relations.iter().any(|r| in_changed_file(&r.element_node.element.identifier))
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        let sources: Vec<String> = affected.iter().cloned().collect();
+        affected.extend(self.propagate_missing_opposites_for(&sources, excluded_filename_patterns));
+
+        let element_ids: Vec<String> = affected.into_iter().collect();
+        self.build_relation_graph_for(&element_ids);
+
+        self.validate_graph(excluded_filename_patterns)
+    }
+
+    /// Removes the elements, page and sections registered from a file
+    pub fn remove_file(&mut self, file_path: &str) {
+        self.nodes.retain(|_, node| node.element.file_path != file_path);
+        self.pages.remove(file_path);
+        self.sections.retain(|key, _| key.file_path != file_path);
+    }
+
+    /// Validates relations and graph structure
+    fn validate_graph(&self, excluded_filename_patterns: &GlobSet) -> Result<Vec<ReqvireError>, ReqvireError> {
         // Validate relations
         let mut errors = self.validate_relations(excluded_filename_patterns)?;
 
@@ -148,8 +251,12 @@ impl GraphRegistry {
     /// Build the relation graph structure
     fn build_relation_graph(&mut self) {
         let element_ids: Vec<String> = self.nodes.keys().cloned().collect();
+        self.build_relation_graph_for(&element_ids);
+    }
 
-        for source_id in &element_ids {
+    /// Build the relation graph structure for the given source elements
+    fn build_relation_graph_for(&mut self, element_ids: &[String]) {
+        for source_id in element_ids {
             let mut relation_nodes = Vec::new();
 
             if let Some(source_node) = self.nodes.get(source_id) {
@@ -176,12 +283,18 @@ impl GraphRegistry {
 
     /// Adds missing opposite relations into the registry (does not return errors).
     fn propagate_missing_opposites(&mut self, excluded_filename_patterns: &GlobSet) {
+        let element_ids: Vec<String> = self.nodes.keys().cloned().collect();
+        self.propagate_missing_opposites_for(&element_ids, excluded_filename_patterns);
+    }
+
+    /// Adds missing opposites of the given source elements' relations and
+    /// returns the identifiers of the elements that received them.
+    fn propagate_missing_opposites_for(&mut self, element_ids: &[String], excluded_filename_patterns: &GlobSet) -> HashSet<String> {
         log::debug!("Propagating missing opposite relations...");
         let mut to_add: Vec<(String, crate::relation::Relation)> = Vec::new();
-        let element_ids: Vec<String> = self.nodes.keys().cloned().collect();
         let md_regex = Regex::new(r"\.md(?:#|$)").unwrap();
 
-        for source_id in &element_ids {
+        for source_id in element_ids {
             if let Some(source_node) = self.nodes.get(source_id) {
                 for relation in &source_node.element.relations {
                     if let crate::relation::LinkType::Identifier(ref target_id) = relation.target.link {
@@ -211,12 +324,15 @@ impl GraphRegistry {
         }
 
         // Apply mutations
+        let mut updated = HashSet::new();
         for (target_id, relation) in to_add {
             if let Some(target_node) = self.nodes.get_mut(&target_id) {
                 target_node.element.relations.push(relation);
                 log::debug!("Added opposite relation to '{}'", target_id);
+                updated.insert(target_id);
             }
         }
+        updated
     }
 
     /// Validates relations for target existence and element type compatibility.
@@ -244,7 +360,11 @@ impl GraphRegistry {
                                 None => {
                                     errors.push(ReqvireError::MissingRelationTarget(
                                         format!("Element '{}' references missing target '{}'", source_node.element.identifier, target_id),
-                                    ));
+                                    ).at(ErrorLocation::element(
+                                        &source_node.element.file_path,
+                                        &source_node.element.identifier,
+                                        Some(target_id),
+                                    )));
                                 }
                                 Some(target_element) => {
                                     if let Some(error) = self.validate_element_types(
@@ -260,17 +380,17 @@ impl GraphRegistry {
                         crate::relation::LinkType::InternalPath(ref file_path) => {
                             // Validate file existence for InternalPath targets
                             // InternalPath contains normalized paths from normalize_identifier which are git-root-relative
-                            let git_root = match crate::git_commands::get_git_root_dir() {
-                                Ok(root) => root,
-                                Err(_) => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
-                            };
-                            let absolute_path = git_root.join(file_path);
+                            let absolute_path = self.context.resolve(file_path);
                             if !absolute_path.exists() {
                                 errors.push(ReqvireError::MissingRelationTarget(
                                     format!("Element '{}' references missing target '{}'",
                                         source_node.element.identifier,
                                         file_path.to_string_lossy()),
-                                ));
+                                ).at(ErrorLocation::element(
+                                    &source_node.element.file_path,
+                                    &source_node.element.identifier,
+                                    Some(&file_path.to_string_lossy()),
+                                )));
                             }
                         }
                         crate::relation::LinkType::ExternalUrl(_) => {
@@ -311,7 +431,11 @@ impl GraphRegistry {
                         target_element.element_type,
                         expected_types
                     )
-                ));
+                ).at(ErrorLocation::element(
+                    &source_element.file_path,
+                    &source_element.identifier,
+                    Some(&target_element.identifier),
+                )));
             }
         }
 
@@ -346,7 +470,7 @@ impl GraphRegistry {
                                         element.identifier,
                                         verification_type
                                     )
-                                ));
+                                ).at(ErrorLocation::element(&element.file_path, &element.identifier, None)));
                             }
                             crate::element::VerificationType::Default |
                             crate::element::VerificationType::Test => {
@@ -394,7 +518,7 @@ impl GraphRegistry {
                         if !has_parent_relation {
                             errors.push(ReqvireError::MissingParentRelation(
                                 format!("File {}: Element '{}' has no parent relation (needs one of: {:?})", element_file, element.name, valid_parent_relations),
-                            ));
+                            ).at(ErrorLocation::element(element_file, &element.identifier, None)));
                         }
                     }
                 }
@@ -431,7 +555,7 @@ impl GraphRegistry {
             let full_cycle = format!("{} -> {}", cycle, element_id);
             errors.push(ReqvireError::CircularDependencyError(
                 format!("Circular dependency error: {}", full_cycle),
-            ));
+            ).at(ErrorLocation::element(&element.file_path, &element_id, None)));
             return;
         }
 
@@ -893,6 +1017,30 @@ impl GraphRegistry {
         self.nodes.get(element_id).map(|node| &node.element)
     }
 
+    /// Resolves a type name to a built-in element type or to a custom type
+    /// used by an element of the model
+    pub fn element_type(&self, name: &str) -> Result<ElementType, ReqvireError> {
+        let element_type = ElementType::from_metadata(name);
+        if !matches!(element_type, ElementType::Other(_))
+            || self.nodes.values().any(|node| node.element.element_type == element_type)
+        {
+            return Ok(element_type);
+        }
+        let mut custom_types: Vec<&str> = self.nodes
+            .values()
+            .filter(|node| matches!(node.element.element_type, ElementType::Other(_)))
+            .map(|node| node.element.element_type.as_str())
+            .collect();
+        custom_types.sort_unstable();
+        custom_types.dedup();
+        let known_types: Vec<&str> = ElementType::BUILT_IN_NAMES.iter().copied().chain(custom_types).collect();
+        Err(ReqvireError::ElementError(format!(
+            "Unknown element type '{}', expected one of: {}",
+            name,
+            known_types.join(", ")
+        )))
+    }
+
     /// Creates a virtual section in an existing file
     pub fn create_virtual_section(&mut self, file_path: &str, section: &str) -> Result<(), ReqvireError> {
         self.add_section_to_file(file_path, section)
@@ -919,7 +1067,7 @@ impl GraphRegistry {
     }
 
 
-    fn element_to_markdown_with_context(&self, element: &Element, _current_file: &str) -> String {
+    pub(crate) fn element_to_markdown_with_context(&self, element: &Element, _current_file: &str) -> String {
         let mut markdown = String::new();
 
         // Add the element header
@@ -959,96 +1107,7 @@ impl GraphRegistry {
         if !user_relations.is_empty() {
             markdown.push_str("#### Relations\n");
             for relation in user_relations {
-                // Format relation target based on type
-                // Format as proper markdown link using element name when possible
-                let target_text = match &relation.target.link {
-                    LinkType::ExternalUrl(url) => {
-                        // For external URLs, preserve the original markdown link format
-                        format!("[{}]({})", relation.target.text, url)
-                    },
-                    LinkType::Identifier(target_id) => {
-                        // Extract fragment to look up the target element
-                        let fragment = if let Some(fragment_pos) = target_id.find('#') {
-                            &target_id[fragment_pos + 1..]
-                        } else {
-                            target_id
-                        };
-
-                        // Use actual element name if available, otherwise fallback to fragment conversion
-                        // First try to lookup by full target_id, then by fragment only
-                        let display_name = if let Some(target_node) = self.nodes.get(target_id) {
-                            target_node.element.name.clone()
-                        } else if let Some(target_node) = self.nodes.get(fragment) {
-                            target_node.element.name.clone()
-                        } else {
-                            // Fallback: convert fragment to title case
-                            fragment.replace('-', " ")
-                                .split_whitespace()
-                                .map(|word| {
-                                    let mut chars = word.chars();
-                                    match chars.next() {
-                                        None => String::new(),
-                                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                                    }
-                                })
-                                .collect::<Vec<String>>()
-                                .join(" ")
-                        };
-
-                        // Check if target is in the same file
-                        let target_file = if let Some(file_pos) = target_id.find('#') {
-                            &target_id[..file_pos]
-                        } else {
-                            target_id
-                        };
-
-                        // Get current file path for comparison
-                        let current_file_path = std::path::PathBuf::from(_current_file);
-                        let current_file_str = _current_file;
-
-                        // If target is in the same file, use just the fragment
-                        if target_file.is_empty() || target_file == current_file_str ||
-                           target_id.starts_with('#') {
-                            format!("[{}](#{})", display_name, fragment)
-                        } else {
-                            // Make the link relative using just the folder of the current file
-                            let current_folder = current_file_path.parent()
-                                .unwrap_or_else(|| std::path::Path::new("."))
-                                .to_path_buf();
-
-                            let relative_link = crate::utils::to_relative_identifier(
-                                relation.target.link.as_str(),
-                                &current_folder,
-                                false
-                            ).unwrap_or_else(|_| relation.target.link.as_str().to_string());
-
-                            format!("[{}]({})", display_name, relative_link)
-                        }
-                    },
-                    LinkType::InternalPath(path) => {
-                        // For InternalPath, use the filename as display text and full relative path as link
-                        let path_str = path.to_str().unwrap_or("invalid_path");
-                        let display_name = std::path::Path::new(path_str)
-                            .file_name()
-                            .and_then(|name| name.to_str())
-                            .unwrap_or(path_str);
-
-                        // Make the path relative using just the folder of the current file
-                        let current_file_path = std::path::PathBuf::from(_current_file);
-                        let current_folder = current_file_path.parent()
-                            .unwrap_or_else(|| std::path::Path::new("."))
-                            .to_path_buf();
-
-                        let relative_link = crate::utils::to_relative_identifier(
-                            relation.target.link.as_str(),
-                            &current_folder,
-                            false
-                        ).unwrap_or_else(|_| relation.target.link.as_str().to_string());
-
-                        format!("[{}]({})", display_name, relative_link)
-                    }
-                };
-
+                let target_text = self.relation_target_to_markdown(relation, _current_file);
                 markdown.push_str(&format!("  * {}: {}\n",
                     relation.relation_type.name,
                     target_text
@@ -1061,6 +1120,91 @@ impl GraphRegistry {
         Self::ensure_blank_lines_before_subsections(&markdown)
     }
 
+    /// Markdown link of a relation target as written in the `#### Relations` subsection of `current_file`
+    pub(crate) fn relation_target_to_markdown(&self, relation: &relation::Relation, current_file: &str) -> String {
+        // Format relation target based on type
+        // Format as proper markdown link using element name when possible
+        match &relation.target.link {
+            LinkType::ExternalUrl(url) => {
+                // For external URLs, preserve the original markdown link format
+                format!("[{}]({})", relation.target.text, url)
+            },
+            LinkType::Identifier(target_id) => {
+                // Extract fragment to look up the target element
+                let fragment = if let Some(fragment_pos) = target_id.find('#') {
+                    &target_id[fragment_pos + 1..]
+                } else {
+                    target_id
+                };
+
+                // Use actual element name if available, otherwise fallback to fragment conversion
+                // First try to lookup by full target_id, then by fragment only
+                let display_name = if let Some(target_node) = self.nodes.get(target_id) {
+                    target_node.element.name.clone()
+                } else if let Some(target_node) = self.nodes.get(fragment) {
+                    target_node.element.name.clone()
+                } else {
+                    // Fallback: convert fragment to title case
+                    fragment.replace('-', " ")
+                        .split_whitespace()
+                        .map(|word| {
+                            let mut chars = word.chars();
+                            match chars.next() {
+                                None => String::new(),
+                                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                            }
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                };
+
+                // Check if target is in the same file
+                let target_file = if let Some(file_pos) = target_id.find('#') {
+                    &target_id[..file_pos]
+                } else {
+                    target_id
+                };
+
+                // Get current file path for comparison
+                let current_file_path = std::path::PathBuf::from(current_file);
+                let current_file_str = current_file;
+
+                // If target is in the same file, use just the fragment
+                if target_file.is_empty() || target_file == current_file_str ||
+                   target_id.starts_with('#') {
+                    format!("[{}](#{})", display_name, fragment)
+                } else {
+                    // Make the link relative using just the folder of the current file
+                    let current_folder = current_file_path.parent()
+                        .unwrap_or_else(|| std::path::Path::new("."))
+                        .to_path_buf();
+
+                    let relative_link = crate::utils::relative_link(relation.target.link.as_str(), &current_folder);
+
+                    format!("[{}]({})", display_name, relative_link)
+                }
+            },
+            LinkType::InternalPath(path) => {
+                // For InternalPath, use the filename as display text and full relative path as link
+                let path_str = path.to_str().unwrap_or("invalid_path");
+                let display_name = std::path::Path::new(path_str)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(path_str);
+
+                // Make the path relative using just the folder of the current file
+                let current_file_path = std::path::PathBuf::from(current_file);
+                let current_folder = current_file_path.parent()
+                    .unwrap_or_else(|| std::path::Path::new("."))
+                    .to_path_buf();
+
+                let relative_link = crate::utils::relative_link(relation.target.link.as_str(), &current_folder);
+
+                format!("[{}]({})", display_name, relative_link)
+            }
+        }
+    }
+
     /// Ensures every #### header has exactly one blank line before it (skips content inside <details> blocks)
     /// and removes blank lines immediately after #### headers
     fn ensure_blank_lines_before_subsections(content: &str) -> String {
@@ -1259,14 +1403,7 @@ impl GraphRegistry {
 
     /// Copies InternalPath files to the output directory
     fn copy_internal_path_files(&self, internal_paths: &HashSet<PathBuf>, output_dir: &Path) -> Result<usize, ReqvireError> {
-        let base_dir = match git_commands::get_git_root_dir() {
-            Ok(git_root) => git_root,
-            Err(_) => {
-                // If Git repository root can't be found, use the current working directory
-                std::env::current_dir()
-                    .map_err(|e| ReqvireError::PathError(format!("Failed to get current directory: {}", e)))?
-            }
-        };
+        let base_dir = self.context.root();
 
         let mut files_copied = 0;
 
@@ -1637,7 +1774,7 @@ impl GraphRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::element::{Element, ElementType, RequirementType};
+    use crate::element::{Element, ElementType, RequirementType, VerificationType};
     use crate::relation::{Relation, RelationTarget, LinkType, RELATION_TYPES};
 
     fn make_element(id: &str, name: &str) -> Element {
@@ -1685,6 +1822,45 @@ mod tests {
         assert_eq!(a_node.relations[0].element_node.element.identifier, "B");
     }
 
+    #[test]
+    fn test_rebuild_relations_patches_changed_file() {
+        let mut registry = GraphRegistry::new();
+        let a = Element::new("A", "a.md#a", "a.md", "TestSection", Some(ElementType::Requirement(RequirementType::System)));
+        let mut b = Element::new("B", "b.md#b", "b.md", "TestSection", Some(ElementType::Requirement(RequirementType::System)));
+        add_relation(&mut b, "derivedFrom", "a.md#a");
+
+        registry.register_element(a, "a.md").unwrap();
+        registry.register_element(b.clone(), "b.md").unwrap();
+        registry.build_relations(&GlobSet::empty()).unwrap();
+
+        let a_node = registry.nodes.get("a.md#a").unwrap();
+        assert_eq!(a_node.element.relations.len(), 1);
+        assert_eq!(a_node.relations[0].element_node.element.identifier, "b.md#b");
+
+        // Re-register b.md without the relation
+        let changed: HashSet<String> = ["b.md".to_string()].into_iter().collect();
+        registry.remove_file("b.md");
+        assert!(!registry.nodes.contains_key("b.md#b"));
+        b.relations.clear();
+        registry.register_element(b.clone(), "b.md").unwrap();
+        registry.rebuild_relations(&changed, &GlobSet::empty()).unwrap();
+
+        let a_node = registry.nodes.get("a.md#a").unwrap();
+        assert!(a_node.element.relations.is_empty());
+        assert!(a_node.relations.is_empty());
+
+        // Re-register b.md with the relation restored
+        registry.remove_file("b.md");
+        add_relation(&mut b, "derivedFrom", "a.md#a");
+        registry.register_element(b, "b.md").unwrap();
+        registry.rebuild_relations(&changed, &GlobSet::empty()).unwrap();
+
+        let a_node = registry.nodes.get("a.md#a").unwrap();
+        assert_eq!(a_node.element.relations.len(), 1);
+        assert!(!a_node.element.relations[0].user_created);
+        assert_eq!(a_node.relations[0].element_node.element.identifier, "b.md#b");
+    }
+
     #[test]
     fn test_update_identifier_updates_links_and_graph() {
         let mut registry = GraphRegistry::new();
@@ -1762,6 +1938,58 @@ mod tests {
         assert_eq!(tree.relations[0].element_node.relations.len(), 0);
     }
 
+    #[test]
+    fn test_impact_tree_follows_propagated_opposites() {
+        let mut registry = GraphRegistry::new();
+        let a = make_element("file.md#a", "Element A");
+        let mut b = make_element("file.md#b", "Element B");
+        let mut t = make_element("file.md#t", "Test T");
+        t.element_type = ElementType::Verification(VerificationType::Test);
+
+        // Opposites are only added for markdown identifiers; only the
+        // backward relations are written: B derivedFrom A, T verify B
+        add_relation(&mut b, "derivedFrom", "file.md#a");
+        add_relation(&mut t, "verify", "file.md#b");
+
+        registry.register_element(a, "file.md").unwrap();
+        registry.register_element(b, "file.md").unwrap();
+        registry.register_element(t, "file.md").unwrap();
+        registry.build_relations(&GlobSet::empty()).unwrap();
+
+        // The propagated derive and verifiedBy opposites are impact edges
+        let tree = registry.get_impact_tree("file.md#a");
+        assert_eq!(tree.relations.len(), 1);
+        assert_eq!(tree.relations[0].relation_trigger, "derive");
+
+        let b_node = &tree.relations[0].element_node;
+        assert_eq!(b_node.element.identifier, "file.md#b");
+        assert_eq!(b_node.relations.len(), 1);
+        assert_eq!(b_node.relations[0].relation_trigger, "verifiedBy");
+        assert_eq!(b_node.relations[0].element_node.element.identifier, "file.md#t");
+
+        // The written backward relations carry no impact
+        assert!(registry.get_impact_tree("file.md#t").relations.is_empty());
+    }
+
+    #[test]
+    fn test_validation_errors_carry_their_location() {
+        let mut registry = GraphRegistry::new();
+        let mut a = make_element("file.md#a", "Element A");
+        add_relation(&mut a, "derivedFrom", "file.md#missing");
+        registry.register_element(a, "file.md").unwrap();
+
+        let errors = registry.build_relations(&GlobSet::empty()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].location(),
+            Some(&ErrorLocation::element("file.md", "file.md#a", Some("file.md#missing")))
+        );
+        assert!(errors[0].to_string().starts_with("Missing relation target: Element 'file.md#a'"));
+
+        let duplicate = registry.register_element(make_element("file.md#a", "Element A"), "file.md").unwrap_err();
+        assert_eq!(duplicate.location(), Some(&ErrorLocation::element("file.md", "file.md#a", None)));
+    }
+
     #[test]
     fn test_move_element_to_existing_location() {
         let mut registry = GraphRegistry::new();
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use pulldown_cmark::{html, Options, Parser};
 use crate::error::ReqvireError;
+use crate::theme::Theme;
 use std::path::PathBuf;
 use lazy_static::lazy_static;
 use regex::{Regex, Captures};
@@ -180,6 +181,34 @@ blockquote {
 .diagram-nav-btn:active {
     background-color: #1a252f;
 }
+.reqvire-logo {
+    height: 32px;
+    margin-right: 15px;
+}
+@media print {
+    body {
+        background-color: #fff;
+    }
+    .reqvire-nav, .reqvire-nav-spacer, #reqvire-search, .element-details, .diagram-nav-buttons {
+        display: none;
+    }
+    .container {
+        max-width: 100%;
+        padding: 0;
+    }
+    .content {
+        box-shadow: none;
+        padding: 0;
+    }
+    .mermaid {
+        height: auto;
+        border: none;
+        break-inside: avoid;
+    }
+    a {
+        color: inherit;
+    }
+}
 </style>
 "#;
 
@@ -196,7 +225,8 @@ pub fn convert_to_html(
     file_path: &PathBuf,
     markdown_content: &str,
     title: &str,
-    base_folder: &PathBuf
+    base_folder: &PathBuf,
+    theme: &Theme
 ) -> Result<String, ReqvireError> {
     // 1. Extract Mermaid blocks before link conversion
     let (markdown_without_mermaid, mermaid_blocks) = extract_mermaid_blocks(markdown_content);
@@ -239,10 +269,14 @@ pub fn convert_to_html(
         HTML_TEMPLATE
     };
 
-    // 8. Final output with relative navigation links
+    // 8. Final output with the theme fragments and relative navigation links
     let html_document = template
         .replace("{title}", title)
         .replace("{styles}", EMBEDDED_STYLES)
+        .replace("{theme_styles}", &theme.stylesheets(&nav_prefix))
+        .replace("{navigation}", &theme.navigation(title, &nav_prefix))
+        .replace("{header}", &theme.header(title, &nav_prefix))
+        .replace("{footer}", &theme.footer(title, &nav_prefix))
         .replace("{content}", &html_with_mermaid)
         .replace("{nav_prefix}", &nav_prefix);
 
@@ -285,12 +319,16 @@ fn add_anchor_ids(html_content: &str) -> String {
         .replace_all(html_content, |caps: &regex::Captures| {
             let tag = &caps[1];
             let text = &caps[2];
-            let id = text.trim().replace(' ', "-").to_lowercase();
-            format!("<{} id=\"{}\">{}</{}>", tag, id, text, tag)
+            format!("<{} id=\"{}\">{}</{}>", tag, heading_id(text), text, tag)
         })
         .to_string()
 }
 
+/// Anchor id of a heading with the given text in exported pages
+pub fn heading_id(text: &str) -> String {
+    text.trim().replace(' ', "-").to_lowercase()
+}
+
 /// Rewrite every `<pre><code class="language-mermaid">…</code></pre>`
 /// into `<div class="mermaid">…</div>` and convert relative links from .md to .html
 /// GitHub blob links are preserved as-is (keeping the .md extension)
@@ -1,50 +1,33 @@
 use std::fs;
-use std::path::{PathBuf,Path};
+use std::path::Path;
 use crate::html;
+use crate::theme::Theme;
 use crate::error::ReqvireError;
-use crate::git_commands;
+use crate::context::ModelContext;
 use crate::info_println;
 use walkdir::WalkDir;
 use log::debug;
 
 
-/// Exports all markdown files to HTML without any processing or filtering
-/// Uses auto-detection logic: if current directory is a subfolder of git root, 
-/// only process files within that subfolder
+/// Exports all markdown files to HTML without any processing or filtering.
+/// Only files within the scan root of the model are processed.
 pub fn export_markdown_to_html(
-    _base_dir: &PathBuf,
+    context: &ModelContext,
     output_folder: &Path,
+    theme: &Theme,
 ) -> Result<usize, ReqvireError> {
 
     let mut processed_count = 0;
 
-    // Get git root directory
-    let git_root = match git_commands::get_git_root_dir() {
-        Ok(dir) => dir,
-        Err(_) => {
-            debug!("Not in a git repository, using current directory");
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-        }
-    };
-    
-    // Get current working directory
-    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    
-    // Determine scan directory - if current directory is within git root but not at git root,
-    // scan only within the current directory subtree
-    let scan_dir = if current_dir.starts_with(&git_root) && current_dir != git_root {
-        current_dir
-    } else {
-        git_root.clone()
-    };
-    
+    let scan_dir = context.scan_root();
     debug!("HTML export scanning for markdown files in: {}", scan_dir.display());
 
-    // Process all markdown files in the determined directory
+    // Process all markdown files in the scan root
     processed_count += process_markdown_files(
-        &scan_dir,
-        &git_root,
+        scan_dir,
+        context.root(),
         output_folder,
+        theme,
     )?;
 
     info_println!("✅ Total Markdown files exported: {}", processed_count);
@@ -56,6 +39,7 @@ fn process_markdown_files(
     scan_folder: &Path,
     base_folder: &Path,
     output_folder: &Path,
+    theme: &Theme,
 ) -> Result<usize, ReqvireError> {
     let mut count = 0;
     let mut all_files = Vec::new();
@@ -74,7 +58,7 @@ fn process_markdown_files(
         
         // Process SpecificationIndex.md first if it exists
         if file_path == spec_index_path {
-            export_file_to_html(&file_path, scan_folder, base_folder, output_folder)?;
+            export_file_to_html(&file_path, scan_folder, base_folder, output_folder, theme)?;
             count += 1;
         } else {
             all_files.push(file_path);
@@ -83,7 +67,7 @@ fn process_markdown_files(
     
     // Process all other Markdown files
     for file_path in all_files {
-        export_file_to_html(&file_path, scan_folder, base_folder, output_folder)?;
+        export_file_to_html(&file_path, scan_folder, base_folder, output_folder, theme)?;
         count += 1;
     }
 
@@ -97,6 +81,7 @@ fn export_file_to_html(
     scan_folder: &Path,
     base_folder: &Path,
     output_folder: &Path,
+    theme: &Theme,
 ) -> Result<(), ReqvireError> {
     let content = fs::read_to_string(file_path)?;
     let file_name = file_path
@@ -114,7 +99,8 @@ fn export_file_to_html(
         &file_path.to_path_buf(),
         &content,
         &title,
-        &base_folder.to_path_buf()
+        &base_folder.to_path_buf(),
+        theme
     )?;
 
     // Determine where to place the output
@@ -0,0 +1,466 @@
+//! Import of externally authored requirements into the Markdown model
+//!
+//! Importers translate their source format into an `ImportedModel` of files,
+//! sections and elements with relations between them. The model is added to
+//! the parsed model of the project, validated as a whole, and every imported
+//! file is rendered with `generate_file_markdown` before any is written, so
+//! imported files have the same structure that `format` produces.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use globset::GlobSet;
+use serde::Serialize;
+
+use crate::element::{Element, ElementType};
+use crate::error::ReqvireError;
+use crate::format;
+use crate::context::ModelContext;
+use crate::graph_registry::{GraphRegistry, SectionKey};
+use crate::model::ModelManager;
+use crate::relation::{self, LinkType, Relation, RelationTarget, RELATION_TYPES};
+use crate::utils;
+
+/// Section used for elements that precede any section heading
+pub const DEFAULT_SECTION: &str = "Requirements";
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedElement {
+    /// Identifier of the element in the source format, used to resolve relations
+    pub key: String,
+    pub name: String,
+    pub content: String,
+    pub element_type: String,
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedSection {
+    pub name: String,
+    pub content: String,
+    pub elements: Vec<ImportedElement>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedFile {
+    /// Path of the markdown file relative to the output directory
+    pub path: String,
+    /// Optional level 1 heading; defaults to one derived from the file name
+    pub title: Option<String>,
+    pub sections: Vec<ImportedSection>,
+}
+
+impl ImportedFile {
+    /// Returns the section with the given name, appending it if missing
+    pub fn section_mut(&mut self, name: &str) -> &mut ImportedSection {
+        let index = match self.sections.iter().position(|section| section.name == name) {
+            Some(index) => index,
+            None => {
+                self.sections.push(ImportedSection {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedRelation {
+    pub source: String,
+    pub relation_type: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedModel {
+    pub files: Vec<ImportedFile>,
+    pub relations: Vec<ImportedRelation>,
+    /// Keys of source objects that stand for files, with the file path
+    /// relative to the model root; relations to them link to the file
+    pub file_targets: BTreeMap<String, String>,
+    /// Relations the importer could not resolve, with the reason
+    pub skipped_relations: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedFileSummary {
+    pub path: String,
+    pub sections: Vec<String>,
+    pub elements: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportSummary {
+    /// True when files were only planned and not written
+    pub dry_run: bool,
+    pub files: Vec<ImportedFileSummary>,
+    pub elements: usize,
+    pub relations: usize,
+    /// Relations that could not be imported, with the reason
+    pub skipped_relations: Vec<String>,
+}
+
+impl ImportSummary {
+    pub fn print(&self, json: bool) {
+        if json {
+            println!("{}", serde_json::to_string_pretty(self).unwrap());
+            return;
+        }
+        let verb = if self.dry_run { "Would import" } else { "Imported" };
+        println!(
+            "✅ {} {} elements and {} relations into {} files",
+            verb,
+            self.elements,
+            self.relations,
+            self.files.len()
+        );
+        for file in &self.files {
+            println!("  - {} ({} elements)", file.path, file.elements);
+            if self.dry_run {
+                for section in &file.sections {
+                    println!("      ## {}", section);
+                }
+            }
+        }
+        if !self.skipped_relations.is_empty() {
+            println!("⚠️ Skipped {} relations:", self.skipped_relations.len());
+            for skipped in &self.skipped_relations {
+                println!("  - {}", skipped);
+            }
+        }
+        if self.dry_run {
+            println!("Run without --dry-run to write these files.");
+        }
+    }
+}
+
+/// Returns a file name derived from a free-form title, e.g. `Supplier Spec` -> `Supplier-Spec.md`
+pub fn file_name_from_title(title: &str) -> String {
+    let stem: Vec<&str> = title
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .filter(|part| !part.is_empty())
+        .collect();
+    if stem.is_empty() {
+        "Imported.md".to_string()
+    } else {
+        format!("{}.md", stem.join("-"))
+    }
+}
+
+/// Collapses whitespace so the value fits on a single markdown line
+pub fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escapes lines the parser would read as structure: headings start new
+/// elements or subsections and `---` ends the current element
+pub fn escape_content(content: &str) -> String {
+    let lines: Vec<String> = content
+        .replace("\r\n", "\n")
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            if trimmed.starts_with('#') {
+                format!("{}\\{}", indent, trimmed)
+            } else if trimmed.trim_end() == "---" {
+                format!("{}***", indent)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Writes the imported model as markdown files below `output_dir`, which is
+/// relative to the model root unless absolute.
+///
+/// The model is validated together with the existing model before anything
+/// is written and existing files are only replaced with `overwrite`. With
+/// `dry_run` the files are only planned.
+pub fn write_imported_model(
+    context: &ModelContext,
+    model: &ImportedModel,
+    output_dir: &Path,
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+    overwrite: bool,
+) -> Result<ImportSummary, ReqvireError> {
+    let (root, prefix) = resolve_output(context, output_dir)?;
+
+    // Model file paths are relative to the project root; files without
+    // elements are not written
+    let files: Vec<&ImportedFile> = model
+        .files
+        .iter()
+        .filter(|file| file.sections.iter().any(|section| !section.elements.is_empty()))
+        .collect();
+    let file_paths: Vec<String> = files
+        .iter()
+        .map(|file| prefix.join(&file.path).to_string_lossy().to_string())
+        .collect();
+    check_file_paths(&root, &file_paths, overwrite)?;
+
+    let mut registry = load_existing_model(context, &file_paths, excluded_filename_patterns)?;
+    let mut elements: Vec<Element> = Vec::new();
+    let mut identifiers: HashMap<&str, usize> = HashMap::new();
+    let mut duplicate_keys: Vec<&str> = Vec::new();
+
+    for (file, file_path) in files.iter().zip(&file_paths) {
+        if let Some(title) = &file.title {
+            registry.register_page(file_path.clone(), format!("# {}\n", single_line(title)));
+        }
+        let mut fragments = HashSet::new();
+        for (section_order, section) in file.sections.iter().enumerate() {
+            registry.register_section_with_order(
+                file_path.clone(),
+                section.name.clone(),
+                escape_content(&section.content),
+                section_order,
+            );
+            for (index, imported) in section.elements.iter().enumerate() {
+                if identifiers.contains_key(imported.key.as_str()) {
+                    duplicate_keys.push(&imported.key);
+                    continue;
+                }
+
+                // Element names must be unique within a file
+                let base_name = single_line(&imported.name);
+                let mut name = base_name.clone();
+                let mut suffix = 2;
+                while !fragments.insert(utils::normalize_fragment(&name)) {
+                    name = format!("{} ({})", base_name, suffix);
+                    suffix += 1;
+                }
+
+                let identifier = format!("{}#{}", file_path, utils::normalize_fragment(&name));
+                let element_type = ElementType::from_metadata(&imported.element_type);
+                let mut element = Element::new(&name, &identifier, file_path, &section.name, Some(element_type));
+                element.content = escape_content(&imported.content);
+                element.freeze_content();
+                element.section_order_index = index;
+                for (key, value) in &imported.metadata {
+                    element.metadata.insert(key.clone(), value.clone());
+                }
+                element.metadata.insert("type".to_string(), element.element_type.as_str().to_string());
+
+                identifiers.insert(imported.key.as_str(), elements.len());
+                elements.push(element);
+            }
+        }
+    }
+
+    if !duplicate_keys.is_empty() {
+        return Err(ReqvireError::ProcessError(format!(
+            "Imported elements must have unique identifiers, found duplicates of: {}",
+            duplicate_keys.join(", ")
+        )));
+    }
+
+    let mut skipped_relations = model.skipped_relations.clone();
+    let mut relation_count = 0;
+    let mut graph_relations = Vec::new();
+    for imported in &model.relations {
+        let description = format!("{} {} {}", imported.source, imported.relation_type, imported.target);
+        let target = match (
+            identifiers.get(imported.target.as_str()),
+            model.file_targets.get(&imported.target),
+        ) {
+            (Some(&target), _) => RelationTarget {
+                text: elements[target].name.clone(),
+                link: LinkType::Identifier(elements[target].identifier.clone()),
+            },
+            (None, Some(path)) => {
+                if !context.resolve(path).is_file() {
+                    skipped_relations.push(format!("{} (file '{}' not found)", description, path));
+                    continue;
+                }
+                RelationTarget {
+                    text: path.clone(),
+                    link: LinkType::InternalPath(PathBuf::from(path)),
+                }
+            }
+            (None, None) => {
+                skipped_relations.push(format!("{} (unknown source or target)", description));
+                continue;
+            }
+        };
+        let Some(&source) = identifiers.get(imported.source.as_str()) else {
+            skipped_relations.push(format!("{} (unknown source or target)", description));
+            continue;
+        };
+        let Some(relation_type) = RELATION_TYPES.get(imported.relation_type.as_str()) else {
+            skipped_relations.push(format!("{} (unsupported relation type)", description));
+            continue;
+        };
+
+        let relation = Relation {
+            relation_type,
+            target,
+            user_created: true,
+        };
+        if elements[source].relations.contains(&relation) {
+            continue;
+        }
+        if let LinkType::Identifier(target_id) = &relation.target.link {
+            graph_relations.push((elements[source].identifier.clone(), target_id.clone(), relation_type.name));
+        }
+        elements[source].relations.push(relation);
+        relation_count += 1;
+    }
+
+    let element_count = elements.len();
+    for element in elements {
+        registry.add_element(element)?;
+    }
+    for (source, target, relation_type) in &graph_relations {
+        if relation::IMPACT_PROPAGATION_RELATIONS.contains(relation_type) {
+            registry.add_relation(source, target, relation_type)?;
+        }
+    }
+
+    let errors = registry.build_relations(excluded_filename_patterns)?;
+    if !errors.is_empty() {
+        return Err(ReqvireError::ValidationError(errors));
+    }
+
+    let summary = ImportSummary {
+        dry_run,
+        files: summarize_files(&registry, &file_paths),
+        elements: element_count,
+        relations: relation_count,
+        skipped_relations,
+    };
+    if dry_run {
+        return Ok(summary);
+    }
+
+    write_files(&registry, &root, &file_paths)?;
+    Ok(summary)
+}
+
+/// Returns the model root and the location of `output_dir` relative to it
+pub(crate) fn resolve_output(context: &ModelContext, output_dir: &Path) -> Result<(PathBuf, PathBuf), ReqvireError> {
+    let root = context.root().canonicalize()?;
+    let output_dir = absolute_path(&root, output_dir);
+    let prefix = output_dir.strip_prefix(&root).map_err(|_| {
+        ReqvireError::PathError(format!(
+            "Import output '{}' must be inside the project root '{}'",
+            output_dir.display(),
+            root.display()
+        ))
+    })?;
+    Ok((root.clone(), prefix.to_path_buf()))
+}
+
+/// Parses the model of the project without the files the import replaces, so
+/// imported elements are validated together with the elements they join
+pub(crate) fn load_existing_model(
+    context: &ModelContext,
+    file_paths: &[String],
+    excluded_filename_patterns: &GlobSet,
+) -> Result<GraphRegistry, ReqvireError> {
+    let mut model_manager = ModelManager::with_context(context.clone());
+    model_manager.parse_and_validate(None, excluded_filename_patterns)?;
+    let mut registry = model_manager.graph_registry;
+    for path in file_paths {
+        registry.remove_file(path);
+    }
+
+    // Opposite relations are propagated again by `build_relations`, also
+    // for the elements whose counterparts were replaced
+    for node in registry.nodes.values_mut() {
+        node.element.relations.retain(|relation| relation.user_created);
+    }
+    Ok(registry)
+}
+
+/// Rejects duplicate target paths and, unless `overwrite` is set, existing files
+pub(crate) fn check_file_paths(root: &Path, file_paths: &[String], overwrite: bool) -> Result<(), ReqvireError> {
+    let mut unique_paths = HashSet::new();
+    for path in file_paths {
+        if !unique_paths.insert(path) {
+            return Err(ReqvireError::ProcessError(format!("Import would write '{}' more than once", path)));
+        }
+        if !overwrite && root.join(path).exists() {
+            return Err(ReqvireError::ProcessError(format!(
+                "Import would overwrite existing file '{}'; choose another --output directory or use --overwrite",
+                path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Renders every file exactly as `format` would write it and only then
+/// writes them, so a rendering failure leaves nothing behind
+pub(crate) fn write_files(registry: &GraphRegistry, root: &Path, file_paths: &[String]) -> Result<(), ReqvireError> {
+    let grouped = registry.group_elements_by_location();
+    let no_elements = HashMap::new();
+    let rendered: Vec<(PathBuf, String)> = file_paths
+        .iter()
+        .map(|path| {
+            let markdown = registry.generate_file_markdown(path, grouped.get(path).unwrap_or(&no_elements));
+            (root.join(path), format::apply_formatting_rules(&markdown))
+        })
+        .collect();
+
+    for (full_path, content) in rendered {
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&full_path, content)?;
+    }
+    Ok(())
+}
+
+/// Lists the sections with elements and the element count of each file in section order
+pub(crate) fn summarize_files(registry: &GraphRegistry, file_paths: &[String]) -> Vec<ImportedFileSummary> {
+    let grouped = registry.group_elements_by_location();
+    file_paths
+        .iter()
+        .map(|path| {
+            let mut sections: Vec<(usize, String)> = grouped
+                .get(path)
+                .into_iter()
+                .flat_map(|sections| sections.keys())
+                .map(|name| {
+                    let key = SectionKey::new(path.clone(), name.clone());
+                    let order = registry.sections.get(&key).map(|s| s.section_order).unwrap_or(usize::MAX);
+                    (order, name.clone())
+                })
+                .collect();
+            sections.sort();
+            ImportedFileSummary {
+                path: path.clone(),
+                sections: sections.into_iter().map(|(_, name)| name).collect(),
+                elements: grouped.get(path).map(|s| s.values().map(Vec::len).sum()).unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+/// Resolves `path` against `root` and removes `.` and `..` components
+/// without touching the filesystem, since the directory may not exist yet
+fn absolute_path(root: &Path, path: &Path) -> PathBuf {
+    let mut absolute = if path.is_absolute() {
+        PathBuf::new()
+    } else {
+        root.to_path_buf()
+    };
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            other => absolute.push(other),
+        }
+    }
+    absolute
+}
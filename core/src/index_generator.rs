@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::graph_registry::GraphRegistry;
 use crate::element::Element;
 use crate::error::ReqvireError;
-use crate::git_commands;
+use crate::context::ModelContext;
 
 
 /// Generates a SpecificationsIndex.md index from the existing element registry
@@ -33,7 +33,7 @@ pub fn generate_readme_index(
         let sections = grouped_elements.get(file).unwrap();
 
         // Compute relative path
-        let relative_path = get_relative_path(file);
+        let relative_path = get_relative_path(&registry.context, file);
 
 
         index_content.push_str(&format!("## [{}]({})\n", relative_path, relative_path));
@@ -85,17 +85,11 @@ fn generate_section_slug(section: &str) -> String {
         .replace(|c: char| !c.is_alphanumeric() && c != '-', "") // Remove special characters
 }
 
-/// Computes a relative path to a file from the git repository root
-fn get_relative_path(file: &str) -> String {
-    match git_commands::get_git_root_dir() {
-        Ok(git_root) => {
-            let file_path = Path::new(file);
-            match file_path.strip_prefix(&git_root) {
-                Ok(relative) => relative.to_string_lossy().into_owned(),
-                Err(_) => file.to_string(), // Fallback to absolute if stripping fails
-            }
-        },
-        Err(_) => file.to_string() // Fall back to the file name if git root not found
+/// Computes a relative path to a file from the model root
+fn get_relative_path(context: &ModelContext, file: &str) -> String {
+    match context.relative_path(Path::new(file)) {
+        Ok(relative) => relative.to_string_lossy().into_owned(),
+        Err(_) => file.to_string(), // Fallback to the file path if stripping fails
     }
 }
 
@@ -9,6 +9,7 @@ pub mod export;
 pub mod html;
 pub mod filesystem;
 pub mod diagrams;
+pub mod diagram_svg;
 pub mod index_generator;
 pub mod reports;
 pub mod sections_summary;
@@ -21,6 +22,22 @@ pub mod format;
 pub mod verification_trace;
 pub mod trace_tree_builder;
 pub mod lint;
+pub mod verification_rules;
+pub mod project_config;
+pub mod coverage_history;
+pub mod tabular;
+pub mod reqif;
+pub mod import;
+pub mod reqif_import;
+pub mod bulk_import;
+pub mod model_json;
+pub mod patch;
+pub mod source_map;
+pub mod search_index;
+pub mod element_pages;
+pub mod theme;
+pub mod context;
+pub mod single_file;
 
 // Re-export commonly used modules
 pub use crate::model::ModelManager;
@@ -28,3 +45,4 @@ pub use crate::element::Element;
 pub use crate::relation::Relation;
 pub use crate::error::ReqvireError;
 pub use crate::graph_registry::GraphRegistry;
+pub use crate::context::ModelContext;
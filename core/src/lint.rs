@@ -67,23 +67,27 @@ pub struct RelationInfo {
 }
 
 impl LintReport {
+    /// The report with only auto-fixable or only auditable issues
+    pub fn filtered(&self, show_only_fixable: bool, show_only_auditable: bool) -> LintReport {
+        if show_only_fixable {
+            LintReport {
+                auto_fixable: self.auto_fixable.clone(),
+                needs_manual_review: vec![],
+            }
+        } else if show_only_auditable {
+            LintReport {
+                auto_fixable: vec![],
+                needs_manual_review: self.needs_manual_review.clone(),
+            }
+        } else {
+            // Show both
+            self.clone()
+        }
+    }
+
     pub fn print(&self, json: bool, show_only_fixable: bool, show_only_auditable: bool) {
         if json {
-            // Filter the report based on flags before serializing
-            let filtered_report = if show_only_fixable {
-                LintReport {
-                    auto_fixable: self.auto_fixable.clone(),
-                    needs_manual_review: vec![],
-                }
-            } else if show_only_auditable {
-                LintReport {
-                    auto_fixable: vec![],
-                    needs_manual_review: self.needs_manual_review.clone(),
-                }
-            } else {
-                // Show both
-                self.clone()
-            };
+            let filtered_report = self.filtered(show_only_fixable, show_only_auditable);
             println!("{}", serde_json::to_string_pretty(&filtered_report).unwrap());
         } else {
             self.print_text(show_only_fixable, show_only_auditable);
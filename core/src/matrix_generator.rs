@@ -1,18 +1,22 @@
 // traceability_matrix.rs
 
 use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
 use serde_json::{json};
+use globset::{Glob, GlobMatcher};
 use crate::graph_registry::GraphRegistry;
 use crate::element::{Element, ElementType};
+use crate::error::ReqvireError;
 use crate::relation::{LinkType, RELATION_TYPES};
-use crate::git_commands;
 use crate::element;
+use crate::tabular::{TableFormat, TableWriter};
 
 /// Enum to specify the matrix format
 pub enum MatrixFormat {
     Markdown,
     Json,
     Svg,
+    Table(TableFormat),
 }
 
 /// Configuration for the traceability matrix
@@ -23,6 +27,91 @@ pub struct MatrixConfig {
     pub target_types: Vec<ElementType>,
     /// Relation types to trace (e.g., ["verifiedBy"])
     pub relation_types: Vec<&'static str>,
+    /// Only include source elements whose file path matches this glob
+    pub file_filter: Option<GlobMatcher>,
+    /// Only include source elements whose section name matches this glob
+    pub section_filter: Option<GlobMatcher>,
+}
+
+/// Named matrix definition as given on the command line or saved under
+/// `matrices` in `reqvire.yaml`. Empty lists fall back to the default
+/// requirements × verifications matrix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MatrixDefinition {
+    /// Name used to select the definition and to name exported files
+    #[serde(default)]
+    pub name: String,
+    /// Source element types (rows), e.g. `requirement`, `user-requirement`
+    #[serde(default)]
+    pub source_types: Vec<String>,
+    /// Target element types (columns), e.g. `test-verification`, `file`
+    #[serde(default)]
+    pub target_types: Vec<String>,
+    /// Relation types traced from source to target elements, e.g. `verifiedBy`
+    #[serde(default)]
+    pub relations: Vec<String>,
+    /// Only include source elements whose file path matches this glob pattern
+    #[serde(default)]
+    pub filter_file: Option<String>,
+    /// Only include source elements whose section name matches this glob pattern
+    #[serde(default)]
+    pub filter_section: Option<String>,
+}
+
+impl MatrixDefinition {
+    /// Resolves the definition into a matrix configuration, rejecting type
+    /// names that are neither built-in nor used in the model
+    pub fn to_config(&self, registry: &GraphRegistry) -> Result<MatrixConfig, ReqvireError> {
+        let mut config = MatrixConfig::default();
+        if !self.source_types.is_empty() {
+            config.source_types = self.source_types
+                .iter()
+                .map(|name| registry.element_type(name))
+                .collect::<Result<Vec<ElementType>, ReqvireError>>()?;
+        }
+        if !self.target_types.is_empty() {
+            config.target_types = self.target_types
+                .iter()
+                .map(|name| registry.element_type(name))
+                .collect::<Result<Vec<ElementType>, ReqvireError>>()?;
+        }
+        if !self.relations.is_empty() {
+            config.relation_types = self.relation_types()?;
+        }
+        config.with_filters(self.filter_file.as_deref(), self.filter_section.as_deref())
+    }
+
+    /// Checks the parts of the definition that do not depend on the model:
+    /// relation types and filter patterns
+    pub fn validate(&self) -> Result<(), ReqvireError> {
+        self.relation_types()?;
+        MatrixConfig::default().with_filters(self.filter_file.as_deref(), self.filter_section.as_deref())?;
+        Ok(())
+    }
+
+    fn relation_types(&self) -> Result<Vec<&'static str>, ReqvireError> {
+        self.relations
+            .iter()
+            .map(|name| {
+                RELATION_TYPES
+                    .get_key_value(name.as_str())
+                    .map(|(key, _)| *key)
+                    .ok_or_else(|| ReqvireError::UnsupportedRelationType(format!(
+                        "Unknown relation type '{}' in matrix definition", name
+                    )))
+            })
+            .collect()
+    }
+
+    /// File-name friendly form of the definition name
+    pub fn slug(&self) -> String {
+        let slug: String = self.name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-")
+    }
 }
 
 impl Default for MatrixConfig {
@@ -38,11 +127,117 @@ impl Default for MatrixConfig {
                 ElementType::Verification(element::VerificationType::Inspection),
                 ElementType::Verification(element::VerificationType::Demonstration)
             ],
-            relation_types: vec!["verifiedBy"]
+            relation_types: vec!["verifiedBy"],
+            file_filter: None,
+            section_filter: None,
+        }
+    }
+}
+
+impl MatrixConfig {
+    /// Requirements × implementations matrix traced through `satisfiedBy` relations.
+    ///
+    /// Targets are all element types used as `satisfiedBy` targets in the model
+    /// plus internal files (e.g. source code) referenced by `satisfiedBy`.
+    pub fn satisfaction(registry: &GraphRegistry) -> Self {
+        let mut target_types = vec![ElementType::File];
+        for element in registry.get_all_elements() {
+            for relation in &element.relations {
+                if relation.relation_type.name != "satisfiedBy" {
+                    continue;
+                }
+                if let LinkType::Identifier(target_id) = &relation.target.link {
+                    if let Some(target) = registry.get_element(target_id) {
+                        if !target_types.contains(&target.element_type) {
+                            target_types.push(target.element_type.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        MatrixConfig {
+            source_types: vec![
+                ElementType::Requirement(element::RequirementType::System),
+                ElementType::Requirement(element::RequirementType::User),
+            ],
+            target_types,
+            relation_types: vec!["satisfiedBy"],
+            file_filter: None,
+            section_filter: None,
+        }
+    }
+
+    /// Restricts source elements to the given file path and section name glob patterns
+    pub fn with_filters(mut self, filter_file: Option<&str>, filter_section: Option<&str>) -> Result<Self, ReqvireError> {
+        fn compile_glob(pattern: &str) -> Result<GlobMatcher, ReqvireError> {
+            Ok(Glob::new(pattern)
+                .map_err(|e| ReqvireError::InvalidGlob(e.to_string()))?
+                .compile_matcher())
+        }
+
+        if let Some(pattern) = filter_file {
+            self.file_filter = Some(compile_glob(pattern)?);
+        }
+        if let Some(pattern) = filter_section {
+            self.section_filter = Some(compile_glob(pattern)?);
+        }
+        Ok(self)
+    }
+
+    /// Returns true if the element is a source type and passes the file and section filters
+    fn includes_source(&self, element: &Element) -> bool {
+        self.source_types.contains(&element.element_type)
+            && self.file_filter.as_ref().is_none_or(|glob| glob.is_match(&element.file_path))
+            && self.section_filter.as_ref().is_none_or(|glob| glob.is_match(&element.section))
+    }
+
+    fn labels(&self) -> MatrixLabels {
+        if self.relation_types.contains(&"verifiedBy") {
+            MatrixLabels {
+                title: "Traceability Matrix",
+                intro: "This matrix shows relationships between requirements and verification elements, grouped by root requirements.",
+                status: "Verified",
+                status_key: "verificationStatus",
+                status_yes: "Requirement is verified by at least one verification element",
+                status_no: "Requirement is not verified by any verification element",
+                relation: "Direct relationship exists between requirement and verification",
+            }
+        } else if self.relation_types.contains(&"satisfiedBy") {
+            MatrixLabels {
+                title: "Implementation Traceability Matrix",
+                intro: "This matrix shows relationships between requirements and implementation elements or files, grouped by root requirements.",
+                status: "Satisfied",
+                status_key: "satisfactionStatus",
+                status_yes: "Requirement is satisfied by at least one implementation",
+                status_no: "Requirement is not satisfied by any implementation",
+                relation: "Direct relationship exists between requirement and implementation",
+            }
+        } else {
+            MatrixLabels {
+                title: "Traceability Matrix",
+                intro: "This matrix shows relationships between source and target elements through the relation types listed below.",
+                status: "Traced",
+                status_key: "traceStatus",
+                status_yes: "Element is related to at least one target element",
+                status_no: "Element is not related to any target element",
+                relation: "Direct relationship exists between source and target element",
+            }
         }
     }
 }
 
+/// Wording used by the matrix renderers for the configured relation
+struct MatrixLabels {
+    title: &'static str,
+    intro: &'static str,
+    status: &'static str,
+    status_key: &'static str,
+    status_yes: &'static str,
+    status_no: &'static str,
+    relation: &'static str,
+}
+
 /// Generates a traceability matrix based on the provided configuration
 pub fn generate_matrix(
     registry: &GraphRegistry,
@@ -50,21 +245,44 @@ pub fn generate_matrix(
     format: MatrixFormat,    
 ) -> String {
     // Retrieve Git repository information
-    let base_url = git_commands::get_repository_base_url().unwrap_or_default();
-    let commit_hash = git_commands::get_commit_hash().unwrap_or_else(|_| String::from("HEAD"));
+    let base_url = registry.context.repository_base_url().unwrap_or_default().to_string();
+    let commit_hash = registry.context.commit_hash().unwrap_or("HEAD").to_string();
 
     // Collect source and target elements based on their types
     let source_elements: Vec<&Element> = registry.get_all_elements()
         .iter()
-        .filter(|elem| config.source_types.contains(&elem.element_type))
+        .filter(|elem| config.includes_source(elem))
         .cloned()
         .collect();
 
-    let target_elements: Vec<&Element> = registry.get_all_elements()
+    // Internal files (e.g. source code) are not model elements, so they are
+    // represented by file elements named after their path
+    let include_files = config.target_types.contains(&ElementType::File);
+    let mut file_paths: Vec<String> = Vec::new();
+    if include_files {
+        for source in &source_elements {
+            for relation in &source.relations {
+                if config.relation_types.contains(&relation.relation_type.name) {
+                    if let LinkType::InternalPath(path) = &relation.target.link {
+                        let path = path.to_string_lossy().to_string();
+                        if !file_paths.contains(&path) {
+                            file_paths.push(path);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let file_targets: Vec<Element> = file_paths.iter()
+        .map(|path| Element::new(path, path, path, "", Some(ElementType::File)))
+        .collect();
+
+    let mut target_elements: Vec<&Element> = registry.get_all_elements()
         .iter()
         .filter(|elem| config.target_types.contains(&elem.element_type))
         .cloned()
         .collect();
+    target_elements.extend(file_targets.iter());
 
     // Build the mapping of source to target elements
     let mut matrix_data: HashMap<String, HashSet<String>> = HashMap::new();
@@ -74,12 +292,18 @@ pub fn generate_matrix(
 
         for relation in &source.relations {
             if config.relation_types.contains(&relation.relation_type.name) {
-                if let LinkType::Identifier(target_id) = &relation.target.link {
-                    if let Some(target) = registry.get_element(target_id) {
-                        if config.target_types.contains(&target.element_type) {
-                            targets.insert(target_id.clone());
+                match &relation.target.link {
+                    LinkType::Identifier(target_id) => {
+                        if let Some(target) = registry.get_element(target_id) {
+                            if config.target_types.contains(&target.element_type) {
+                                targets.insert(target_id.clone());
+                            }
                         }
                     }
+                    LinkType::InternalPath(path) if include_files => {
+                        targets.insert(path.to_string_lossy().to_string());
+                    }
+                    _ => {}
                 }
             }
         }
@@ -90,12 +314,13 @@ pub fn generate_matrix(
     }
 
     // Generate the matrix in the specified format
+    let labels = config.labels();
     match format {
         MatrixFormat::Markdown => generate_markdown_matrix(
             &matrix_data,
             &source_elements,
             &target_elements,
-            &config.relation_types,
+            config,
             &registry,
             &base_url,
             &commit_hash,
@@ -107,15 +332,87 @@ pub fn generate_matrix(
             &config.relation_types,
             &base_url,
             &commit_hash,
+            &labels,
         ),
         MatrixFormat::Svg => generate_svg_matrix(
             &matrix_data,
             &source_elements,
             &target_elements,
+            &labels,
+        ),
+        MatrixFormat::Table(table_format) => generate_table_matrix(
+            &matrix_data,
+            &source_elements,
+            &target_elements,
+            &labels,
+            table_format,
         ),
     }
 }
 
+/// Generates a CSV/TSV matrix with one row per source element and one column
+/// per related target element, marking direct relations with `X`
+fn generate_table_matrix(
+    matrix_data: &HashMap<String, HashSet<String>>,
+    source_elements: &[&Element],
+    target_elements: &[&Element],
+    labels: &MatrixLabels,
+    table_format: TableFormat,
+) -> String {
+    let mut relevant_targets: Vec<&Element> = target_elements
+        .iter()
+        .filter(|target| matrix_data.values().any(|targets| targets.contains(&target.identifier)))
+        .cloned()
+        .collect();
+    relevant_targets.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    let mut header = vec!["identifier", "name", "file", "section", "type", labels.status];
+    header.extend(relevant_targets.iter().map(|target| target.identifier.as_str()));
+    let mut writer = TableWriter::new(table_format, header);
+
+    // Build parent-child hierarchy for roll-up computation
+    let mut parent_to_children: HashMap<String, Vec<&Element>> = HashMap::new();
+    let parent_relation_types = crate::relation::get_parent_relation_types();
+    for source in source_elements {
+        for relation in &source.relations {
+            if parent_relation_types.contains(&relation.relation_type.name) {
+                if let LinkType::Identifier(parent_id) = &relation.target.link {
+                    parent_to_children.entry(parent_id.clone()).or_default().push(source);
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut status_cache: HashMap<String, bool> = HashMap::new();
+    let mut sorted_sources = source_elements.to_vec();
+    sorted_sources.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    for source in sorted_sources {
+        let status = compute_verification_status_with_rollup(
+            &source.identifier,
+            matrix_data,
+            &parent_to_children,
+            &mut status_cache
+        );
+        let mut row = vec![
+            source.identifier.clone(),
+            source.name.clone(),
+            source.file_path.clone(),
+            source.section.clone(),
+            source.element_type.as_str().to_string(),
+            if status { "yes" } else { "no" }.to_string(),
+        ];
+        let targets = matrix_data.get(&source.identifier);
+        for target in &relevant_targets {
+            let related = targets.is_some_and(|t| t.contains(&target.identifier));
+            row.push(if related { "X" } else { "" }.to_string());
+        }
+        writer.push_row(row);
+    }
+
+    writer.render()
+}
+
 
 fn generate_matrix_table(
     all_targets: &[&Element],
@@ -124,6 +421,7 @@ fn generate_matrix_table(
     output: &mut String,
     base_url: &str,
     commit_hash: &str,
+    labels: &MatrixLabels,
 ) {
     // If no source elements, return early
     if source_elements.is_empty() {
@@ -143,7 +441,7 @@ fn generate_matrix_table(
         .collect();
 
     // Start the table
-    output.push_str("| Requirement | Verified |");
+    output.push_str(&format!("| Requirement | {} |", labels.status));
     for target in &relevant_targets {
         let short_name = get_short_element_name(target);
         let target_url = format!("{}/blob/{}/{}", base_url, commit_hash, &target.identifier);
@@ -243,20 +541,21 @@ fn generate_markdown_matrix(
     matrix_data: &HashMap<String, HashSet<String>>,
     source_elements: &[&Element],
     target_elements: &[&Element],
-    relation_types: &[&str],
+    config: &MatrixConfig,
     registry: &GraphRegistry,
     base_url: &str,
     commit_hash: &str,
 ) -> String {
     let mut output = String::new();
+    let labels = &config.labels();
 
     // Title and intro
-    output.push_str("# Traceability Matrix\n\n");
-    output.push_str("This matrix shows relationships between requirements and verification elements, grouped by root requirements.\n\n");
+    output.push_str(&format!("# {}\n\n", labels.title));
+    output.push_str(&format!("{}\n\n", labels.intro));
 
     // Relation types
     output.push_str("## Relation Types Used\n\n");
-    for rel_type in relation_types {
+    for rel_type in &config.relation_types {
         if let Some(info) = RELATION_TYPES.get(*rel_type) {
             output.push_str(&format!("- **{}**: {}\n", rel_type, info.description));
         } else {
@@ -271,9 +570,12 @@ fn generate_markdown_matrix(
 
     // Grouping logic from old script
     let requirements_by_root = registry.get_requirements_by_root();
+    let only_requirements = source_elements
+        .iter()
+        .all(|elem| matches!(elem.element_type, ElementType::Requirement(_)));
 
-    if requirements_by_root.is_empty() {
-        output.push_str("## All Requirements\n\n");
+    if requirements_by_root.is_empty() || !only_requirements {
+        output.push_str(if only_requirements { "## All Requirements\n\n" } else { "## All Elements\n\n" });
         generate_matrix_table(
             &sorted_target_elements,
             source_elements,
@@ -281,6 +583,7 @@ fn generate_markdown_matrix(
             &mut output,
             base_url,
             commit_hash,
+            labels,
         );
     } else {
         let mut root_ids: Vec<String> = requirements_by_root.keys().cloned().collect();
@@ -303,6 +606,7 @@ fn generate_markdown_matrix(
                         &mut output,
                         base_url,
                         commit_hash,
+                        labels,
                     );
                 }
             }
@@ -312,9 +616,9 @@ fn generate_markdown_matrix(
 
     // Legend
     output.push_str("## Legend\n\n");
-    output.push_str("- ✅ (in 'Verified' column): Requirement is verified by at least one verification element\n");
-    output.push_str("- ❌ (in 'Verified' column): Requirement is not verified by any verification element\n");
-    output.push_str("- ✔️ (in element columns): Direct relationship exists between requirement and verification\n");
+    output.push_str(&format!("- ✅ (in '{}' column): {}\n", labels.status, labels.status_yes));
+    output.push_str(&format!("- ❌ (in '{}' column): {}\n", labels.status, labels.status_no));
+    output.push_str(&format!("- ✔️ (in element columns): {}\n", labels.relation));
 
     output
 }
@@ -328,6 +632,7 @@ fn generate_json_matrix(
     relation_types: &[&str],
     base_url: &str,
     commit_hash: &str,
+    labels: &MatrixLabels,
 ) -> String {
     let metadata = json!({
         "relation_types": relation_types,
@@ -379,13 +684,13 @@ fn generate_json_matrix(
         (&e.identifier, is_verified)
     }).collect::<HashMap<_, _>>();
 
-    let output = json!({
+    let mut output = json!({
         "metadata": metadata,
         "sources": sources,
         "targets": targets,
         "matrix": matrix,
-        "verificationStatus": verification_status
     });
+    output[labels.status_key] = json!(verification_status);
 
     serde_json::to_string_pretty(&output).unwrap()
 }
@@ -437,6 +742,7 @@ fn generate_svg_matrix(
     matrix_data: &HashMap<String, HashSet<String>>,
     source_elements: &[&Element],
     target_elements: &[&Element],
+    labels: &MatrixLabels,
 ) -> String {
     // Sort target elements by identifier for deterministic column ordering
     let mut sorted_target_elements = target_elements.to_vec();
@@ -461,7 +767,7 @@ fn generate_svg_matrix(
     
     // Convert character lengths to pixel widths
     let source_column_width = (source_max_width * 8 + 30).max(180);
-    let verified_column_width = 60; // Narrower column for verification status
+    let verified_column_width = (labels.status.len() as i32 * 8 - 4).max(60); // Narrower column for status
     
     // Calculate total width needed
     let _total_columns = 2 + sorted_target_elements.len() as i32; // Requirements + Verified + All targets
@@ -485,7 +791,7 @@ fn generate_svg_matrix(
     svg.push_str("</style>\n");
     
     // Add title
-    svg.push_str("<text x=\"20\" y=\"30\" class=\"header\" font-size=\"18\">Traceability Matrix</text>\n");
+    svg.push_str(&format!("<text x=\"20\" y=\"30\" class=\"header\" font-size=\"18\">{}</text>\n", labels.title));
     
     // Constants
     let cell_height = 30;
@@ -503,8 +809,8 @@ fn generate_svg_matrix(
     let verified_col_x = start_x + source_column_width;
     svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#f0f0f0\" stroke=\"#333\" />\n", 
         verified_col_x, start_y, verified_column_width, cell_height));
-    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"header\" text-anchor=\"middle\">Verified</text>\n", 
-        verified_col_x + verified_column_width/2, start_y + 20));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"header\" text-anchor=\"middle\">{}</text>\n", 
+        verified_col_x + verified_column_width/2, start_y + 20, labels.status));
     
     // Verification elements header columns
     let mut current_x = verified_col_x + verified_column_width;
@@ -627,8 +933,8 @@ fn generate_svg_matrix(
     // Add legend
     let legend_y = start_y + header_offset + source_elements.len() as i32 * cell_height + 30;
     svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"header\">Legend:</text>\n", start_x, legend_y));
-    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"cell\">✅ Verified requirement | ❌ Unverified requirement</text>\n", 
-        start_x + 80, legend_y));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" class=\"cell\">✅ {} requirement | ❌ Un{} requirement</text>\n", 
+        start_x + 80, legend_y, labels.status, labels.status.to_lowercase()));
     
     // Close SVG tag
     svg.push_str("</svg>");
@@ -771,6 +1077,78 @@ mod tests {
         assert_eq!(json_output["sources"].as_array().unwrap().len(), 2);
         assert_eq!(json_output["targets"].as_array().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_generate_satisfaction_matrix_with_file_targets() {
+        let mut registry = GraphRegistry::new();
+        let mut req = Element::new(
+            "Implemented Requirement",
+            "reqs/REQ-003",
+            "reqs/REQ-003",
+            "",
+            Some(ElementType::Requirement(RequirementType::System)),
+        );
+        req.relations.push(Relation {
+            relation_type: RELATION_TYPES.get("satisfiedBy").unwrap(),
+            target: RelationTarget {
+                text: "lib.rs".to_string(),
+                link: LinkType::InternalPath(std::path::PathBuf::from("src/lib.rs")),
+            },
+            user_created: true,
+        });
+        registry.register_element(req, "reqs/REQ-003").unwrap();
+
+        let config = MatrixConfig::satisfaction(&registry);
+        assert_eq!(config.relation_types, vec!["satisfiedBy"]);
+
+        let output = generate_matrix(&registry, &config, MatrixFormat::Json);
+        let json_output: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json_output["targets"][0]["id"], "src/lib.rs");
+        assert_eq!(json_output["satisfactionStatus"]["reqs/REQ-003"], true);
+
+        let markdown = generate_matrix(&registry, &config, MatrixFormat::Markdown);
+        assert!(markdown.contains("| Requirement | Satisfied |"));
+    }
+
+    #[test]
+    fn test_matrix_definition_to_config_with_filters() {
+        let definition = MatrixDefinition {
+            name: "System Tests".to_string(),
+            source_types: vec!["requirement".to_string()],
+            target_types: vec!["test-verification".to_string()],
+            relations: vec!["verifiedBy".to_string()],
+            filter_file: Some("specs/system/*.md".to_string()),
+            filter_section: None,
+        };
+        let config = definition.to_config(&GraphRegistry::new()).unwrap();
+        assert_eq!(definition.slug(), "system-tests");
+        assert_eq!(config.source_types, vec![ElementType::Requirement(RequirementType::System)]);
+        assert_eq!(config.relation_types, vec!["verifiedBy"]);
+
+        let included = Element::new("A", "specs/system/a.md#a", "specs/system/a.md", "", Some(ElementType::Requirement(RequirementType::System)));
+        let excluded = Element::new("B", "specs/user/b.md#b", "specs/user/b.md", "", Some(ElementType::Requirement(RequirementType::System)));
+        assert!(config.includes_source(&included));
+        assert!(!config.includes_source(&excluded));
+
+        let unknown = MatrixDefinition { relations: vec!["testedBy".to_string()], ..Default::default() };
+        assert!(unknown.to_config(&GraphRegistry::new()).is_err());
+        assert!(unknown.validate().is_err());
+    }
+
+    #[test]
+    fn test_matrix_definition_rejects_unknown_types() {
+        let mut registry = GraphRegistry::new();
+        registry.add_element(Element::new("H", "specs/h.md#h", "specs/h.md", "", Some(ElementType::Other("hazard".to_string())))).unwrap();
+
+        let custom = MatrixDefinition { source_types: vec!["Hazard".to_string()], ..Default::default() };
+        assert_eq!(custom.to_config(&registry).unwrap().source_types, vec![ElementType::Other("hazard".to_string())]);
+
+        let misspelled = MatrixDefinition { target_types: vec!["test-verfication".to_string()], ..Default::default() };
+        let error = misspelled.to_config(&registry).err().unwrap().to_string();
+        assert!(error.contains("Unknown element type 'test-verfication'"));
+        assert!(error.ends_with("file, hazard"));
+        assert!(misspelled.validate().is_ok());
+    }
 }
 
 
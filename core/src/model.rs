@@ -1,7 +1,11 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 
 use log::debug;
 use crate::graph_registry::GraphRegistry;
+use crate::context::ModelContext;
 use crate::error::ReqvireError;
 use crate::filesystem;
 
@@ -14,19 +18,40 @@ pub struct ModelManager {
     /// In-memory graph registry of elements and relations
     pub graph_registry: GraphRegistry,
 
+    /// Files whose last parse reported errors, re-parsed on every update
+    files_with_errors: BTreeSet<PathBuf>,
+
+    /// Whether relations have been built for the registered elements
+    relations_built: bool,
+
+    /// Files re-registered since relations were last rebuilt
+    pending_files: HashSet<String>,
 }
 
 impl ModelManager {
-    /// Creates a new ModelManager
+    /// Creates a new ModelManager for the model of the current directory
     pub fn new() -> Self {
+        Self::with_context(ModelContext::from_current_dir())
+    }
+
+    /// Creates a new ModelManager for the model at `context`
+    pub fn with_context(context: ModelContext) -> Self {
         // Initialize empty graph registry
-        let graph_registry = GraphRegistry::new();
+        let graph_registry = GraphRegistry::with_context(context);
 
         Self {
-            graph_registry
+            graph_registry,
+            files_with_errors: BTreeSet::new(),
+            relations_built: false,
+            pending_files: HashSet::new(),
         }
     }
 
+    /// Location of the model
+    pub fn context(&self) -> &ModelContext {
+        &self.graph_registry.context
+    }
+
 
     pub fn parse_and_validate(
         &mut self,
@@ -70,47 +95,16 @@ impl ModelManager {
     ) -> Result<Vec<ReqvireError>, ReqvireError> {
         let mut errors = Vec::new();
 
-        let files = utils::scan_markdown_files(git_commit_hash, excluded_filename_patterns);
+        let files = utils::scan_markdown_files(self.context(), git_commit_hash, excluded_filename_patterns);
         debug!("Pass 1: Found {} markdown files.", files.len());
 
-        let file_iterator = filesystem::FileReaderIterator::new(git_commit_hash, files);
+        let file_iterator = filesystem::FileReaderIterator::new(self.context().root(), git_commit_hash, files);
         for file_result in file_iterator {
             match file_result {
                 Err(e) => return Err(e),
                 Ok((path, file_name, file_content)) => {
                     debug!("Pass 1: Processing file: {}", file_name);
-
-                    let relative_path_str = utils::get_relative_path(&path)?.to_string_lossy().to_string();
-
-                    // Parse Elements, page content, and section content
-                    let (elements, parse_errors, page_content, sections) = parser::parse_elements(
-                        &file_name,
-                        &file_content,
-                        &path,
-                    );
-
-                    // Collect parse-time errors
-                    errors.extend(parse_errors);
-
-                    // Register page content
-                    self.graph_registry.register_page(relative_path_str.clone(), page_content);
-
-                    // Register section content
-                    for (section_name, section_content, section_order) in sections {
-                        self.graph_registry.register_section_with_order(
-                            relative_path_str.clone(),
-                            section_name,
-                            section_content,
-                            section_order
-                        );
-                    }
-
-                    // Register parsed elements with local validation
-                    for element in elements {
-                        if let Err(e) = self.graph_registry.register_element(element, &relative_path_str) {
-                            errors.push(e);
-                        }
-                    }
+                    errors.extend(self.register_file(&path, &file_name, &file_content)?);
                 }
             }
         }
@@ -118,12 +112,123 @@ impl ModelManager {
         Ok(errors)
     }
 
+    /// Parses a file and registers its page, sections and elements
+    fn register_file(
+        &mut self,
+        path: &Path,
+        file_name: &str,
+        file_content: &str
+    ) -> Result<Vec<ReqvireError>, ReqvireError> {
+        let mut errors = Vec::new();
+        let path = path.to_path_buf();
+        let relative_path_str = utils::get_relative_path(self.context(), &path)?.to_string_lossy().to_string();
+
+        // Parse Elements, page content, and section content
+        let (elements, parse_errors, page_content, sections) = parser::parse_elements(
+            self.context(),
+            file_name,
+            file_content,
+            &path,
+        );
+
+        // Collect parse-time errors
+        errors.extend(parse_errors);
+
+        // Register page content
+        self.graph_registry.register_page(relative_path_str.clone(), page_content);
+
+        // Register section content
+        for (section_name, section_content, section_order) in sections {
+            self.graph_registry.register_section_with_order(
+                relative_path_str.clone(),
+                section_name,
+                section_content,
+                section_order
+            );
+        }
+
+        // Register parsed elements with local validation
+        for element in elements {
+            if let Err(e) = self.graph_registry.register_element(element, &relative_path_str) {
+                errors.push(e);
+            }
+        }
+
+        if !errors.is_empty() {
+            self.files_with_errors.insert(path);
+        }
+
+        Ok(errors)
+    }
+
+    /// Re-parses changed files and patches the registry incrementally.
+    ///
+    /// Changed markdown files are removed from the registry and registered
+    /// again, and only the relations touching them are rebuilt. Other paths
+    /// only trigger re-validation since they may be relation targets. Files
+    /// that failed to parse before are re-parsed so their errors are reported
+    /// until fixed.
+    pub fn update_files(
+        &mut self,
+        paths: &[PathBuf],
+        excluded_filename_patterns: &GlobSet
+    ) -> Result<Vec<ReqvireError>, ReqvireError> {
+        let previous_error_files = std::mem::take(&mut self.files_with_errors);
+        let mut changed_files = HashSet::new();
+        let mut files = Vec::new();
+
+        for path in paths.iter().chain(previous_error_files.iter()) {
+            if path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            let Ok(relative_path) = utils::get_relative_path(self.context(), path) else {
+                continue;
+            };
+            let relative_path_str = relative_path.to_string_lossy().to_string();
+            if !changed_files.insert(relative_path_str.clone()) {
+                continue;
+            }
+
+            self.graph_registry.remove_file(&relative_path_str);
+            if path.is_file() && utils::is_requirements_file_by_path(self.context(), path, excluded_filename_patterns) {
+                files.push(path.clone());
+            }
+        }
+        debug!("Updating {} changed markdown files.", changed_files.len());
+        self.pending_files.extend(changed_files);
+
+        let mut errors = Vec::new();
+        for file_result in filesystem::FileReaderIterator::new(self.context().root(), None, files) {
+            let (path, file_name, file_content) = file_result?;
+            errors.extend(self.register_file(&path, &file_name, &file_content)?);
+        }
+
+        if !errors.is_empty() {
+            return Err(ReqvireError::ValidationError(errors));
+        }
+
+        let errors = if self.relations_built {
+            let changed_files = std::mem::take(&mut self.pending_files);
+            self.graph_registry.rebuild_relations(&changed_files, excluded_filename_patterns)?
+        } else {
+            self.pass2_build_relations(excluded_filename_patterns)?
+        };
+
+        if !errors.is_empty() {
+            return Err(ReqvireError::ValidationError(errors));
+        }
+
+        Ok(Vec::new())
+    }
+
     /// Pass 2: Build relations and validate graph structure
     fn pass2_build_relations(
         &mut self,
         excluded_filename_patterns: &GlobSet
     ) -> Result<Vec<ReqvireError>, ReqvireError> {
         debug!("Pass 2: Delegating to GraphRegistry for relation building and validation");
+        self.relations_built = true;
+        self.pending_files.clear();
         self.graph_registry.build_relations(excluded_filename_patterns)
     }
 }
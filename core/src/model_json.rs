@@ -0,0 +1,389 @@
+//! Canonical JSON model format
+//!
+//! A lossless, versioned representation of the model as data: files with
+//! their page content, sections in `section_order` and elements with
+//! metadata, content and user-created relations. Relations added by
+//! propagating opposites are not part of the format. The schema is
+//! documented in `core/schemas/reqvire-model-v1.schema.json`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::context::ModelContext;
+use crate::element::{Element, ElementType};
+use crate::error::ReqvireError;
+use crate::graph_registry::{GraphRegistry, SectionKey};
+use crate::import::{self, ImportSummary};
+use crate::relation::{self, LinkType, Relation, RelationTarget, RELATION_TYPES};
+use crate::utils;
+
+/// Value of the `format` field identifying canonical model documents
+pub const MODEL_FORMAT: &str = "reqvire-model";
+/// Current version of the canonical model format
+pub const MODEL_FORMAT_VERSION: u32 = 1;
+/// JSON Schema of the current format version
+pub const MODEL_SCHEMA: &str = include_str!("../schemas/reqvire-model-v1.schema.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDocument {
+    pub format: String,
+    pub version: u32,
+    pub files: Vec<ModelFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFile {
+    /// Path relative to the project root
+    pub path: String,
+    /// Content before the first section, including the level 1 heading if present
+    #[serde(default)]
+    pub page: String,
+    pub sections: Vec<ModelSection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelSection {
+    pub name: String,
+    pub section_order: usize,
+    #[serde(default)]
+    pub content: String,
+    pub elements: Vec<ModelElement>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelElement {
+    /// Identifier at export time; derived from file path and name on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub element_type: String,
+    #[serde(default)]
+    pub content: String,
+    /// Metadata other than the element type
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub relations: Vec<ModelRelation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    /// Element identifier
+    Element,
+    /// File path relative to the project root
+    File,
+    /// External URL
+    Url,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRelation {
+    #[serde(rename = "type")]
+    pub relation_type: String,
+    pub kind: TargetKind,
+    pub target: String,
+    /// Link text of external URLs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Builds the canonical document for all files that `format` would write
+pub fn export_model(registry: &GraphRegistry) -> ModelDocument {
+    let grouped = registry.group_elements_by_location();
+    let mut paths: Vec<&String> = grouped.keys().collect();
+    paths.sort();
+
+    let files = paths
+        .into_iter()
+        .map(|path| {
+            let element_sections = &grouped[path];
+            let mut sections: Vec<ModelSection> = registry
+                .sections
+                .iter()
+                .filter(|(key, _)| &key.file_path == path)
+                .map(|(key, section)| (key.section_name.clone(), section.section_order, section.content.clone()))
+                .chain(
+                    // Sections known only through their elements
+                    element_sections
+                        .keys()
+                        .filter(|name| !registry.sections.contains_key(&SectionKey::new(path.clone(), (*name).clone())))
+                        .map(|name| (name.clone(), usize::MAX, String::new())),
+                )
+                .map(|(name, section_order, content)| ModelSection {
+                    elements: element_sections
+                        .get(&name)
+                        .map(|elements| elements.iter().map(|element| export_element(element)).collect())
+                        .unwrap_or_default(),
+                    name,
+                    section_order,
+                    content,
+                })
+                .collect();
+            sections.sort_by(|a, b| (a.section_order, &a.name).cmp(&(b.section_order, &b.name)));
+            // Keep orders dense so documents don't carry placeholder values
+            for (index, section) in sections.iter_mut().enumerate() {
+                section.section_order = index;
+            }
+
+            ModelFile {
+                path: path.clone(),
+                page: registry
+                    .pages
+                    .get(path)
+                    .map(|page| page.frontmatter_content.clone())
+                    .unwrap_or_default(),
+                sections,
+            }
+        })
+        .collect();
+
+    ModelDocument {
+        format: MODEL_FORMAT.to_string(),
+        version: MODEL_FORMAT_VERSION,
+        files,
+    }
+}
+
+/// Canonical form of an element with the relations written in its file
+pub fn export_element(element: &Element) -> ModelElement {
+    ModelElement {
+        identifier: Some(element.identifier.clone()),
+        name: element.name.clone(),
+        element_type: element.element_type.as_str().to_string(),
+        content: element.content.clone(),
+        metadata: element
+            .metadata
+            .iter()
+            .filter(|(key, _)| key.as_str() != "type")
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        relations: element
+            .relations
+            .iter()
+            .filter(|relation| relation.user_created)
+            .map(export_relation)
+            .collect(),
+    }
+}
+
+/// Canonical form of a relation
+pub fn export_relation(relation: &Relation) -> ModelRelation {
+    let (kind, target, text) = match &relation.target.link {
+        LinkType::Identifier(identifier) => (TargetKind::Element, identifier.clone(), None),
+        LinkType::InternalPath(path) => (TargetKind::File, path.to_string_lossy().to_string(), None),
+        LinkType::ExternalUrl(url) => (TargetKind::Url, url.clone(), Some(relation.target.text.clone())),
+    };
+    ModelRelation {
+        relation_type: relation.relation_type.name.to_string(),
+        kind,
+        target,
+        text,
+    }
+}
+
+impl ModelDocument {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Parses a canonical document, rejecting unknown formats and newer versions
+    pub fn from_value(value: Value) -> Result<Self, ReqvireError> {
+        let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+        if !is_model_document(&value) || version == 0 {
+            return Err(ReqvireError::ProcessError(format!(
+                "Not a canonical model document: expected \"format\": \"{}\" and a version",
+                MODEL_FORMAT
+            )));
+        }
+        if version > MODEL_FORMAT_VERSION as u64 {
+            return Err(ReqvireError::ProcessError(format!(
+                "Model format version {} is newer than the supported version {}",
+                version, MODEL_FORMAT_VERSION
+            )));
+        }
+        serde_json::from_value(value)
+            .map_err(|e| ReqvireError::ProcessError(format!("Invalid canonical model document: {}", e)))
+    }
+}
+
+/// Returns true if the JSON value is a canonical model document rather than a list of records
+pub fn is_model_document(value: &Value) -> bool {
+    value.get("format").and_then(Value::as_str) == Some(MODEL_FORMAT)
+}
+
+/// Writes the canonical document to `output`, or to stdout when `output` is `-`
+pub fn export_model_json(registry: &GraphRegistry, output: &Path) -> Result<(), ReqvireError> {
+    let json = export_model(registry).to_json();
+    if output == Path::new("-") {
+        println!("{}", json);
+        return Ok(());
+    }
+    if let Some(parent) = output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, format!("{}\n", json))?;
+    Ok(())
+}
+
+/// Rebuilds markdown files from a canonical document.
+///
+/// File paths and element identifiers are moved below `output_dir`, which is
+/// relative to the model root unless absolute, and
+/// element identifiers are derived from file path and name, so relations
+/// follow renamed or moved elements. The model is validated together with
+/// the existing model before any file is written.
+pub fn import_model(
+    context: &ModelContext,
+    document: &ModelDocument,
+    output_dir: &Path,
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+    overwrite: bool,
+) -> Result<ImportSummary, ReqvireError> {
+    let (root, prefix) = import::resolve_output(context, output_dir)?;
+    let relocate = |path: &str| -> String { prefix.join(path).to_string_lossy().to_string() };
+
+    let file_paths: Vec<String> = document.files.iter().map(|file| relocate(&file.path)).collect();
+    import::check_file_paths(&root, &file_paths, overwrite)?;
+
+    let mut registry = import::load_existing_model(context, &file_paths, excluded_filename_patterns)?;
+    let mut elements: Vec<(Element, &ModelElement)> = Vec::new();
+    // Identifiers in the document to identifiers of the rebuilt elements
+    let mut identifiers: HashMap<String, String> = HashMap::new();
+
+    for (file, file_path) in document.files.iter().zip(&file_paths) {
+        if !file.page.trim().is_empty() {
+            registry.register_page(file_path.clone(), file.page.clone());
+        }
+        for section in &file.sections {
+            registry.register_section_with_order(
+                file_path.clone(),
+                section.name.clone(),
+                section.content.clone(),
+                section.section_order,
+            );
+            for (index, model_element) in section.elements.iter().enumerate() {
+                let identifier = format!("{}#{}", file_path, utils::normalize_fragment(&model_element.name));
+                let element_type = ElementType::from_metadata(&model_element.element_type);
+                let mut element =
+                    Element::new(&model_element.name, &identifier, file_path, &section.name, Some(element_type));
+                element.content = model_element.content.clone();
+                element.freeze_content();
+                element.section_order_index = index;
+                for (key, value) in &model_element.metadata {
+                    element.metadata.insert(key.clone(), value.clone());
+                }
+                element.metadata.insert("type".to_string(), element.element_type.as_str().to_string());
+
+                let original = model_element.identifier.clone().unwrap_or_else(|| {
+                    format!("{}#{}", file.path, utils::normalize_fragment(&model_element.name))
+                });
+                if identifiers.values().any(|existing| existing == &identifier) {
+                    return Err(ReqvireError::DuplicateElement(identifier));
+                }
+                if identifiers.insert(original.clone(), identifier).is_some() {
+                    return Err(ReqvireError::DuplicateElement(original));
+                }
+                elements.push((element, model_element));
+            }
+        }
+    }
+
+    let mut graph_relations = Vec::new();
+    let mut relation_count = 0;
+    for (element, model_element) in elements.iter_mut() {
+        for model_relation in &model_element.relations {
+            let relation_type = RELATION_TYPES.get(model_relation.relation_type.as_str()).ok_or_else(|| {
+                ReqvireError::UnsupportedRelationType(format!(
+                    "Element '{}' uses unknown relation type '{}'",
+                    element.identifier, model_relation.relation_type
+                ))
+            })?;
+            let (text, link) = match model_relation.kind {
+                TargetKind::Element => {
+                    let target = identifiers
+                        .get(&model_relation.target)
+                        .cloned()
+                        .unwrap_or_else(|| model_relation.target.clone());
+                    (target.clone(), LinkType::Identifier(target))
+                }
+                TargetKind::File => (
+                    model_relation.target.clone(),
+                    LinkType::InternalPath(PathBuf::from(&model_relation.target)),
+                ),
+                TargetKind::Url => (
+                    model_relation.text.clone().unwrap_or_else(|| model_relation.target.clone()),
+                    LinkType::ExternalUrl(model_relation.target.clone()),
+                ),
+            };
+            if let LinkType::Identifier(target) = &link {
+                if relation::IMPACT_PROPAGATION_RELATIONS.contains(&relation_type.name) {
+                    graph_relations.push((element.identifier.clone(), target.clone(), relation_type.name));
+                }
+            }
+            element.relations.push(Relation {
+                relation_type,
+                target: RelationTarget { text, link },
+                user_created: true,
+            });
+            relation_count += 1;
+        }
+    }
+
+    let element_count = elements.len();
+    for (element, _) in elements {
+        registry.add_element(element)?;
+    }
+    for (source, target, relation_type) in &graph_relations {
+        if registry.get_element(target).is_some() {
+            registry.add_relation(source, target, relation_type)?;
+        }
+    }
+
+    let errors = registry.build_relations(excluded_filename_patterns)?;
+    if !errors.is_empty() {
+        return Err(ReqvireError::ValidationError(errors));
+    }
+
+    let summary = ImportSummary {
+        dry_run,
+        files: import::summarize_files(&registry, &file_paths),
+        elements: element_count,
+        relations: relation_count,
+        skipped_relations: Vec::new(),
+    };
+    if dry_run {
+        return Ok(summary);
+    }
+
+    import::write_files(&registry, &root, &file_paths)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_value_checks_format_and_version() {
+        let document = json!({"format": MODEL_FORMAT, "version": 1, "files": []});
+        assert!(is_model_document(&document));
+        assert!(ModelDocument::from_value(document).unwrap().files.is_empty());
+
+        let records = json!([{"id": "R-1", "name": "Braking"}]);
+        assert!(!is_model_document(&records));
+        assert!(ModelDocument::from_value(records).is_err());
+
+        let newer = json!({"format": MODEL_FORMAT, "version": MODEL_FORMAT_VERSION + 1, "files": []});
+        let error = ModelDocument::from_value(newer).unwrap_err().to_string();
+        assert!(error.contains("newer than the supported version"));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::element::{Element, SubSection, ElementType, RequirementType};
 use crate::relation::Relation;
-use crate::error::ReqvireError;
+use crate::error::{ErrorLocation, ReqvireError};
 use crate::utils;
+use crate::context::ModelContext;
 use log::debug;
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -50,6 +51,7 @@ fn remove_generated_diagrams(content: &str) -> String {
 /// Parses a markdown document and extracts elements with metadata and relations.
 /// Returns: (elements, errors, page_content, sections)
 pub fn parse_elements(
+    context: &ModelContext,
     file: &str,
     content: &str,
     file_path: &PathBuf,
@@ -57,6 +59,10 @@ pub fn parse_elements(
     let mut elements = Vec::new();
     let mut current_element: Option<Element> = None;
     let mut errors = Vec::new();
+    // Errors point at the file by its path relative to the model root
+    let error_file = utils::get_relative_path(context, file_path)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| file.to_string());
     let mut seen_identifiers = HashSet::new();
     let mut skip_current_element = false;
     let mut seen_subsections = HashSet::new();
@@ -151,12 +157,13 @@ pub fn parse_elements(
                     let identifier = format!("{}#{}", file, element_name);
 
                     match utils::normalize_identifier(
+                        context,
                         &identifier,
                         &file_folder.to_path_buf()
                     ) {
                         Ok(identifier) => {
                         
-                            let relative_file = match utils::get_relative_path(&file_path) {
+                            let relative_file = match utils::get_relative_path(context, file_path) {
                                 Ok(path) => path,
                                 Err(err) => {                                   
                                     debug!("Error: {}", &err);
@@ -172,7 +179,7 @@ pub fn parse_elements(
                                     relative_file.display(),
                                     line_num + 1
                                 );
-                                errors.push(ReqvireError::DuplicateElement(msg.clone()));
+                                errors.push(ReqvireError::DuplicateElement(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                                 debug!("Error: {}", msg);
                                 skip_current_element = true;
                             } else {
@@ -206,7 +213,7 @@ pub fn parse_elements(
                                 file_path.display(),
                                 line_num + 1
                             );
-                            errors.push(ReqvireError::InvalidIdentifier(msg.clone()));
+                            errors.push(ReqvireError::InvalidIdentifier(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                             debug!("Error: {}", msg);
                             skip_current_element = true;
                         }
@@ -220,7 +227,7 @@ pub fn parse_elements(
                         file_path.display(),
                         line_num + 1
                     );
-                    errors.push(ReqvireError::InvalidIdentifier(msg.clone()));
+                    errors.push(ReqvireError::InvalidIdentifier(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                     debug!("Error: {}", msg);
                     skip_current_element = true;
                 }
@@ -234,7 +241,7 @@ pub fn parse_elements(
                 file_path.display(),
                 line_num + 1
             );
-            errors.push(ReqvireError::InvalidMarkdownStructure(msg.clone()));
+            errors.push(ReqvireError::InvalidMarkdownStructure(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
             debug!("Error: {}", msg);
 
         } else if trimmed.starts_with("#### ") && current_element.is_some() {
@@ -249,7 +256,7 @@ pub fn parse_elements(
                         file_path.display(),
                         line_num + 1
                     );
-                    errors.push(ReqvireError::DuplicateSubsection(msg.clone()));
+                    errors.push(ReqvireError::DuplicateSubsection(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                     debug!("Error: {}", msg);
                 } else {
                     seen_subsections.insert(subsection.clone());
@@ -299,7 +306,7 @@ pub fn parse_elements(
                         "Element '{}' has invalid metadata format: '{}' (file: {}, line {})",
                         element.name, trimmed, file, line_num + 1
                     );
-                    errors.push(ReqvireError::InvalidMetadataFormat(msg.clone()));
+                    errors.push(ReqvireError::InvalidMetadataFormat(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                     debug!("Error: {}", msg);
                     current_subsection = SubSection::Other("".to_string());
                 }
@@ -319,6 +326,7 @@ pub fn parse_elements(
                             match file_path.parent() {
                                 Some(file_folder) => {
                                     match utils::normalize_identifier(
+                                        context,
                                         &final_link,
                                         &file_folder.to_path_buf()
                                     ) {
@@ -332,7 +340,7 @@ pub fn parse_elements(
                                                         "'{}' in element '{}': (file: {}, line {})",
                                                         relation_type, element.name, file, line_num + 1
                                                     );
-                                                    errors.push(ReqvireError::UnsupportedRelationType(msg.clone()));
+                                                    errors.push(ReqvireError::UnsupportedRelationType(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                                                     debug!("Error: {}", msg);
                                                 }
                                             }
@@ -342,7 +350,7 @@ pub fn parse_elements(
                                                 "Failed to normalize identifier for '{}': {} (file: {}, line {})",
                                                 element.name, e, file, line_num + 1
                                             );
-                                            errors.push(ReqvireError::InvalidIdentifier(msg.clone()));
+                                            errors.push(ReqvireError::InvalidIdentifier(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                                             debug!("Error: {}", msg);
                                         }
                                     }
@@ -355,7 +363,7 @@ pub fn parse_elements(
                                         file_path.display(),
                                         line_num + 1
                                     );
-                                    errors.push(ReqvireError::InvalidIdentifier(msg.clone()));
+                                    errors.push(ReqvireError::InvalidIdentifier(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                                     debug!("Error: {}", msg);
                                 }
                             }
@@ -365,7 +373,7 @@ pub fn parse_elements(
                                 "Element '{}' has invalid relation format: '{}'. (file: {}, line {})",
                                 element.name, trimmed, file, line_num + 1
                             );
-                            errors.push(ReqvireError::UnsupportedRelationType(msg.clone()));
+                            errors.push(ReqvireError::UnsupportedRelationType(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                             debug!("Error: {}", msg);
                         }
                     }
@@ -376,7 +384,7 @@ pub fn parse_elements(
                         "Element '{}' has invalid relations format: '{}' (file: {}, line {})",
                         element.name, trimmed, file, line_num + 1
                     );
-                    errors.push(ReqvireError::InvalidRelationFormat(msg.clone()));
+                    errors.push(ReqvireError::InvalidRelationFormat(msg.clone()).at(ErrorLocation::line(&error_file, line_num + 1)));
                     debug!("Error: {}", msg);
                     current_subsection = SubSection::Other("".to_string());
                 }
@@ -0,0 +1,789 @@
+//! Batch model edits
+//!
+//! A patch is an ordered list of operations on the `GraphRegistry`. The batch
+//! is applied as a whole: every operation must succeed and the edited model
+//! must pass `build_relations` validation before any file is written.
+//! Edits are written as text patches of the affected elements, see
+//! `source_map`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::element::Element;
+use crate::error::ReqvireError;
+use crate::format::{self, FileDiff};
+use crate::graph_registry::{GraphRegistry, SectionKey};
+use crate::model_json::TargetKind;
+use crate::relation::{LinkType, Relation, RelationTarget, RELATION_TYPES};
+use crate::source_map::{self, Baseline, FilePatch};
+use crate::utils;
+
+/// Section used by `add-element` when none is given
+const DEFAULT_SECTION: &str = "Requirements";
+
+/// A single model edit. Elements are addressed by identifier, e.g.
+/// `specifications/Requirements.md#remote-start`; file paths are relative
+/// to the project root.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum PatchOperation {
+    AddElement {
+        name: String,
+        file: String,
+        #[serde(default = "default_section")]
+        section: String,
+        #[serde(rename = "type", default)]
+        element_type: Option<String>,
+        #[serde(default)]
+        content: String,
+        #[serde(default)]
+        metadata: BTreeMap<String, String>,
+    },
+    UpdateElement {
+        element: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(rename = "type", default)]
+        element_type: Option<String>,
+        #[serde(default)]
+        content: Option<String>,
+        /// Metadata to set; `null` values remove the key
+        #[serde(default)]
+        metadata: BTreeMap<String, Option<String>>,
+    },
+    RemoveElement {
+        element: String,
+    },
+    MoveElement {
+        element: String,
+        file: String,
+        section: String,
+    },
+    AddRelation {
+        source: String,
+        #[serde(rename = "type")]
+        relation_type: String,
+        target: String,
+        #[serde(default = "default_target_kind")]
+        kind: TargetKind,
+        /// Link text of external URLs
+        #[serde(default)]
+        text: Option<String>,
+    },
+    RemoveRelation {
+        source: String,
+        #[serde(rename = "type")]
+        relation_type: String,
+        target: String,
+    },
+}
+
+fn default_section() -> String {
+    DEFAULT_SECTION.to_string()
+}
+
+fn default_target_kind() -> TargetKind {
+    TargetKind::Element
+}
+
+/// Operation name and its parsed form, or the reason it could not be parsed
+#[derive(Debug, Clone)]
+pub struct PatchEntry {
+    pub op: String,
+    pub operation: Result<PatchOperation, String>,
+}
+
+/// Parses a patch: an array of operations or an object with an `operations` array
+pub fn parse_patch(document: &str) -> Result<Vec<PatchEntry>, ReqvireError> {
+    let value: Value = serde_json::from_str(document)
+        .map_err(|e| ReqvireError::ProcessError(format!("Invalid patch: {}", e)))?;
+    let operations = match value {
+        Value::Array(operations) => operations,
+        Value::Object(mut object) => match object.remove("operations") {
+            Some(Value::Array(operations)) => operations,
+            _ => {
+                return Err(ReqvireError::ProcessError(
+                    "Invalid patch: expected an array of operations or an object with an `operations` array".to_string(),
+                ))
+            }
+        },
+        _ => {
+            return Err(ReqvireError::ProcessError(
+                "Invalid patch: expected an array of operations or an object with an `operations` array".to_string(),
+            ))
+        }
+    };
+
+    Ok(operations
+        .into_iter()
+        .map(|operation| PatchEntry {
+            op: operation.get("op").and_then(Value::as_str).unwrap_or("").to_string(),
+            operation: serde_json::from_value(operation).map_err(|e| e.to_string()),
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationStatus {
+    Ok,
+    Error,
+    /// Not applied because an earlier operation failed
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationResult {
+    pub index: usize,
+    pub op: String,
+    pub status: OperationStatus,
+    /// Identifier of the edited element, or of the relation source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of applying a patch
+#[derive(Debug)]
+pub struct ApplyResult {
+    pub dry_run: bool,
+    pub operations: Vec<OperationResult>,
+    /// Validation errors of the edited model
+    pub errors: Vec<String>,
+    pub files_changed: usize,
+    pub diffs: Vec<FileDiff>,
+    /// Changed files with their new content
+    pub patches: Vec<FilePatch>,
+}
+
+impl ApplyResult {
+    /// True when every operation succeeded and the edited model is valid
+    pub fn succeeded(&self) -> bool {
+        self.errors.is_empty() && self.operations.iter().all(|result| result.status == OperationStatus::Ok)
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "dry_run": self.dry_run,
+            "applied": self.succeeded() && !self.dry_run,
+            "operations": self.operations,
+            "errors": self.errors,
+            "files_changed": self.files_changed,
+            "diffs": format::diffs_to_json(&self.diffs)
+        })
+    }
+
+    pub fn print(&self, json: bool) {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&self.to_json()).unwrap());
+            return;
+        }
+
+        println!("Operations:");
+        for result in &self.operations {
+            match result.status {
+                OperationStatus::Ok => println!(
+                    "  ✅ {}. {} {}",
+                    result.index + 1,
+                    result.op,
+                    result.element.as_deref().unwrap_or("")
+                ),
+                OperationStatus::Error => println!(
+                    "  ❌ {}. {}: {}",
+                    result.index + 1,
+                    result.op,
+                    result.error.as_deref().unwrap_or("")
+                ),
+                OperationStatus::Skipped => println!("  ⏭️ {}. {} (skipped)", result.index + 1, result.op),
+            }
+        }
+        println!();
+        self.print_changes("Run with --fix to apply these changes.");
+    }
+
+    /// Prints validation errors or the diffs of the changed files
+    fn print_changes(&self, preview_hint: &str) {
+        if !self.errors.is_empty() {
+            println!("❌ {} validation failed with error(s):", self.errors.len());
+            println!();
+            for (i, error) in self.errors.iter().enumerate() {
+                println!("  {}. {}", i + 1, error);
+                println!();
+            }
+        }
+        if !self.succeeded() {
+            println!("No files were changed.");
+            return;
+        }
+
+        if self.diffs.is_empty() {
+            println!("No file changes.");
+        } else if self.dry_run {
+            println!("Found {} file(s) with changes:\n", self.diffs.len());
+            format::render_file_diffs(&self.diffs);
+            println!("{}", preview_hint);
+        } else {
+            println!("Changed {} file(s):\n", self.files_changed);
+            format::render_file_diffs(&self.diffs);
+        }
+    }
+}
+
+/// Applies the patch to the registry and, unless `dry_run` is set, writes
+/// the changed files.
+///
+/// Operations are applied in order and the first failing operation stops
+/// the batch. Files are only written when all operations succeed and the
+/// edited model is valid.
+pub fn apply_patch(
+    registry: &mut GraphRegistry,
+    entries: &[PatchEntry],
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+) -> Result<ApplyResult, ReqvireError> {
+    let mut result = ApplyResult {
+        dry_run,
+        operations: Vec::new(),
+        errors: Vec::new(),
+        files_changed: 0,
+        diffs: Vec::new(),
+        patches: Vec::new(),
+    };
+
+    // A patch with operations that can't be parsed is not applied at all
+    if entries.iter().any(|entry| entry.operation.is_err()) {
+        result.operations = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| OperationResult {
+                index,
+                op: entry.op.clone(),
+                status: if entry.operation.is_err() { OperationStatus::Error } else { OperationStatus::Skipped },
+                element: None,
+                error: entry.operation.as_ref().err().cloned(),
+            })
+            .collect();
+        return Ok(result);
+    }
+
+    let mut baseline = Baseline::capture(registry);
+
+    // Opposite relations are propagated again by `build_relations`, so edits
+    // only need to handle relations written in the files
+    for node in registry.nodes.values_mut() {
+        node.element.relations.retain(|relation| relation.user_created);
+    }
+
+    let mut failed = false;
+    for (index, entry) in entries.iter().enumerate() {
+        let mut operation_result = OperationResult {
+            index,
+            op: entry.op.clone(),
+            status: OperationStatus::Skipped,
+            element: None,
+            error: None,
+        };
+        if !failed {
+            let operation = entry.operation.as_ref().expect("operations are parsed");
+            match apply_operation(registry, operation) {
+                Ok(element) => {
+                    match operation {
+                        PatchOperation::UpdateElement { element: original, .. }
+                        | PatchOperation::MoveElement { element: original, .. } => baseline.rename(original, &element),
+                        PatchOperation::RemoveElement { element: original } => baseline.remove(original),
+                        _ => {}
+                    }
+                    operation_result.status = OperationStatus::Ok;
+                    operation_result.element = Some(element);
+                }
+                Err(e) => {
+                    operation_result.status = OperationStatus::Error;
+                    operation_result.error = Some(e.to_string());
+                    failed = true;
+                }
+            }
+        }
+        result.operations.push(operation_result);
+    }
+    if failed {
+        return Ok(result);
+    }
+
+    result.errors = registry
+        .build_relations(excluded_filename_patterns)?
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    if !result.errors.is_empty() {
+        return Ok(result);
+    }
+
+    // File paths are relative to the model root, wherever the process runs
+    let base_dir = registry.context.root().to_path_buf();
+    let patches = source_map::patch_files(registry, &baseline, &base_dir)?;
+    result.patches = patches.into_iter().filter(|patch| patch.current != patch.patched).collect();
+    for patch in &result.patches {
+        result.diffs.push(format::generate_file_diff(&patch.file_path, &patch.current, &patch.patched));
+    }
+    result.diffs.retain(|diff| !diff.lines.is_empty());
+    result.files_changed = result.patches.len();
+
+    if !dry_run {
+        for patch in &result.patches {
+            let full_file_path = base_dir.join(&patch.file_path);
+            if let Some(parent_dir) = full_file_path.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::write(full_file_path, &patch.patched)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Outcome of moving or renaming a single element
+#[derive(Debug)]
+pub struct ElementChange {
+    pub element: String,
+    pub new_element: String,
+    /// Elements with relations to the element
+    pub impact: Vec<String>,
+    pub result: ApplyResult,
+}
+
+impl ElementChange {
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "element": self.element,
+            "new_element": self.new_element,
+            "impact": self.impact,
+            "dry_run": self.result.dry_run,
+            "applied": self.result.succeeded() && !self.result.dry_run,
+            "errors": self.result.errors,
+            "files_changed": self.result.files_changed,
+            "diffs": format::diffs_to_json(&self.result.diffs)
+        })
+    }
+
+    pub fn print(&self, json: bool) {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&self.to_json()).unwrap());
+            return;
+        }
+
+        println!("{} -> {}", self.element, self.new_element);
+        if self.impact.is_empty() {
+            println!("No elements have relations to '{}'", self.element);
+        } else {
+            println!("Elements with relations to '{}':", self.element);
+            for identifier in &self.impact {
+                println!("  - {}", identifier);
+            }
+        }
+        println!();
+        self.result.print_changes("Run without --dry-run to apply these changes.");
+    }
+}
+
+/// Moves an element to a file and section and updates all relations to it.
+///
+/// `element` is an identifier or a unique element name; `section` defaults
+/// to the element's current section.
+pub fn move_element(
+    registry: &mut GraphRegistry,
+    element: &str,
+    file: &str,
+    section: Option<&str>,
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+) -> Result<ElementChange, ReqvireError> {
+    let identifier = resolve_element(registry, element)?;
+    let section = match section {
+        Some(section) => section.to_string(),
+        None => find_element(registry, &identifier)?.section.clone(),
+    };
+    let operation = PatchOperation::MoveElement {
+        element: identifier.clone(),
+        file: file.to_string(),
+        section,
+    };
+    change_element(registry, identifier, "move-element", operation, excluded_filename_patterns, dry_run)
+}
+
+/// Renames an element and updates all relations to it.
+///
+/// `element` is an identifier or a unique element name.
+pub fn rename_element(
+    registry: &mut GraphRegistry,
+    element: &str,
+    new_name: &str,
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+) -> Result<ElementChange, ReqvireError> {
+    let identifier = resolve_element(registry, element)?;
+    let operation = PatchOperation::UpdateElement {
+        element: identifier.clone(),
+        name: Some(new_name.to_string()),
+        element_type: None,
+        content: None,
+        metadata: BTreeMap::new(),
+    };
+    change_element(registry, identifier, "update-element", operation, excluded_filename_patterns, dry_run)
+}
+
+fn change_element(
+    registry: &mut GraphRegistry,
+    identifier: String,
+    op: &str,
+    operation: PatchOperation,
+    excluded_filename_patterns: &GlobSet,
+    dry_run: bool,
+) -> Result<ElementChange, ReqvireError> {
+    let impact = registry.get_move_impact(&identifier);
+    let entries = [PatchEntry { op: op.to_string(), operation: Ok(operation) }];
+    let result = apply_patch(registry, &entries, excluded_filename_patterns, dry_run)?;
+    let outcome = &result.operations[0];
+    if let Some(error) = &outcome.error {
+        return Err(ReqvireError::ElementMoveError(error.clone()));
+    }
+    Ok(ElementChange {
+        new_element: outcome.element.clone().unwrap_or_else(|| identifier.clone()),
+        element: identifier,
+        impact,
+        result,
+    })
+}
+
+/// Resolves an element identifier, or the name of exactly one element
+pub fn resolve_element(registry: &GraphRegistry, reference: &str) -> Result<String, ReqvireError> {
+    if let Ok(element) = find_element(registry, reference) {
+        return Ok(element.identifier.clone());
+    }
+    let mut matches: Vec<&str> = registry
+        .get_all_elements()
+        .into_iter()
+        .filter(|element| element.name == reference && !element.identifier.starts_with("__virtual__"))
+        .map(|element| element.identifier.as_str())
+        .collect();
+    matches.sort();
+    match matches.as_slice() {
+        [identifier] => Ok(identifier.to_string()),
+        [] => Err(ReqvireError::MissingElement(format!("Element '{}' not found", reference))),
+        _ => Err(ReqvireError::ElementError(format!(
+            "'{}' names several elements, use an identifier: {}",
+            reference,
+            matches.join(", ")
+        ))),
+    }
+}
+
+/// Applies one operation and returns the identifier of the edited element
+fn apply_operation(registry: &mut GraphRegistry, operation: &PatchOperation) -> Result<String, ReqvireError> {
+    match operation {
+        PatchOperation::AddElement { name, file, section, element_type, content, metadata } => {
+            check_name(name)?;
+            check_file(file)?;
+            check_name(section)?;
+            check_content(content)?;
+            let identifier = element_identifier(file, name);
+            if registry.nodes.contains_key(&identifier) {
+                return Err(ReqvireError::DuplicateElement(identifier));
+            }
+
+            let element_type = registry.element_type(element_type.as_deref().unwrap_or("requirement"))?;
+            let mut element = Element::new(name, &identifier, file, section, Some(element_type));
+            element.content = content.clone();
+            element.freeze_content();
+            element.metadata.insert("type".to_string(), element.element_type.as_str().to_string());
+            for (key, value) in metadata {
+                check_metadata(key, Some(value))?;
+                element.metadata.insert(key.clone(), value.clone());
+            }
+            element.section_order_index = next_order_index(registry, file, section);
+            ensure_section(registry, file, section);
+            registry.add_element(element)?;
+            Ok(identifier)
+        }
+        PatchOperation::UpdateElement { element, name, element_type, content, metadata } => {
+            let existing = find_element(registry, element)?;
+            let file = existing.file_path.clone();
+            let element_type = element_type.as_deref().map(|name| registry.element_type(name)).transpose()?;
+            if let Some(content) = content {
+                check_content(content)?;
+            }
+            for (key, value) in metadata {
+                check_metadata(key, value.as_ref())?;
+            }
+
+            let mut identifier = element.clone();
+            if let Some(name) = name {
+                check_name(name)?;
+                identifier = element_identifier(&file, name);
+                if identifier != *element && registry.nodes.contains_key(&identifier) {
+                    return Err(ReqvireError::DuplicateElement(identifier));
+                }
+                registry.update_identifier(element, &identifier);
+            }
+
+            let node = registry.nodes.get_mut(&identifier).expect("element exists");
+            if let Some(name) = name {
+                node.element.name = name.clone();
+            }
+            if let Some(element_type) = element_type {
+                node.element.element_type = element_type;
+                node.element.metadata.insert("type".to_string(), node.element.element_type.as_str().to_string());
+            }
+            if let Some(content) = content {
+                node.element.content = content.clone();
+                node.element.freeze_content();
+            }
+            for (key, value) in metadata {
+                match value {
+                    Some(value) => node.element.metadata.insert(key.clone(), value.clone()),
+                    None => node.element.metadata.remove(key),
+                };
+            }
+            Ok(identifier)
+        }
+        PatchOperation::RemoveElement { element } => {
+            find_element(registry, element)?;
+            registry.remove_element(element)?;
+            for node in registry.nodes.values_mut() {
+                node.element.relations.retain(|relation| {
+                    !matches!(&relation.target.link, LinkType::Identifier(target) if target == element)
+                });
+            }
+            Ok(element.clone())
+        }
+        PatchOperation::MoveElement { element, file, section } => {
+            check_file(file)?;
+            check_name(section)?;
+            let name = find_element(registry, element)?.name.clone();
+            let identifier = element_identifier(file, &name);
+            if identifier != *element && registry.nodes.contains_key(&identifier) {
+                return Err(ReqvireError::DuplicateElement(identifier));
+            }
+
+            let order_index = next_order_index(registry, file, section);
+            ensure_section(registry, file, section);
+            registry.update_identifier(element, &identifier);
+            let node = registry.nodes.get_mut(&identifier).expect("element exists");
+            node.element.file_path = file.clone();
+            node.element.section = section.clone();
+            node.element.section_order_index = order_index;
+            Ok(identifier)
+        }
+        PatchOperation::AddRelation { source, relation_type, target, kind, text } => {
+            find_element(registry, source)?;
+            let relation_info = RELATION_TYPES
+                .get(relation_type.as_str())
+                .ok_or_else(|| ReqvireError::UnsupportedRelationType(relation_type.clone()))?;
+            let (text, link) = match kind {
+                TargetKind::Element => {
+                    find_element(registry, target)?;
+                    (target.clone(), LinkType::Identifier(target.clone()))
+                }
+                TargetKind::File => {
+                    check_path(target)?;
+                    (target.clone(), LinkType::InternalPath(PathBuf::from(target)))
+                }
+                TargetKind::Url => (
+                    text.clone().unwrap_or_else(|| target.clone()),
+                    LinkType::ExternalUrl(target.clone()),
+                ),
+            };
+
+            let node = registry.nodes.get_mut(source).expect("element exists");
+            if node
+                .element
+                .relations
+                .iter()
+                .any(|relation| relation.relation_type.name == relation_info.name && relation.target.link.as_str() == link.as_str())
+            {
+                return Err(ReqvireError::DuplicateRelation(format!(
+                    "'{}' already has relation '{}' to '{}'",
+                    source, relation_type, target
+                )));
+            }
+            node.element.relations.push(Relation {
+                relation_type: relation_info,
+                target: RelationTarget { text, link },
+                user_created: true,
+            });
+            Ok(source.clone())
+        }
+        PatchOperation::RemoveRelation { source, relation_type, target } => {
+            find_element(registry, source)?;
+            let node = registry.nodes.get_mut(source).expect("element exists");
+            let count = node.element.relations.len();
+            node.element.relations.retain(|relation| {
+                !(relation.relation_type.name == relation_type.as_str() && relation.target.link.as_str() == target.as_str())
+            });
+            if node.element.relations.len() == count {
+                return Err(ReqvireError::MissingRelationTarget(format!(
+                    "'{}' has no relation '{}' to '{}'",
+                    source, relation_type, target
+                )));
+            }
+            Ok(source.clone())
+        }
+    }
+}
+
+fn find_element<'a>(registry: &'a GraphRegistry, identifier: &str) -> Result<&'a Element, ReqvireError> {
+    registry
+        .get_element(identifier)
+        .filter(|element| !element.identifier.starts_with("__virtual__"))
+        .ok_or_else(|| ReqvireError::MissingElement(format!("Element '{}' not found", identifier)))
+}
+
+fn element_identifier(file: &str, name: &str) -> String {
+    format!("{}#{}", file, utils::normalize_fragment(name))
+}
+
+/// Registers the section at the end of the file if it doesn't exist yet
+fn ensure_section(registry: &mut GraphRegistry, file: &str, section: &str) {
+    let key = SectionKey::new(file.to_string(), section.to_string());
+    if registry.sections.contains_key(&key) {
+        return;
+    }
+    let section_order = registry
+        .sections
+        .iter()
+        .filter(|(key, _)| key.file_path == file)
+        .map(|(_, section)| section.section_order + 1)
+        .max()
+        .unwrap_or(0);
+    registry.register_section_with_order(file.to_string(), section.to_string(), String::new(), section_order);
+}
+
+/// Order index that places an element after the existing elements of a section
+fn next_order_index(registry: &GraphRegistry, file: &str, section: &str) -> usize {
+    registry
+        .nodes
+        .values()
+        .filter(|node| node.element.file_path == file && node.element.section == section)
+        .map(|node| node.element.section_order_index + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+fn check_name(name: &str) -> Result<(), ReqvireError> {
+    if name.trim().is_empty() || name.contains('\n') || name.trim() != name {
+        return Err(ReqvireError::InvalidMarkdownStructure(format!(
+            "'{}' is not a valid name: names must be a single non-empty line without surrounding whitespace",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn check_path(path: &str) -> Result<(), ReqvireError> {
+    let relative = Path::new(path);
+    if path.is_empty() || !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(ReqvireError::PathError(format!(
+            "'{}' must be a path relative to the project root",
+            path
+        )));
+    }
+    Ok(())
+}
+
+fn check_file(file: &str) -> Result<(), ReqvireError> {
+    check_path(file)?;
+    if !file.ends_with(".md") {
+        return Err(ReqvireError::PathError(format!("'{}' is not a markdown file", file)));
+    }
+    Ok(())
+}
+
+/// Content may use `####` subsections but nothing that starts a new element or section
+fn check_content(content: &str) -> Result<(), ReqvireError> {
+    for line in content.lines() {
+        let line = line.trim_start();
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let is_heading = (1..=3).contains(&level) && line[level..].starts_with(' ');
+        if is_heading || line.trim_end() == "---" {
+            return Err(ReqvireError::InvalidMarkdownStructure(format!(
+                "Content line '{}' would start a new element or section",
+                line
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_metadata(key: &str, value: Option<&String>) -> Result<(), ReqvireError> {
+    if key == "type" {
+        return Err(ReqvireError::InvalidMetadataFormat(
+            "Set the element type with the `type` field instead of metadata".to_string(),
+        ));
+    }
+    if key.trim().is_empty() || key.contains(':') || key.contains('\n') || value.is_some_and(|value| value.contains('\n')) {
+        return Err(ReqvireError::InvalidMetadataFormat(format!(
+            "Metadata '{}' must be a single-line key without ':' and a single-line value",
+            key
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_patch_reports_invalid_operations() {
+        let entries = parse_patch(
+            r#"{"operations": [
+                {"op": "add-element", "name": "Braking", "file": "specs/Requirements.md"},
+                {"op": "remove-relation", "source": "specs/Requirements.md#braking", "type": "derivedFrom"},
+                {"op": "rename-element", "element": "specs/Requirements.md#braking"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let ops: Vec<&str> = entries.iter().map(|entry| entry.op.as_str()).collect();
+        assert_eq!(ops, vec!["add-element", "remove-relation", "rename-element"]);
+        match &entries[0].operation {
+            Ok(PatchOperation::AddElement { section, element_type, .. }) => {
+                assert_eq!(section, DEFAULT_SECTION);
+                assert!(element_type.is_none());
+            }
+            other => panic!("unexpected operation: {:?}", other),
+        }
+        assert!(entries[1].operation.as_ref().unwrap_err().contains("missing field `target`"));
+        assert!(entries[2].operation.as_ref().unwrap_err().contains("unknown variant `rename-element`"));
+
+        assert!(parse_patch(r#"{"op": "remove-element"}"#).is_err());
+    }
+
+    #[test]
+    fn test_resolve_element_by_identifier_or_unique_name() {
+        let mut registry = GraphRegistry::new();
+        for (name, file) in [("Braking", "specs/A.md"), ("Horn", "specs/A.md"), ("Horn", "specs/B.md")] {
+            let identifier = element_identifier(file, name);
+            registry.add_element(Element::new(name, &identifier, file, DEFAULT_SECTION, None)).unwrap();
+        }
+
+        assert_eq!(resolve_element(&registry, "specs/B.md#horn").unwrap(), "specs/B.md#horn");
+        assert_eq!(resolve_element(&registry, "Braking").unwrap(), "specs/A.md#braking");
+        let error = resolve_element(&registry, "Horn").unwrap_err().to_string();
+        assert!(error.contains("specs/A.md#horn, specs/B.md#horn"));
+        assert!(resolve_element(&registry, "Siren").is_err());
+    }
+
+    #[test]
+    fn test_check_content() {
+        assert!(check_content("The pump shall stop.\n\n#### Details\n- within 2 s").is_ok());
+        assert!(check_content("### Another Element").is_err());
+        assert!(check_content("Text\n---\nMore").is_err());
+        assert!(check_content("#hashtag").is_ok());
+    }
+}
@@ -0,0 +1,84 @@
+//! Project configuration loaded from `reqvire.yaml` at the repository root
+//!
+//! Unknown keys are ignored so older configuration files keep working.
+
+use crate::error::ReqvireError;
+use crate::matrix_generator::MatrixDefinition;
+use crate::verification_rules::VerificationRule;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub const PROJECT_CONFIG_FILENAME: &str = "reqvire.yaml";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Verification method rules checked by the coverage report
+    #[serde(default)]
+    pub verification_rules: Vec<VerificationRule>,
+    /// Saved traceability matrix definitions, selectable by name
+    #[serde(default)]
+    pub matrices: Vec<MatrixDefinition>,
+    /// Options for the HTML export
+    #[serde(default)]
+    pub export: ExportConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportConfig {
+    /// Git revision range whose coverage trend chart is embedded in the coverage page
+    #[serde(default)]
+    pub coverage_history: Option<String>,
+    /// Title of the single-file document
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl ProjectConfig {
+    /// Parses and validates configuration from YAML content
+    pub fn from_yaml(content: &str) -> Result<Self, ReqvireError> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let config: ProjectConfig = serde_yml::from_str(content)
+            .map_err(|e| ReqvireError::InitializationError(format!("Invalid {}: {}", PROJECT_CONFIG_FILENAME, e)))?;
+
+        for rule in &config.verification_rules {
+            rule.validate()?;
+        }
+
+        let mut matrix_slugs = HashSet::new();
+        for matrix in &config.matrices {
+            if matrix.slug().is_empty() {
+                return Err(ReqvireError::InitializationError(
+                    "Matrix definitions must have a name".to_string()
+                ));
+            }
+            if !matrix_slugs.insert(matrix.slug()) {
+                return Err(ReqvireError::InitializationError(format!(
+                    "Duplicate matrix definition '{}'", matrix.name
+                )));
+            }
+            matrix.validate()?;
+        }
+
+        Ok(config)
+    }
+
+    /// Finds a saved matrix definition by name
+    pub fn matrix(&self, name: &str) -> Option<&MatrixDefinition> {
+        self.matrices.iter().find(|matrix| matrix.name == name)
+    }
+
+    /// Loads configuration from a file, returning the default configuration if the file does not exist
+    pub fn load(path: &Path) -> Result<Self, ReqvireError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+}
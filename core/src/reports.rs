@@ -5,6 +5,9 @@ use std::collections::HashMap;
 use crate::error::ReqvireError;
 use crate::relation;
 use globset::{Glob, GlobMatcher};
+use crate::verification_rules::{self, UnmetRule, VerificationRule};
+use crate::tabular::{self, TableFormat, TableWriter};
+use std::collections::BTreeSet;
 use regex::Regex;
 
 
@@ -197,6 +200,8 @@ pub enum SummaryOutputFormat {
     Text,
     Json,
     Cypher,
+    /// One row per element; all metadata keys are included when `metadata_keys` is empty
+    Table { format: TableFormat, metadata_keys: Vec<String> },
 }
 
 pub fn print_registry_summary(
@@ -217,9 +222,82 @@ pub fn print_registry_summary(
             let cypher_output = print_summary_cypher(&summary);
             println!("{}", cypher_output);
         }
+        SummaryOutputFormat::Table { format, metadata_keys } => {
+            print!("{}", format_summary_table(registry, filters, format, &metadata_keys));
+        }
     }
 }
 
+/// Summary of the filtered elements in the `summary --json` format
+pub fn registry_summary_json(registry: &GraphRegistry, filters: &Filters) -> serde_json::Value {
+    serde_json::to_value(build_summary(registry, filters)).expect("failed to serialize summary")
+}
+
+/// Renders filtered elements as a table with identifier, name, file, section,
+/// type, metadata and one column per relation type, in stable column order.
+/// The `type` metadata key is already covered by the type column.
+pub fn format_summary_table(
+    registry: &GraphRegistry,
+    filters: &Filters,
+    format: TableFormat,
+    metadata_keys: &[String],
+) -> String {
+    let elements: Vec<&element::Element> = registry
+        .get_all_elements()
+        .into_iter()
+        .filter(|elem| filters.matches(elem))
+        .collect();
+
+    let metadata_columns: Vec<String> = if metadata_keys.is_empty() {
+        elements
+            .iter()
+            .flat_map(|elem| elem.metadata.keys())
+            .filter(|key| key.as_str() != "type")
+            .cloned()
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    } else {
+        metadata_keys.iter().filter(|key| key.as_str() != "type").cloned().collect()
+    };
+    let relation_columns: Vec<&str> = elements
+        .iter()
+        .flat_map(|elem| elem.relations.iter().map(|r| r.relation_type.name))
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .collect();
+
+    let mut header = vec!["identifier", "name", "file", "section", "type"];
+    header.extend(metadata_columns.iter().map(|key| key.as_str()));
+    header.extend(relation_columns.iter().copied());
+    let mut writer = TableWriter::new(format, header);
+
+    for elem in elements {
+        let mut row = vec![
+            elem.identifier.clone(),
+            elem.name.clone(),
+            elem.file_path.clone(),
+            elem.section.clone(),
+            elem.element_type.as_str().to_string(),
+        ];
+        for key in &metadata_columns {
+            row.push(elem.metadata.get(key).cloned().unwrap_or_default());
+        }
+        for relation_name in &relation_columns {
+            let targets: Vec<String> = elem
+                .relations
+                .iter()
+                .filter(|r| r.relation_type.name == *relation_name)
+                .map(|r| tabular::link_text(&r.target.link))
+                .collect();
+            row.push(tabular::join_list(&targets));
+        }
+        writer.push_row(row);
+    }
+
+    writer.render()
+}
+
 
 
 fn build_summary(
@@ -506,6 +584,30 @@ fn print_summary_text(summary: &Summary) {
     println!("Requirements not satisfied: {}", c.requirements_not_satisfied);
 }
 
+/// Columns of the coverage tables; `related` lists verifications, satisfying
+/// elements or unmet rules depending on the row category
+const COVERAGE_TABLE_HEADER: [&str; 8] = ["category", "identifier", "name", "file", "section", "type", "status", "related"];
+
+/// Flattens per-file report entries ordered by file and identifier
+fn sorted_by_file<T>(
+    files: &HashMap<String, Vec<T>>,
+    identifier: impl Fn(&T) -> &String,
+) -> Vec<(&str, &T)> {
+    let mut entries: Vec<(&str, &T)> = files
+        .iter()
+        .flat_map(|(file, items)| items.iter().map(move |item| (file.as_str(), item)))
+        .collect();
+    entries.sort_by(|(file_a, a), (file_b, b)| file_a.cmp(file_b).then_with(|| identifier(a).cmp(identifier(b))));
+    entries
+}
+
+fn element_type_name(registry: &GraphRegistry, identifier: &str) -> String {
+    registry
+        .get_element(identifier)
+        .map(|elem| elem.element_type.as_str().to_string())
+        .unwrap_or_default()
+}
+
 #[derive(Serialize)]
 pub struct CoverageReport {
     summary: CoverageSummary,
@@ -514,37 +616,44 @@ pub struct CoverageReport {
     satisfied_test_verifications: VerificationsByFile,
     unsatisfied_test_verifications: VerificationsByFile,
     orphaned_verifications: VerificationsByFile,
+    unmet_verification_rules: UnmetRulesByFile,
 }
 
-#[derive(Serialize)]
-struct CoverageSummary {
+/// Headline coverage metrics, also recorded per commit by the coverage history
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageSummary {
     // Leaf requirements metrics
-    total_leaf_requirements: usize,
-    verified_leaf_requirements: usize,
-    unverified_leaf_requirements: usize,
-    leaf_requirements_coverage_percentage: f64,
+    pub total_leaf_requirements: usize,
+    pub verified_leaf_requirements: usize,
+    pub unverified_leaf_requirements: usize,
+    pub leaf_requirements_coverage_percentage: f64,
 
     // Test verifications metrics
-    total_test_verifications: usize,
-    satisfied_test_verifications: usize,
-    unsatisfied_test_verifications: usize,
-    test_verifications_satisfaction_percentage: f64,
+    pub total_test_verifications: usize,
+    pub satisfied_test_verifications: usize,
+    pub unsatisfied_test_verifications: usize,
+    pub test_verifications_satisfaction_percentage: f64,
 
     // Orphaned verifications metrics
-    total_verifications: usize,
-    orphaned_verifications: usize,
-    orphaned_verifications_percentage: f64,
+    pub total_verifications: usize,
+    pub orphaned_verifications: usize,
+    pub orphaned_verifications_percentage: f64,
 
     // Verification types breakdown
-    verification_types: VerificationTypeCounts,
+    pub verification_types: VerificationTypeCounts,
+
+    // Verification rules metrics
+    pub verification_rules: usize,
+    pub requirements_checked_by_rules: usize,
+    pub requirements_with_unmet_rules: usize,
 }
 
-#[derive(Serialize)]
-struct VerificationTypeCounts {
-    test: usize,
-    analysis: usize,
-    inspection: usize,
-    demonstration: usize,
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationTypeCounts {
+    pub test: usize,
+    pub analysis: usize,
+    pub inspection: usize,
+    pub demonstration: usize,
 }
 
 #[derive(Serialize)]
@@ -556,6 +665,11 @@ struct VerificationsByFile {
     files: HashMap<String, Vec<VerificationDetails>>,
 }
 
+#[derive(Serialize)]
+struct UnmetRulesByFile {
+    files: HashMap<String, Vec<UnmetRulesDetails>>,
+}
+
 #[derive(Serialize, Clone)]
 struct RequirementDetails {
     identifier: String,
@@ -572,6 +686,14 @@ struct VerificationDetails {
     satisfied_by: Vec<String>,
 }
 
+#[derive(Serialize, Clone)]
+struct UnmetRulesDetails {
+    identifier: String,
+    name: String,
+    section: String,
+    unmet_rules: Vec<UnmetRule>,
+}
+
 /// Helper function to format an identifier as a markdown link
 /// Splits identifier like "path/file.md#fragment" into proper link format
 fn format_identifier_link(identifier: &str) -> String {
@@ -585,6 +707,73 @@ fn format_identifier_link(identifier: &str) -> String {
 }
 
 impl CoverageReport {
+    pub fn summary(&self) -> &CoverageSummary {
+        &self.summary
+    }
+
+    /// Renders the report as a table with one row per reported element
+    pub fn format_table(&self, registry: &GraphRegistry, format: TableFormat) -> String {
+        let mut writer = TableWriter::new(format, COVERAGE_TABLE_HEADER.to_vec());
+
+        let requirement_groups = [
+            (&self.verified_leaf_requirements, "verified"),
+            (&self.unverified_leaf_requirements, "unverified"),
+        ];
+        for (requirements, status) in requirement_groups {
+            for (file, req) in sorted_by_file(&requirements.files, |r| &r.identifier) {
+                writer.push_row(vec![
+                    "leaf-requirement".to_string(),
+                    req.identifier.clone(),
+                    req.name.clone(),
+                    file.to_string(),
+                    req.section.clone(),
+                    element_type_name(registry, &req.identifier),
+                    status.to_string(),
+                    tabular::join_list(&req.verified_by),
+                ]);
+            }
+        }
+
+        let verification_groups = [
+            (&self.satisfied_test_verifications, "test-verification", "satisfied"),
+            (&self.unsatisfied_test_verifications, "test-verification", "unsatisfied"),
+            (&self.orphaned_verifications, "verification", "orphaned"),
+        ];
+        for (verifications, category, status) in verification_groups {
+            for (file, ver) in sorted_by_file(&verifications.files, |v| &v.identifier) {
+                writer.push_row(vec![
+                    category.to_string(),
+                    ver.identifier.clone(),
+                    ver.name.clone(),
+                    file.to_string(),
+                    ver.section.clone(),
+                    ver.verification_type.clone(),
+                    status.to_string(),
+                    tabular::join_list(&ver.satisfied_by),
+                ]);
+            }
+        }
+
+        for (file, req) in sorted_by_file(&self.unmet_verification_rules.files, |r| &r.identifier) {
+            let unmet: Vec<String> = req.unmet_rules
+                .iter()
+                .map(|rule| format!("{}: missing {}", rule.rule, rule.missing.join(", ")))
+                .collect();
+            writer.push_row(vec![
+                "verification-rule".to_string(),
+                req.identifier.clone(),
+                req.name.clone(),
+                file.to_string(),
+                req.section.clone(),
+                element_type_name(registry, &req.identifier),
+                "unmet".to_string(),
+                tabular::join_list(&unmet),
+            ]);
+        }
+
+        writer.render()
+    }
+
     pub fn print(&self, json_output: bool) {
         if json_output {
             println!("{}", serde_json::to_string_pretty(&self).unwrap());
@@ -632,6 +821,13 @@ impl CoverageReport {
         output.push_str(&format!("- Inspection: {}\n", self.summary.verification_types.inspection));
         output.push_str(&format!("- Demonstration: {}\n\n", self.summary.verification_types.demonstration));
 
+        if self.summary.verification_rules > 0 {
+            output.push_str("### Verification Rules\n\n");
+            output.push_str(&format!("- **Rules:** {}\n", self.summary.verification_rules));
+            output.push_str(&format!("- **Requirements Checked:** {}\n", self.summary.requirements_checked_by_rules));
+            output.push_str(&format!("- **Requirements With Unmet Rules:** {}\n\n", self.summary.requirements_with_unmet_rules));
+        }
+
         // Verified leaf requirements
         if !self.verified_leaf_requirements.files.is_empty() {
             output.push_str("## Verified Leaf Requirements\n\n");
@@ -734,11 +930,48 @@ impl CoverageReport {
             }
         }
 
+        // Requirements with unmet verification rules
+        if !self.unmet_verification_rules.files.is_empty() {
+            output.push_str("## Unmet Verification Rules\n\n");
+            let mut sorted_files: Vec<_> = self.unmet_verification_rules.files.iter().collect();
+            sorted_files.sort_by_key(|(file, _)| *file);
+
+            for (file, requirements) in sorted_files {
+                output.push_str(&format!("### [{}]({})\n\n", file, file));
+                let mut sorted_requirements = requirements.clone();
+                sorted_requirements.sort_by(|a, b| a.name.cmp(&b.name));
+
+                for requirement in sorted_requirements {
+                    output.push_str(&format!("- ❌ **[{}]({})**\n", requirement.name, requirement.identifier));
+                    for unmet in &requirement.unmet_rules {
+                        output.push_str(&format!("  - {}: missing {}\n", unmet.rule, unmet.missing.join(", ")));
+                    }
+                }
+                output.push('\n');
+            }
+        }
+
         output
     }
 }
 
-pub fn generate_coverage_report(registry: &GraphRegistry) -> CoverageReport {
+/// Checks if a requirement is a leaf requirement (no forward relations to other requirements)
+fn is_leaf_requirement(element: &element::Element) -> bool {
+    !element.relations.iter().any(|relation| {
+        // Check if relation is a forward relation to another requirement
+        match relation.relation_type.name {
+            "contain" | "derive" | "refinedBy" => {
+                // These are forward relations - check if target is a requirement
+                // Assume it's a requirement if it's an identifier link
+                // This is a simplified check - in practice you'd resolve the target
+                matches!(&relation.target.link, relation::LinkType::Identifier(_))
+            }
+            _ => false
+        }
+    })
+}
+
+pub fn generate_coverage_report(registry: &GraphRegistry, rules: &[VerificationRule]) -> CoverageReport {
     // Initialize counters and data structures
     let mut total_leaf_requirements = 0;
     let mut verified_leaf_requirements = 0;
@@ -844,22 +1077,7 @@ pub fn generate_coverage_report(registry: &GraphRegistry) -> CoverageReport {
         // Only process requirement-type elements
         if matches!(element.element_type, element::ElementType::Requirement(_)) {
             // Check if this is a leaf requirement (no forward relations to other requirements)
-            let has_forward_relations = element.relations.iter().any(|relation| {
-                // Check if relation is a forward relation to another requirement
-                match relation.relation_type.name {
-                    "contain" | "derive" | "refinedBy" => {
-                        // These are forward relations - check if target is a requirement
-                        if let relation::LinkType::Identifier(_) = &relation.target.link {
-                            // Assume it's a requirement if it's an identifier link
-                            // This is a simplified check - in practice you'd resolve the target
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    _ => false
-                }
-            });
+            let has_forward_relations = !is_leaf_requirement(element);
 
             if !has_forward_relations {
                 // This is a leaf requirement
@@ -885,19 +1103,46 @@ pub fn generate_coverage_report(registry: &GraphRegistry) -> CoverageReport {
                 if verified_by.is_empty() {
                     // Unverified leaf requirement
                     unverified_leaf_files.entry(element.file_path.clone())
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(requirement_details);
                 } else {
                     // Verified leaf requirement
                     verified_leaf_requirements += 1;
                     verified_leaf_files.entry(element.file_path.clone())
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(requirement_details);
                 }
             }
         }
     }
 
+    // Third pass: check verification method rules against all requirements
+    let mut requirements_checked_by_rules = 0;
+    let mut requirements_with_unmet_rules = 0;
+    let mut unmet_rules_files: HashMap<String, Vec<UnmetRulesDetails>> = HashMap::new();
+
+    if !rules.is_empty() {
+        for element in registry.get_all_elements() {
+            if !rules.iter().any(|rule| rule.applies_to(element)) {
+                continue;
+            }
+            requirements_checked_by_rules += 1;
+
+            let unmet_rules = verification_rules::check_element(registry, element, rules);
+            if !unmet_rules.is_empty() {
+                requirements_with_unmet_rules += 1;
+                unmet_rules_files.entry(element.file_path.clone())
+                    .or_default()
+                    .push(UnmetRulesDetails {
+                        identifier: element.identifier.clone(),
+                        name: element.name.clone(),
+                        section: element.section.clone(),
+                        unmet_rules,
+                    });
+            }
+        }
+    }
+
     // Calculate percentages
     let leaf_requirements_coverage_percentage = if total_leaf_requirements > 0 {
         (verified_leaf_requirements as f64 / total_leaf_requirements as f64) * 100.0
@@ -934,6 +1179,10 @@ pub fn generate_coverage_report(registry: &GraphRegistry) -> CoverageReport {
             orphaned_verifications_percentage,
 
             verification_types,
+
+            verification_rules: rules.len(),
+            requirements_checked_by_rules,
+            requirements_with_unmet_rules,
         },
         verified_leaf_requirements: RequirementsByFile {
             files: verified_leaf_files,
@@ -950,5 +1199,282 @@ pub fn generate_coverage_report(registry: &GraphRegistry) -> CoverageReport {
         orphaned_verifications: VerificationsByFile {
             files: orphaned_verifications_files,
         },
+        unmet_verification_rules: UnmetRulesByFile {
+            files: unmet_rules_files,
+        },
+    }
+}
+
+/// Kind of element or artifact a requirement is satisfied by
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum SatisfactionTargetKind {
+    Element,
+    File,
+    External,
+}
+
+#[derive(Serialize, Clone)]
+struct SatisfactionTarget {
+    target: String,
+    kind: SatisfactionTargetKind,
+}
+
+#[derive(Serialize, Clone)]
+struct RequirementSatisfactionDetails {
+    identifier: String,
+    name: String,
+    section: String,
+    satisfied_by: Vec<SatisfactionTarget>,
+}
+
+#[derive(Serialize)]
+struct SatisfactionByFile {
+    files: HashMap<String, Vec<RequirementSatisfactionDetails>>,
+}
+
+#[derive(Serialize, Default)]
+struct SatisfactionTargetCounts {
+    element: usize,
+    file: usize,
+    external: usize,
+}
+
+#[derive(Serialize)]
+struct SatisfactionCoverageSummary {
+    total_leaf_requirements: usize,
+    satisfied_leaf_requirements: usize,
+    unsatisfied_leaf_requirements: usize,
+    leaf_requirements_satisfaction_percentage: f64,
+
+    // Leaf requirements by kind of satisfying target
+    satisfied_by_elements: usize,
+    satisfied_by_files: usize,
+    satisfied_by_external: usize,
+
+    // Number of satisfiedBy relations by target kind
+    satisfaction_targets: SatisfactionTargetCounts,
+}
+
+#[derive(Serialize)]
+pub struct SatisfactionCoverageReport {
+    summary: SatisfactionCoverageSummary,
+    satisfied_leaf_requirements: SatisfactionByFile,
+    unsatisfied_leaf_requirements: SatisfactionByFile,
+}
+
+impl SatisfactionCoverageReport {
+    /// Renders the report as a table with one row per leaf requirement
+    pub fn format_table(&self, registry: &GraphRegistry, format: TableFormat) -> String {
+        let mut writer = TableWriter::new(format, COVERAGE_TABLE_HEADER.to_vec());
+        let groups = [
+            (&self.satisfied_leaf_requirements, "satisfied"),
+            (&self.unsatisfied_leaf_requirements, "unsatisfied"),
+        ];
+        for (requirements, status) in groups {
+            for (file, req) in sorted_by_file(&requirements.files, |r| &r.identifier) {
+                let targets: Vec<&str> = req.satisfied_by.iter().map(|t| t.target.as_str()).collect();
+                writer.push_row(vec![
+                    "leaf-requirement".to_string(),
+                    req.identifier.clone(),
+                    req.name.clone(),
+                    file.to_string(),
+                    req.section.clone(),
+                    element_type_name(registry, &req.identifier),
+                    status.to_string(),
+                    tabular::join_list(&targets),
+                ]);
+            }
+        }
+        writer.render()
+    }
+
+    pub fn print(&self, json_output: bool) {
+        if json_output {
+            println!("{}", serde_json::to_string_pretty(&self).unwrap());
+        } else {
+            print!("{}", self.format_text());
+        }
+    }
+
+    pub fn format_text(&self) -> String {
+        let mut output = String::new();
+        output.push_str("# Implementation Coverage Report\n\n");
+
+        // Summary
+        output.push_str("## Summary\n\n");
+
+        output.push_str("### Leaf Requirements\n\n");
+        output.push_str(&format!("- **Total Leaf Requirements:** {}\n", self.summary.total_leaf_requirements));
+        output.push_str(&format!("- **Satisfied Leaf Requirements:** {} ({:.1}%)\n",
+            self.summary.satisfied_leaf_requirements,
+            self.summary.leaf_requirements_satisfaction_percentage
+        ));
+        output.push_str(&format!("- **Unsatisfied Leaf Requirements:** {}\n\n", self.summary.unsatisfied_leaf_requirements));
+
+        output.push_str("### Satisfied By\n\n");
+        output.push_str(&format!("- Elements: {} requirement(s), {} relation(s)\n",
+            self.summary.satisfied_by_elements,
+            self.summary.satisfaction_targets.element
+        ));
+        output.push_str(&format!("- Files: {} requirement(s), {} relation(s)\n",
+            self.summary.satisfied_by_files,
+            self.summary.satisfaction_targets.file
+        ));
+        output.push_str(&format!("- External: {} requirement(s), {} relation(s)\n\n",
+            self.summary.satisfied_by_external,
+            self.summary.satisfaction_targets.external
+        ));
+
+        // Satisfied leaf requirements
+        if !self.satisfied_leaf_requirements.files.is_empty() {
+            output.push_str("## Satisfied Leaf Requirements\n\n");
+            let mut sorted_files: Vec<_> = self.satisfied_leaf_requirements.files.iter().collect();
+            sorted_files.sort_by_key(|(file, _)| *file);
+
+            for (file, requirements) in sorted_files {
+                output.push_str(&format!("### [{}]({})\n\n", file, file));
+                let mut sorted_requirements = requirements.clone();
+                sorted_requirements.sort_by(|a, b| a.name.cmp(&b.name));
+
+                for requirement in sorted_requirements {
+                    output.push_str(&format!("- ✅ **[{}]({})**\n", requirement.name, requirement.identifier));
+                    for (kind, label) in [
+                        (SatisfactionTargetKind::Element, "Satisfied by elements"),
+                        (SatisfactionTargetKind::File, "Satisfied by files"),
+                        (SatisfactionTargetKind::External, "Satisfied by external links"),
+                    ] {
+                        let targets: Vec<&SatisfactionTarget> = requirement.satisfied_by.iter()
+                            .filter(|t| t.kind == kind)
+                            .collect();
+                        if targets.is_empty() {
+                            continue;
+                        }
+                        output.push_str(&format!("  - {}:\n", label));
+                        for target in targets {
+                            output.push_str(&format!("    - {}\n", format_identifier_link(&target.target)));
+                        }
+                    }
+                }
+                output.push('\n');
+            }
+        }
+
+        // Unsatisfied leaf requirements
+        if !self.unsatisfied_leaf_requirements.files.is_empty() {
+            output.push_str("## Unsatisfied Leaf Requirements\n\n");
+            let mut sorted_files: Vec<_> = self.unsatisfied_leaf_requirements.files.iter().collect();
+            sorted_files.sort_by_key(|(file, _)| *file);
+
+            for (file, requirements) in sorted_files {
+                output.push_str(&format!("### [{}]({})\n\n", file, file));
+                let mut sorted_requirements = requirements.clone();
+                sorted_requirements.sort_by(|a, b| a.name.cmp(&b.name));
+
+                for requirement in sorted_requirements {
+                    output.push_str(&format!("- ❌ **[{}]({})**\n", requirement.name, requirement.identifier));
+                }
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+/// Generates the implementation coverage report listing leaf requirements with and
+/// without `satisfiedBy` relations, broken down by the kind of satisfying target
+pub fn generate_satisfaction_coverage_report(registry: &GraphRegistry) -> SatisfactionCoverageReport {
+    let mut total_leaf_requirements = 0;
+    let mut satisfied_leaf_requirements = 0;
+    let mut satisfied_by_elements = 0;
+    let mut satisfied_by_files = 0;
+    let mut satisfied_by_external = 0;
+    let mut satisfaction_targets = SatisfactionTargetCounts::default();
+
+    let mut satisfied_files: HashMap<String, Vec<RequirementSatisfactionDetails>> = HashMap::new();
+    let mut unsatisfied_files: HashMap<String, Vec<RequirementSatisfactionDetails>> = HashMap::new();
+
+    for element in registry.get_all_elements() {
+        if !matches!(element.element_type, element::ElementType::Requirement(_)) || !is_leaf_requirement(element) {
+            continue;
+        }
+        total_leaf_requirements += 1;
+
+        let satisfied_by: Vec<SatisfactionTarget> = element.relations.iter()
+            .filter(|r| r.relation_type.name == "satisfiedBy")
+            .map(|r| match &r.target.link {
+                relation::LinkType::Identifier(id) => SatisfactionTarget {
+                    target: id.clone(),
+                    kind: SatisfactionTargetKind::Element,
+                },
+                relation::LinkType::InternalPath(path) => SatisfactionTarget {
+                    target: path.to_string_lossy().to_string(),
+                    kind: SatisfactionTargetKind::File,
+                },
+                relation::LinkType::ExternalUrl(url) => SatisfactionTarget {
+                    target: url.clone(),
+                    kind: SatisfactionTargetKind::External,
+                },
+            })
+            .collect();
+
+        for target in &satisfied_by {
+            match target.kind {
+                SatisfactionTargetKind::Element => satisfaction_targets.element += 1,
+                SatisfactionTargetKind::File => satisfaction_targets.file += 1,
+                SatisfactionTargetKind::External => satisfaction_targets.external += 1,
+            }
+        }
+        let has_kind = |kind: SatisfactionTargetKind| satisfied_by.iter().any(|t| t.kind == kind);
+        if has_kind(SatisfactionTargetKind::Element) {
+            satisfied_by_elements += 1;
+        }
+        if has_kind(SatisfactionTargetKind::File) {
+            satisfied_by_files += 1;
+        }
+        if has_kind(SatisfactionTargetKind::External) {
+            satisfied_by_external += 1;
+        }
+
+        let is_satisfied = !satisfied_by.is_empty();
+        let details = RequirementSatisfactionDetails {
+            identifier: element.identifier.clone(),
+            name: element.name.clone(),
+            section: element.section.clone(),
+            satisfied_by,
+        };
+
+        if is_satisfied {
+            satisfied_leaf_requirements += 1;
+            satisfied_files.entry(element.file_path.clone()).or_default().push(details);
+        } else {
+            unsatisfied_files.entry(element.file_path.clone()).or_default().push(details);
+        }
+    }
+
+    let leaf_requirements_satisfaction_percentage = if total_leaf_requirements > 0 {
+        (satisfied_leaf_requirements as f64 / total_leaf_requirements as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    SatisfactionCoverageReport {
+        summary: SatisfactionCoverageSummary {
+            total_leaf_requirements,
+            satisfied_leaf_requirements,
+            unsatisfied_leaf_requirements: total_leaf_requirements - satisfied_leaf_requirements,
+            leaf_requirements_satisfaction_percentage,
+            satisfied_by_elements,
+            satisfied_by_files,
+            satisfied_by_external,
+            satisfaction_targets,
+        },
+        satisfied_leaf_requirements: SatisfactionByFile {
+            files: satisfied_files,
+        },
+        unsatisfied_leaf_requirements: SatisfactionByFile {
+            files: unsatisfied_files,
+        },
     }
 }
@@ -0,0 +1,538 @@
+//! ReqIF (OMG Requirements Interchange Format) export
+//!
+//! Elements become SPEC-OBJECTs, user-authored relations become SPEC-RELATIONs
+//! typed after `RELATION_TYPES`, and every model file becomes a SPECIFICATION
+//! whose sections are chapter objects holding the section's elements.
+//! Identifiers are derived from reqvire identifiers and every collection is
+//! sorted, so the same model always produces byte-identical output.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+use crate::element::Element;
+use crate::error::ReqvireError;
+use crate::git_commands;
+use crate::graph_registry::{GraphRegistry, SectionKey};
+use crate::relation::{LinkType, RELATION_TYPES};
+
+const REQIF_NAMESPACE: &str = "http://www.omg.org/spec/ReqIF/20110401/reqif.xsd";
+const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+const TITLE: &str = "Reqvire model";
+/// Used as creation and change time when the model is not inside a git repository
+const FALLBACK_TIMESTAMP: &str = "1970-01-01T00:00:00Z";
+const STRING_MAX_LENGTH: usize = 65535;
+
+// Identifiers of the fixed datatypes, spec types and attribute definitions
+const DATATYPE_STRING: &str = "datatype-string";
+const DATATYPE_XHTML: &str = "datatype-xhtml";
+const ELEMENT_OBJECT_TYPE: &str = "spec-object-type-element";
+const SECTION_OBJECT_TYPE: &str = "spec-object-type-section";
+const SPECIFICATION_TYPE: &str = "specification-type-file";
+const ATTRIBUTE_IDENTIFIER: &str = "attribute-element-identifier";
+const ATTRIBUTE_NAME: &str = "attribute-element-name";
+const ATTRIBUTE_TEXT: &str = "attribute-element-text";
+const ATTRIBUTE_TYPE: &str = "attribute-element-type";
+const ATTRIBUTE_CHAPTER_NAME: &str = "attribute-section-name";
+const ATTRIBUTE_CHAPTER_TEXT: &str = "attribute-section-text";
+
+/// Long names of the element attribute definitions, following the ReqIF
+/// Implementation Guide where a standard name exists
+pub const FOREIGN_ID_ATTRIBUTE: &str = "ReqIF.ForeignID";
+pub const NAME_ATTRIBUTE: &str = "ReqIF.Name";
+pub const TEXT_ATTRIBUTE: &str = "ReqIF.Text";
+pub const TYPE_ATTRIBUTE: &str = "Type";
+pub const CHAPTER_NAME_ATTRIBUTE: &str = "ReqIF.ChapterName";
+
+/// Hands out unique XML identifiers derived from model keys
+#[derive(Default)]
+struct IdAllocator {
+    used: HashSet<String>,
+}
+
+impl IdAllocator {
+    fn allocate(&mut self, prefix: &str, key: &str) -> String {
+        let base = format!("{}-{}", prefix, sanitize_identifier(key));
+        let mut candidate = base.clone();
+        let mut suffix = 2;
+        while !self.used.insert(candidate.clone()) {
+            candidate = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        candidate
+    }
+}
+
+/// Replaces characters that are not allowed in an `xsd:ID`
+fn sanitize_identifier(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+struct SpecObject {
+    id: String,
+    object_type: &'static str,
+    strings: Vec<(String, String)>,
+    xhtml: Vec<(&'static str, String)>,
+}
+
+struct SpecRelation {
+    id: String,
+    relation_type: &'static str,
+    source: String,
+    target: String,
+}
+
+/// Writes the model as ReqIF. A `.reqifz` output is a zip archive holding a
+/// single `.reqif` document; a `.reqif` output is the plain XML document.
+pub fn export_reqif(registry: &GraphRegistry, output: &Path) -> Result<(), ReqvireError> {
+    let creation_time = git_commands::get_commit_date(registry.context.root(), "HEAD").unwrap_or_else(|_| FALLBACK_TIMESTAMP.to_string());
+    let document = generate_reqif(registry, &creation_time);
+
+    if let Some(parent) = output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let extension = output.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        "reqifz" => write_reqifz(output, &document),
+        "reqif" => {
+            fs::write(output, document)?;
+            Ok(())
+        }
+        _ => Err(ReqvireError::ProcessError(format!(
+            "ReqIF output '{}' must end with .reqifz or .reqif",
+            output.display()
+        ))),
+    }
+}
+
+fn write_reqifz(output: &Path, document: &str) -> Result<(), ReqvireError> {
+    let entry_name = format!(
+        "{}.reqif",
+        output.file_stem().and_then(|s| s.to_str()).unwrap_or("model")
+    );
+    // A fixed timestamp and mode keep the archive identical across exports
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+
+    let zip_error = |e: zip::result::ZipError| ReqvireError::ProcessError(format!("Failed to write {}: {}", output.display(), e));
+    let mut archive = ZipWriter::new(fs::File::create(output)?);
+    archive.start_file(entry_name, options).map_err(zip_error)?;
+    archive.write_all(document.as_bytes())?;
+    archive.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// Renders the ReqIF XML document. `timestamp` is used for the header
+/// creation time and every LAST-CHANGE attribute.
+pub fn generate_reqif(registry: &GraphRegistry, timestamp: &str) -> String {
+    let mut ids = IdAllocator::default();
+
+    let mut elements: Vec<&Element> = registry
+        .get_all_elements()
+        .into_iter()
+        .filter(|element| !element.identifier.starts_with("__virtual__"))
+        .collect();
+    elements.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    let metadata_keys: BTreeSet<&str> = elements
+        .iter()
+        .flat_map(|element| element.metadata.keys())
+        .map(String::as_str)
+        .filter(|key| !key.eq_ignore_ascii_case("type"))
+        .collect();
+    let metadata_attributes: BTreeMap<&str, String> = metadata_keys
+        .iter()
+        .map(|key| (*key, ids.allocate("attribute-element-metadata", key)))
+        .collect();
+
+    let mut objects = Vec::new();
+    let mut object_ids: HashMap<&str, String> = HashMap::new();
+    for element in &elements {
+        let id = ids.allocate("element", &element.identifier);
+        let mut strings = vec![
+            (ATTRIBUTE_IDENTIFIER.to_string(), element.identifier.clone()),
+            (ATTRIBUTE_NAME.to_string(), element.name.clone()),
+            (ATTRIBUTE_TYPE.to_string(), element.element_type.as_str().to_string()),
+        ];
+        for (key, attribute) in &metadata_attributes {
+            if let Some(value) = element.metadata.get(*key) {
+                strings.push((attribute.clone(), value.clone()));
+            }
+        }
+        object_ids.insert(element.identifier.as_str(), id.clone());
+        objects.push(SpecObject {
+            id,
+            object_type: ELEMENT_OBJECT_TYPE,
+            strings,
+            xhtml: vec![(ATTRIBUTE_TEXT, element.content.clone())],
+        });
+    }
+
+    // Relations to files become relations to file objects so that
+    // implementation links survive the exchange
+    let mut relations = Vec::new();
+    let mut file_objects: BTreeMap<String, String> = BTreeMap::new();
+    for element in &elements {
+        let mut element_relations: Vec<_> = element.relations.iter().filter(|r| r.user_created).collect();
+        element_relations.sort();
+        for relation in element_relations {
+            let target = match &relation.target.link {
+                LinkType::Identifier(id) => match object_ids.get(id.as_str()) {
+                    Some(object_id) => object_id.clone(),
+                    None => continue,
+                },
+                LinkType::InternalPath(path) => {
+                    let path = path.to_string_lossy().to_string();
+                    file_objects
+                        .entry(path.clone())
+                        .or_insert_with(|| ids.allocate("file", &path))
+                        .clone()
+                }
+                LinkType::ExternalUrl(_) => continue,
+            };
+            relations.push(SpecRelation {
+                id: ids.allocate(
+                    "relation",
+                    &format!("{}-{}-{}", element.identifier, relation.relation_type.name, relation.target.link.as_str()),
+                ),
+                relation_type: relation.relation_type.name,
+                source: object_ids[element.identifier.as_str()].clone(),
+                target,
+            });
+        }
+    }
+    for (path, id) in &file_objects {
+        objects.push(SpecObject {
+            id: id.clone(),
+            object_type: ELEMENT_OBJECT_TYPE,
+            strings: vec![
+                (ATTRIBUTE_IDENTIFIER.to_string(), path.clone()),
+                (ATTRIBUTE_NAME.to_string(), path.clone()),
+                (ATTRIBUTE_TYPE.to_string(), "file".to_string()),
+            ],
+            xhtml: vec![],
+        });
+    }
+
+    // One specification per file with a chapter per section
+    let locations = registry.group_elements_by_location();
+    let mut files: BTreeSet<&str> = locations.keys().map(String::as_str).collect();
+    files.extend(registry.sections.keys().map(|key| key.file_path.as_str()));
+
+    let mut specifications = String::new();
+    for file in files {
+        let mut sections: Vec<(usize, &str)> = registry
+            .sections
+            .iter()
+            .filter(|(key, _)| key.file_path == file)
+            .map(|(key, section)| (section.section_order, key.section_name.as_str()))
+            .collect();
+        if let Some(file_sections) = locations.get(file) {
+            for name in file_sections.keys() {
+                if !sections.iter().any(|(_, existing)| existing == name) {
+                    sections.push((usize::MAX, name.as_str()));
+                }
+            }
+        }
+        sections.sort();
+
+        let mut chapters = String::new();
+        for (_, section_name) in sections {
+            let key = SectionKey::new(file.to_string(), section_name.to_string());
+            let section_id = ids.allocate("section", &format!("{}#{}", file, section_name));
+            let section_content = registry.sections.get(&key).map(|s| s.content.clone()).unwrap_or_default();
+            objects.push(SpecObject {
+                id: section_id.clone(),
+                object_type: SECTION_OBJECT_TYPE,
+                strings: vec![(ATTRIBUTE_CHAPTER_NAME.to_string(), section_name.to_string())],
+                xhtml: vec![(ATTRIBUTE_CHAPTER_TEXT, section_content)],
+            });
+
+            let mut children = String::new();
+            let section_elements = locations.get(file).and_then(|s| s.get(section_name));
+            for element in section_elements.into_iter().flatten() {
+                let hierarchy_id = ids.allocate("hierarchy", &element.identifier);
+                children.push_str(&spec_hierarchy(&hierarchy_id, &object_ids[element.identifier.as_str()], "", timestamp, 16));
+            }
+            let hierarchy_id = ids.allocate("hierarchy", &format!("{}#{}", file, section_name));
+            chapters.push_str(&spec_hierarchy(&hierarchy_id, &section_id, &children, timestamp, 12));
+        }
+
+        let specification_id = ids.allocate("specification", file);
+        specifications.push_str(&format!(
+            "        <SPECIFICATION IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"{}\">\n",
+            specification_id,
+            timestamp,
+            escape_xml(file)
+        ));
+        specifications.push_str(&format!(
+            "          <TYPE>\n            <SPECIFICATION-TYPE-REF>{}</SPECIFICATION-TYPE-REF>\n          </TYPE>\n",
+            SPECIFICATION_TYPE
+        ));
+        if !chapters.is_empty() {
+            specifications.push_str("          <CHILDREN>\n");
+            specifications.push_str(&chapters);
+            specifications.push_str("          </CHILDREN>\n");
+        }
+        specifications.push_str("        </SPECIFICATION>\n");
+    }
+
+    objects.sort_by(|a, b| a.id.cmp(&b.id));
+    relations.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<REQ-IF xmlns=\"{}\" xmlns:xhtml=\"{}\">\n", REQIF_NAMESPACE, XHTML_NAMESPACE));
+    xml.push_str("  <THE-HEADER>\n");
+    xml.push_str("    <REQ-IF-HEADER IDENTIFIER=\"header\">\n");
+    xml.push_str(&format!("      <CREATION-TIME>{}</CREATION-TIME>\n", timestamp));
+    xml.push_str("      <REQ-IF-TOOL-ID>reqvire</REQ-IF-TOOL-ID>\n");
+    xml.push_str("      <REQ-IF-VERSION>1.0</REQ-IF-VERSION>\n");
+    xml.push_str(&format!("      <SOURCE-TOOL-ID>reqvire {}</SOURCE-TOOL-ID>\n", env!("CARGO_PKG_VERSION")));
+    xml.push_str(&format!("      <TITLE>{}</TITLE>\n", TITLE));
+    xml.push_str("    </REQ-IF-HEADER>\n");
+    xml.push_str("  </THE-HEADER>\n");
+    xml.push_str("  <CORE-CONTENT>\n");
+    xml.push_str("    <REQ-IF-CONTENT>\n");
+
+    // Datatypes
+    xml.push_str("      <DATATYPES>\n");
+    xml.push_str(&format!(
+        "        <DATATYPE-DEFINITION-STRING IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"String\" MAX-LENGTH=\"{}\"/>\n",
+        DATATYPE_STRING, timestamp, STRING_MAX_LENGTH
+    ));
+    xml.push_str(&format!(
+        "        <DATATYPE-DEFINITION-XHTML IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"XHTML\"/>\n",
+        DATATYPE_XHTML, timestamp
+    ));
+    xml.push_str("      </DATATYPES>\n");
+
+    // Spec types
+    let mut element_attributes = vec![
+        (ATTRIBUTE_IDENTIFIER.to_string(), FOREIGN_ID_ATTRIBUTE, false),
+        (ATTRIBUTE_NAME.to_string(), NAME_ATTRIBUTE, false),
+        (ATTRIBUTE_TEXT.to_string(), TEXT_ATTRIBUTE, true),
+        (ATTRIBUTE_TYPE.to_string(), TYPE_ATTRIBUTE, false),
+    ];
+    for (key, attribute) in &metadata_attributes {
+        element_attributes.push((attribute.clone(), *key, false));
+    }
+    let section_attributes = vec![
+        (ATTRIBUTE_CHAPTER_NAME.to_string(), CHAPTER_NAME_ATTRIBUTE, false),
+        (ATTRIBUTE_CHAPTER_TEXT.to_string(), TEXT_ATTRIBUTE, true),
+    ];
+    xml.push_str("      <SPEC-TYPES>\n");
+    xml.push_str(&spec_object_type(ELEMENT_OBJECT_TYPE, "Reqvire Element", &element_attributes, timestamp));
+    xml.push_str(&spec_object_type(SECTION_OBJECT_TYPE, "Reqvire Section", &section_attributes, timestamp));
+    let mut relation_types: Vec<_> = RELATION_TYPES.values().collect();
+    relation_types.sort_by_key(|info| info.name);
+    for info in relation_types {
+        xml.push_str(&format!(
+            "        <SPEC-RELATION-TYPE IDENTIFIER=\"spec-relation-type-{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"{}\" DESC=\"{}\"/>\n",
+            info.name,
+            timestamp,
+            info.name,
+            escape_xml(info.description)
+        ));
+    }
+    xml.push_str(&format!(
+        "        <SPECIFICATION-TYPE IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"Reqvire File\"/>\n",
+        SPECIFICATION_TYPE, timestamp
+    ));
+    xml.push_str("      </SPEC-TYPES>\n");
+
+    // Spec objects
+    xml.push_str("      <SPEC-OBJECTS>\n");
+    for object in &objects {
+        xml.push_str(&format!(
+            "        <SPEC-OBJECT IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\">\n",
+            object.id, timestamp
+        ));
+        xml.push_str("          <VALUES>\n");
+        for (definition, value) in &object.strings {
+            xml.push_str(&format!(
+                "            <ATTRIBUTE-VALUE-STRING THE-VALUE=\"{}\">\n              <DEFINITION>\n                <ATTRIBUTE-DEFINITION-STRING-REF>{}</ATTRIBUTE-DEFINITION-STRING-REF>\n              </DEFINITION>\n            </ATTRIBUTE-VALUE-STRING>\n",
+                escape_xml(value),
+                definition
+            ));
+        }
+        for (definition, markdown) in &object.xhtml {
+            xml.push_str(&format!(
+                "            <ATTRIBUTE-VALUE-XHTML>\n              <DEFINITION>\n                <ATTRIBUTE-DEFINITION-XHTML-REF>{}</ATTRIBUTE-DEFINITION-XHTML-REF>\n              </DEFINITION>\n              <THE-VALUE>{}</THE-VALUE>\n            </ATTRIBUTE-VALUE-XHTML>\n",
+                definition,
+                markdown_to_xhtml(markdown)
+            ));
+        }
+        xml.push_str("          </VALUES>\n");
+        xml.push_str(&format!(
+            "          <TYPE>\n            <SPEC-OBJECT-TYPE-REF>{}</SPEC-OBJECT-TYPE-REF>\n          </TYPE>\n",
+            object.object_type
+        ));
+        xml.push_str("        </SPEC-OBJECT>\n");
+    }
+    xml.push_str("      </SPEC-OBJECTS>\n");
+
+    // Spec relations
+    xml.push_str("      <SPEC-RELATIONS>\n");
+    for relation in &relations {
+        xml.push_str(&format!(
+            "        <SPEC-RELATION IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\">\n          <TARGET>\n            <SPEC-OBJECT-REF>{}</SPEC-OBJECT-REF>\n          </TARGET>\n          <SOURCE>\n            <SPEC-OBJECT-REF>{}</SPEC-OBJECT-REF>\n          </SOURCE>\n          <TYPE>\n            <SPEC-RELATION-TYPE-REF>spec-relation-type-{}</SPEC-RELATION-TYPE-REF>\n          </TYPE>\n        </SPEC-RELATION>\n",
+            relation.id, timestamp, relation.target, relation.source, relation.relation_type
+        ));
+    }
+    xml.push_str("      </SPEC-RELATIONS>\n");
+
+    xml.push_str("      <SPECIFICATIONS>\n");
+    xml.push_str(&specifications);
+    xml.push_str("      </SPECIFICATIONS>\n");
+    xml.push_str("    </REQ-IF-CONTENT>\n");
+    xml.push_str("  </CORE-CONTENT>\n");
+    xml.push_str("</REQ-IF>\n");
+    xml
+}
+
+/// Renders a SPEC-OBJECT-TYPE whose attributes are `(identifier, long name, is_xhtml)`
+fn spec_object_type(id: &str, long_name: &str, attributes: &[(String, &str, bool)], timestamp: &str) -> String {
+    let mut xml = format!(
+        "        <SPEC-OBJECT-TYPE IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"{}\">\n          <SPEC-ATTRIBUTES>\n",
+        id, timestamp, long_name
+    );
+    for (attribute_id, attribute_name, is_xhtml) in attributes {
+        let (kind, datatype) = if *is_xhtml { ("XHTML", DATATYPE_XHTML) } else { ("STRING", DATATYPE_STRING) };
+        xml.push_str(&format!(
+            "            <ATTRIBUTE-DEFINITION-{kind} IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\" LONG-NAME=\"{}\">\n              <TYPE>\n                <DATATYPE-DEFINITION-{kind}-REF>{}</DATATYPE-DEFINITION-{kind}-REF>\n              </TYPE>\n            </ATTRIBUTE-DEFINITION-{kind}>\n",
+            attribute_id,
+            timestamp,
+            escape_xml(attribute_name),
+            datatype,
+            kind = kind
+        ));
+    }
+    xml.push_str("          </SPEC-ATTRIBUTES>\n        </SPEC-OBJECT-TYPE>\n");
+    xml
+}
+
+fn spec_hierarchy(id: &str, object_id: &str, children: &str, timestamp: &str, indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    let mut xml = format!("{}<SPEC-HIERARCHY IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\">\n", pad, id, timestamp);
+    if !children.is_empty() {
+        xml.push_str(&format!("{}  <CHILDREN>\n{}{}  </CHILDREN>\n", pad, children, pad));
+    }
+    xml.push_str(&format!(
+        "{pad}  <OBJECT>\n{pad}    <SPEC-OBJECT-REF>{}</SPEC-OBJECT-REF>\n{pad}  </OBJECT>\n{pad}</SPEC-HIERARCHY>\n",
+        object_id,
+        pad = pad
+    ));
+    xml
+}
+
+/// Renders markdown as the XHTML subset carried by ReqIF attribute values.
+/// Raw HTML in the markdown is kept as text.
+pub fn markdown_to_xhtml(markdown: &str) -> String {
+    let mut xhtml = String::from("<xhtml:div>");
+    let mut in_table_head = false;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(tag) => {
+                if matches!(tag, Tag::TableHead) {
+                    in_table_head = true;
+                }
+                let (name, attributes) = xhtml_tag(&tag, in_table_head);
+                xhtml.push_str(&format!("<xhtml:{}{}>", name, attributes));
+            }
+            Event::End(tag) => {
+                let (name, _) = xhtml_tag(&tag, in_table_head);
+                xhtml.push_str(&format!("</xhtml:{}>", name));
+                if matches!(tag, Tag::TableHead) {
+                    in_table_head = false;
+                }
+            }
+            Event::Text(text) | Event::Html(text) => xhtml.push_str(&escape_xml(&text)),
+            Event::Code(code) => xhtml.push_str(&format!("<xhtml:code>{}</xhtml:code>", escape_xml(&code))),
+            Event::FootnoteReference(name) => xhtml.push_str(&format!("[{}]", escape_xml(&name))),
+            Event::SoftBreak => xhtml.push('\n'),
+            Event::HardBreak => xhtml.push_str("<xhtml:br/>"),
+            Event::Rule => xhtml.push_str("<xhtml:hr/>"),
+            Event::TaskListMarker(checked) => xhtml.push_str(if checked { "[x] " } else { "[ ] " }),
+        }
+    }
+
+    xhtml.push_str("</xhtml:div>");
+    xhtml
+}
+
+/// Maps a markdown tag to an XHTML element name and its attributes
+fn xhtml_tag(tag: &Tag, in_table_head: bool) -> (String, String) {
+    match tag {
+        Tag::Paragraph => ("p".to_string(), String::new()),
+        Tag::Heading(level, _, _) => (format!("h{}", *level as usize), String::new()),
+        Tag::BlockQuote => ("blockquote".to_string(), String::new()),
+        Tag::CodeBlock(_) => ("pre".to_string(), String::new()),
+        Tag::List(Some(_)) => ("ol".to_string(), String::new()),
+        Tag::List(None) => ("ul".to_string(), String::new()),
+        Tag::Item => ("li".to_string(), String::new()),
+        Tag::FootnoteDefinition(_) => ("div".to_string(), String::new()),
+        Tag::Table(_) => ("table".to_string(), String::new()),
+        Tag::TableHead | Tag::TableRow => ("tr".to_string(), String::new()),
+        Tag::TableCell => ((if in_table_head { "th" } else { "td" }).to_string(), String::new()),
+        Tag::Emphasis => ("em".to_string(), String::new()),
+        Tag::Strong => ("strong".to_string(), String::new()),
+        Tag::Strikethrough => ("del".to_string(), String::new()),
+        Tag::Link(_, destination, _) | Tag::Image(_, destination, _) => {
+            ("a".to_string(), format!(" href=\"{}\"", escape_xml(destination)))
+        }
+    }
+}
+
+/// Escapes XML special characters and drops characters XML 1.0 cannot carry
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_xhtml() {
+        assert_eq!(
+            markdown_to_xhtml("Some *text* & `code`.\n\n* one\n* two\n"),
+            "<xhtml:div><xhtml:p>Some <xhtml:em>text</xhtml:em> &amp; <xhtml:code>code</xhtml:code>.</xhtml:p><xhtml:ul><xhtml:li>one</xhtml:li><xhtml:li>two</xhtml:li></xhtml:ul></xhtml:div>"
+        );
+        assert_eq!(
+            markdown_to_xhtml("<details>raw</details>\n"),
+            "<xhtml:div>&lt;details&gt;raw&lt;/details&gt;\n</xhtml:div>"
+        );
+        assert_eq!(markdown_to_xhtml(""), "<xhtml:div></xhtml:div>");
+    }
+
+    #[test]
+    fn test_identifiers_are_sanitized_and_unique() {
+        let mut ids = IdAllocator::default();
+        assert_eq!(ids.allocate("element", "specs/Reqs.md#a-b"), "element-specs_Reqs.md_a-b");
+        assert_eq!(ids.allocate("element", "specs/Reqs.md/a-b"), "element-specs_Reqs.md_a-b-2");
+    }
+}
@@ -0,0 +1,715 @@
+//! ReqIF import into structured Markdown
+//!
+//! SPECIFICATIONs become markdown files, chapter objects (those carrying the
+//! mapped section attribute) become sections, and every other SPEC-OBJECT in
+//! a hierarchy becomes an element of the closest enclosing chapter. A mapping
+//! file selects which ReqIF attributes provide element names, content, type
+//! and metadata and how SPEC-RELATION-TYPEs map onto reqvire relations.
+//! Objects typed `file` stand for implementation files; relations to them
+//! link to the file named by the object.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Deserialize;
+
+use crate::error::ReqvireError;
+use crate::import::{self, ImportedElement, ImportedFile, ImportedModel, ImportedRelation, DEFAULT_SECTION};
+use crate::reqif::{CHAPTER_NAME_ATTRIBUTE, FOREIGN_ID_ATTRIBUTE, NAME_ATTRIBUTE, TEXT_ATTRIBUTE, TYPE_ATTRIBUTE};
+
+/// Element type of objects that stand for implementation files in reqvire exports
+const FILE_TYPE: &str = "file";
+
+/// Maps ReqIF attribute, type and relation names onto the reqvire model.
+/// Every field is optional; the defaults read documents written by `export --format reqif`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReqifMapping {
+    /// Attribute holding the element name
+    pub name: String,
+    /// XHTML or string attribute holding the element content
+    pub content: String,
+    /// Attribute whose presence marks a chapter object; its value is the section name
+    pub section: String,
+    /// Attribute holding the element type
+    #[serde(rename = "type")]
+    pub type_attribute: Option<String>,
+    /// Attribute name to metadata key
+    pub metadata: BTreeMap<String, String>,
+    /// Whether attributes not mapped otherwise become metadata under their own name
+    pub unmapped_metadata: bool,
+    /// Attributes that are never imported
+    pub ignore: Vec<String>,
+    /// SPEC-OBJECT-TYPE name or type attribute value to element type
+    pub types: BTreeMap<String, String>,
+    /// SPEC-RELATION-TYPE name to reqvire relation type
+    pub relations: BTreeMap<String, String>,
+    /// Element type used when neither the type attribute nor the object type is mapped
+    pub default_type: String,
+}
+
+impl Default for ReqifMapping {
+    fn default() -> Self {
+        ReqifMapping {
+            name: NAME_ATTRIBUTE.to_string(),
+            content: TEXT_ATTRIBUTE.to_string(),
+            section: CHAPTER_NAME_ATTRIBUTE.to_string(),
+            type_attribute: Some(TYPE_ATTRIBUTE.to_string()),
+            metadata: BTreeMap::new(),
+            unmapped_metadata: true,
+            ignore: vec![FOREIGN_ID_ATTRIBUTE.to_string()],
+            types: BTreeMap::new(),
+            relations: BTreeMap::new(),
+            default_type: "requirement".to_string(),
+        }
+    }
+}
+
+impl ReqifMapping {
+    /// Loads a mapping file (YAML)
+    pub fn load(path: &Path) -> Result<Self, ReqvireError> {
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yml::from_str(&content).map_err(|e| {
+            ReqvireError::InitializationError(format!("Invalid ReqIF mapping {}: {}", path.display(), e))
+        })
+    }
+}
+
+// Minimal element tree of the parsed document
+
+#[derive(Debug, Default)]
+struct XmlNode {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlContent>,
+}
+
+#[derive(Debug)]
+enum XmlContent {
+    Node(XmlNode),
+    Text(String),
+}
+
+impl XmlNode {
+    fn from_start(start: &BytesStart) -> Result<Self, ReqvireError> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| xml_error(&e))?;
+            let value = attribute.unescape_value().map_err(|e| xml_error(&e))?;
+            attributes.push((local_name(attribute.key.local_name().as_ref()), value.to_string()));
+        }
+        Ok(XmlNode {
+            name: local_name(start.local_name().as_ref()),
+            attributes,
+            children: Vec::new(),
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &XmlNode> {
+        self.children.iter().filter_map(|child| match child {
+            XmlContent::Node(node) => Some(node),
+            XmlContent::Text(_) => None,
+        })
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlNode> {
+        self.elements().filter(move |node| node.name == name)
+    }
+
+    /// Follows a chain of child element names
+    fn path(&self, names: &[&str]) -> Option<&XmlNode> {
+        let mut node = self;
+        for name in names {
+            node = node.elements().find(|child| child.name == *name)?;
+        }
+        Some(node)
+    }
+
+    /// Text of the element and its descendants
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                XmlContent::Node(node) => text.push_str(&node.text()),
+                XmlContent::Text(value) => text.push_str(value),
+            }
+        }
+        text
+    }
+
+    /// Depth-first search for all descendants with the given name
+    fn descendants<'a>(&'a self, name: &str, found: &mut Vec<&'a XmlNode>) {
+        for node in self.elements() {
+            if node.name == name {
+                found.push(node);
+            }
+            node.descendants(name, found);
+        }
+    }
+}
+
+fn local_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).to_string()
+}
+
+fn xml_error(error: &dyn std::fmt::Display) -> ReqvireError {
+    ReqvireError::ProcessError(format!("Invalid ReqIF document: {}", error))
+}
+
+fn resolve_entity(entity: &str) -> Option<&'static str> {
+    match entity {
+        "nbsp" => Some("\u{a0}"),
+        _ => quick_xml::escape::resolve_predefined_entity(entity),
+    }
+}
+
+fn parse_xml(document: &str) -> Result<XmlNode, ReqvireError> {
+    let mut reader = Reader::from_str(document);
+    let mut stack = vec![XmlNode::default()];
+
+    loop {
+        match reader.read_event().map_err(|e| xml_error(&e))? {
+            Event::Start(start) => stack.push(XmlNode::from_start(&start)?),
+            Event::Empty(start) => {
+                let node = XmlNode::from_start(&start)?;
+                stack.last_mut().unwrap().children.push(XmlContent::Node(node));
+            }
+            Event::End(_) => {
+                let node = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(XmlContent::Node(node)),
+                    None => return Err(xml_error(&"unbalanced end tag")),
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape_with(resolve_entity).map_err(|e| xml_error(&e))?;
+                stack.last_mut().unwrap().children.push(XmlContent::Text(text.to_string()));
+            }
+            Event::CData(data) => {
+                let text = String::from_utf8_lossy(&data.into_inner()).to_string();
+                stack.last_mut().unwrap().children.push(XmlContent::Text(text));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(xml_error(&"unexpected end of document"));
+    }
+    let document = stack.pop().unwrap();
+    if document.path(&["REQ-IF"]).is_none() {
+        return Err(xml_error(&"missing REQ-IF root element"));
+    }
+    Ok(document)
+}
+
+/// Reads the ReqIF document from a `.reqif` file or the first `.reqif` entry of a `.reqifz` archive
+fn read_document(path: &Path) -> Result<String, ReqvireError> {
+    let is_archive = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("reqifz") || e.eq_ignore_ascii_case("zip"));
+    if !is_archive {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let zip_error = |e: zip::result::ZipError| ReqvireError::ProcessError(format!("Failed to read {}: {}", path.display(), e));
+    let mut archive = zip::ZipArchive::new(fs::File::open(path)?).map_err(zip_error)?;
+    let mut entries: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.to_lowercase().ends_with(".reqif"))
+        .map(String::from)
+        .collect();
+    entries.sort();
+    let entry = entries.first().ok_or_else(|| {
+        ReqvireError::ProcessError(format!("{} does not contain a .reqif document", path.display()))
+    })?;
+
+    let mut document = String::new();
+    archive.by_name(entry).map_err(zip_error)?.read_to_string(&mut document)?;
+    Ok(document)
+}
+
+struct SpecObject {
+    object_type: String,
+    long_name: Option<String>,
+    values: BTreeMap<String, String>,
+}
+
+/// Parses a ReqIF file and maps it onto an `ImportedModel`
+pub fn import_reqif(path: &Path, mapping: &ReqifMapping) -> Result<ImportedModel, ReqvireError> {
+    let document = parse_xml(&read_document(path)?)?;
+    let content = document
+        .path(&["REQ-IF", "CORE-CONTENT", "REQ-IF-CONTENT"])
+        .ok_or_else(|| xml_error(&"missing REQ-IF-CONTENT"))?;
+
+    // Names of types, attribute definitions and enumeration values by identifier
+    let mut names: HashMap<&str, String> = HashMap::new();
+    for section in ["DATATYPES", "SPEC-TYPES"] {
+        let Some(node) = content.path(&[section]) else { continue };
+        let mut definitions = Vec::new();
+        node.descendants("ENUM-VALUE", &mut definitions);
+        for definition in node.elements() {
+            definitions.push(definition);
+            if let Some(attributes) = definition.path(&["SPEC-ATTRIBUTES"]) {
+                definitions.extend(attributes.elements());
+            }
+        }
+        for definition in definitions {
+            if let Some(identifier) = definition.attribute("IDENTIFIER") {
+                let name = definition.attribute("LONG-NAME").unwrap_or(identifier);
+                names.insert(identifier, name.to_string());
+            }
+        }
+    }
+    let name_of = |identifier: &str| names.get(identifier).cloned().unwrap_or_else(|| identifier.to_string());
+
+    let mut objects: HashMap<String, SpecObject> = HashMap::new();
+    for object in content.path(&["SPEC-OBJECTS"]).into_iter().flat_map(|n| n.children_named("SPEC-OBJECT")) {
+        let Some(identifier) = object.attribute("IDENTIFIER") else { continue };
+        let object_type = object
+            .path(&["TYPE", "SPEC-OBJECT-TYPE-REF"])
+            .map(|node| name_of(node.text().trim()))
+            .unwrap_or_default();
+
+        let mut values = BTreeMap::new();
+        for value in object.path(&["VALUES"]).into_iter().flat_map(|n| n.elements()) {
+            let Some(definition) = value.path(&["DEFINITION"]).and_then(|d| d.elements().next()) else { continue };
+            let attribute = name_of(definition.text().trim());
+            let text = match value.name.as_str() {
+                "ATTRIBUTE-VALUE-XHTML" => value
+                    .path(&["THE-VALUE"])
+                    .map(xhtml_to_markdown)
+                    .unwrap_or_default(),
+                "ATTRIBUTE-VALUE-ENUMERATION" => value
+                    .path(&["VALUES"])
+                    .into_iter()
+                    .flat_map(|v| v.children_named("ENUM-VALUE-REF"))
+                    .map(|r| name_of(r.text().trim()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                _ => value.attribute("THE-VALUE").unwrap_or_default().to_string(),
+            };
+            values.insert(attribute, text);
+        }
+
+        objects.insert(
+            identifier.to_string(),
+            SpecObject {
+                object_type,
+                long_name: object.attribute("LONG-NAME").map(String::from),
+                values,
+            },
+        );
+    }
+
+    let mut model = ImportedModel::default();
+    let mut placed = HashSet::new();
+    let mut used_paths = HashSet::new();
+    for specification in content
+        .path(&["SPECIFICATIONS"])
+        .into_iter()
+        .flat_map(|n| n.children_named("SPECIFICATION"))
+    {
+        let title = specification
+            .attribute("LONG-NAME")
+            .or(specification.attribute("IDENTIFIER"))
+            .unwrap_or("Imported")
+            .to_string();
+        let mut file = ImportedFile {
+            path: unique_path(&file_path_for(&title), &mut used_paths),
+            title: if title.ends_with(".md") { None } else { Some(title.clone()) },
+            sections: Vec::new(),
+        };
+        let mut section = DEFAULT_SECTION.to_string();
+        if let Some(children) = specification.path(&["CHILDREN"]) {
+            place_hierarchy(children, &objects, mapping, &mut file, &mut section, &mut placed);
+        }
+        model.files.push(file);
+    }
+
+    for (identifier, object) in &objects {
+        if is_file_object(object, mapping) {
+            let path = to_element(identifier, object, mapping).name;
+            if is_plain_relative(&path) {
+                model.file_targets.insert(identifier.clone(), path);
+            }
+        }
+    }
+
+    // Objects outside of any specification go to a file named after the input
+    let mut unplaced: Vec<(&String, &SpecObject)> = objects
+        .iter()
+        .filter(|(identifier, object)| !placed.contains(identifier.as_str()) && !is_file_object(object, mapping))
+        .collect();
+    if !unplaced.is_empty() {
+        unplaced.sort_by(|a, b| a.0.cmp(b.0));
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Imported");
+        let mut file = ImportedFile {
+            path: unique_path(&import::file_name_from_title(stem), &mut used_paths),
+            title: None,
+            sections: Vec::new(),
+        };
+        for (identifier, object) in unplaced {
+            let element = to_element(identifier, object, mapping);
+            file.section_mut(DEFAULT_SECTION).elements.push(element);
+        }
+        model.files.push(file);
+    }
+
+    for relation in content
+        .path(&["SPEC-RELATIONS"])
+        .into_iter()
+        .flat_map(|n| n.children_named("SPEC-RELATION"))
+    {
+        let reference = |names: &[&str]| relation.path(names).map(|node| node.text().trim().to_string());
+        let (Some(source), Some(target)) = (
+            reference(&["SOURCE", "SPEC-OBJECT-REF"]),
+            reference(&["TARGET", "SPEC-OBJECT-REF"]),
+        ) else {
+            continue;
+        };
+        let relation_type = reference(&["TYPE", "SPEC-RELATION-TYPE-REF"])
+            .map(|identifier| name_of(&identifier))
+            .unwrap_or_default();
+        model.relations.push(ImportedRelation {
+            source,
+            relation_type: mapping.relations.get(&relation_type).cloned().unwrap_or(relation_type),
+            target,
+        });
+    }
+
+    Ok(model)
+}
+
+/// Uses the specification name as the file path when it already is a relative
+/// markdown path (as written by reqvire), otherwise derives a file name from it
+fn file_path_for(title: &str) -> String {
+    if title.ends_with(".md") && is_plain_relative(title) {
+        title.to_string()
+    } else {
+        import::file_name_from_title(title.trim_end_matches(".md"))
+    }
+}
+
+/// Whether the path is relative and stays below the directory it is relative to
+fn is_plain_relative(path: &str) -> bool {
+    let path = Path::new(path);
+    path.is_relative() && path.components().all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
+fn unique_path(path: &str, used: &mut HashSet<String>) -> String {
+    let stem = path.trim_end_matches(".md");
+    let mut candidate = path.to_string();
+    let mut suffix = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{}-{}.md", stem, suffix);
+        suffix += 1;
+    }
+    candidate
+}
+
+fn place_hierarchy(
+    children: &XmlNode,
+    objects: &HashMap<String, SpecObject>,
+    mapping: &ReqifMapping,
+    file: &mut ImportedFile,
+    section: &mut String,
+    placed: &mut HashSet<String>,
+) {
+    for hierarchy in children.children_named("SPEC-HIERARCHY") {
+        let reference = hierarchy
+            .path(&["OBJECT", "SPEC-OBJECT-REF"])
+            .map(|node| node.text().trim().to_string());
+        if let Some((identifier, object)) = reference.and_then(|r| objects.get_key_value(&r)) {
+            if !placed.contains(identifier) && !is_file_object(object, mapping) {
+                placed.insert(identifier.clone());
+                let chapter = object.values.get(&mapping.section).map(|name| import::single_line(name));
+                match chapter.filter(|name| !name.is_empty()) {
+                    Some(name) => {
+                        *section = name;
+                        let text = object.values.get(&mapping.content).cloned().unwrap_or_default();
+                        let target = file.section_mut(section);
+                        if target.content.is_empty() {
+                            target.content = text;
+                        }
+                    }
+                    None => {
+                        let element = to_element(identifier, object, mapping);
+                        file.section_mut(section).elements.push(element);
+                    }
+                }
+            }
+        }
+        if let Some(nested) = hierarchy.path(&["CHILDREN"]) {
+            place_hierarchy(nested, objects, mapping, file, section, placed);
+        }
+    }
+}
+
+fn element_type(object: &SpecObject, mapping: &ReqifMapping) -> String {
+    if let Some(value) = mapping.type_attribute.as_ref().and_then(|a| object.values.get(a)) {
+        let value = value.trim();
+        if !value.is_empty() {
+            return mapping.types.get(value).cloned().unwrap_or_else(|| value.to_string());
+        }
+    }
+    mapping
+        .types
+        .get(&object.object_type)
+        .cloned()
+        .unwrap_or_else(|| mapping.default_type.clone())
+}
+
+fn is_file_object(object: &SpecObject, mapping: &ReqifMapping) -> bool {
+    element_type(object, mapping) == FILE_TYPE
+}
+
+fn to_element(identifier: &str, object: &SpecObject, mapping: &ReqifMapping) -> ImportedElement {
+    let name = object
+        .values
+        .get(&mapping.name)
+        .map(|name| import::single_line(name))
+        .filter(|name| !name.is_empty())
+        .or_else(|| object.long_name.clone())
+        .unwrap_or_else(|| identifier.to_string());
+
+    let mut metadata = BTreeMap::new();
+    for (attribute, value) in &object.values {
+        let value = import::single_line(value);
+        if value.is_empty() || mapping.ignore.contains(attribute) {
+            continue;
+        }
+        let key = match mapping.metadata.get(attribute) {
+            Some(key) => key.clone(),
+            None if mapping.unmapped_metadata => attribute.clone(),
+            None => continue,
+        };
+        let is_mapped_elsewhere = [&mapping.name, &mapping.content, &mapping.section].contains(&attribute)
+            || mapping.type_attribute.as_ref() == Some(attribute);
+        if is_mapped_elsewhere && !mapping.metadata.contains_key(attribute) {
+            continue;
+        }
+        // Metadata keys end at the first colon of a `* key: value` line
+        let key = key.replace(':', "").trim().to_string();
+        if !key.is_empty() && !key.eq_ignore_ascii_case("type") {
+            metadata.insert(key, value);
+        }
+    }
+
+    ImportedElement {
+        key: identifier.to_string(),
+        name,
+        content: object.values.get(&mapping.content).cloned().unwrap_or_default(),
+        element_type: element_type(object, mapping),
+        metadata,
+    }
+}
+
+/// Converts an XHTML attribute value to markdown. Headings become bold
+/// paragraphs since markdown headings would start new elements.
+fn xhtml_to_markdown(value: &XmlNode) -> String {
+    blocks(value).join("\n\n").trim().to_string()
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "li" | "pre" | "blockquote" | "table" | "hr"
+    )
+}
+
+/// Renders the children of a node as a list of markdown blocks
+fn blocks(node: &XmlNode) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut paragraph = String::new();
+
+    for child in &node.children {
+        let element = match child {
+            XmlContent::Text(text) => {
+                paragraph.push_str(&text.replace('\n', " "));
+                continue;
+            }
+            XmlContent::Node(element) if !is_block(&element.name) => {
+                paragraph.push_str(&inline(element));
+                continue;
+            }
+            XmlContent::Node(element) => element,
+        };
+        push_paragraph(&mut result, &mut paragraph);
+
+        match element.name.as_str() {
+            "p" => {
+                let mut text = inline_children(element);
+                push_paragraph(&mut result, &mut text);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = collapse(&inline_children(element));
+                if !text.is_empty() {
+                    result.push(format!("**{}**", text));
+                }
+            }
+            "ul" | "ol" => {
+                let items: Vec<String> = element
+                    .children_named("li")
+                    .enumerate()
+                    .map(|(index, item)| {
+                        let marker = if element.name == "ol" { format!("{}. ", index + 1) } else { "* ".to_string() };
+                        let indent = " ".repeat(marker.len());
+                        let body = blocks(item).join("\n");
+                        let mut lines = body.lines();
+                        let mut rendered = format!("{}{}", marker, lines.next().unwrap_or_default());
+                        for line in lines {
+                            rendered.push('\n');
+                            if !line.is_empty() {
+                                rendered.push_str(&indent);
+                                rendered.push_str(line);
+                            }
+                        }
+                        rendered
+                    })
+                    .collect();
+                if !items.is_empty() {
+                    result.push(items.join("\n"));
+                }
+            }
+            "pre" => result.push(format!("```\n{}\n```", element.text().trim_end())),
+            "blockquote" => {
+                let body = blocks(element).join("\n\n");
+                let quoted: Vec<String> = body.lines().map(|line| format!("> {}", line).trim_end().to_string()).collect();
+                if !quoted.is_empty() {
+                    result.push(quoted.join("\n"));
+                }
+            }
+            "table" => {
+                let mut rows = Vec::new();
+                element.descendants("tr", &mut rows);
+                let mut lines = Vec::new();
+                for (index, row) in rows.iter().enumerate() {
+                    let cells: Vec<String> = row
+                        .elements()
+                        .filter(|cell| cell.name == "td" || cell.name == "th")
+                        .map(|cell| collapse(&inline_children(cell)).replace('|', "\\|"))
+                        .collect();
+                    lines.push(format!("| {} |", cells.join(" | ")));
+                    if index == 0 {
+                        lines.push(format!("|{}", " --- |".repeat(cells.len().max(1))));
+                    }
+                }
+                if !lines.is_empty() {
+                    result.push(lines.join("\n"));
+                }
+            }
+            // Plain `---` would end the element
+            "hr" => result.push("***".to_string()),
+            "li" | "div" => result.extend(blocks(element)),
+            _ => {}
+        }
+    }
+    push_paragraph(&mut result, &mut paragraph);
+    result
+}
+
+fn push_paragraph(result: &mut Vec<String>, paragraph: &mut String) {
+    let text = collapse(paragraph);
+    paragraph.clear();
+    if text.is_empty() {
+        return;
+    }
+    // Lines starting with `#` would be read as headings
+    let escaped: Vec<String> = text
+        .lines()
+        .map(|line| if line.starts_with('#') { format!("\\{}", line) } else { line.to_string() })
+        .collect();
+    result.push(escaped.join("\n"));
+}
+
+/// Collapses XML whitespace while keeping explicit line breaks
+fn collapse(text: &str) -> String {
+    text.split('\n')
+        .map(import::single_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn inline_children(node: &XmlNode) -> String {
+    node.children
+        .iter()
+        .map(|child| match child {
+            XmlContent::Text(text) => text.replace('\n', " "),
+            XmlContent::Node(node) => inline(node),
+        })
+        .collect()
+}
+
+fn inline(node: &XmlNode) -> String {
+    let text = || inline_children(node).trim().to_string();
+    match node.name.as_str() {
+        "br" => "\n".to_string(),
+        "strong" | "b" => wrap("**", &text()),
+        "em" | "i" => wrap("*", &text()),
+        "del" | "s" | "strike" => wrap("~~", &text()),
+        "code" | "tt" => wrap("`", &node.text()),
+        "a" => match node.attribute("href") {
+            Some(href) => format!("[{}]({})", text(), href),
+            None => text(),
+        },
+        "object" => node.attribute("name").map(String::from).unwrap_or_else(text),
+        _ => inline_children(node),
+    }
+}
+
+fn wrap(marker: &str, text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}{}", marker, text, marker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xhtml(fragment: &str) -> String {
+        let document = parse_xml(&format!(
+            "<REQ-IF xmlns:xhtml=\"http://www.w3.org/1999/xhtml\"><THE-VALUE>{}</THE-VALUE></REQ-IF>",
+            fragment
+        ))
+        .unwrap();
+        xhtml_to_markdown(document.path(&["REQ-IF", "THE-VALUE"]).unwrap())
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown() {
+        assert_eq!(
+            xhtml("<xhtml:div><xhtml:p>The <xhtml:b>system</xhtml:b>\n  shall <xhtml:i>log</xhtml:i>.</xhtml:p><xhtml:ul><xhtml:li>one</xhtml:li><xhtml:li><xhtml:code>two</xhtml:code></xhtml:li></xhtml:ul></xhtml:div>"),
+            "The **system** shall *log*.\n\n* one\n* `two`"
+        );
+        assert_eq!(
+            xhtml("<xhtml:div><xhtml:h2>Scope</xhtml:h2>#1 &amp; more<xhtml:hr/></xhtml:div>"),
+            "**Scope**\n\n\\#1 & more\n\n***"
+        );
+    }
+
+    #[test]
+    fn test_file_paths_from_specification_names() {
+        assert_eq!(file_path_for("specifications/Requirements.md"), "specifications/Requirements.md");
+        assert_eq!(file_path_for("../outside.md"), "outside.md");
+        assert_eq!(file_path_for("Supplier Spec: Brakes"), "Supplier-Spec-Brakes.md");
+    }
+}
@@ -0,0 +1,152 @@
+//! Search index of the HTML export
+//!
+//! The index is a compact JSON document that the search box of the exported
+//! pages loads to search elements without a server. Element types and files
+//! are stored once and referenced by position, so the index stays small for
+//! large models.
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::graph_registry::GraphRegistry;
+use crate::html;
+
+/// File name of the JSON search index in the export
+pub const SEARCH_INDEX_FILE: &str = "search-index.json";
+
+/// File name of the search index wrapped in a script, which pages opened
+/// from disk load since browsers refuse to fetch files there
+pub const SEARCH_INDEX_SCRIPT_FILE: &str = "search-index.js";
+
+/// File name of the search box script
+pub const SEARCH_SCRIPT_FILE: &str = "search.js";
+
+/// Search box script, loaded by every exported page
+pub const SEARCH_SCRIPT: &str = include_str!("../templates/search.js");
+
+/// Version of the index format, increased on incompatible changes
+pub const SEARCH_INDEX_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct SearchIndex {
+    pub version: u32,
+    /// Element types, referenced by position from the elements
+    pub types: Vec<String>,
+    /// Model files, referenced by position from the elements
+    pub files: Vec<String>,
+    pub elements: Vec<SearchEntry>,
+}
+
+/// An element, serialized as `[name, identifier, type, file, page, text]`
+/// with the page relative to the export root and the text holding content
+/// and metadata with whitespace collapsed
+#[derive(Debug, Serialize)]
+pub struct SearchEntry(pub String, pub String, pub usize, pub usize, pub String, pub String);
+
+/// Builds the search index over all elements of the model
+pub fn generate_search_index(registry: &GraphRegistry) -> SearchIndex {
+    let mut elements: Vec<_> = registry
+        .get_all_elements()
+        .into_iter()
+        .filter(|element| !element.identifier.starts_with("__virtual__"))
+        .collect();
+    elements.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    let types: Vec<String> = elements
+        .iter()
+        .map(|element| element.element_type.as_str().to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let files: Vec<String> = elements
+        .iter()
+        .map(|element| element.file_path.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let elements = elements
+        .into_iter()
+        .map(|element| {
+            let mut text = element.content.clone();
+            for (key, value) in &element.metadata {
+                if key != "type" {
+                    text.push_str(&format!("\n{}: {}", key, value));
+                }
+            }
+            let page = match element.file_path.strip_suffix(".md") {
+                Some(stem) => format!("{}.html#{}", stem, html::heading_id(&element.name)),
+                None => element.file_path.clone(),
+            };
+            SearchEntry(
+                element.name.clone(),
+                element.identifier.clone(),
+                types.iter().position(|t| t == element.element_type.as_str()).unwrap_or_default(),
+                files.iter().position(|f| *f == element.file_path).unwrap_or_default(),
+                page,
+                text.split_whitespace().collect::<Vec<_>>().join(" "),
+            )
+        })
+        .collect();
+
+    SearchIndex { version: SEARCH_INDEX_VERSION, types, files, elements }
+}
+
+impl SearchIndex {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("search index serializes")
+    }
+
+    /// The index as a script setting `window.reqvireSearchIndex`
+    pub fn to_script(&self) -> String {
+        format!("window.reqvireSearchIndex = {};\n", self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{Element, ElementType, RequirementType, VerificationType};
+
+    #[test]
+    fn test_search_index_references_types_and_files() {
+        let mut registry = GraphRegistry::new();
+        let mut start = Element::new(
+            "Remote Start (v2)",
+            "specs/Requirements.md#remote-start-v2",
+            "specs/Requirements.md",
+            "System",
+            Some(ElementType::Requirement(RequirementType::System)),
+        );
+        start.add_content("The pump shall\nstart   remotely.");
+        start.metadata.insert("priority".to_string(), "high".to_string());
+        registry.register_element(start, "specs/Requirements.md").unwrap();
+        let test = Element::new(
+            "Start Test",
+            "specs/Tests.md#start-test",
+            "specs/Tests.md",
+            "Tests",
+            Some(ElementType::Verification(VerificationType::Test)),
+        );
+        registry.register_element(test, "specs/Tests.md").unwrap();
+
+        let index = generate_search_index(&registry);
+        assert_eq!(index.types, vec!["requirement", "test-verification"]);
+        assert_eq!(index.files, vec!["specs/Requirements.md", "specs/Tests.md"]);
+        let json: serde_json::Value = serde_json::from_str(&index.to_json()).unwrap();
+        assert_eq!(
+            json["elements"][0],
+            serde_json::json!([
+                "Remote Start (v2)",
+                "specs/Requirements.md#remote-start-v2",
+                0,
+                0,
+                "specs/Requirements.html#remote-start-(v2)",
+                "The pump shall start remotely. priority: high"
+            ])
+        );
+        assert_eq!(json["elements"][1][2], 1);
+        assert!(index.to_script().starts_with("window.reqvireSearchIndex = {"));
+    }
+}
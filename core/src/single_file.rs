@@ -0,0 +1,349 @@
+//! Single-file HTML document of the model
+//!
+//! For formal deliveries the whole model is rendered into one portable HTML
+//! file: model files in path order with their sections and elements in
+//! document order, numbered headings, a table of contents and the
+//! traceability matrix as a closing chapter. Links between model files become
+//! anchors within the document, links to other repository files point to the
+//! repository when it has a remote and to the model root otherwise, and local
+//! images, the matrix and the theme are embedded. Flowchart diagrams are
+//! rendered to SVG, so the document needs no script and no network access;
+//! other diagrams are shown as their Mermaid source.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, HeadingLevel, Options, Parser, Tag};
+
+use crate::diagram_svg;
+use crate::diagrams;
+use crate::error::ReqvireError;
+use crate::filesystem;
+use crate::graph_registry::GraphRegistry;
+use crate::html::EMBEDDED_STYLES;
+use crate::matrix_generator::{self, MatrixConfig, MatrixFormat};
+use crate::project_config::ProjectConfig;
+use crate::theme::Theme;
+use crate::utils;
+
+/// HTML template of the document
+/// Loaded at compile time from templates/document.html
+pub const DOCUMENT_TEMPLATE: &str = include_str!("../templates/document.html");
+
+/// Title of the document when `export.title` is not configured
+pub const DEFAULT_TITLE: &str = "Specification";
+
+const MATRIX_TITLE: &str = "Traceability Matrix";
+
+/// Writes the single-file document of the model to `output`
+pub fn export_single_file(
+    registry: &GraphRegistry,
+    output: &Path,
+    config: &ProjectConfig,
+) -> Result<(), ReqvireError> {
+    let document = generate_document(registry, config)?;
+    filesystem::write_file(output, document)
+}
+
+/// Renders the single-file document of the model
+pub fn generate_document(registry: &GraphRegistry, config: &ProjectConfig) -> Result<String, ReqvireError> {
+    let context = &registry.context;
+    let mut theme = Theme::load(context.root(), context.commit_hash().unwrap_or_default())?;
+    theme.embed_logo()?;
+    let title = config.export.title.as_deref().unwrap_or(DEFAULT_TITLE);
+
+    let elements_by_file = registry.group_elements_by_location();
+    let files: BTreeSet<&str> = registry.pages.keys()
+        .chain(elements_by_file.keys())
+        .map(String::as_str)
+        .collect();
+
+    // Diagrams are generated like in the HTML export, but only in memory
+    let mut diagrams_by_file: HashMap<&str, Vec<(&str, &String)>> = HashMap::new();
+    let diagrams = diagrams::generate_diagrams_by_section(registry, false)?;
+    for (key, diagram) in &diagrams {
+        if let Some((file, section)) = key.split_once("::") {
+            diagrams_by_file.entry(file).or_default().push((section, diagram));
+        }
+    }
+
+    let mut document = Document::new(registry, &files);
+    for file in &files {
+        let sections = elements_by_file.get(*file).cloned().unwrap_or_default();
+        let mut markdown = registry.generate_file_markdown(file, &sections);
+        for (section, diagram) in diagrams_by_file.get(file).into_iter().flatten() {
+            markdown = diagrams::replace_section_diagram(&markdown, section, diagram);
+        }
+        document.add_file(file, &markdown);
+    }
+
+    let matrix_svg = matrix_generator::generate_matrix(registry, &MatrixConfig::default(), MatrixFormat::Svg);
+    document.add_matrix(&matrix_svg);
+
+    Ok(DOCUMENT_TEMPLATE
+        .replace("{title}", &utils::escape_html(title))
+        .replace("{styles}", EMBEDDED_STYLES)
+        .replace("{theme_styles}", &theme.inline_stylesheets()?)
+        .replace("{header}", &theme.header(title, ""))
+        .replace("{footer}", &theme.footer(title, ""))
+        .replace("{toc}", &format!("<ul>\n{}</ul>", document.toc))
+        .replace("{content}", &document.content))
+}
+
+/// Anchor of a model file or of a heading within it, from its identifier
+pub fn anchor_id(identifier: &str) -> String {
+    identifier
+        .replace('#', "--")
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/') { c } else { '-' })
+        .collect()
+}
+
+/// Content and table of contents of the document, built file by file
+struct Document<'a> {
+    registry: &'a GraphRegistry,
+    files: &'a BTreeSet<&'a str>,
+    /// Numbers of the current level 1 to 3 headings
+    numbers: [usize; 3],
+    ids: HashSet<String>,
+    /// Number of diagrams rendered so far
+    diagrams: usize,
+    toc: String,
+    content: String,
+}
+
+impl<'a> Document<'a> {
+    fn new(registry: &'a GraphRegistry, files: &'a BTreeSet<&'a str>) -> Self {
+        Self {
+            registry,
+            files,
+            numbers: [0; 3],
+            ids: HashSet::new(),
+            diagrams: 0,
+            toc: String::new(),
+            content: String::new(),
+        }
+    }
+
+    fn add_file(&mut self, file: &str, markdown: &str) {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+
+        let mut events: Vec<Event> = Vec::new();
+        let mut heading: Option<(HeadingLevel, Vec<Event>)> = None;
+        let mut mermaid: Option<String> = None;
+        let mut file_anchor = true;
+
+        for event in Parser::new_ext(markdown, options) {
+            if let Some(code) = mermaid.as_mut() {
+                match event {
+                    Event::Text(text) => code.push_str(&text),
+                    Event::End(_) => {
+                        let code = mermaid.take().unwrap_or_default();
+                        events.push(Event::Html(self.mermaid(file, &code).into()));
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some((_, inner)) = heading.as_mut() {
+                if let Event::End(Tag::Heading(..)) = event {
+                    let (level, inner) = heading.take().unwrap_or((HeadingLevel::H1, Vec::new()));
+                    // The first level 1 heading is the title of the file and
+                    // the target of links to the file without a fragment
+                    let text = plain_text(&inner);
+                    let identifier = if level == HeadingLevel::H1 && file_anchor {
+                        file_anchor = false;
+                        file.to_string()
+                    } else {
+                        format!("{}#{}", file, utils::normalize_fragment(&text))
+                    };
+                    let id = self.unique_id(anchor_id(&identifier));
+                    events.push(Event::Html(self.heading(level, &id, &text, inner).into()));
+                } else {
+                    inner.push(self.rewrite(file, event));
+                }
+                continue;
+            }
+
+            match event {
+                Event::Start(Tag::Heading(level, _, _)) if level <= HeadingLevel::H3 => {
+                    heading = Some((level, Vec::new()));
+                }
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(language))) if language.as_ref() == "mermaid" => {
+                    mermaid = Some(String::new());
+                }
+                event => events.push(self.rewrite(file, event)),
+            }
+        }
+
+        html::push_html(&mut self.content, events.into_iter());
+    }
+
+    /// Closing chapter with the traceability matrix embedded as an image
+    fn add_matrix(&mut self, svg: &str) {
+        let id = self.unique_id(anchor_id("traceability-matrix"));
+        let heading = self.heading(HeadingLevel::H1, &id, MATRIX_TITLE, vec![Event::Text(MATRIX_TITLE.into())]);
+        self.content.push_str(&heading);
+        self.content.push_str(&format!(
+            "\n<div class=\"reqvire-matrix\"><img src=\"{}\" alt=\"{}\"></div>\n",
+            utils::data_uri("image/svg+xml", svg.as_bytes()),
+            MATRIX_TITLE
+        ));
+    }
+
+    /// Numbered heading with its table of contents entry
+    fn heading(&mut self, level: HeadingLevel, id: &str, text: &str, inner: Vec<Event>) -> String {
+        let depth = level as usize;
+        self.numbers[depth - 1] += 1;
+        for number in &mut self.numbers[depth..] {
+            *number = 0;
+        }
+        let number = self.numbers[..depth]
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        self.toc.push_str(&format!(
+            "<li class=\"toc-level-{}\"><a href=\"#{}\">{} {}</a></li>\n",
+            depth, id, number, utils::escape_html(text)
+        ));
+
+        let mut inner_html = String::new();
+        html::push_html(&mut inner_html, inner.into_iter());
+        format!(
+            "<h{} id=\"{}\"><span class=\"section-number\">{}</span>{}</h{}>\n",
+            depth, id, number, inner_html, depth
+        )
+    }
+
+    /// Anchor not used by an earlier heading
+    fn unique_id(&mut self, id: String) -> String {
+        let mut unique = id.clone();
+        let mut n = 1;
+        while !self.ids.insert(unique.clone()) {
+            unique = format!("{}-{}", id, n);
+            n += 1;
+        }
+        unique
+    }
+
+    /// Rewrites links to in-document anchors and embeds local images
+    fn rewrite<'e>(&self, file: &str, event: Event<'e>) -> Event<'e> {
+        match event {
+            Event::Start(Tag::Link(kind, destination, title)) => {
+                let link = self.link(file, &destination);
+                Event::Start(Tag::Link(kind, link.into(), title))
+            }
+            Event::Start(Tag::Image(kind, destination, title)) => {
+                let image = self.image(file, &destination).map(CowStr::from).unwrap_or(destination);
+                Event::Start(Tag::Image(kind, image, title))
+            }
+            event => event,
+        }
+    }
+
+    /// Mermaid diagram rendered to SVG with its click links rewritten like
+    /// other links, or its source when it cannot be rendered
+    fn mermaid(&mut self, file: &str, code: &str) -> String {
+        self.diagrams += 1;
+        let id_prefix = format!("reqvire-diagram-{}", self.diagrams);
+        match diagram_svg::render_flowchart(code, &id_prefix, |target| self.link(file, target)) {
+            Some(svg) => format!("<div class=\"reqvire-diagram-container\">\n{}</div>\n", svg),
+            None => format!(
+                "<pre class=\"reqvire-diagram-source\"><code class=\"language-mermaid\">{}</code></pre>\n",
+                utils::escape_html(code)
+            ),
+        }
+    }
+
+    /// Link from a model file: model files and their headings become anchors,
+    /// other repository files link to the repository when it has a remote
+    /// and are relative to the model root otherwise
+    fn link(&self, file: &str, destination: &str) -> String {
+        if let Some(fragment) = destination.strip_prefix('#') {
+            return format!("#{}", anchor_id(&format!("{}#{}", file, utils::normalize_fragment(fragment))));
+        }
+        if destination.is_empty() || utils::EXTERNAL_SCHEMES.iter().any(|scheme| destination.starts_with(scheme)) {
+            return destination.to_string();
+        }
+
+        let context = &self.registry.context;
+        let folder = context.resolve(file).parent().map(Path::to_path_buf).unwrap_or_default();
+        let Ok(identifier) = utils::normalize_identifier(context, destination, &folder) else {
+            return destination.to_string();
+        };
+        let (path, _) = utils::extract_path_and_fragment(&identifier);
+        if self.files.contains(path) {
+            return format!("#{}", anchor_id(&identifier));
+        }
+        match (context.repository_base_url(), context.commit_hash()) {
+            (Some(base_url), Some(commit)) => format!("{}/blob/{}/{}", base_url, commit, identifier),
+            _ => identifier,
+        }
+    }
+
+    /// Data URI of a local image referenced from a model file
+    fn image(&self, file: &str, destination: &str) -> Option<String> {
+        if utils::EXTERNAL_SCHEMES.iter().any(|scheme| destination.starts_with(scheme)) {
+            return None;
+        }
+        let context = &self.registry.context;
+        let path = match destination.strip_prefix('/') {
+            Some(path) => context.resolve(path),
+            None => context.resolve(file).parent()?.join(destination),
+        };
+        let media_type = utils::image_media_type(&path)?;
+        let bytes = fs::read(&path).ok()?;
+        Some(utils::data_uri(media_type, &bytes))
+    }
+}
+
+/// Text of heading contents without markup
+fn plain_text(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_id() {
+        assert_eq!(anchor_id("specs/Requirements.md"), "specs/Requirements.md");
+        assert_eq!(anchor_id("specs/Requirements.md#remote-start"), "specs/Requirements.md--remote-start");
+        assert_eq!(anchor_id("specs/My File.md#a?b"), "specs/My-File.md--a-b");
+    }
+
+    #[test]
+    fn test_diagrams_render_to_svg_or_stay_source() {
+        let registry = GraphRegistry::new();
+        let files = BTreeSet::from(["specs/Requirements.md"]);
+        let mut document = Document::new(&registry, &files);
+        document.add_file(
+            "specs/Requirements.md",
+            "```mermaid\ngraph LR;\n  a[\"Remote Start\"] --> b;\n  click a \"#remote-start\";\n```\n\n\
+             ```mermaid\nsequenceDiagram\n  A->>B: start & stop\n```\n",
+        );
+
+        assert!(document.content.contains("<div class=\"reqvire-diagram-container\">\n<svg "));
+        assert!(document.content.contains("<marker id=\"reqvire-diagram-1-arrow\""));
+        assert!(document.content.contains("<a href=\"#specs/Requirements.md--remote-start\"><g>"));
+        assert!(document.content.contains(
+            "<pre class=\"reqvire-diagram-source\"><code class=\"language-mermaid\">sequenceDiagram\n  A-&gt;&gt;B: start &amp; stop\n</code></pre>"
+        ));
+        assert_eq!(document.diagrams, 2);
+    }
+}
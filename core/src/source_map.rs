@@ -0,0 +1,896 @@
+//! Concrete syntax of specification files
+//!
+//! The model keeps only what it needs to reason about elements, so a file
+//! regenerated from it loses layout such as blank lines, comments, HTML
+//! between elements and the order of metadata. `SourceMap` records where each
+//! section, element and subsection starts and ends in the original text, and
+//! `patch_files` writes model edits back as minimal text patches on top of
+//! it. `format` remains the only operation that rewrites whole files.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::context::ModelContext;
+use crate::element::{Element, SubSection};
+use crate::error::ReqvireError;
+use crate::format;
+use crate::graph_registry::GraphRegistry;
+use crate::relation::Relation;
+use crate::utils;
+
+/// Section holding the elements that appear before the first `## ` header
+const DEFAULT_SECTION: &str = "Requirements";
+
+/// Byte ranges of the sections and elements of a Markdown file
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub sections: Vec<SectionSpan>,
+    pub elements: Vec<ElementSpan>,
+}
+
+/// A `## ` section
+#[derive(Debug, Clone)]
+pub struct SectionSpan {
+    pub name: String,
+    /// From the header line up to the next section. The implicit section
+    /// before the first header has no header line.
+    pub range: Range<usize>,
+    /// Start of the text after the header line
+    pub body_start: usize,
+    pub has_header: bool,
+    pub element_count: usize,
+}
+
+/// A `### ` element
+#[derive(Debug, Clone)]
+pub struct ElementSpan {
+    pub name: String,
+    pub section: String,
+    /// The header line without its line break
+    pub header: Range<usize>,
+    /// From the header line up to the `---` separator ending the element
+    pub body: Range<usize>,
+    /// The body followed by the separator and the blank lines after it
+    pub range: Range<usize>,
+    pub subsections: Vec<SubsectionSpan>,
+}
+
+/// A `#### ` subsection of an element
+#[derive(Debug, Clone)]
+pub struct SubsectionSpan {
+    pub kind: SubSection,
+    /// From the header line up to the next subsection or the end of the element body
+    pub range: Range<usize>,
+    /// The lines after the header line
+    pub body: Range<usize>,
+}
+
+impl ElementSpan {
+    pub fn subsection(&self, kind: &SubSection) -> Option<&SubsectionSpan> {
+        self.subsections.iter().find(|subsection| &subsection.kind == kind)
+    }
+
+    /// Range of the text between the header line and the first subsection
+    pub fn description(&self, content: &str) -> Range<usize> {
+        let start = line_end(content, self.header.end);
+        let end = self.subsections.first().map_or(self.body.end, |subsection| subsection.range.start);
+        start..end
+    }
+}
+
+impl SourceMap {
+    /// Records the spans of `content` following the same structure rules as
+    /// `parser::parse_elements`
+    pub fn parse(content: &str) -> Self {
+        let mut map = SourceMap::default();
+        map.sections.push(SectionSpan {
+            name: DEFAULT_SECTION.to_string(),
+            range: 0..content.len(),
+            body_start: 0,
+            has_header: false,
+            element_count: 0,
+        });
+
+        // Whether the current element is still open, i.e. no `---` was seen yet
+        let mut element_open = false;
+        let mut subsection = SubSection::Other(String::new());
+        let mut in_details_block = false;
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let trimmed = line.trim();
+
+            if in_details_block {
+                if trimmed.starts_with("</details>") {
+                    in_details_block = false;
+                }
+                continue;
+            }
+
+            if trimmed == "---" {
+                if element_open {
+                    map.close_element(start);
+                    element_open = false;
+                    if let Some(element) = map.elements.last_mut() {
+                        element.range.end = offset;
+                    }
+                }
+                subsection = SubSection::Other(String::new());
+            } else if let Some(name) = trimmed.strip_prefix("## ") {
+                map.close_element(start);
+                element_open = false;
+                if let Some(section) = map.sections.last_mut() {
+                    section.range.end = start;
+                }
+                map.sections.push(SectionSpan {
+                    name: name.trim().to_string(),
+                    range: start..content.len(),
+                    body_start: offset,
+                    has_header: true,
+                    element_count: 0,
+                });
+                subsection = SubSection::Other(String::new());
+            } else if let Some(name) = trimmed.strip_prefix("### ") {
+                map.close_element(start);
+                let section = map.sections.last_mut().expect("the implicit section always exists");
+                section.element_count += 1;
+                let header_end = start + line.trim_end_matches(['\n', '\r']).len();
+                map.elements.push(ElementSpan {
+                    name: name.trim().to_string(),
+                    section: section.name.clone(),
+                    header: start..header_end,
+                    body: start..content.len(),
+                    range: start..content.len(),
+                    subsections: Vec::new(),
+                });
+                element_open = true;
+                subsection = SubSection::Requirement;
+            } else if let Some(name) = trimmed.strip_prefix("#### ").filter(|_| element_open) {
+                subsection = SubSection::from_str(name.trim());
+                let element = map.elements.last_mut().expect("an element is open");
+                if let Some(previous) = element.subsections.last_mut() {
+                    previous.range.end = start;
+                    previous.body.end = start;
+                }
+                element.subsections.push(SubsectionSpan {
+                    kind: subsection.clone(),
+                    range: start..content.len(),
+                    body: offset..content.len(),
+                });
+            } else if element_open
+                && (subsection == SubSection::Requirement || subsection == SubSection::Details)
+                && trimmed.starts_with("<details")
+            {
+                in_details_block = true;
+            } else if !element_open && trimmed.is_empty() {
+                // Blank lines after a separator belong to the element before it
+                if let Some(element) = map.elements.last_mut() {
+                    if element.range.end == start {
+                        element.range.end = offset;
+                    }
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Ends the body of the last element at `position` unless it was already ended by a separator
+    fn close_element(&mut self, position: usize) {
+        if let Some(element) = self.elements.last_mut() {
+            if element.body.end > position {
+                element.body.end = position;
+                element.range.end = position;
+                if let Some(subsection) = element.subsections.last_mut() {
+                    subsection.range.end = position;
+                    subsection.body.end = position;
+                }
+            }
+        }
+    }
+
+    pub fn element(&self, name: &str) -> Option<&ElementSpan> {
+        self.elements.iter().find(|element| element.name == name)
+    }
+
+    /// The section new elements named `name` are appended to. The implicit
+    /// section only counts when it holds elements.
+    pub fn section(&self, name: &str) -> Option<&SectionSpan> {
+        self.section_index(name).map(|index| &self.sections[index])
+    }
+
+    fn section_index(&self, name: &str) -> Option<usize> {
+        self.sections
+            .iter()
+            .rposition(|section| section.name == name && (section.has_header || section.element_count > 0))
+    }
+}
+
+/// Elements as they were before an edit, keyed by their identifier after it
+#[derive(Debug, Default)]
+pub struct Baseline {
+    elements: BTreeMap<String, Element>,
+    removed: Vec<Element>,
+}
+
+impl Baseline {
+    /// Records the elements of the registry with the relations written in their files
+    pub fn capture(registry: &GraphRegistry) -> Self {
+        let elements = registry
+            .get_all_elements()
+            .into_iter()
+            .filter(|element| !element.identifier.starts_with("__virtual__"))
+            .map(|element| {
+                let mut element = element.clone();
+                element.relations.retain(|relation| relation.user_created);
+                (element.identifier.clone(), element)
+            })
+            .collect();
+        Baseline { elements, removed: Vec::new() }
+    }
+
+    /// Follows an element to its new identifier after a rename or move
+    pub fn rename(&mut self, identifier: &str, new_identifier: &str) {
+        if let Some(element) = self.elements.remove(identifier) {
+            self.elements.insert(new_identifier.to_string(), element);
+        }
+    }
+
+    /// Marks an element as removed from the model
+    pub fn remove(&mut self, identifier: &str) {
+        if let Some(element) = self.elements.remove(identifier) {
+            self.removed.push(element);
+        }
+    }
+}
+
+/// A model file before and after patching
+#[derive(Debug, Clone)]
+pub struct FilePatch {
+    pub file_path: String,
+    pub current: String,
+    pub patched: String,
+}
+
+/// A replacement of `range`; edits are applied in order of position, with
+/// insertions at the same position kept in the order they were added
+#[derive(Debug, Clone)]
+struct TextEdit {
+    range: Range<usize>,
+    text: String,
+}
+
+/// An element block waiting to be inserted at the end of a section
+struct Insertion {
+    section: String,
+    order: usize,
+    block: String,
+}
+
+/// Computes the new content of every file touched by the changes between
+/// `baseline` and `registry`. Files are read relative to `base_dir`; files
+/// that don't exist or are empty are generated from the model.
+pub fn patch_files(
+    registry: &GraphRegistry,
+    baseline: &Baseline,
+    base_dir: &Path,
+) -> Result<Vec<FilePatch>, ReqvireError> {
+    let current: Vec<&Element> = registry
+        .get_all_elements()
+        .into_iter()
+        .filter(|element| !element.identifier.starts_with("__virtual__"))
+        .collect();
+
+    // Files touched by the edit
+    let mut file_paths = BTreeSet::new();
+    for element in &current {
+        match baseline.elements.get(&element.identifier) {
+            Some(before) if !is_changed(before, element) => {}
+            Some(before) => {
+                file_paths.insert(before.file_path.clone());
+                file_paths.insert(element.file_path.clone());
+            }
+            None => {
+                file_paths.insert(element.file_path.clone());
+            }
+        }
+    }
+    for element in &baseline.removed {
+        file_paths.insert(element.file_path.clone());
+    }
+
+    let mut contents = BTreeMap::new();
+    for file_path in &file_paths {
+        let full_file_path = base_dir.join(file_path);
+        let content = if full_file_path.exists() {
+            fs::read_to_string(&full_file_path)?
+        } else {
+            String::new()
+        };
+        contents.insert(file_path.clone(), content);
+    }
+    let maps: HashMap<&String, SourceMap> = contents
+        .iter()
+        .filter(|(_, content)| !content.trim().is_empty())
+        .map(|(file_path, content)| (file_path, SourceMap::parse(content)))
+        .collect();
+
+    // Files that can't be patched because an element is not where the model expects it
+    let mut regenerate: BTreeSet<String> = contents
+        .iter()
+        .filter(|(_, content)| content.trim().is_empty())
+        .map(|(file_path, _)| file_path.clone())
+        .collect();
+    let locate = |element: &Element| maps.get(&element.file_path).and_then(|map| map.element(&element.name));
+
+    let mut edits: BTreeMap<String, Vec<TextEdit>> = BTreeMap::new();
+    let mut insertions: BTreeMap<String, Vec<Insertion>> = BTreeMap::new();
+
+    for element in &baseline.removed {
+        match locate(element) {
+            Some(span) => edits.entry(element.file_path.clone()).or_default().push(TextEdit {
+                range: span.range.clone(),
+                text: String::new(),
+            }),
+            None => {
+                regenerate.insert(element.file_path.clone());
+            }
+        }
+    }
+
+    for element in &current {
+        let Some(before) = baseline.elements.get(&element.identifier) else {
+            insertions.entry(element.file_path.clone()).or_default().push(Insertion {
+                section: element.section.clone(),
+                order: element.section_order_index,
+                block: registry.element_to_markdown_with_context(element, &element.file_path),
+            });
+            continue;
+        };
+        if !is_changed(before, element) {
+            continue;
+        }
+        let Some(span) = locate(before) else {
+            regenerate.insert(before.file_path.clone());
+            regenerate.insert(element.file_path.clone());
+            continue;
+        };
+
+        let content = &contents[&before.file_path];
+        let rewrite_links = before.file_path != element.file_path;
+        let element_edits = element_edits(registry, content, span, before, element, base_dir, rewrite_links);
+        if before.file_path == element.file_path && before.section == element.section {
+            edits.entry(element.file_path.clone()).or_default().extend(element_edits);
+            continue;
+        }
+
+        // Moved elements keep their text, with the edits applied
+        edits.entry(before.file_path.clone()).or_default().push(TextEdit {
+            range: span.range.clone(),
+            text: String::new(),
+        });
+        let block = apply_edits(&content[span.body.clone()], span.body.start, element_edits);
+        insertions.entry(element.file_path.clone()).or_default().push(Insertion {
+            section: element.section.clone(),
+            order: element.section_order_index,
+            block: format!("{}\n", block.trim_end()),
+        });
+    }
+
+    let grouped = registry.group_elements_by_location();
+    let no_elements = HashMap::new();
+    let mut patches = Vec::new();
+    for (file_path, content) in &contents {
+        let patched = match maps.get(file_path) {
+            Some(_) if !regenerate.contains(file_path) => {
+                let mut edited = apply_edits(content, 0, edits.remove(file_path).unwrap_or_default());
+                // Removed elements don't leave blank lines at the end of the file
+                if !content.ends_with("\n\n") && edited.ends_with("\n\n") {
+                    edited = format!("{}\n", edited.trim_end_matches('\n'));
+                }
+                // Blocks are inserted into the edited text, so they are spaced
+                // against what remains of their sections
+                match insertions.remove(file_path) {
+                    Some(mut file_insertions) => {
+                        file_insertions.sort_by_key(|insertion| insertion.order);
+                        let edited_map = SourceMap::parse(&edited);
+                        let insertion_edits = insertion_edits(&edited, &edited_map, file_insertions);
+                        apply_edits(&edited, 0, insertion_edits)
+                    }
+                    None => edited,
+                }
+            }
+            _ => {
+                let markdown = registry.generate_file_markdown(file_path, grouped.get(file_path).unwrap_or(&no_elements));
+                format::apply_formatting_rules(&markdown)
+            }
+        };
+        patches.push(FilePatch {
+            file_path: file_path.clone(),
+            current: content.clone(),
+            patched,
+        });
+    }
+
+    Ok(patches)
+}
+
+/// Writes the files changed between `baseline` and `registry` and returns their number
+pub fn write_changes(registry: &GraphRegistry, baseline: &Baseline, base_dir: &Path) -> Result<usize, ReqvireError> {
+    let mut files_changed = 0;
+    for patch in patch_files(registry, baseline, base_dir)? {
+        if patch.patched != patch.current {
+            let full_file_path = base_dir.join(&patch.file_path);
+            if let Some(parent_dir) = full_file_path.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::write(full_file_path, patch.patched)?;
+            files_changed += 1;
+        }
+    }
+    Ok(files_changed)
+}
+
+fn is_changed(before: &Element, after: &Element) -> bool {
+    before.name != after.name
+        || before.file_path != after.file_path
+        || before.section != after.section
+        || before.content != after.content
+        || before.metadata != after.metadata
+        || relation_keys(before) != relation_keys(after)
+}
+
+/// Relation type and target of the relations written in the element's file
+fn relation_keys(element: &Element) -> Vec<(String, String)> {
+    element
+        .relations
+        .iter()
+        .filter(|relation| relation.user_created)
+        .map(relation_key)
+        .collect()
+}
+
+fn relation_key(relation: &Relation) -> (String, String) {
+    (relation.relation_type.name.to_string(), relation.target.link.as_str().to_string())
+}
+
+/// Relation type and normalized target of a line in a `#### Relations`
+/// subsection of `file_path`, relative to the model root `base_dir`
+pub fn parse_relation_key(line: &str, file_path: &str, base_dir: &Path) -> Option<(String, String)> {
+    let (relation_type, (text, link)) = utils::parse_relation_line(line.trim()).ok()?;
+    let link = if link.starts_with('#') {
+        let file_name = Path::new(file_path).file_name()?.to_string_lossy();
+        format!("{}{}", file_name, link)
+    } else {
+        link
+    };
+    let file_folder = base_dir.join(file_path).parent()?.to_path_buf();
+    let target = utils::normalize_identifier(&ModelContext::new(base_dir), &link, &file_folder).ok()?;
+    let relation = Relation::new(&relation_type, text, &target).ok()?;
+    Some(relation_key(&relation))
+}
+
+/// Edits turning the text of `before` into `after`. With `rewrite_links`
+/// every relation link is written again for the element's new file.
+fn element_edits(
+    registry: &GraphRegistry,
+    content: &str,
+    span: &ElementSpan,
+    before: &Element,
+    after: &Element,
+    base_dir: &Path,
+    rewrite_links: bool,
+) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+
+    if before.name != after.name {
+        edits.push(TextEdit { range: span.header.clone(), text: format!("### {}", after.name) });
+    }
+
+    if before.content != after.content {
+        let (description, details) = split_details(&before.content);
+        let (new_description, new_details) = split_details(&after.content);
+        let description_range = span.description(content);
+        if description.trim() != new_description.trim() {
+            let text = if new_description.trim().is_empty() {
+                "\n".to_string()
+            } else {
+                format!("\n{}\n\n", new_description.trim())
+            };
+            edits.push(TextEdit { range: description_range.clone(), text });
+        }
+        let details_span = span.subsection(&SubSection::Details);
+        if details.map(str::trim) != new_details.map(str::trim) {
+            let text = new_details.map_or(String::new(), |details| format!("#### Details\n\n{}\n\n", details.trim()));
+            let range = match details_span {
+                Some(subsection) => subsection.range.clone(),
+                None => description_range.end..description_range.end,
+            };
+            edits.push(TextEdit { range, text });
+        }
+    }
+
+    if before.metadata != after.metadata {
+        edits.extend(metadata_edits(content, span, before, after));
+    }
+
+    if rewrite_links || relation_keys(before) != relation_keys(after) {
+        edits.extend(relation_edits(registry, content, span, before, after, base_dir, rewrite_links));
+    }
+
+    edits
+}
+
+/// Splits element content into the description and the `#### Details` text
+fn split_details(content: &str) -> (&str, Option<&str>) {
+    const DETAILS_HEADER: &str = "#### Details\n";
+    if let Some(details) = content.strip_prefix(DETAILS_HEADER) {
+        return ("", Some(details));
+    }
+    match content.find(&format!("\n{}", DETAILS_HEADER)) {
+        Some(position) => (&content[..position], Some(&content[position + DETAILS_HEADER.len() + 1..])),
+        None => (content, None),
+    }
+}
+
+fn metadata_edits(content: &str, span: &ElementSpan, before: &Element, after: &Element) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    let mut new_keys: Vec<&String> = after.metadata.keys().filter(|key| !before.metadata.contains_key(*key)).collect();
+    new_keys.sort_by_key(|key| (key.as_str() != "type", key.as_str()));
+
+    let Some(subsection) = span.subsection(&SubSection::Metadata) else {
+        if !new_keys.is_empty() {
+            let mut text = String::from("#### Metadata\n");
+            if !after.metadata.contains_key("type") {
+                text.push_str(&format!("  * type: {}\n", after.element_type.as_str()));
+            }
+            for key in new_keys {
+                text.push_str(&format!("  * {}: {}\n", key, after.metadata[key]));
+            }
+            let position = span.subsection(&SubSection::Relations).map_or(span.body.end, |relations| relations.range.start);
+            text.push_str(blank_line_after(content, position));
+            edits.push(insert_block(content, position, text));
+        }
+        return edits;
+    };
+
+    let mut indent = "  ";
+    let mut last_line_end = subsection.body.start;
+    let mut kept_lines = 0;
+    for (range, line) in lines(content, subsection.body.clone()) {
+        let Some((key, value)) = utils::parse_metadata_line(line) else {
+            continue;
+        };
+        indent = &line[..line.len() - line.trim_start().len()];
+        last_line_end = range.end;
+        match after.metadata.get(&key) {
+            None => edits.push(TextEdit { range, text: String::new() }),
+            Some(new_value) if *new_value != value => {
+                let bullet = &line.trim_start()[..1];
+                edits.push(TextEdit { range, text: format!("{}{} {}: {}\n", indent, bullet, key, new_value) });
+                kept_lines += 1;
+            }
+            Some(_) => kept_lines += 1,
+        }
+    }
+
+    if kept_lines == 0 && new_keys.is_empty() {
+        return vec![remove_subsection(content, subsection)];
+    }
+    if !new_keys.is_empty() {
+        let mut text = line_break_before(content, last_line_end);
+        for key in new_keys {
+            text.push_str(&format!("{}* {}: {}\n", indent, key, after.metadata[key]));
+        }
+        edits.push(TextEdit { range: last_line_end..last_line_end, text });
+    }
+    edits
+}
+
+fn relation_edits(
+    registry: &GraphRegistry,
+    content: &str,
+    span: &ElementSpan,
+    before: &Element,
+    after: &Element,
+    base_dir: &Path,
+    rewrite_links: bool,
+) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    let before_keys = relation_keys(before);
+    let added: Vec<&Relation> = after
+        .relations
+        .iter()
+        .filter(|relation| relation.user_created && !before_keys.contains(&relation_key(relation)))
+        .collect();
+    let render = |indent: &str, relation: &Relation| {
+        format!(
+            "{}* {}: {}\n",
+            indent,
+            relation.relation_type.name,
+            registry.relation_target_to_markdown(relation, &after.file_path)
+        )
+    };
+
+    let Some(subsection) = span.subsection(&SubSection::Relations) else {
+        if !added.is_empty() {
+            let mut text = String::from("#### Relations\n");
+            for relation in &added {
+                text.push_str(&render("  ", relation));
+            }
+            text.push_str(blank_line_after(content, span.body.end));
+            edits.push(insert_block(content, span.body.end, text));
+        }
+        return edits;
+    };
+
+    // Relation lines no longer in the model, in file order
+    let mut indent = "  ";
+    let mut last_line_end = subsection.body.start;
+    let mut removed = Vec::new();
+    let mut kept_lines = 0;
+    for (range, line) in lines(content, subsection.body.clone()) {
+        if !line.trim_start().starts_with("* ") {
+            continue;
+        }
+        indent = &line[..line.len() - line.trim_start().len()];
+        last_line_end = range.end;
+        let Some(key) = parse_relation_key(line, &before.file_path, base_dir) else {
+            kept_lines += 1;
+            continue;
+        };
+        match after.relations.iter().find(|relation| relation.user_created && relation_key(relation) == key) {
+            Some(relation) => {
+                kept_lines += 1;
+                if rewrite_links {
+                    edits.push(TextEdit { range, text: render(indent, relation) });
+                }
+            }
+            None => removed.push(range),
+        }
+    }
+
+    if removed.len() == added.len() {
+        // Replaced targets, e.g. after a rename, stay on their line
+        for (range, relation) in removed.into_iter().zip(&added) {
+            let line = &content[range.clone()];
+            let line_indent = &line[..line.len() - line.trim_start().len()];
+            edits.push(TextEdit { range, text: render(line_indent, relation) });
+        }
+        return edits;
+    }
+    if kept_lines == 0 && added.is_empty() {
+        return vec![remove_subsection(content, subsection)];
+    }
+    for range in removed {
+        edits.push(TextEdit { range, text: String::new() });
+    }
+    if !added.is_empty() {
+        let mut text = line_break_before(content, last_line_end);
+        for relation in &added {
+            text.push_str(&render(indent, relation));
+        }
+        edits.push(TextEdit { range: last_line_end..last_line_end, text });
+    }
+    edits
+}
+
+/// Edits appending element blocks to the end of their sections; sections
+/// missing from the file are added at its end
+fn insertion_edits(content: &str, map: &SourceMap, insertions: Vec<Insertion>) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    let mut new_sections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut by_section: Vec<(usize, Vec<String>)> = Vec::new();
+
+    for insertion in insertions {
+        match map.section_index(&insertion.section) {
+            Some(index) => {
+                match by_section.iter_mut().find(|(existing, _)| *existing == index) {
+                    Some((_, blocks)) => blocks.push(insertion.block),
+                    None => by_section.push((index, vec![insertion.block])),
+                }
+            }
+            None => match new_sections.iter_mut().find(|(name, _)| *name == insertion.section) {
+                Some((_, blocks)) => blocks.push(insertion.block),
+                None => new_sections.push((insertion.section, vec![insertion.block])),
+            },
+        }
+    }
+
+    for (index, blocks) in by_section {
+        let section = &map.sections[index];
+        let position = section.range.end;
+        let mut text = blank_line_before(content, position);
+        let section_text = content[section.body_start.min(position)..position].trim_end();
+        if !section_text.trim().is_empty() && !section_text.ends_with("---") {
+            text.push_str("---\n\n");
+        }
+        text.push_str(&element_blocks(&blocks));
+        if position < content.len() {
+            text.push('\n');
+        }
+        edits.push(TextEdit { range: position..position, text });
+    }
+
+    if !new_sections.is_empty() {
+        let position = content.len();
+        let mut text = blank_line_before(content, position);
+        for (index, (name, blocks)) in new_sections.iter().enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            text.push_str(&format!("## {}\n\n", name));
+            text.push_str(&element_blocks(blocks));
+        }
+        edits.push(TextEdit { range: position..position, text });
+    }
+
+    edits
+}
+
+/// Removes a subsection. When the subsection runs straight into what follows
+/// it, the blank lines before it go too, so the element keeps its spacing.
+fn remove_subsection(content: &str, subsection: &SubsectionSpan) -> TextEdit {
+    let mut start = subsection.range.start;
+    if !content[subsection.range.clone()].ends_with("\n\n") {
+        while start > 0 && content[..start].ends_with("\n\n") {
+            start -= 1;
+        }
+    }
+    TextEdit { range: start..subsection.range.end, text: String::new() }
+}
+
+/// Element blocks each followed by a separator
+fn element_blocks(blocks: &[String]) -> String {
+    blocks
+        .iter()
+        .map(|block| format!("{}\n---\n", block.trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Inserts a subsection at `position`, separated from the text before it by a blank line
+fn insert_block(content: &str, position: usize, block: String) -> TextEdit {
+    let mut text = blank_line_before(content, position);
+    text.push_str(&block);
+    TextEdit { range: position..position, text }
+}
+
+/// Line breaks needed so that text inserted at `position` starts on a new line
+fn line_break_before(content: &str, position: usize) -> String {
+    let before = &content[..position];
+    if before.is_empty() || before.ends_with('\n') {
+        String::new()
+    } else {
+        "\n".to_string()
+    }
+}
+
+/// Line breaks needed so that text inserted at `position` follows a blank line
+fn blank_line_before(content: &str, position: usize) -> String {
+    let before = &content[..position];
+    if before.is_empty() || before.ends_with("\n\n") {
+        String::new()
+    } else if before.ends_with('\n') {
+        "\n".to_string()
+    } else {
+        "\n\n".to_string()
+    }
+}
+
+/// Line break ending a subsection inserted at `position`; the element
+/// separator follows the last subsection directly
+fn blank_line_after(content: &str, position: usize) -> &'static str {
+    if content[position..].starts_with("---") {
+        ""
+    } else {
+        "\n"
+    }
+}
+
+/// Offset just past the line break of the line containing `position`
+fn line_end(content: &str, position: usize) -> usize {
+    content[position..].find('\n').map_or(content.len(), |index| position + index + 1)
+}
+
+/// Lines within `range` with their ranges, line breaks included
+fn lines(content: &str, range: Range<usize>) -> Vec<(Range<usize>, &str)> {
+    let mut offset = range.start;
+    content[range]
+        .split_inclusive('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+            (start..offset, line)
+        })
+        .collect()
+}
+
+/// Applies edits to `text`, which starts at `offset` of the content the edit ranges refer to
+fn apply_edits(text: &str, offset: usize, mut edits: Vec<TextEdit>) -> String {
+    let end = offset + text.len();
+    edits.retain(|edit| edit.range.start >= offset && edit.range.end <= end);
+    // Stable, so insertions at the same position keep their order
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+    let mut result = String::with_capacity(text.len());
+    let mut position = offset;
+    for edit in edits {
+        // Overlapping edits only apply to the text not edited yet
+        let start = edit.range.start.max(position);
+        if edit.range.end < start {
+            continue;
+        }
+        result.push_str(&text[position - offset..start - offset]);
+        result.push_str(&edit.text);
+        position = edit.range.end;
+    }
+    result.push_str(&text[position - offset..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Requirements\n\n<!-- owned by the platform team -->\n\n## Power\n\n### Remote Start\n\nThe vehicle shall start remotely.\n\n#### Metadata\n  * priority: high\n  * type: requirement\n\n#### Relations\n  * derivedFrom: [Access](#access)\n  * satisfiedBy: [start.rs](../src/start.rs)\n\n---\n\n<div class=\"note\">Reviewed</div>\n\n### Access\n\nThe vehicle shall be accessible.\n\n#### Details\n\n<details>\n### Not an element\n</details>\n\n---\n\n## Comfort\n\nSection text.\n";
+
+    #[test]
+    fn test_source_map_records_element_and_subsection_spans() {
+        let map = SourceMap::parse(CONTENT);
+
+        assert_eq!(map.elements.len(), 2);
+        let remote_start = map.element("Remote Start").unwrap();
+        assert_eq!(remote_start.section, "Power");
+        assert_eq!(&CONTENT[remote_start.header.clone()], "### Remote Start");
+        assert!(CONTENT[remote_start.body.clone()].trim_end().ends_with("(../src/start.rs)"));
+        assert!(CONTENT[remote_start.range.clone()].ends_with("---\n\n"));
+        assert_eq!(
+            remote_start.subsections.iter().map(|subsection| subsection.kind.clone()).collect::<Vec<_>>(),
+            vec![SubSection::Metadata, SubSection::Relations]
+        );
+        let metadata = remote_start.subsection(&SubSection::Metadata).unwrap();
+        assert_eq!(&CONTENT[metadata.body.clone()], "  * priority: high\n  * type: requirement\n\n");
+        assert_eq!(
+            &CONTENT[remote_start.description(CONTENT)],
+            "\nThe vehicle shall start remotely.\n\n"
+        );
+
+        // HTML between elements is not part of either element
+        let access = map.element("Access").unwrap();
+        assert!(CONTENT[remote_start.range.end..access.range.start].contains("<div"));
+        assert!(CONTENT[access.subsection(&SubSection::Details).unwrap().body.clone()].contains("### Not an element"));
+
+        assert_eq!(map.section("Comfort").unwrap().element_count, 0);
+        assert!(map.section("Requirements").is_none());
+    }
+
+    #[test]
+    fn test_apply_edits_keeps_untouched_text() {
+        let content = "one\ntwo\nthree\n";
+        let edits = vec![
+            TextEdit { range: 8..8, text: "after two\n".to_string() },
+            TextEdit { range: 4..8, text: String::new() },
+            TextEdit { range: 14..14, text: "four\n".to_string() },
+        ];
+        assert_eq!(apply_edits(content, 0, edits), "one\nafter two\nthree\nfour\n");
+    }
+
+    #[test]
+    fn test_blank_line_after_subsection_unless_separator_follows() {
+        let content = "  * type: requirement\n---\n\n### Next\n";
+        assert_eq!(blank_line_after(content, 22), "");
+        assert_eq!(blank_line_after(content, 28), "\n");
+    }
+
+    #[test]
+    fn test_split_details() {
+        assert_eq!(split_details("Text"), ("Text", None));
+        assert_eq!(split_details("Text\n\n#### Details\nMore"), ("Text\n", Some("More")));
+        assert_eq!(split_details("#### Details\nMore"), ("", Some("More")));
+    }
+}
@@ -0,0 +1,123 @@
+//! Delimited text output (CSV and TSV) for spreadsheet tools
+//!
+//! Fields are quoted following RFC 4180: a field containing the delimiter,
+//! a double quote or a line break is wrapped in double quotes and embedded
+//! quotes are doubled. Records are terminated by CRLF.
+
+use crate::error::ReqvireError;
+use crate::relation::LinkType;
+
+/// Separator used between list values inside a single cell
+pub const LIST_SEPARATOR: &str = "; ";
+
+const RECORD_TERMINATOR: &str = "\r\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+}
+
+impl TableFormat {
+    /// Parses a format name as given on the command line (`csv` or `tsv`)
+    pub fn from_name(name: &str) -> Result<Self, ReqvireError> {
+        match name.to_lowercase().as_str() {
+            "csv" => Ok(TableFormat::Csv),
+            "tsv" => Ok(TableFormat::Tsv),
+            other => Err(ReqvireError::ProcessError(format!(
+                "Unsupported table format '{}' (expected csv or tsv)", other
+            ))),
+        }
+    }
+
+    pub fn delimiter(&self) -> char {
+        match self {
+            TableFormat::Csv => ',',
+            TableFormat::Tsv => '\t',
+        }
+    }
+}
+
+/// Collects a header and rows and renders them as delimited text
+pub struct TableWriter {
+    format: TableFormat,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl TableWriter {
+    pub fn new<S: Into<String>>(format: TableFormat, header: Vec<S>) -> Self {
+        TableWriter {
+            format,
+            header: header.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row, padding or truncating it to the header width
+    pub fn push_row(&mut self, mut row: Vec<String>) {
+        row.resize(self.header.len(), String::new());
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for record in std::iter::once(&self.header).chain(self.rows.iter()) {
+            let fields: Vec<String> = record
+                .iter()
+                .map(|field| escape_field(field, self.format.delimiter()))
+                .collect();
+            output.push_str(&fields.join(&self.format.delimiter().to_string()));
+            output.push_str(RECORD_TERMINATOR);
+        }
+        output
+    }
+}
+
+/// Quotes a field if it contains the delimiter, a double quote or a line break
+pub fn escape_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Joins list values into a single cell
+pub fn join_list<S: AsRef<str>>(values: &[S]) -> String {
+    values.iter().map(|v| v.as_ref()).collect::<Vec<_>>().join(LIST_SEPARATOR)
+}
+
+/// Cell text for a relation target: identifier, path or URL
+pub fn link_text(link: &LinkType) -> String {
+    match link {
+        LinkType::Identifier(id) => id.clone(),
+        LinkType::ExternalUrl(url) => url.clone(),
+        LinkType::InternalPath(path) => path.to_string_lossy().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc4180_quoting() {
+        assert_eq!(escape_field("plain", ','), "plain");
+        assert_eq!(escape_field("a,b", ','), "\"a,b\"");
+        assert_eq!(escape_field("a,b", '\t'), "a,b");
+        assert_eq!(escape_field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("line\nbreak", '\t'), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_rows_are_padded_to_header() {
+        let mut writer = TableWriter::new(TableFormat::Csv, vec!["id", "name", "notes"]);
+        writer.push_row(vec!["a".to_string(), "Name, with comma".to_string()]);
+        assert_eq!(writer.render(), "id,name,notes\r\na,\"Name, with comma\",\r\n");
+
+        let mut writer = TableWriter::new(TableFormat::Tsv, vec!["id", "name"]);
+        writer.push_row(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(writer.render(), "id\tname\r\na\tb\r\n");
+    }
+}
@@ -0,0 +1,303 @@
+//! Theme of the HTML export
+//!
+//! A theme lives in `.reqvire/theme/` at the repository root and may hold:
+//! - `theme.css`, linked after the built-in styles to override them
+//! - `print.css`, linked for printing after the built-in print styles
+//! - `logo.*`, an image shown in the navigation bar
+//! - `header.html`, `footer.html` and `nav.html` fragments placed above and
+//!   below the page content and replacing the built-in navigation bar
+//!
+//! Fragments may use the `{title}`, `{git_commit}`, `{export_date}`,
+//! `{base_url}` and `{logo}` placeholders, where `{base_url}` is the relative
+//! path from the page to the export root. All other files of the theme are
+//! copied to the export so stylesheets and fragments can refer to them.
+//! Without a theme directory the export keeps the built-in look.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use walkdir::WalkDir;
+
+use crate::error::ReqvireError;
+use crate::filesystem;
+use crate::utils;
+
+/// Theme directory relative to the repository root
+pub const THEME_DIR: &str = ".reqvire/theme";
+
+/// Folder of the export the theme files are copied to
+pub const THEME_ASSETS_FOLDER: &str = "reqvire-theme";
+
+const HEADER_FILE: &str = "header.html";
+const FOOTER_FILE: &str = "footer.html";
+const NAVIGATION_FILE: &str = "nav.html";
+const STYLESHEET_FILE: &str = "theme.css";
+const PRINT_STYLESHEET_FILE: &str = "print.css";
+const LOGO_EXTENSIONS: &[&str] = &["svg", "png", "jpg", "jpeg", "gif", "webp"];
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"\{(title|git_commit|export_date|base_url|logo)\}").unwrap();
+}
+
+/// Built-in navigation bar, used when the theme has no `nav.html`
+pub const NAVIGATION_TEMPLATE: &str = include_str!("../templates/navigation.html");
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Directory the theme was loaded from, `None` for the built-in look
+    dir: Option<PathBuf>,
+    header: String,
+    navigation: String,
+    footer: String,
+    /// File name of the logo in the theme directory
+    logo: Option<String>,
+    /// Data URI of the logo, set when fragments embed it instead of linking it
+    embedded_logo: Option<String>,
+    stylesheet: bool,
+    print_stylesheet: bool,
+    git_commit: String,
+    export_date: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            header: String::new(),
+            navigation: NAVIGATION_TEMPLATE.to_string(),
+            footer: String::new(),
+            logo: None,
+            embedded_logo: None,
+            stylesheet: false,
+            print_stylesheet: false,
+            git_commit: String::new(),
+            export_date: String::new(),
+        }
+    }
+}
+
+impl Theme {
+    /// Loads the theme of the repository at `root`, or the built-in look if
+    /// it has no theme directory. `git_commit` is the exported commit.
+    pub fn load(root: &Path, git_commit: &str) -> Result<Self, ReqvireError> {
+        let dir = root.join(THEME_DIR);
+        if !dir.is_dir() {
+            return Ok(Self::default());
+        }
+
+        let read = |name: &str| -> Result<Option<String>, ReqvireError> {
+            let path = dir.join(name);
+            if path.is_file() {
+                Ok(Some(fs::read_to_string(path)?))
+            } else {
+                Ok(None)
+            }
+        };
+
+        let mut logos: Vec<String> = fs::read_dir(&dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| {
+                name.split_once('.').is_some_and(|(stem, extension)| {
+                    stem == "logo" && LOGO_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+                })
+            })
+            .collect();
+        logos.sort();
+
+        Ok(Self {
+            header: read(HEADER_FILE)?.unwrap_or_default(),
+            navigation: read(NAVIGATION_FILE)?.unwrap_or_else(|| NAVIGATION_TEMPLATE.to_string()),
+            footer: read(FOOTER_FILE)?.unwrap_or_default(),
+            logo: logos.into_iter().next(),
+            embedded_logo: None,
+            stylesheet: dir.join(STYLESHEET_FILE).is_file(),
+            print_stylesheet: dir.join(PRINT_STYLESHEET_FILE).is_file(),
+            git_commit: git_commit.to_string(),
+            export_date: export_date(),
+            dir: Some(dir),
+        })
+    }
+
+    /// Header fragment of a page
+    pub fn header(&self, title: &str, base_url: &str) -> String {
+        self.render(&self.header, title, base_url)
+    }
+
+    /// Navigation fragment of a page
+    pub fn navigation(&self, title: &str, base_url: &str) -> String {
+        self.render(&self.navigation, title, base_url)
+    }
+
+    /// Footer fragment of a page
+    pub fn footer(&self, title: &str, base_url: &str) -> String {
+        self.render(&self.footer, title, base_url)
+    }
+
+    /// Links to the theme stylesheets, placed after the built-in styles
+    pub fn stylesheets(&self, base_url: &str) -> String {
+        let mut links = String::new();
+        if self.stylesheet {
+            links.push_str(&format!(
+                "<link rel=\"stylesheet\" href=\"{}{}/{}\">\n",
+                base_url, THEME_ASSETS_FOLDER, STYLESHEET_FILE
+            ));
+        }
+        if self.print_stylesheet {
+            links.push_str(&format!(
+                "<link rel=\"stylesheet\" media=\"print\" href=\"{}{}/{}\">\n",
+                base_url, THEME_ASSETS_FOLDER, PRINT_STYLESHEET_FILE
+            ));
+        }
+        links
+    }
+
+    /// Theme stylesheets inlined in `<style>` elements, for documents that
+    /// are shared without the export folder
+    pub fn inline_stylesheets(&self) -> Result<String, ReqvireError> {
+        let Some(dir) = &self.dir else {
+            return Ok(String::new());
+        };
+        let mut styles = String::new();
+        if self.stylesheet {
+            styles.push_str(&format!("<style>\n{}\n</style>\n", fs::read_to_string(dir.join(STYLESHEET_FILE))?));
+        }
+        if self.print_stylesheet {
+            styles.push_str(&format!("<style media=\"print\">\n{}\n</style>\n", fs::read_to_string(dir.join(PRINT_STYLESHEET_FILE))?));
+        }
+        Ok(styles)
+    }
+
+    /// Embeds the logo in fragments as a data URI instead of linking the
+    /// copied theme file
+    pub fn embed_logo(&mut self) -> Result<(), ReqvireError> {
+        if let (Some(dir), Some(logo)) = (&self.dir, &self.logo) {
+            let path = dir.join(logo);
+            if let Some(media_type) = utils::image_media_type(&path) {
+                self.embedded_logo = Some(utils::data_uri(media_type, &fs::read(path)?));
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies the theme files other than fragments to the export
+    pub fn copy_assets(&self, export_dir: &Path) -> Result<(), ReqvireError> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+            let path = entry.path();
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let fragment = [HEADER_FILE, FOOTER_FILE, NAVIGATION_FILE]
+                .iter()
+                .any(|name| relative == Path::new(name));
+            if path.is_file() && !fragment {
+                filesystem::copy_file_with_structure(path, &export_dir.join(THEME_ASSETS_FOLDER).join(relative))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&self, fragment: &str, title: &str, base_url: &str) -> String {
+        let logo = match (&self.embedded_logo, &self.logo) {
+            (Some(data_uri), _) => format!("<img class=\"reqvire-logo\" src=\"{}\" alt=\"\">", data_uri),
+            (None, Some(logo)) => format!(
+                "<img class=\"reqvire-logo\" src=\"{}{}/{}\" alt=\"\">",
+                base_url, THEME_ASSETS_FOLDER, logo
+            ),
+            (None, None) => String::new(),
+        };
+        let title = utils::escape_html(title);
+
+        // One pass, so placeholders within substituted values stay as they are
+        PLACEHOLDER
+            .replace_all(fragment, |caps: &Captures| match &caps[1] {
+                "title" => title.clone(),
+                "git_commit" => self.git_commit.clone(),
+                "export_date" => self.export_date.clone(),
+                "base_url" => base_url.to_string(),
+                _ => logo.clone(),
+            })
+            .into_owned()
+    }
+}
+
+/// Date of the export as `YYYY-MM-DD` in UTC, taken from `SOURCE_DATE_EPOCH`
+/// when set so exports can be reproduced
+fn export_date() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or_default()
+        });
+    format_date(seconds.div_euclid(86400))
+}
+
+/// Formats days since 1970-01-01 as a proleptic Gregorian date
+fn format_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(11_016), "2000-02-29");
+        assert_eq!(format_date(20_745), "2026-10-19");
+        assert_eq!(format_date(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn test_theme_fragments_and_fallback() {
+        let root = tempfile::tempdir().unwrap();
+        let builtin = Theme::load(root.path(), "abc123").unwrap();
+        assert!(builtin.navigation("Index", "../").contains("<a href=\"../index.html\">Index</a>"));
+        assert_eq!(builtin.header("Index", "../"), "");
+        assert_eq!(builtin.stylesheets("../"), "");
+
+        let dir = root.path().join(THEME_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(HEADER_FILE), "<header>{logo} {title} {git_commit} {export_date}</header>").unwrap();
+        fs::write(dir.join(STYLESHEET_FILE), "body {}").unwrap();
+        fs::write(dir.join("logo.PNG"), "").unwrap();
+        let theme = Theme::load(root.path(), "abc123").unwrap();
+        let header = theme.header("A & B", "../");
+        assert!(theme.header("{logo}{base_url}", "../").contains("> {logo}{base_url} abc123 "));
+        assert!(header.starts_with(
+            "<header><img class=\"reqvire-logo\" src=\"../reqvire-theme/logo.PNG\" alt=\"\"> A &amp; B abc123 "
+        ));
+        assert_eq!(theme.stylesheets(""), "<link rel=\"stylesheet\" href=\"reqvire-theme/theme.css\">\n");
+        assert!(theme.navigation("A", "").contains("reqvire-logo"));
+
+        let export = tempfile::tempdir().unwrap();
+        theme.copy_assets(export.path()).unwrap();
+        assert!(export.path().join("reqvire-theme/theme.css").is_file());
+        assert!(!export.path().join("reqvire-theme/header.html").exists());
+
+        assert_eq!(theme.inline_stylesheets().unwrap(), "<style>\nbody {}\n</style>\n");
+        let mut embedded = theme.clone();
+        embedded.embed_logo().unwrap();
+        assert!(embedded.header("A", "").starts_with("<header><img class=\"reqvire-logo\" src=\"data:image/png;base64,\""));
+    }
+}
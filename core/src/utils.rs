@@ -9,6 +9,7 @@ use regex::Regex;
 use rustc_hash::FxHasher;
 use std::hash::{Hasher};
 use crate::git_commands;
+use crate::context::ModelContext;
 use std::cell::RefCell;
 
 thread_local! {
@@ -42,20 +43,20 @@ macro_rules! info_println {
 
 
 /// Checks if a file should be processed
-pub fn is_requirements_file_by_path(path: &Path, excluded_filename_patterns: &GlobSet) -> bool {
+pub fn is_requirements_file_by_path(context: &ModelContext, path: &Path, excluded_filename_patterns: &GlobSet) -> bool {
     let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
        
-    filename.ends_with(".md") && !is_excluded_by_patterns(path, &excluded_filename_patterns)
+    filename.ends_with(".md") && !is_excluded_by_patterns(context, path, excluded_filename_patterns)
 }
 
 
 /// Checks if a file is excluded based on configured patterns
-pub fn is_excluded_by_patterns(path: &Path, excluded_filename_patterns: &GlobSet) -> bool {
+pub fn is_excluded_by_patterns(context: &ModelContext, path: &Path, excluded_filename_patterns: &GlobSet) -> bool {
     let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
 
-    // Convert absolute path to relative path from git root for pattern matching
+    // Convert absolute path to relative path from the model root for pattern matching
     // This ensures patterns like "external/**/*.md" work correctly regardless of working directory
-    let relative_path = match get_relative_path(&path.to_path_buf()) {
+    let relative_path = match get_relative_path(context, path) {
         Ok(rel_path) => rel_path,
         Err(_) => {
             // If we can't get relative path, fall back to original behavior
@@ -77,11 +78,11 @@ pub fn is_excluded_by_patterns(path: &Path, excluded_filename_patterns: &GlobSet
 }
 
 
-/// Scans the git root folder for markdown files, excluding files based on patterns.
-/// If the current working directory is a subfolder of the git root, only scans within that subfolder.
+/// Scans the scan root of the model for markdown files, excluding files based on patterns.
 /// 
 /// # Arguments
 /// 
+/// * `context` - Location of the model
 /// * `commit` - Optional commit ID to scan files from a specific commit
 /// * `excluded_filename_patterns` - Glob patterns for files to exclude
 /// 
@@ -89,47 +90,30 @@ pub fn is_excluded_by_patterns(path: &Path, excluded_filename_patterns: &GlobSet
 /// 
 /// A vector of paths to the markdown files found
 pub fn scan_markdown_files(
+    context: &ModelContext,
     commit: Option<&str>,
     excluded_filename_patterns: &GlobSet,
 ) -> Vec<PathBuf> {
     match commit {
         Some(commit_id) => {
             scan_markdown_files_from_commit(
+                context,
                 commit_id,
                 excluded_filename_patterns
             )
         }
         None => {
             let mut files = Vec::new();
-            
-            // Get git root directory
-            let git_root = match git_commands::get_git_root_dir() {
-                Ok(dir) => dir,
-                Err(_) => {
-                    debug!("Not in a git repository, using current directory");
-                    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-                }
-            };
-            
-            // Get current working directory
-            let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-            
-            // Determine scan directory - if current directory is within git root but not at git root,
-            // scan only within the current directory subtree
-            let scan_dir = if current_dir.starts_with(&git_root) && current_dir != git_root {
-                current_dir
-            } else {
-                git_root
-            };
-            
+            let scan_dir = context.scan_root();
+
             debug!("Scanning for markdown files in: {}", scan_dir.display());
             
             // Scan all markdown files in the repository or specified subdirectory
-            for entry in WalkDir::new(&scan_dir)
+            for entry in WalkDir::new(scan_dir)
                 .into_iter()
                 .filter_map(Result::ok)
                 .filter(|e| e.path().is_file() && e.path().extension().map_or(false, |ext| ext == "md"))
-                .filter(|e| is_requirements_file_by_path(e.path(), excluded_filename_patterns))
+                .filter(|e| is_requirements_file_by_path(context, e.path(), excluded_filename_patterns))
             {
                 files.push(entry.path().to_path_buf());
             }
@@ -143,25 +127,18 @@ pub fn scan_markdown_files(
 /// Scans the given Git commit for markdown files,
 /// excluding files based on provided patterns.
 /// 
+/// - `context`: Location of the model, whose root is the repository root
 /// - `commit`: The Git commit (e.g. `"HEAD"`) where we want to look for files.
 /// - `excluded_filename_patterns`: Glob patterns for files to exclude
 pub fn scan_markdown_files_from_commit(
+    context: &ModelContext,
     commit: &str,
     excluded_filename_patterns: &GlobSet,
 ) -> Vec<PathBuf> {
     let mut files = Vec::new();
 
-    // Get git root directory
-    let git_root = match git_commands::get_git_root_dir() {
-        Ok(dir) => dir,
-        Err(_) => {
-            debug!("Not in a git repository, using current directory");
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-        }
-    };
-
     // Run git ls-tree command to get all files in the commit
-    let result = git_commands::ls_tree_commit(&commit);
+    let result = git_commands::ls_tree_commit(context.root(), commit);
     let documents_vec = match result {
         Err(e) => {
             eprintln!("Error listing files in commit: {}", e);
@@ -172,9 +149,9 @@ pub fn scan_markdown_files_from_commit(
 
     let matching_paths = documents_vec
         .into_iter() 
-        .map(|p| git_root.join(p))             
+        .map(|p| context.resolve(p))             
         .filter(|p| p.extension().map_or(false, |ext| ext == "md"))
-        .filter(|p| is_requirements_file_by_path(p, excluded_filename_patterns))
+        .filter(|p| is_requirements_file_by_path(context, p, excluded_filename_patterns))
         .collect::<Vec<PathBuf>>();
 
     files.extend(matching_paths);
@@ -183,24 +160,9 @@ pub fn scan_markdown_files_from_commit(
 }
 
 
-/// Gets the relative path of a file from the git repository root
-pub fn get_relative_path(path: &PathBuf) -> Result<PathBuf, ReqvireError> {
-    let git_root = match git_commands::get_git_root_dir() {
-        Ok(dir) => dir,
-        Err(_) => {
-            debug!("Not in a git repository, using current directory");
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-        }
-    };
-    
-    if let Ok(relative) = path.strip_prefix(&git_root) {
-        return Ok(relative.to_path_buf());
-    }
-    
-    Err(ReqvireError::PathError(format!(
-        "Failed to determine relative path: {}",
-        path.display()
-    )))
+/// Gets the relative path of a file from the model root
+pub fn get_relative_path(context: &ModelContext, path: &Path) -> Result<PathBuf, ReqvireError> {
+    context.relative_path(path)
 }
 
 
@@ -248,13 +210,13 @@ pub const EXTERNAL_SCHEMES: &[&str] = &[
     "http://", "https://", "ftp://", "file://", "mailto:", "ssh://", "git://", "data:",
 ];
 
-/// Unified path resolution function that handles git-root-relative paths consistently
+/// Unified path resolution function that handles root-relative paths consistently
 ///
 /// Rules:
-/// - Paths starting with '/' are treated as relative to git repository root
+/// - Paths starting with '/' are treated as relative to the model root
 /// - Other paths are treated as relative to base_path
 /// - External URLs are passed through unchanged
-fn resolve_path_to_absolute(path_part: &str, base_path: &PathBuf) -> Result<PathBuf, ReqvireError> {
+fn resolve_path_to_absolute(context: &ModelContext, path_part: &str, base_path: &Path) -> Result<PathBuf, ReqvireError> {
     // Check for external URLs first
     if EXTERNAL_SCHEMES.iter().any(|scheme| path_part.starts_with(scheme)) {
         return Err(ReqvireError::PathError("External URLs should not be resolved as paths".to_string()));
@@ -264,11 +226,9 @@ fn resolve_path_to_absolute(path_part: &str, base_path: &PathBuf) -> Result<Path
 
 
     if p.is_absolute() {
-        // For paths starting with '/', treat them as relative to git root
-        let git_root = crate::git_commands::get_git_root_dir()
-            .map_err(|e| ReqvireError::PathError(format!("Failed to get git root: {}", e)))?;
+        // For paths starting with '/', treat them as relative to the model root
         let relative_part = p.strip_prefix("/").unwrap_or(p);
-        Ok(git_root.join(relative_part))
+        Ok(context.resolve(relative_part))
     } else {
         // For relative paths, resolve relative to base_path
         let joined_path = base_path.join(p);
@@ -277,7 +237,7 @@ fn resolve_path_to_absolute(path_part: &str, base_path: &PathBuf) -> Result<Path
             Ok(canonical) => Ok(canonical),
             Err(_) => {
                 // Logical path resolution for non-existent files
-                let mut resolved_path = base_path.clone();
+                let mut resolved_path = base_path.to_path_buf();
                 for component in p.components() {
                     match component {
                         std::path::Component::Normal(_) => {
@@ -301,6 +261,7 @@ fn resolve_path_to_absolute(path_part: &str, base_path: &PathBuf) -> Result<Path
 }
 
 pub fn normalize_identifier(
+    context: &ModelContext,
     identifier: &str,
     base_path: &PathBuf,
 ) -> Result<String, ReqvireError> {
@@ -313,18 +274,17 @@ pub fn normalize_identifier(
     }
 
     // 2) Use unified path resolution
-    let full_path = resolve_path_to_absolute(path_part, base_path)?;
+    let full_path = resolve_path_to_absolute(context, path_part, base_path)?;
 
-    // 3) Get git root for normalization
-    let git_root = crate::git_commands::get_git_root_dir()
-        .map_err(|e| ReqvireError::PathError(format!("Failed to get git root: {}", e)))?
+    // 3) Get model root for normalization
+    let git_root = context.root()
         .canonicalize()
         .map_err(|e| ReqvireError::PathError(format!(
-            "Failed to canonicalize git root: {}",
+            "Failed to canonicalize model root: {}",
             e
         )))?;
 
-    // 4) Strip the Git‐root prefix to get relative path
+    // 4) Strip the root prefix to get relative path
     let rel = full_path
         .strip_prefix(&git_root)
         .map_err(|_| ReqvireError::PathError(format!(
@@ -348,9 +308,12 @@ pub fn normalize_identifier(
 }
 
 
+/// Link from `base_path` to `identifier`, where relative base paths and
+/// identifiers are relative to the model root
 pub fn to_relative_identifier(
+    context: &ModelContext,
     identifier: &str,
-    base_path: &PathBuf,
+    base_path: &Path,
     should_normalize_fragment: bool,
 ) -> Result<String, ReqvireError> {
     let (path, fragment_opt) = extract_path_and_fragment(identifier);
@@ -360,17 +323,14 @@ pub fn to_relative_identifier(
     }
 
 
-    let git_root = crate::git_commands::get_git_root_dir()
-        .map_err(|e| ReqvireError::PathError(format!("Failed to get git root: {}", e)))?;
-
-
     let stripped = if path.starts_with('/') {
         &path[1..]
     } else {
         path
     };
     
-    let resolved_path=git_root.join(stripped);
+    let resolved_path = context.resolve(stripped);
+    let base_path = context.resolve(base_path);
     
     let canonical_path = resolved_path.canonicalize().ok();
     let canonical_base = base_path.canonicalize().ok();
@@ -400,6 +360,24 @@ pub fn to_relative_identifier(
 }
 
 
+/// Link from `base_folder` to `identifier`, both relative to the model root,
+/// computed from the paths alone so neither has to exist on disk
+pub fn relative_link(identifier: &str, base_folder: &Path) -> String {
+    if EXTERNAL_SCHEMES.iter().any(|&scheme| identifier.starts_with(scheme)) {
+        return identifier.to_string();
+    }
+    let (path, fragment_opt) = extract_path_and_fragment(identifier);
+    let path = path.trim_start_matches('/');
+    let relative = diff_paths(path, base_folder)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    match fragment_opt {
+        Some(frag) => format!("{}#{}", relative, frag),
+        None => relative,
+    }
+}
+
+
 /// Parses a metadata line and extracts a (key, value) pair if valid.
 /// Expected format: `* key: value` or `- key: value`
 pub fn parse_metadata_line(line: &str) -> Option<(String, String)> {
@@ -511,7 +489,46 @@ pub fn hash_identifier(identifier: &str) -> String {
 pub fn hash_content(content: &str) -> String {
     let mut hasher = FxHasher::default();
     hasher.write(content.as_bytes());
-    format!("{:x}", hasher.finish()).to_string() 
+    format!("{:x}", hasher.finish()).to_string()
+}
+
+/// Media type of an image file, from its extension
+pub fn image_media_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "svg" => Some("image/svg+xml"),
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Escapes text for HTML and XML content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let _ = pulldown_cmark::escape::escape_html(&mut escaped, text);
+    escaped
+}
+
+/// Base64 data URI embedding `bytes` of the given media type
+pub fn data_uri(media_type: &str, bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    format!("data:{};base64,{}", media_type, encoded)
 }
 
 
@@ -646,7 +663,7 @@ mod tests {
 
             fs::write(&full_path, "test").unwrap();
 
-            let result = to_relative_identifier(identifier, base_path,true)
+            let result = to_relative_identifier(&ModelContext::from_current_dir(), identifier, base_path,true)
                 .expect("Failed to to relative identifier");
 
             assert_eq!(
@@ -658,6 +675,20 @@ mod tests {
         }
     }
    
+    #[test]
+    fn test_relative_link_without_files() {
+        let cases = vec![
+            ("specs/new/Moved.md#remote-start", "specs", "new/Moved.md#remote-start"),
+            ("specs/Requirements.md#remote-start", "specs/new", "../Requirements.md#remote-start"),
+            ("/src/main.rs", "specs", "../src/main.rs"),
+            ("Root.md", "", "Root.md"),
+            ("https://example.com/doc.md#Section", "specs", "https://example.com/doc.md#Section"),
+        ];
+        for (identifier, base_folder, expected) in cases {
+            assert_eq!(relative_link(identifier, Path::new(base_folder)), expected, "for '{}'", identifier);
+        }
+    }
+
     #[test]
     fn test_to_relative_identifier_external_links() {
 
@@ -672,7 +703,7 @@ mod tests {
         ];
 
         for identifier in test_cases {
-            let result = to_relative_identifier(identifier, &dummy_path,true)
+            let result = to_relative_identifier(&ModelContext::new(&dummy_path), identifier, &dummy_path,true)
                 .expect("Should return external URL unchanged");
             assert_eq!(
                 result, identifier,
@@ -732,7 +763,7 @@ mod tests {
             if !path.exists() {
                 continue;
             }
-            assert!(is_requirements_file_by_path(&path, &config_with_externals.get_excluded_filename_patterns_glob_set()), 
+            assert!(is_requirements_file_by_path(&ModelContext::from_current_dir(), &path, &config_with_externals.get_excluded_filename_patterns_glob_set()), 
                     "Expected {} to be identified as a requirements file", path_str);
         }
         
@@ -743,7 +774,7 @@ mod tests {
             if !path.exists() {
                 continue;
             }
-            assert!(!is_requirements_file_by_path(&path, &config_with_externals.get_excluded_filename_patterns_glob_set()), 
+            assert!(!is_requirements_file_by_path(&ModelContext::from_current_dir(), &path, &config_with_externals.get_excluded_filename_patterns_glob_set()), 
                     "Expected {} to NOT be identified as a requirements file", path_str);
         }
     }
@@ -805,7 +836,7 @@ mod tests {
         
         for (path, should_exclude) in &test_files {
             let test_path = PathBuf::from(path);
-            let matches_pattern = is_excluded_by_patterns(&test_path, &config_with_externals.get_excluded_filename_patterns_glob_set());                
+            let matches_pattern = is_excluded_by_patterns(&ModelContext::from_current_dir(), &test_path, &config_with_externals.get_excluded_filename_patterns_glob_set());                
 
             if *should_exclude {
                  assert!(
@@ -909,13 +940,13 @@ mod tests {
 
         // 1. External URL should be returned unchanged
         let external_url = "http://example.com/path/to/spec";
-        let result = to_relative_identifier(external_url, &base_path, false)
+        let result = to_relative_identifier(&ModelContext::from_current_dir(), external_url, &base_path, false)
             .expect("Should return external URL as-is");
         assert_eq!(result, external_url, "Failed external URL check");
 
         // 2. Absolute identifier path resolved relative to Git root
         let spec_identifier = "/subfolder/file.yaml";
-        let result = to_relative_identifier(&spec_identifier, &base_path, false)
+        let result = to_relative_identifier(&ModelContext::from_current_dir(), spec_identifier, &base_path, false)
             .expect("Should return relative path inside specifications folder");
         assert_eq!(
             result,
@@ -927,12 +958,22 @@ mod tests {
         let spec_identifier = "/file.yaml";
                 
         let result =
-            to_relative_identifier(&spec_identifier, &specifications_folder, false)
+            to_relative_identifier(&ModelContext::from_current_dir(), spec_identifier, &specifications_folder, false)
                 .expect("Should return relative path inside specifications folder");
         assert_eq!(result, "file.yaml", "Failed same-folder file check");
 
 
     }
+
+    #[test]
+    fn test_data_uri() {
+        assert_eq!(data_uri("text/plain", b""), "data:text/plain;base64,");
+        assert_eq!(data_uri("text/plain", b"f"), "data:text/plain;base64,Zg==");
+        assert_eq!(data_uri("text/plain", b"fo"), "data:text/plain;base64,Zm8=");
+        assert_eq!(data_uri("text/plain", b"foobar"), "data:text/plain;base64,Zm9vYmFy");
+        assert_eq!(image_media_type(Path::new("logo.SVG")), Some("image/svg+xml"));
+        assert_eq!(image_media_type(Path::new("notes.txt")), None);
+    }
 }
 
 /// Diagram utility functions for consistent filtering across the codebase
@@ -0,0 +1,217 @@
+//! Verification method rules for requirements
+//!
+//! Rules select requirements by element type and metadata values and require
+//! that each selected requirement is verified by at least one verification of
+//! every listed verification type, e.g.:
+//!
+//! ```yaml
+//! verification_rules:
+//!   - name: high-criticality
+//!     metadata:
+//!       criticality: high
+//!     requires: [test-verification, analysis-verification]
+//!   - name: user-demonstration
+//!     element_type: user-requirement
+//!     requires: [demonstration-verification]
+//! ```
+
+use crate::element::{Element, ElementType};
+use crate::error::ReqvireError;
+use crate::graph_registry::GraphRegistry;
+use crate::relation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerificationRule {
+    /// Rule name shown in coverage reports
+    pub name: String,
+    /// Element type the rule applies to (any requirement type when omitted)
+    #[serde(default)]
+    pub element_type: Option<String>,
+    /// Metadata values that must all match for the rule to apply
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Verification types that must all be present
+    pub requires: Vec<String>,
+}
+
+/// A rule that a requirement does not fulfil, with the missing verification types
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UnmetRule {
+    pub rule: String,
+    pub missing: Vec<String>,
+}
+
+impl VerificationRule {
+    /// Checks that the rule only references known element and verification types
+    pub fn validate(&self) -> Result<(), ReqvireError> {
+        if let Some(element_type) = &self.element_type {
+            if !matches!(ElementType::from_metadata(element_type), ElementType::Requirement(_)) {
+                return Err(ReqvireError::InitializationError(format!(
+                    "Verification rule '{}' targets '{}' which is not a requirement type",
+                    self.name, element_type
+                )));
+            }
+        }
+        if self.requires.is_empty() {
+            return Err(ReqvireError::InitializationError(format!(
+                "Verification rule '{}' does not require any verification type",
+                self.name
+            )));
+        }
+        for required in &self.requires {
+            if !matches!(ElementType::from_metadata(required), ElementType::Verification(_)) {
+                return Err(ReqvireError::InitializationError(format!(
+                    "Verification rule '{}' requires '{}' which is not a verification type",
+                    self.name, required
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the rule selects the given element
+    pub fn applies_to(&self, element: &Element) -> bool {
+        if !matches!(element.element_type, ElementType::Requirement(_)) {
+            return false;
+        }
+        if let Some(element_type) = &self.element_type {
+            if ElementType::from_metadata(element_type) != element.element_type {
+                return false;
+            }
+        }
+        self.metadata.iter().all(|(key, value)| {
+            element.metadata.get(key).is_some_and(|actual| actual.trim().eq_ignore_ascii_case(value.trim()))
+        })
+    }
+
+    /// Returns the required verification types missing from `present`, in rule order
+    pub fn missing_types(&self, present: &BTreeSet<String>) -> Vec<String> {
+        self.requires
+            .iter()
+            .map(|required| ElementType::from_metadata(required).as_str().to_string())
+            .filter(|required| !present.contains(required))
+            .collect()
+    }
+}
+
+/// Collects the verification types of all verifications directly verifying the element
+fn verification_types_of(registry: &GraphRegistry, element: &Element) -> BTreeSet<String> {
+    element
+        .relations
+        .iter()
+        .filter(|r| relation::is_verification_relation(r.relation_type))
+        .filter_map(|r| match &r.target.link {
+            relation::LinkType::Identifier(id) => registry.get_element(id),
+            _ => None,
+        })
+        .filter(|target| matches!(target.element_type, ElementType::Verification(_)))
+        .map(|target| target.element_type.as_str().to_string())
+        .collect()
+}
+
+/// Evaluates the rules against a single requirement and returns every unmet rule
+pub fn check_element(registry: &GraphRegistry, element: &Element, rules: &[VerificationRule]) -> Vec<UnmetRule> {
+    let applicable: Vec<&VerificationRule> = rules.iter().filter(|rule| rule.applies_to(element)).collect();
+    if applicable.is_empty() {
+        return Vec::new();
+    }
+
+    let present = verification_types_of(registry, element);
+    applicable
+        .into_iter()
+        .filter_map(|rule| {
+            let missing = rule.missing_types(&present);
+            if missing.is_empty() {
+                None
+            } else {
+                Some(UnmetRule { rule: rule.name.clone(), missing })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::RequirementType;
+    use crate::relation::{LinkType, Relation, RelationTarget, RELATION_TYPES};
+
+    fn make_element(id: &str, element_type: ElementType, metadata: &[(&str, &str)]) -> Element {
+        let mut element = Element::new(id, id, "spec.md", "Section", Some(element_type));
+        for (key, value) in metadata {
+            element.metadata.insert(key.to_string(), value.to_string());
+        }
+        element
+    }
+
+    fn rule(name: &str, element_type: Option<&str>, metadata: &[(&str, &str)], requires: &[&str]) -> VerificationRule {
+        VerificationRule {
+            name: name.to_string(),
+            element_type: element_type.map(|t| t.to_string()),
+            metadata: metadata.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            requires: requires.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    fn verify(element: &mut Element, target: &str) {
+        element.relations.push(Relation {
+            relation_type: RELATION_TYPES.get("verifiedBy").unwrap(),
+            target: RelationTarget {
+                text: target.to_string(),
+                link: LinkType::Identifier(target.to_string()),
+            },
+            user_created: true,
+        });
+    }
+
+    #[test]
+    fn test_rule_selection_by_type_and_metadata() {
+        let high = rule("high", None, &[("criticality", "high")], &["test-verification"]);
+        let user = rule("user", Some("user-requirement"), &[], &["demonstration-verification"]);
+
+        let system_high = make_element("a", ElementType::Requirement(RequirementType::System), &[("criticality", "High")]);
+        let system_low = make_element("b", ElementType::Requirement(RequirementType::System), &[("criticality", "low")]);
+        let user_req = make_element("c", ElementType::Requirement(RequirementType::User), &[]);
+
+        assert!(high.applies_to(&system_high));
+        assert!(!high.applies_to(&system_low));
+        assert!(!user.applies_to(&system_high));
+        assert!(user.applies_to(&user_req));
+    }
+
+    #[test]
+    fn test_unmet_rules_list_missing_types() {
+        let mut registry = GraphRegistry::new();
+        let mut requirement = make_element(
+            "spec.md#req",
+            ElementType::Requirement(RequirementType::System),
+            &[("criticality", "high")],
+        );
+        verify(&mut requirement, "spec.md#test");
+        let test = make_element("spec.md#test", ElementType::from_metadata("test-verification"), &[]);
+        registry.register_element(test, "spec.md").unwrap();
+
+        let rules = vec![rule(
+            "high",
+            None,
+            &[("criticality", "high")],
+            &["test-verification", "analysis-verification"],
+        )];
+
+        let unmet = check_element(&registry, &requirement, &rules);
+        assert_eq!(unmet, vec![UnmetRule {
+            rule: "high".to_string(),
+            missing: vec!["analysis-verification".to_string()],
+        }]);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_verification_type() {
+        assert!(rule("ok", Some("requirement"), &[], &["verification"]).validate().is_ok());
+        assert!(rule("bad", None, &[], &["unit-test"]).validate().is_err());
+        assert!(rule("bad", Some("test-verification"), &[], &["test-verification"]).validate().is_err());
+        assert!(rule("empty", None, &[], &[]).validate().is_err());
+    }
+}
@@ -1,11 +1,11 @@
 use crate::element::{Element, ElementType};
-use crate::git_commands;
 use crate::graph_registry::GraphRegistry;
 use crate::relation::{VERIFY_RELATION, VERIFICATION_TRACES_RELATIONS};
+use crate::tabular::{self, TableFormat, TableWriter};
 use crate::utils;
 use serde::Serialize;
-use std::collections::{BTreeMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
 
 #[derive(Debug, Serialize)]
 pub struct VerificationTracesReport {
@@ -254,24 +254,12 @@ impl<'a> VerificationTraceGenerator<'a> {
         let mut diagram = String::new();
 
         // Get Git repository information for creating proper links
-        let repo_root = match git_commands::get_git_root_dir() {
-            Ok(root) => root,
-            Err(_) => PathBuf::from(""),
-        };
-
-        let base_url = match git_commands::get_repository_base_url() {
-            Ok(url) => url,
-            Err(_) => String::new(),
-        };
-
-        let commit_hash = match git_commands::get_commit_hash() {
-            Ok(hash) => hash,
-            Err(_) => String::new(),
-        };
+        let context = &self.registry.context;
+        let repo_root = context.root();
+        let base_url = context.repository_base_url().unwrap_or("");
+        let commit_hash = context.commit_hash().unwrap_or("");
 
-        let has_git_info = !repo_root.as_os_str().is_empty()
-            && !base_url.is_empty()
-            && !commit_hash.is_empty();
+        let has_git_info = !base_url.is_empty() && !commit_hash.is_empty();
 
         // Header with CSS classes (matching diagrams.rs color scheme)
         diagram.push_str("```mermaid\n");
@@ -293,7 +281,7 @@ impl<'a> VerificationTraceGenerator<'a> {
         // Add click handler for verification node
         let verification_click_target = if self.diagrams_with_blobs && has_git_info {
             // Use GitHub blob URLs when diagrams_with_blobs is enabled
-            let relative_id = match utils::get_relative_path(&PathBuf::from(&trace.identifier)) {
+            let relative_id = match utils::get_relative_path(context, Path::new(&trace.identifier)) {
                 Ok(rel_path) => rel_path.to_string_lossy().to_string(),
                 Err(_) => trace.identifier.clone(),
             };
@@ -305,7 +293,7 @@ impl<'a> VerificationTraceGenerator<'a> {
             } else {
                 // Calculate relative path from from_folder to the identifier
                 let from_folder_path = repo_root.join(from_folder);
-                match utils::to_relative_identifier(&trace.identifier, &from_folder_path, false) {
+                match utils::to_relative_identifier(context, &trace.identifier, &from_folder_path, false) {
                     Ok(rel_path) => rel_path,
                     Err(_) => trace.identifier.clone(),
                 }
@@ -339,9 +327,9 @@ impl<'a> VerificationTraceGenerator<'a> {
                 &mut diagram,
                 &mut visited_nodes,
                 &mut visited_edges,
-                &repo_root,
-                &base_url,
-                &commit_hash,
+                repo_root,
+                base_url,
+                commit_hash,
                 has_git_info,
             );
         }
@@ -400,7 +388,7 @@ impl<'a> VerificationTraceGenerator<'a> {
         diagram: &mut String,
         visited_nodes: &mut HashSet<String>,
         visited_edges: &mut HashSet<(String, String, String)>,
-        repo_root: &PathBuf,
+        repo_root: &Path,
         base_url: &str,
         commit_hash: &str,
         has_git_info: bool,
@@ -424,7 +412,7 @@ impl<'a> VerificationTraceGenerator<'a> {
 
             // Add click handler for requirement node
             let click_target = if self.diagrams_with_blobs && has_git_info {
-                let relative_id = match utils::get_relative_path(&PathBuf::from(&node.id)) {
+                let relative_id = match utils::get_relative_path(&self.registry.context, Path::new(&node.id)) {
                     Ok(rel_path) => rel_path.to_string_lossy().to_string(),
                     Err(_) => node.id.clone(),
                 };
@@ -436,7 +424,7 @@ impl<'a> VerificationTraceGenerator<'a> {
                 } else {
                     // Calculate relative path from from_folder to the identifier
                     let from_folder_path = repo_root.join(from_folder);
-                    match utils::to_relative_identifier(&node.id, &from_folder_path, false) {
+                    match utils::to_relative_identifier(&self.registry.context, &node.id, &from_folder_path, false) {
                         Ok(rel_path) => rel_path,
                         Err(_) => node.id.clone(),
                     }
@@ -529,6 +517,54 @@ impl<'a> VerificationTraceGenerator<'a> {
     }
 }
 
+/// Renders the report as a table with one row per verification
+pub fn format_traces_table(report: &VerificationTracesReport, format: TableFormat) -> String {
+    fn collect_ids<'a>(nodes: &'a [RequirementNode], ids: &mut BTreeSet<&'a str>) {
+        for node in nodes {
+            ids.insert(&node.id);
+            collect_ids(&node.children, ids);
+        }
+    }
+
+    let mut writer = TableWriter::new(format, vec![
+        "identifier",
+        "name",
+        "file",
+        "section",
+        "type",
+        "directly_verified_requirements",
+        "traced_requirements",
+        "directly_verified_count",
+        "total_requirements_in_tree",
+    ]);
+
+    for file_verifications in report.files.values() {
+        for section_verifications in file_verifications.sections.values() {
+            let mut sorted_verifications: Vec<_> = section_verifications.verifications.iter().collect();
+            sorted_verifications.sort_by_key(|v| v.section_order_index);
+
+            for trace in sorted_verifications {
+                let mut traced = BTreeSet::new();
+                collect_ids(&trace.trace_tree.requirements, &mut traced);
+                let traced: Vec<&str> = traced.into_iter().collect();
+                writer.push_row(vec![
+                    trace.identifier.clone(),
+                    trace.name.clone(),
+                    trace.file.clone(),
+                    trace.section.clone(),
+                    trace.verification_type.clone(),
+                    tabular::join_list(&trace.directly_verified_requirements),
+                    tabular::join_list(&traced),
+                    trace.directly_verified_count.to_string(),
+                    trace.total_requirements_in_tree.to_string(),
+                ]);
+            }
+        }
+    }
+
+    writer.render()
+}
+
 /// Apply filters to verification traces report
 pub fn apply_filters(
     mut report: VerificationTracesReport,
@@ -0,0 +1,264 @@
+// Search box of the Reqvire HTML export.
+//
+// Builds its controls in the #reqvire-search element and searches the
+// elements of search-index.json, which is loaded on first use. The element's
+// data-root attribute holds the relative path to the export root.
+(function () {
+    'use strict';
+
+    var MAX_RESULTS = 50;
+    var SNIPPET_LENGTH = 160;
+
+    var container = document.getElementById('reqvire-search');
+    if (!container) {
+        return;
+    }
+    var root = container.getAttribute('data-root') || '';
+    var index = null;
+    var loading = null;
+    var selected = -1;
+
+    var style = document.createElement('style');
+    style.textContent =
+        '#reqvire-search{position:relative;margin-left:auto;display:flex;gap:6px;align-items:center;font-size:14px}' +
+        '#reqvire-search input,#reqvire-search select{padding:5px 8px;border:1px solid #ccc;border-radius:3px;font-size:14px;background:#fff;color:#333}' +
+        '#reqvire-search input{width:260px}#reqvire-search select{max-width:160px}' +
+        '#reqvire-search-results{position:absolute;top:100%;right:0;width:560px;max-width:90vw;max-height:70vh;overflow:auto;margin-top:6px;' +
+        'background:#fff;color:#333;border:1px solid #ccc;border-radius:4px;box-shadow:0 4px 12px rgba(0,0,0,.2);z-index:2000}' +
+        '#reqvire-search-results[hidden]{display:none}' +
+        '#reqvire-search-results a{display:block;padding:8px 12px;margin:0;border-bottom:1px solid #eee;border-radius:0;color:#333;text-decoration:none}' +
+        '#reqvire-search-results a:hover,#reqvire-search-results a.selected{background:#eef4fb}' +
+        '#reqvire-search-results .name{font-weight:600;color:#1a6fb7}' +
+        '#reqvire-search-results .location{font-size:12px;color:#777}' +
+        '#reqvire-search-results .snippet{font-size:13px;color:#555}' +
+        '#reqvire-search-results .status{padding:8px 12px;color:#777}' +
+        '#reqvire-search-results mark{background:#fff3b0;color:inherit}';
+    document.head.appendChild(style);
+
+    var input = document.createElement('input');
+    input.type = 'search';
+    input.placeholder = 'Search elements';
+    input.setAttribute('aria-label', 'Search elements');
+    input.autocomplete = 'off';
+    var typeFilter = createSelect('Element type', 'All types');
+    var fileFilter = createSelect('File', 'All files');
+    var results = document.createElement('div');
+    results.id = 'reqvire-search-results';
+    results.hidden = true;
+    container.appendChild(input);
+    container.appendChild(typeFilter);
+    container.appendChild(fileFilter);
+    container.appendChild(results);
+
+    function createSelect(label, all) {
+        var select = document.createElement('select');
+        select.setAttribute('aria-label', label);
+        select.appendChild(new Option(all, ''));
+        select.addEventListener('focus', load);
+        select.addEventListener('change', search);
+        return select;
+    }
+
+    // Pages opened from disk can't fetch files, so they load the index as a script
+    function load() {
+        if (loading) {
+            return loading;
+        }
+        if (location.protocol === 'http:' || location.protocol === 'https:') {
+            loading = fetch(root + 'search-index.json').then(function (response) {
+                if (!response.ok) {
+                    throw new Error(response.status + ' ' + response.statusText);
+                }
+                return response.json();
+            });
+        } else {
+            loading = new Promise(function (resolve, reject) {
+                var script = document.createElement('script');
+                script.src = root + 'search-index.js';
+                script.onload = function () { resolve(window.reqvireSearchIndex); };
+                script.onerror = function () { reject(new Error('search-index.js not found')); };
+                document.head.appendChild(script);
+            });
+        }
+        loading = loading.then(function (data) {
+            index = data;
+            index.elements.forEach(function (element) {
+                element.search = [element[0].toLowerCase(), element[1].toLowerCase(), element[5].toLowerCase()];
+            });
+            index.types.forEach(function (type, position) {
+                typeFilter.appendChild(new Option(type, String(position)));
+            });
+            index.files.forEach(function (file, position) {
+                fileFilter.appendChild(new Option(file, String(position)));
+            });
+            search();
+        }, function (error) {
+            showStatus('Search index unavailable: ' + error.message);
+        });
+        return loading;
+    }
+
+    // Every term has to match; matches in names rank above identifiers and text
+    function score(element, terms) {
+        var total = 0;
+        for (var i = 0; i < terms.length; i++) {
+            var term = terms[i];
+            if (element.search[0].indexOf(term) === 0) {
+                total += 4;
+            } else if (element.search[0].indexOf(term) >= 0) {
+                total += 3;
+            } else if (element.search[1].indexOf(term) >= 0) {
+                total += 2;
+            } else if (element.search[2].indexOf(term) >= 0) {
+                total += 1;
+            } else {
+                return -1;
+            }
+        }
+        return total;
+    }
+
+    function search() {
+        if (!index) {
+            return;
+        }
+        var terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+        var type = typeFilter.value;
+        var file = fileFilter.value;
+        if (terms.length === 0 && type === '' && file === '') {
+            results.hidden = true;
+            return;
+        }
+
+        var matches = [];
+        index.elements.forEach(function (element) {
+            if ((type !== '' && String(element[2]) !== type) || (file !== '' && String(element[3]) !== file)) {
+                return;
+            }
+            var value = score(element, terms);
+            if (value >= 0) {
+                matches.push({ element: element, score: value });
+            }
+        });
+        matches.sort(function (a, b) {
+            return b.score - a.score || a.element[0].localeCompare(b.element[0]);
+        });
+        render(matches, terms);
+    }
+
+    function render(matches, terms) {
+        results.textContent = '';
+        selected = -1;
+        matches.slice(0, MAX_RESULTS).forEach(function (match) {
+            var element = match.element;
+            var link = document.createElement('a');
+            link.href = root + element[4];
+            appendText(link, 'div', 'name', element[0]);
+            appendText(link, 'div', 'location', index.types[element[2]] + ' · ' + element[1]);
+            var snippet = document.createElement('div');
+            snippet.className = 'snippet';
+            highlight(snippet, excerpt(element[5], terms), terms);
+            link.appendChild(snippet);
+            results.appendChild(link);
+        });
+        if (matches.length === 0) {
+            appendText(results, 'div', 'status', 'No matching elements');
+        } else if (matches.length > MAX_RESULTS) {
+            appendText(results, 'div', 'status', (matches.length - MAX_RESULTS) + ' more, refine the search to see them');
+        }
+        results.hidden = false;
+    }
+
+    function showStatus(message) {
+        results.textContent = '';
+        appendText(results, 'div', 'status', message);
+        results.hidden = false;
+    }
+
+    function appendText(parent, tag, className, text) {
+        var node = document.createElement(tag);
+        node.className = className;
+        node.textContent = text;
+        parent.appendChild(node);
+    }
+
+    // Part of the text around the first matching term
+    function excerpt(text, terms) {
+        var lower = text.toLowerCase();
+        var start = 0;
+        for (var i = 0; i < terms.length; i++) {
+            var position = lower.indexOf(terms[i]);
+            if (position >= 0) {
+                start = Math.max(0, position - SNIPPET_LENGTH / 4);
+                break;
+            }
+        }
+        var end = Math.min(text.length, start + SNIPPET_LENGTH);
+        return (start > 0 ? '…' : '') + text.slice(start, end) + (end < text.length ? '…' : '');
+    }
+
+    function highlight(parent, text, terms) {
+        if (terms.length === 0) {
+            parent.textContent = text;
+            return;
+        }
+        var pattern = new RegExp('(' + terms.map(function (term) {
+            return term.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+        }).join('|') + ')', 'gi');
+        text.split(pattern).forEach(function (part, position) {
+            if (position % 2 === 1) {
+                var mark = document.createElement('mark');
+                mark.textContent = part;
+                parent.appendChild(mark);
+            } else {
+                parent.appendChild(document.createTextNode(part));
+            }
+        });
+    }
+
+    function select(position) {
+        var links = results.querySelectorAll('a');
+        if (links.length === 0) {
+            return;
+        }
+        selected = (position + links.length) % links.length;
+        links.forEach(function (link, i) {
+            link.classList.toggle('selected', i === selected);
+        });
+        links[selected].scrollIntoView({ block: 'nearest' });
+    }
+
+    input.addEventListener('focus', load);
+    input.addEventListener('input', search);
+    input.addEventListener('keydown', function (event) {
+        if (event.key === 'ArrowDown') {
+            event.preventDefault();
+            select(selected + 1);
+        } else if (event.key === 'ArrowUp') {
+            event.preventDefault();
+            select(selected - 1);
+        } else if (event.key === 'Enter') {
+            var links = results.querySelectorAll('a');
+            var link = links[Math.max(selected, 0)];
+            if (link) {
+                location.href = link.href;
+            }
+        } else if (event.key === 'Escape') {
+            results.hidden = true;
+            input.blur();
+        }
+    });
+    document.addEventListener('keydown', function (event) {
+        var target = event.target;
+        var editing = target.isContentEditable || /^(INPUT|TEXTAREA|SELECT)$/.test(target.tagName);
+        if (event.key === '/' && !editing) {
+            event.preventDefault();
+            input.focus();
+        }
+    });
+    document.addEventListener('click', function (event) {
+        if (!container.contains(event.target)) {
+            results.hidden = true;
+        }
+    });
+})();
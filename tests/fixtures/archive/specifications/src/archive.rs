@@ -0,0 +1 @@
+// archive writer
@@ -30,8 +30,15 @@ run_test_case() {
 
     TEST_DIR=$(mktemp -d -t reqvire-${test_name}-XXXXXX)
         
-    # Copy fixtures to TMP_DIR
+    # Copy fixtures to TMP_DIR, starting with the shared fixtures from
+    # tests/fixtures named in the test's `fixture` file; later ones win
+    if [[ -f "$test_folder/fixture" ]]; then
+        for fixture in $(cat "$test_folder/fixture"); do
+            cp -a "$ROOT_DIR/fixtures/$fixture/." "$TEST_DIR/"
+        done
+    fi
     cp -a "$test_folder/." "$TEST_DIR/"
+    rm -f "$TEST_DIR/fixture"
     mkdir -p "${TEST_DIR}/output"  
 
     pushd $TEST_DIR  > /dev/null 2>&1
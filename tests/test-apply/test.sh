@@ -0,0 +1,145 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Apply Model Patch
+# --------------------------------------
+# Satisfies: specifications/Verifications/ModelEditingTests.md#apply-model-patch-test
+#
+# Acceptance Criteria:
+# - apply reads an ordered list of operations from a patch file or stdin
+# - Without --fix the changes are previewed as a diff and no file is written
+# - With --fix the changed files are written as format would write them
+# - A failing operation or an invalid edited model leaves all files unchanged
+# - Element types must be built-in or used in the model
+#
+# Test Criteria:
+# - Commands exit with success (0) return code when the patch applies
+# - Commands exit with code 1 and report per-operation status in JSON when it doesn't
+
+# Test 1: Preview without --fix
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" apply patch.json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: apply exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "Found 2 file(s) with changes" \
+    || ! echo "$OUTPUT" | grep -qF "+   ### Remote Stop" \
+    || ! echo "$OUTPUT" | grep -q "Run with --fix to apply these changes"; then
+    echo "❌ FAILED: Missing diff preview"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet); then
+    echo "❌ FAILED: Preview changed files"
+    exit 1
+fi
+
+# Test 2: Per-operation results in JSON, patch read from stdin
+JSON_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" apply --json < patch.json)
+if [ "$(echo "$JSON_OUTPUT" | jq -r '[.operations[] | "\(.op):\(.status)"] | join(" ")')" != "add-element:ok add-relation:ok add-relation:ok update-element:ok move-element:ok remove-relation:ok" ] \
+    || [ "$(echo "$JSON_OUTPUT" | jq -r '.operations[3].element')" != "specifications/Requirements.md#remote-pump-start" ] \
+    || [ "$(echo "$JSON_OUTPUT" | jq -r '"\(.dry_run) \(.applied) \(.files_changed)"')" != "true false 2" ]; then
+    echo "❌ FAILED: Unexpected JSON result"
+    echo "$JSON_OUTPUT"
+    exit 1
+fi
+
+# Test 3: Failing operation stops the batch
+FAILING='[
+  {"op": "remove-relation", "source": "specifications/Requirements.md#remote-operation", "type": "trace", "target": "https://example.com/workshops/2024"},
+  {"op": "add-relation", "source": "specifications/Requirements.md#remote-stop", "type": "derivedFrom", "target": "specifications/Requirements.md#remote-operation"},
+  {"op": "remove-element", "element": "specifications/Requirements.md#remote-start"}
+]'
+set +e
+FAIL_OUTPUT=$(cd "$TEST_DIR" && echo "$FAILING" | "$REQVIRE_BIN" apply --fix --json)
+FAIL_EXIT=$?
+set -e
+
+if [ $FAIL_EXIT -ne 1 ] \
+    || [ "$(echo "$FAIL_OUTPUT" | jq -r '[.operations[].status] | join(" ")')" != "ok error skipped" ] \
+    || ! echo "$FAIL_OUTPUT" | jq -e '.operations[1].error | contains("remote-stop")' > /dev/null \
+    || ! echo "$FAIL_OUTPUT" | jq -e '.applied == false' > /dev/null; then
+    echo "❌ FAILED: Failing operation not reported"
+    echo "$FAIL_OUTPUT"
+    exit 1
+fi
+
+# Test 4: Unknown element types are rejected
+UNKNOWN_TYPE='[{"op": "update-element", "element": "specifications/Requirements.md#remote-start", "type": "requirment"}]'
+set +e
+TYPE_OUTPUT=$(cd "$TEST_DIR" && echo "$UNKNOWN_TYPE" | "$REQVIRE_BIN" apply --fix --json)
+TYPE_EXIT=$?
+set -e
+
+if [ $TYPE_EXIT -ne 1 ] \
+    || ! echo "$TYPE_OUTPUT" | jq -e '.operations[0].error | contains("Unknown element type")' > /dev/null; then
+    echo "❌ FAILED: Unknown element type not reported"
+    echo "$TYPE_OUTPUT"
+    exit 1
+fi
+
+# Test 5: Invalid edited model is not written
+INVALID='[{"op": "remove-element", "element": "specifications/Requirements.md#remote-operation"}]'
+set +e
+INVALID_OUTPUT=$(cd "$TEST_DIR" && echo "$INVALID" | "$REQVIRE_BIN" apply --fix --json)
+INVALID_EXIT=$?
+set -e
+
+if [ $INVALID_EXIT -ne 1 ] \
+    || ! echo "$INVALID_OUTPUT" | jq -e '.errors[0] | contains("Remote Start")' > /dev/null; then
+    echo "❌ FAILED: Validation errors not reported"
+    echo "$INVALID_OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet); then
+    echo "❌ FAILED: Failed patches changed files"
+    (cd "$TEST_DIR" && git diff)
+    exit 1
+fi
+
+# Test 6: Apply with --fix
+set +e
+FIX_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" apply patch.json --fix 2>&1)
+FIX_EXIT=$?
+set -e
+
+if [ $FIX_EXIT -ne 0 ] || ! echo "$FIX_OUTPUT" | grep -q "Changed 2 file(s)"; then
+    echo "❌ FAILED: apply --fix exited with code $FIX_EXIT"
+    echo "$FIX_OUTPUT"
+    exit 1
+fi
+
+REQUIREMENTS="${TEST_DIR}/specifications/Requirements.md"
+VERIFICATIONS="${TEST_DIR}/specifications/Verifications.md"
+if ! grep -qF "### Remote Pump Start" "$REQUIREMENTS" \
+    || ! grep -qF "  * status: approved" "$REQUIREMENTS" \
+    || ! grep -qF "  * satisfiedBy: [main.rs](src/main.rs)" "$REQUIREMENTS" \
+    || grep -qF "Workshop Notes" "$REQUIREMENTS" \
+    || ! grep -qF "## Acceptance Tests" "$VERIFICATIONS" \
+    || ! grep -qF "  * verify: [Remote Pump Start](Requirements.md#remote-pump-start)" "$VERIFICATIONS"; then
+    echo "❌ FAILED: Patch not written"
+    cat "$REQUIREMENTS" "$VERIFICATIONS"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" validate > /dev/null 2>&1); then
+    echo "❌ FAILED: Patched model does not validate"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" format | grep -q "No formatting changes needed"); then
+    echo "❌ FAILED: Patched files need formatting"
+    exit 1
+fi
+
+exit 0
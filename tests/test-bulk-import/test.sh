@@ -0,0 +1,187 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Bulk Import
+# --------------------------------------
+# Satisfies: specifications/Verifications/InterchangeTests.md#bulk-import-test
+#
+# Acceptance Criteria:
+# - import csv and import json create one element per record using a mapping file
+# - Files and sections are chosen by templates over record fields
+# - Parent references are resolved by external ID or element name
+# - The imported model is validated together with the existing model before anything is written
+# - Records sharing an ID are rejected
+# - --dry-run shows the planned files without writing them
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - Elements carry mapped types, metadata and parent relations
+# - Unresolved parent references are reported as skipped
+# - Imported files validate and need no formatting changes
+# - Invalid models and duplicate IDs are rejected with non-zero exit code and no files are written
+
+# Test 1: Dry run plans files without writing them
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import csv legacy/requirements.csv --mapping legacy/csv-mapping.yaml --dry-run --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: import csv --dry-run exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | jq -r '[.files[].path] | join(" ")')" != "specifications/legacy/Monitoring.md specifications/legacy/Alarms.md" ] \
+    || [ "$(echo "$OUTPUT" | jq -r '.dry_run')" != "true" ]; then
+    echo "❌ FAILED: Dry run did not plan the expected files"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ -e "${TEST_DIR}/specifications/legacy" ]; then
+    echo "❌ FAILED: Dry run wrote files"
+    exit 1
+fi
+
+# Test 2: CSV import with templates, metadata and parent references
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import csv legacy/requirements.csv --mapping legacy/csv-mapping.yaml --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: import csv exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | jq -r '.elements')" -ne 4 ] || [ "$(echo "$OUTPUT" | jq -r '.relations')" -ne 2 ]; then
+    echo "❌ FAILED: Expected 4 elements and 2 relations from CSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | jq -r '.skipped_relations[0]')" != "REQ-4 derivedFrom REQ-99 (unknown parent)" ]; then
+    echo "❌ FAILED: Unknown parent reference not reported"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MONITORING="${TEST_DIR}/specifications/legacy/Monitoring.md"
+ALARMS="${TEST_DIR}/specifications/legacy/Alarms.md"
+for EXPECTED in "## Operations" "### Pressure Telemetry" "  * type: requirement" "  * legacy-id: REQ-2" \
+    "\\# Not a heading" "Values are in bar, rounded to 0.1." \
+    "  * derivedFrom: [Remote Monitoring](#remote-monitoring)"; do
+    if ! grep -qF -- "$EXPECTED" "$MONITORING"; then
+        echo "❌ FAILED: Monitoring.md is missing: $EXPECTED"
+        cat "$MONITORING"
+        exit 1
+    fi
+done
+
+# Parent resolved by name across files
+for EXPECTED in "## Unassigned" "  * priority: Medium, reviewed" "  * derivedFrom: [Remote Monitoring](Monitoring.md#remote-monitoring)"; do
+    if ! grep -qF -- "$EXPECTED" "$ALARMS"; then
+        echo "❌ FAILED: Alarms.md is missing: $EXPECTED"
+        cat "$ALARMS"
+        exit 1
+    fi
+done
+
+# Test 3: JSON import of a Jira export
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import json legacy/jira.json --mapping legacy/json-mapping.yaml 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: import json exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+CONTROL="${TEST_DIR}/specifications/jira/Control.md"
+for EXPECTED in "## Backlog" "### Soft Start" "  * type: user-requirement" "  * jira-key: PUMP-11" "  * priority: Major" \
+    "  * labels: control, operator" "  * derivedFrom: [Pump Control](#pump-control)"; do
+    if ! grep -qF -- "$EXPECTED" "$CONTROL"; then
+        echo "❌ FAILED: Control.md is missing: $EXPECTED"
+        cat "$CONTROL"
+        exit 1
+    fi
+done
+
+# Test 4: The imported model validates and is already formatted
+set +e
+VALIDATE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" validate 2>&1)
+VALIDATE_EXIT=$?
+FORMAT_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" format --json 2>&1)
+set -e
+
+if [ $VALIDATE_EXIT -ne 0 ]; then
+    echo "❌ FAILED: Imported model does not validate"
+    echo "$VALIDATE_OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$FORMAT_OUTPUT" | jq '.diffs | length')" -ne 0 ]; then
+    echo "❌ FAILED: Imported files need formatting changes"
+    echo "$FORMAT_OUTPUT"
+    exit 1
+fi
+
+# Test 5: Invalid models are not written
+set +e
+INVALID_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import csv legacy/invalid.csv --output specifications/invalid 2>&1)
+INVALID_EXIT=$?
+set -e
+
+if [ $INVALID_EXIT -eq 0 ] || ! echo "$INVALID_OUTPUT" | grep -q "has no parent relation"; then
+    echo "❌ FAILED: Import of an invalid model should fail with validation errors"
+    echo "$INVALID_OUTPUT"
+    exit 1
+fi
+
+if [ -e "${TEST_DIR}/specifications/invalid" ]; then
+    echo "❌ FAILED: Invalid import wrote files"
+    exit 1
+fi
+
+# Test 6: Records sharing an ID are rejected
+set +e
+DUPLICATE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import csv legacy/duplicates.csv --output specifications/duplicates 2>&1)
+DUPLICATE_EXIT=$?
+set -e
+
+if [ $DUPLICATE_EXIT -eq 0 ] || ! echo "$DUPLICATE_OUTPUT" | grep -qF "'NEED-1' in records 1, 3"; then
+    echo "❌ FAILED: Import of records sharing an ID should fail naming the records"
+    echo "$DUPLICATE_OUTPUT"
+    exit 1
+fi
+
+if [ -e "${TEST_DIR}/specifications/duplicates" ]; then
+    echo "❌ FAILED: Import of duplicate IDs wrote files"
+    exit 1
+fi
+
+# Test 7: Replacing files is validated against the rest of the model
+cp "$MONITORING" "${TEST_DIR}/Monitoring.md.before"
+set +e
+OVERWRITE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import csv legacy/monitoring-update.csv --mapping legacy/csv-mapping.yaml --overwrite 2>&1)
+OVERWRITE_EXIT=$?
+set -e
+
+if [ $OVERWRITE_EXIT -eq 0 ] || ! echo "$OVERWRITE_OUTPUT" | grep -q "Monitoring.md#remote-monitoring"; then
+    echo "❌ FAILED: Replacing an element other files relate to should fail validation"
+    echo "$OVERWRITE_OUTPUT"
+    exit 1
+fi
+
+if ! cmp -s "$MONITORING" "${TEST_DIR}/Monitoring.md.before"; then
+    echo "❌ FAILED: Failed import replaced Monitoring.md"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,54 @@
+#!/bin/bash
+set -euo pipefail
+
+# Test: Change Impact Tree Through Opposite Relations
+# --------------------------------------
+# Satisfies: specifications/Verifications/ChangeImpactTests.md#change-impact-tree-opposite-relations-test
+#
+# Acceptance Criteria:
+# - The impact tree follows relations declared only on the impacted element
+# - derivedFrom and verify relations propagate impact as their derive and verifiedBy opposites
+#
+# Test Criteria:
+# - Shell exits with success (0) return code
+# - The impact tree of the user requirement lists the derived requirement
+# - The impact tree of the derived requirement lists its verification
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && printf 'impact-tree Requirements.md#remote-operation\nexit\n' | "$REQVIRE_BIN" shell 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: shell exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "derive -> Requirements.md#remote-start"; then
+    echo "❌ FAILED: Impact tree does not follow derivedFrom as derive"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "└─ Requirements.md#remote-start (Remote Start)"; then
+    echo "❌ FAILED: Derived requirement missing from impact tree"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "verifiedBy -> Requirements.md#remote-start-test"; then
+    echo "❌ FAILED: Impact tree does not follow verify as verifiedBy"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "└─ Requirements.md#remote-start-test (Remote Start Test)"; then
+    echo "❌ FAILED: Verification missing from impact tree"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+exit 0
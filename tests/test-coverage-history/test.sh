@@ -0,0 +1,246 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Coverage History
+# --------------------------------------
+# Satisfies: specifications/Verifications/ReportsTests.md#coverage-history-test
+#
+# Acceptance Criteria:
+# - Coverage is computed for each commit in the revision range that changes model files
+# - Commits that do not touch model files are skipped
+# - The time series is emitted as CSV by default and as JSON with --json
+# - An SVG trend chart is rendered with --svg
+# - HTML export embeds the trend chart when configured in reqvire.yaml, serve leaves it out
+# - Merged branches count as the merge commit on the first-parent history
+# - Revision ranges starting with '-' are rejected instead of passed to git as options
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - CSV output contains one row per model-changing commit with coverage percentages
+# - JSON output reports the number of skipped commits
+# - SVG output contains one line per metric
+# - Exported coverage page references coverage-history.svg, the served one does not
+# - A merge bringing in model changes is a point, the merged commits are not
+# - Option-like ranges fail on the command line and skip the exported chart, writing no files
+
+cd "${TEST_DIR}"
+
+# Commit that does not touch model files
+echo "notes" > notes.txt
+git add -A :/
+git commit -m "Add notes" > /dev/null 2>&1
+
+# Commit that verifies the remaining requirement
+sed -i 's/^\* verify: \[Import Data\](#import-data)$/* verify: [Import Data](#import-data)\n* verify: [Export Data](#export-data)/' specifications/Requirements.md
+git add -A :/
+git commit -m "Verify export" > /dev/null 2>&1
+
+# Test 1: CSV output
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --history HEAD 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --history exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | head -1 | grep -q "^commit,date,total_leaf_requirements,"; then
+    echo "❌ FAILED: CSV header missing"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+ROWS=$(echo "$OUTPUT" | tail -n +2 | wc -l)
+if [ "$ROWS" -ne 2 ]; then
+    echo "❌ FAILED: Expected 2 CSV rows, got $ROWS"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+FIRST=$(echo "$OUTPUT" | sed -n 2p | cut -d, -f5)
+LAST=$(echo "$OUTPUT" | sed -n 3p | cut -d, -f5)
+if [ "$FIRST" != "50.00" ] || [ "$LAST" != "100.00" ]; then
+    echo "❌ FAILED: Expected leaf coverage 50.00 then 100.00, got $FIRST then $LAST"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 2: JSON output
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --history HEAD --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --history --json exited with code $EXIT_CODE"
+    exit 1
+fi
+
+POINTS=$(echo "$OUTPUT" | jq '.points | length')
+SKIPPED=$(echo "$OUTPUT" | jq '.skipped_commits')
+LAST_COMMIT=$(echo "$OUTPUT" | jq -r '.points[-1].commit')
+if [ "$POINTS" -ne 2 ] || [ "$SKIPPED" -ne 1 ] || [ "$LAST_COMMIT" != "$(git rev-parse HEAD)" ]; then
+    echo "❌ FAILED: Expected 2 points, 1 skipped commit and HEAD last, got $POINTS, $SKIPPED and $LAST_COMMIT"
+    exit 1
+fi
+
+# Test 3: SVG trend chart
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --history HEAD --svg 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --history --svg exited with code $EXIT_CODE"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | head -1 | grep -q "^<svg"; then
+    echo "❌ FAILED: SVG output does not start with <svg"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+LINES=$(echo "$OUTPUT" | grep -c "<polyline")
+if [ "$LINES" -ne 2 ]; then
+    echo "❌ FAILED: Expected 2 polylines in SVG, got $LINES"
+    exit 1
+fi
+
+# Test 4: --svg requires --history
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --svg 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -eq 0 ]; then
+    echo "❌ FAILED: coverage --svg without --history should fail"
+    exit 1
+fi
+
+# Test 5: Merges are points of the first-parent history
+MAIN_BRANCH=$(git rev-parse --abbrev-ref HEAD)
+git checkout -q -b side
+sed -i 's/The system shall export data files\./The system shall export data files as CSV./' specifications/Requirements.md
+git add -A :/
+git commit -m "Refine export" > /dev/null 2>&1
+SIDE_COMMIT=$(git rev-parse HEAD)
+git checkout -q "$MAIN_BRANCH"
+git merge -q --no-ff side -m "Merge side" > /dev/null 2>&1
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --history HEAD --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --history with a merge exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+POINTS=$(echo "$OUTPUT" | jq '.points | length')
+SKIPPED=$(echo "$OUTPUT" | jq '.skipped_commits')
+LAST_COMMIT=$(echo "$OUTPUT" | jq -r '.points[-1].commit')
+if [ "$POINTS" -ne 3 ] || [ "$SKIPPED" -ne 1 ] || [ "$LAST_COMMIT" != "$(git rev-parse HEAD)" ] \
+    || echo "$OUTPUT" | jq -e --arg side "$SIDE_COMMIT" '.points | any(.commit == $side)' > /dev/null; then
+    echo "❌ FAILED: Expected 3 points ending at the merge, 1 skipped commit and no side branch commit"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 6: HTML export embeds the chart
+cat > "${TEST_DIR}/reqvire.yaml" << 'YAML'
+export:
+  coverage_history: HEAD
+YAML
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --output html 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ ! -f "${TEST_DIR}/html/coverage-history.svg" ]; then
+    echo "❌ FAILED: coverage-history.svg not exported"
+    exit 1
+fi
+
+if ! grep -q "coverage-history.svg" "${TEST_DIR}/html/coverage.html"; then
+    echo "❌ FAILED: coverage.html does not embed coverage-history.svg"
+    exit 1
+fi
+
+# Test 7: The served site leaves the chart out
+TEST_PORT=$((8000 + RANDOM % 1000))
+(cd "$TEST_DIR" && exec "$REQVIRE_BIN" serve --host 127.0.0.1 --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1) &
+SERVE_PID=$!
+trap 'kill "$SERVE_PID" 2>/dev/null || true' EXIT
+
+for i in {1..20}; do
+    if curl -s "http://127.0.0.1:$TEST_PORT/" >/dev/null 2>&1; then
+        break
+    fi
+    if [ $i -eq 20 ]; then
+        echo "❌ FAILED: Server did not start within 10 seconds"
+        cat "${TEST_DIR}/serve_output.log"
+        exit 1
+    fi
+    sleep 0.5
+done
+
+COVERAGE_PAGE=$(curl -s "http://127.0.0.1:$TEST_PORT/coverage.html")
+kill "$SERVE_PID" 2>/dev/null || true
+wait "$SERVE_PID" 2>/dev/null || true
+
+if [ -z "$COVERAGE_PAGE" ] || echo "$COVERAGE_PAGE" | grep -q "coverage-history.svg"; then
+    echo "❌ FAILED: served coverage page should not embed the coverage history chart"
+    exit 1
+fi
+
+# Test 8: Option-like revision ranges are rejected
+INJECTED="${TEST_DIR}/output/injected.txt"
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --history="--output=${INJECTED}" 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -eq 0 ] || ! echo "$OUTPUT" | grep -q "Invalid revision range"; then
+    echo "❌ FAILED: coverage --history should reject an option-like range"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+cat > "${TEST_DIR}/reqvire.yaml" << YAML
+export:
+  coverage_history: "--output=${INJECTED}"
+YAML
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --output html 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ] || [ -f "${TEST_DIR}/html/coverage-history.svg" ]; then
+    echo "❌ FAILED: export should skip the chart of an option-like coverage_history range"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ -e "$INJECTED" ]; then
+    echo "❌ FAILED: option-like range was passed to git"
+    exit 1
+fi
+
+exit 0
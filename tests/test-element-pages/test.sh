@@ -0,0 +1,115 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: HTML Export Element Pages
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#export-element-pages-verification
+#
+# Acceptance Criteria:
+# - export writes a page per element below elements/, titled with the element name
+# - The page shows content, metadata and relations grouped by type and direction
+# - The page shows the verification trace and the change impact tree
+# - The page links to the model file page and the files referenced by relations
+# - Element headings of the model file pages link to the element pages
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - Page contents match the model
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --output output 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export exited with $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+OUT="$TEST_DIR/output"
+REQUIREMENT="$OUT/elements/specifications/Requirements/remote-start-pump.html"
+NEED="$OUT/elements/specifications/Requirements/remote-operation.html"
+TEST="$OUT/elements/specifications/Tests/Verifications/remote-start-test.html"
+
+for PAGE in "$REQUIREMENT" "$NEED" "$TEST"; do
+    if [ ! -f "$PAGE" ]; then
+        echo "❌ FAILED: Element page ${PAGE#$OUT/} was not exported"
+        exit 1
+    fi
+done
+
+if [ -n "$(find "$OUT/elements" -name '*.md')" ]; then
+    echo "❌ FAILED: Markdown sources of element pages were exported"
+    exit 1
+fi
+
+expect() {
+    local page="$1" text="$2"
+    if ! grep -qF -- "$text" "$page"; then
+        echo "❌ FAILED: ${page#$OUT/} does not contain: $text"
+        exit 1
+    fi
+}
+
+expect "$REQUIREMENT" '<title>Remote Start (Pump)</title>'
+expect "$REQUIREMENT" '<h1 id="remote-start-(pump)">Remote Start (Pump)</h1>'
+expect "$REQUIREMENT" '<li><strong>Type</strong>: requirement</li>'
+expect "$REQUIREMENT" '<li><strong>Identifier</strong>: <code>specifications/Requirements.md#remote-start-pump</code></li>'
+expect "$REQUIREMENT" 'on a remote command.</p>'
+expect "$REQUIREMENT" '<li><strong>priority</strong>: high</li>'
+expect "$REQUIREMENT" '<div id="reqvire-search" data-root="../../../"></div>'
+
+# Outgoing relations come before incoming ones, grouped by relation type
+RELATIONS=$(sed -n '/<h2 id="outgoing-relations">/,/<h2 id="verification-trace">/p' "$REQUIREMENT")
+EXPECTED_RELATIONS='<h2 id="outgoing-relations">Outgoing Relations</h2>
+<h3 id="derivedfrom">derivedFrom</h3>
+<ul>
+<li><a href="../../../elements/specifications/Requirements/remote-operation.html">Remote Operation</a> (user-requirement)</li>
+</ul>
+<h3 id="satisfiedby">satisfiedBy</h3>
+<ul>
+<li><a href="../../../specifications/src/main.rs">specifications/src/main.rs</a></li>
+</ul>
+<h2 id="incoming-relations">Incoming Relations</h2>
+<h3 id="verifiedby">verifiedBy</h3>
+<ul>
+<li><a href="../../../elements/specifications/Tests/Verifications/remote-start-test.html">Remote Start Test</a> (test-verification)</li>
+</ul>
+<h2 id="verification-trace">Verification Trace</h2>'
+if [ "$RELATIONS" != "$EXPECTED_RELATIONS" ]; then
+    echo "❌ FAILED: Unexpected relations"
+    echo "$RELATIONS"
+    exit 1
+fi
+
+expect "$REQUIREMENT" '<a href="../../../elements/specifications/Tests/Verifications/remote-start-test.html">Remote Start Test</a> (test-verification), verifies it directly</li>'
+expect "$REQUIREMENT" '<a href="../../../specifications/Requirements.html#remote-start-(pump)">specifications/Requirements.html</a>'
+expect "$NEED" '<li><a href="../../../elements/specifications/Tests/Verifications/remote-start-test.html">Remote Start Test</a> (test-verification)</li>'
+
+# Upward trace of the verification
+TRACE=$(sed -n '/<h2 id="verification-trace">/,/<h2 id="change-impact">/p' "$TEST" | tr -d '\n')
+if [[ "$TRACE" != *'<li><a href="../../../../elements/specifications/Requirements/remote-start-pump.html">Remote Start (Pump)</a> (requirement), verified directly<ul><li><a href="../../../../elements/specifications/Requirements/remote-operation.html">Remote Operation</a> (user-requirement)</li></ul>'* ]]; then
+    echo "❌ FAILED: Unexpected verification trace"
+    echo "$TRACE"
+    exit 1
+fi
+
+# Downstream impact of the stakeholder need
+IMPACT=$(sed -n '/<h2 id="change-impact">/,/<h2 id="source-files">/p' "$NEED" | tr -d '\n')
+if [[ "$IMPACT" != *'<li>derive: <a href="../../../elements/specifications/Requirements/remote-start-pump.html">Remote Start (Pump)</a> (requirement)<ul><li>verifiedBy: <a href="../../../elements/specifications/Tests/Verifications/remote-start-test.html">Remote Start Test</a> (test-verification)</li></ul>'* ]]; then
+    echo "❌ FAILED: Unexpected change impact tree"
+    echo "$IMPACT"
+    exit 1
+fi
+expect "$TEST" 'A change of this element impacts no other element.'
+
+HEADING=$(grep -A1 -F '<h3 id="remote-start-(pump)">' "$OUT/specifications/Requirements.html" | tail -1)
+if [ "$HEADING" != '<p class="element-details"><a href="../elements/specifications/Requirements/remote-start-pump.html">Element details</a></p>' ]; then
+    echo "❌ FAILED: Element heading does not link to the element page"
+    echo "$HEADING"
+    exit 1
+fi
+expect "$OUT/specifications/Tests/Verifications.html" '<a href="../../elements/specifications/Tests/Verifications/remote-start-test.html">Element details</a>'
+
+exit 0
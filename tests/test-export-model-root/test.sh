@@ -0,0 +1,83 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Export From An Explicit Model Root
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#explicit-model-root-verification
+#
+# Acceptance Criteria:
+# - Exports of the whole repository and of a subdirectory can run at the same time
+# - Both exports produce the same pages as when run alone
+# - export leaves no scratch git repository in the output
+# - export does not modify the model files
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - Exported files match the files of a sequential export
+
+run_export() {
+    local dir="$1" output="$2"
+    (cd "$dir" && "$REQVIRE_BIN" export --output "$output") > "$output.log" 2>&1
+}
+
+OUT_DIR=$(mktemp -d)
+trap 'rm -rf "$OUT_DIR"' EXIT
+
+# Sequential exports for reference
+run_export "$TEST_DIR" "$OUT_DIR/root-alone" || { echo "❌ FAILED: root export failed"; cat "$OUT_DIR/root-alone.log"; exit 1; }
+run_export "$TEST_DIR/specifications" "$OUT_DIR/sub-alone" || { echo "❌ FAILED: subdirectory export failed"; cat "$OUT_DIR/sub-alone.log"; exit 1; }
+
+# Concurrent exports
+run_export "$TEST_DIR" "$OUT_DIR/root" & ROOT_PID=$!
+run_export "$TEST_DIR/specifications" "$OUT_DIR/sub" & SUB_PID=$!
+run_export "$TEST_DIR" "$OUT_DIR/root-again" & AGAIN_PID=$!
+
+for job in "$ROOT_PID:root" "$SUB_PID:sub" "$AGAIN_PID:root-again"; do
+    if ! wait "${job%%:*}"; then
+        echo "❌ FAILED: concurrent export ${job#*:} failed"
+        cat "$OUT_DIR/${job#*:}.log"
+        exit 1
+    fi
+done
+
+list_files() {
+    (cd "$1" && find . -type f | sort)
+}
+
+for pair in "root:root-alone" "root-again:root-alone" "sub:sub-alone"; do
+    if ! diff <(list_files "$OUT_DIR/${pair%%:*}") <(list_files "$OUT_DIR/${pair#*:}") > /dev/null; then
+        echo "❌ FAILED: concurrent export ${pair%%:*} differs from sequential export"
+        diff <(list_files "$OUT_DIR/${pair%%:*}") <(list_files "$OUT_DIR/${pair#*:}") || true
+        exit 1
+    fi
+done
+
+for page in specifications/Requirements.html elements/specifications/Requirements/remote-start-pump.html; do
+    if ! grep -q "Remote Start (Pump)" "$OUT_DIR/root/$page"; then
+        echo "❌ FAILED: $page is missing from the root export"
+        exit 1
+    fi
+done
+
+# Subdirectory exports are rooted at the subdirectory
+if [ ! -f "$OUT_DIR/sub/Requirements.html" ] || [ ! -f "$OUT_DIR/sub/elements/Requirements/remote-start-pump.html" ]; then
+    echo "❌ FAILED: subdirectory export is not rooted at the subdirectory"
+    list_files "$OUT_DIR/sub"
+    exit 1
+fi
+
+for output in root sub root-again; do
+    if [ -e "$OUT_DIR/$output/.git" ]; then
+        echo "❌ FAILED: export $output contains a git repository"
+        exit 1
+    fi
+done
+
+CHANGES=$(cd "$TEST_DIR" && git status --porcelain -- specifications)
+if [ -n "$CHANGES" ]; then
+    echo "❌ FAILED: export modified the model files"
+    echo "$CHANGES"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,112 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Single-File HTML Export
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#export-single-file-verification
+#
+# Acceptance Criteria:
+# - export --single-file writes the whole model to one HTML file and nothing else
+# - Files, sections and elements appear in file and section order with numbered headings
+# - The table of contents links to every numbered heading
+# - Relation links between model files point to anchors within the document
+# - Links to other repository files point to the repository
+# - Styles, theme, local images and the traceability matrix are embedded
+# - Diagrams are rendered to SVG with their nodes linking to anchors, and no scripts are loaded
+# - --single-file is rejected for formats other than html
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - Document contents and anchors match the model
+
+expect() {
+    if ! grep -qF -- "$1" "$DOC"; then
+        echo "❌ FAILED: document does not contain: $1"
+        exit 1
+    fi
+}
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --single-file delivery/spec.html 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export --single-file exited with $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+DOC="$TEST_DIR/delivery/spec.html"
+if [ "$(ls "$TEST_DIR/delivery")" != "spec.html" ]; then
+    echo "❌ FAILED: export --single-file wrote more than the document"
+    ls "$TEST_DIR/delivery"
+    exit 1
+fi
+
+# Title from reqvire.yaml and the theme, with the logo embedded
+expect "<title>Pump Controller Specification</title>"
+expect "<span>DOC-0042 | Pump Controller Specification</span>"
+expect '<img class="reqvire-logo" src="data:image/svg+xml;base64,'
+expect ".company-header { color: #004080; }"
+expect ".reqvire-nav {"
+
+# Numbered headings in file and section order
+expect '<h1 id="specifications/Requirements.md"><span class="section-number">1</span>Pump Requirements</h1>'
+expect '<h2 id="specifications/Requirements.md--stakeholder-needs"><span class="section-number">1.1</span>Stakeholder Needs</h2>'
+expect '<h3 id="specifications/Requirements.md--remote-operation"><span class="section-number">1.1.1</span>Remote Operation</h3>'
+expect '<h2 id="specifications/Requirements.md--system-requirements"><span class="section-number">1.2</span>System Requirements</h2>'
+expect '<h3 id="specifications/Requirements.md--remote-start-pump"><span class="section-number">1.2.1</span>Remote Start (Pump)</h3>'
+expect '<h1 id="specifications/Tests/Verifications.md"><span class="section-number">2</span>Verifications</h1>'
+expect '<h3 id="specifications/Tests/Verifications.md--remote-start-test"><span class="section-number">2.1.1</span>Remote Start Test</h3>'
+expect '<h1 id="traceability-matrix"><span class="section-number">3</span>Traceability Matrix</h1>'
+
+# Table of contents
+expect '<li class="toc-level-1"><a href="#specifications/Requirements.md">1 Pump Requirements</a></li>'
+expect '<li class="toc-level-3"><a href="#specifications/Requirements.md--remote-start-pump">1.2.1 Remote Start (Pump)</a></li>'
+expect '<li class="toc-level-1"><a href="#traceability-matrix">3 Traceability Matrix</a></li>'
+
+# Links within the document and to the repository
+expect '<a href="#specifications/Requirements.md--remote-start-pump">Remote Start (Pump)</a>'
+expect '<a href="#specifications/Requirements.md--remote-operation">Remote Operation</a>'
+COMMIT=$(cd "$TEST_DIR" && git rev-parse HEAD)
+expect "<a href=\"https://dummy.example.com/dummy-repo/blob/$COMMIT/specifications/src/main.rs\">main.rs</a>"
+
+# Embedded image, matrix and diagrams
+if ! grep -qE '<img src="data:image/svg\+xml;base64,[^"]+" alt="Pump"' "$DOC"; then
+    echo "❌ FAILED: local image is not embedded"
+    exit 1
+fi
+expect '<div class="reqvire-matrix"><img src="data:image/svg+xml;base64,'
+expect '<svg xmlns="http://www.w3.org/2000/svg" class="reqvire-diagram"'
+expect '<a href="#specifications/Requirements.md--remote-start-pump"><g><rect'
+if grep -qE '<script|```mermaid|class="mermaid"' "$DOC"; then
+    echo "❌ FAILED: document depends on the Mermaid script"
+    exit 1
+fi
+
+# Every anchor link has a target in the document
+for anchor in $(grep -oE '(href=|click [^ ]+ )"#[^"]+"' "$DOC" | grep -oE '#[^"]+' | sort -u); do
+    if ! grep -qF "id=\"${anchor#\#}\"" "$DOC"; then
+        echo "❌ FAILED: no target for anchor $anchor"
+        exit 1
+    fi
+done
+
+# No links to exported pages remain
+if grep -qE 'href="[^"#:]+\.(md|html)' "$DOC"; then
+    echo "❌ FAILED: document links to other pages"
+    grep -oE 'href="[^"#:]+\.(md|html)[^"]*"' "$DOC"
+    exit 1
+fi
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json --single-file spec.json 2>&1)
+EXIT_CODE=$?
+set -e
+if [ $EXIT_CODE -eq 0 ]; then
+    echo "❌ FAILED: --single-file should be rejected for the json format"
+    exit 1
+fi
+
+exit 0
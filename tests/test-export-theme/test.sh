@@ -0,0 +1,104 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: HTML Export Theme
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#export-theme-verification
+#
+# Acceptance Criteria:
+# - export applies the header, footer and navigation fragments of .reqvire/theme/
+# - Fragment placeholders are replaced with title, git commit, export date, base URL and logo
+# - Theme and print stylesheets are linked after the built-in styles
+# - Theme files other than fragments are copied to the export
+# - Without a theme directory the export keeps the built-in navigation and adds no theme files
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - Page contents and exported files match the theme
+
+run_export() {
+    set +e
+    OUTPUT=$(cd "$TEST_DIR" && SOURCE_DATE_EPOCH=1792368000 "$REQVIRE_BIN" export --output "$1" 2>&1)
+    EXIT_CODE=$?
+    set -e
+
+    if [ $EXIT_CODE -ne 0 ]; then
+        echo "❌ FAILED: export exited with $EXIT_CODE"
+        echo "$OUTPUT"
+        exit 1
+    fi
+}
+
+expect() {
+    local page="$1" text="$2"
+    if ! grep -qF -- "$text" "$page"; then
+        echo "❌ FAILED: ${page#$TEST_DIR/} does not contain: $text"
+        exit 1
+    fi
+}
+
+COMMIT=$(cd "$TEST_DIR" && git rev-parse HEAD)
+run_export themed
+OUT="$TEST_DIR/themed"
+PAGE="$OUT/specifications/Requirements.html"
+ELEMENT="$OUT/elements/specifications/Requirements/remote-start-pump.html"
+
+expect "$OUT/index.html" "<span>DOC-0042 | index | Commit $COMMIT | Exported 2026-10-19</span>"
+expect "$OUT/index.html" '<img class="reqvire-logo" src="reqvire-theme/logo.svg" alt="">'
+expect "$OUT/index.html" '<link rel="stylesheet" href="reqvire-theme/theme.css">'
+expect "$OUT/index.html" '<link rel="stylesheet" media="print" href="reqvire-theme/print.css">'
+
+expect "$PAGE" "<span>DOC-0042 | Requirements | Commit $COMMIT | Exported 2026-10-19</span>"
+expect "$PAGE" '<img class="reqvire-logo" src="../reqvire-theme/logo.svg" alt="">'
+expect "$PAGE" '<link rel="stylesheet" href="../reqvire-theme/theme.css">'
+expect "$PAGE" '<nav class="company-nav"><a href="../index.html">Home</a> <a href="../traces.html">Traces</a><div id="reqvire-search" data-root="../"></div></nav>'
+expect "$PAGE" '<footer class="company-footer">ACME confidential | <a href="../index.html">Index</a></footer>'
+
+expect "$ELEMENT" '<span>DOC-0042 | Remote Start (Pump) | Commit'
+expect "$ELEMENT" '<link rel="stylesheet" href="../../../reqvire-theme/theme.css">'
+
+# The theme navigation replaces the built-in one
+if grep -q 'class="reqvire-nav"' "$PAGE"; then
+    echo "❌ FAILED: Built-in navigation is still present"
+    exit 1
+fi
+
+# Theme stylesheets follow the built-in styles so they override them
+STYLES_LINE=$(grep -n '^<style>' "$PAGE" | head -1 | cut -d: -f1)
+THEME_LINE=$(grep -n 'reqvire-theme/theme.css' "$PAGE" | head -1 | cut -d: -f1)
+if [ "$THEME_LINE" -le "$STYLES_LINE" ]; then
+    echo "❌ FAILED: Theme stylesheet is linked before the built-in styles"
+    exit 1
+fi
+
+for FILE in theme.css print.css logo.svg fonts/brand.woff2; do
+    if [ ! -f "$OUT/reqvire-theme/$FILE" ]; then
+        echo "❌ FAILED: Theme file $FILE was not exported"
+        exit 1
+    fi
+done
+for FILE in header.html footer.html nav.html; do
+    if [ -e "$OUT/reqvire-theme/$FILE" ]; then
+        echo "❌ FAILED: Theme fragment $FILE was exported"
+        exit 1
+    fi
+done
+
+# Without a theme the built-in look is kept
+rm -rf "$TEST_DIR/.reqvire"
+run_export plain
+OUT="$TEST_DIR/plain"
+PAGE="$OUT/specifications/Requirements.html"
+
+expect "$PAGE" '<nav class="reqvire-nav">'
+expect "$PAGE" '<a href="../matrix.html">Matrix</a>'
+if grep -q 'DOC-0042\|reqvire-theme\|<img class="reqvire-logo"' "$PAGE"; then
+    echo "❌ FAILED: Theme content without a theme directory"
+    exit 1
+fi
+if [ -e "$OUT/reqvire-theme" ]; then
+    echo "❌ FAILED: Theme folder exported without a theme directory"
+    exit 1
+fi
+
+exit 0
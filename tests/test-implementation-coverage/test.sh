@@ -0,0 +1,114 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Implementation Coverage Report
+# --------------------------------------
+# Satisfies: specifications/Verifications/ReportsTests.md#implementation-coverage-report-test
+#
+# Acceptance Criteria:
+# - `reqvire coverage --satisfaction` lists satisfied and unsatisfied leaf requirements
+# - Satisfying targets are broken down into elements and files
+# - `reqvire matrix --satisfaction` produces Markdown, JSON and SVG matrices with implementation columns
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - JSON summary counts match the fixture model
+# - Matrix outputs use satisfaction wording
+
+# Test 1: JSON implementation coverage report
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --satisfaction --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --satisfaction --json exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+TOTAL=$(echo "$OUTPUT" | jq '.summary.total_leaf_requirements')
+SATISFIED=$(echo "$OUTPUT" | jq '.summary.satisfied_leaf_requirements')
+UNSATISFIED=$(echo "$OUTPUT" | jq '.summary.unsatisfied_leaf_requirements')
+BY_ELEMENTS=$(echo "$OUTPUT" | jq '.summary.satisfied_by_elements')
+BY_FILES=$(echo "$OUTPUT" | jq '.summary.satisfied_by_files')
+
+if [ "$TOTAL" -ne 3 ] || [ "$SATISFIED" -ne 2 ] || [ "$UNSATISFIED" -ne 1 ]; then
+    echo "❌ FAILED: Expected 3 leaf requirements (2 satisfied, 1 unsatisfied), got $TOTAL ($SATISFIED, $UNSATISFIED)"
+    exit 1
+fi
+
+if [ "$BY_ELEMENTS" -ne 1 ] || [ "$BY_FILES" -ne 1 ]; then
+    echo "❌ FAILED: Expected 1 requirement satisfied by elements and 1 by files, got $BY_ELEMENTS and $BY_FILES"
+    exit 1
+fi
+
+FILE_KIND=$(echo "$OUTPUT" | jq -r '.satisfied_leaf_requirements.files[][] | select(.name == "Entry Point Requirement") | .satisfied_by[0].kind')
+if [ "$FILE_KIND" != "file" ]; then
+    echo "❌ FAILED: Expected 'file' target kind for Entry Point Requirement, got '$FILE_KIND'"
+    exit 1
+fi
+
+UNSATISFIED_NAME=$(echo "$OUTPUT" | jq -r '.unsatisfied_leaf_requirements.files[][] | .name')
+if [ "$UNSATISFIED_NAME" != "Unimplemented Requirement" ]; then
+    echo "❌ FAILED: Expected 'Unimplemented Requirement' to be unsatisfied, got '$UNSATISFIED_NAME'"
+    exit 1
+fi
+
+# Test 2: Text implementation coverage report
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --satisfaction 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --satisfaction exited with code $EXIT_CODE"
+    exit 1
+fi
+
+for EXPECTED in "# Implementation Coverage Report" "Satisfied by elements:" "Satisfied by files:" "## Unsatisfied Leaf Requirements"; do
+    if ! echo "$OUTPUT" | grep -q "$EXPECTED"; then
+        echo "❌ FAILED: Text report missing '$EXPECTED'"
+        echo "$OUTPUT"
+        exit 1
+    fi
+done
+
+# Test 3: Implementation matrix in all formats
+set +e
+MARKDOWN=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --satisfaction 2>&1)
+JSON=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --satisfaction --json 2>&1)
+SVG=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --satisfaction --svg 2>&1)
+set -e
+
+if ! echo "$MARKDOWN" | grep -q "| Requirement | Satisfied |"; then
+    echo "❌ FAILED: Markdown matrix missing 'Satisfied' status column"
+    echo "$MARKDOWN"
+    exit 1
+fi
+
+if ! echo "$MARKDOWN" | grep -q "main.rs"; then
+    echo "❌ FAILED: Markdown matrix missing file column for main.rs"
+    exit 1
+fi
+
+TARGETS=$(echo "$JSON" | jq -r '[.targets[].id] | sort | join(",")')
+if [ "$TARGETS" != "specifications/Requirements.md#parser-component,src/main.rs" ]; then
+    echo "❌ FAILED: Unexpected matrix targets: $TARGETS"
+    exit 1
+fi
+
+STATUS=$(echo "$JSON" | jq '.satisfactionStatus["specifications/Requirements.md#parent-requirement"]')
+if [ "$STATUS" != "false" ]; then
+    echo "❌ FAILED: Parent requirement should roll up as unsatisfied, got $STATUS"
+    exit 1
+fi
+
+if ! echo "$SVG" | grep -q "<svg" || ! echo "$SVG" | grep -q ">Satisfied</text>"; then
+    echo "❌ FAILED: SVG matrix missing or without 'Satisfied' header"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,159 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Canonical JSON Model
+# --------------------------------------
+# Satisfies: specifications/Verifications/InterchangeTests.md#json-model-round-trip-test
+#
+# Acceptance Criteria:
+# - export --format json writes a versioned canonical model document
+# - The document holds pages, sections with section_order, elements with metadata and content,
+#   and user-created relations only
+# - import json rebuilds the markdown files from the document without losses
+# - Transformed documents are written back with relations following renamed elements
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - Exporting and importing a formatted model reproduces the files byte for byte
+# - Existing files are only replaced with --overwrite
+# - Documents with a newer format version are rejected
+# - An output file named html is written like any other, a missing output is rejected
+
+# Test 1: Export the model
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json --output output/model.json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export --format json exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MODEL="${TEST_DIR}/output/model.json"
+if [ "$(jq -r '"\(.format) \(.version)"' "$MODEL")" != "reqvire-model 1" ]; then
+    echo "❌ FAILED: Missing format and version"
+    exit 1
+fi
+
+REQUIREMENTS=$(jq '.files[] | select(.path == "specifications/Requirements.md")' "$MODEL")
+if [ "$(echo "$REQUIREMENTS" | jq -r '[.sections[] | "\(.section_order):\(.name)"] | join(",")')" != "0:Stakeholder Needs,1:System Requirements" ] \
+    || ! echo "$REQUIREMENTS" | jq -e '.page | startswith("# Pump Requirements")' > /dev/null \
+    || ! echo "$REQUIREMENTS" | jq -e '.sections[0].content == "Needs collected during the 2024 workshops."' > /dev/null; then
+    echo "❌ FAILED: Pages and sections not exported"
+    echo "$REQUIREMENTS"
+    exit 1
+fi
+
+ELEMENT=$(echo "$REQUIREMENTS" | jq '.sections[0].elements[0]')
+if [ "$(echo "$ELEMENT" | jq -c '[.type, .metadata, .relations[0]]')" != '["user-requirement",{"owner":"Operations","priority":"high"},{"type":"trace","kind":"url","target":"https://example.com/workshops/2024","text":"Workshop Notes"}]' ]; then
+    echo "❌ FAILED: Element type, metadata or URL relation not exported"
+    echo "$ELEMENT"
+    exit 1
+fi
+
+# Only user-created relations: no propagated derive, verifiedBy or satisfy opposites
+RELATIONS=$(jq -r '[.files[].sections[].elements[].relations[] | "\(.type):\(.kind):\(.target)"] | join(" ")' "$MODEL")
+EXPECTED_RELATIONS="trace:url:https://example.com/workshops/2024 derivedFrom:element:specifications/Requirements.md#remote-operation satisfiedBy:file:specifications/src/main.rs verify:element:specifications/Requirements.md#remote-start satisfiedBy:file:specifications/src/main.rs"
+if [ "$RELATIONS" != "$EXPECTED_RELATIONS" ]; then
+    echo "❌ FAILED: Unexpected relations: $RELATIONS"
+    exit 1
+fi
+
+# Test 2: Export to stdout matches the file
+if [ "$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json --output -)" != "$(cat "$MODEL")" ]; then
+    echo "❌ FAILED: Export to stdout differs from export to file"
+    exit 1
+fi
+
+# Test 3: Lossless round trip
+rm "${TEST_DIR}/specifications/Requirements.md" "${TEST_DIR}/specifications/Verifications.md"
+set +e
+IMPORT_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import json output/model.json 2>&1)
+IMPORT_EXIT=$?
+set -e
+
+if [ $IMPORT_EXIT -ne 0 ]; then
+    echo "❌ FAILED: import json exited with code $IMPORT_EXIT"
+    echo "$IMPORT_OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet -- specifications); then
+    echo "❌ FAILED: Round trip changed the markdown files"
+    (cd "$TEST_DIR" && git diff -- specifications)
+    exit 1
+fi
+
+# Test 4: Existing files are only replaced with --overwrite
+set +e
+(cd "$TEST_DIR" && "$REQVIRE_BIN" import json output/model.json > /dev/null 2>&1)
+NO_OVERWRITE_EXIT=$?
+set -e
+
+if [ $NO_OVERWRITE_EXIT -eq 0 ]; then
+    echo "❌ FAILED: Import should refuse to replace existing files without --overwrite"
+    exit 1
+fi
+
+# Test 5: Write back a transformed model; relations follow the renamed element
+jq '(.files[].sections[].elements[] | select(.name == "Remote Start")) |= (.name = "Remote Pump Start" | .metadata.status = "approved")' \
+    "$MODEL" > "${TEST_DIR}/output/transformed.json"
+
+set +e
+TRANSFORM_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import json output/transformed.json --overwrite 2>&1)
+TRANSFORM_EXIT=$?
+set -e
+
+if [ $TRANSFORM_EXIT -ne 0 ]; then
+    echo "❌ FAILED: Import of transformed model exited with code $TRANSFORM_EXIT"
+    echo "$TRANSFORM_OUTPUT"
+    exit 1
+fi
+
+if ! grep -qF "### Remote Pump Start" "${TEST_DIR}/specifications/Requirements.md" \
+    || ! grep -qF "  * status: approved" "${TEST_DIR}/specifications/Requirements.md" \
+    || ! grep -qF "  * verify: [Remote Pump Start](Requirements.md#remote-pump-start)" "${TEST_DIR}/specifications/Verifications.md"; then
+    echo "❌ FAILED: Transformed model not written back"
+    cat "${TEST_DIR}/specifications/Requirements.md" "${TEST_DIR}/specifications/Verifications.md"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" validate > /dev/null 2>&1); then
+    echo "❌ FAILED: Transformed model does not validate"
+    exit 1
+fi
+
+# Test 6: Newer format versions are rejected
+jq '.version = 2' "$MODEL" > "${TEST_DIR}/output/future.json"
+set +e
+FUTURE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import json output/future.json --output future 2>&1)
+FUTURE_EXIT=$?
+set -e
+
+if [ $FUTURE_EXIT -eq 0 ] || ! echo "$FUTURE_OUTPUT" | grep -q "newer than the supported version"; then
+    echo "❌ FAILED: Newer format version should be rejected"
+    echo "$FUTURE_OUTPUT"
+    exit 1
+fi
+
+# Test 7: An output named html is not taken for a missing output file
+(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json -o html)
+if [ "$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json --output -)" != "$(cat "${TEST_DIR}/html")" ]; then
+    echo "❌ FAILED: export --format json -o html should write the model document to html"
+    exit 1
+fi
+
+set +e
+MISSING_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format json 2>&1)
+set -e
+if ! echo "$MISSING_OUTPUT" | grep -q "JSON export requires an output file"; then
+    echo "❌ FAILED: JSON export without output file should fail"
+    echo "$MISSING_OUTPUT"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,264 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Language Server
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#language-server-verification
+#
+# Acceptance Criteria:
+# - lsp serves the language server protocol over stdio
+# - Hover shows element content and verification status
+# - Definition resolves relation targets to elements and files
+# - References lists the relations pointing to an element
+# - Completion offers relation types and element links inside Relations
+# - Rename returns a workspace edit without writing files
+# - Validation errors are published as diagnostics on save and cleared when fixed
+# - Parse errors point at their line, relation errors at the element or its relation line
+# - Documents opened through a symlink to the checkout map to the same model files
+#
+# Test Criteria:
+# - Server exits with success (0) return code after shutdown
+# - Responses carry the expected locations, items and edits
+
+REQUIREMENTS_URI="file://${TEST_DIR}/specifications/Requirements.md"
+VERIFICATIONS_URI="file://${TEST_DIR}/specifications/Verifications.md"
+
+# Writes a JSON-RPC message with its Content-Length header
+send() {
+    printf 'Content-Length: %d\r\n\r\n%s' "${#1}" "$1"
+}
+
+request() {
+    send "{\"jsonrpc\":\"2.0\",\"id\":$1,\"method\":\"$2\",\"params\":$3}"
+}
+
+notify() {
+    send "{\"jsonrpc\":\"2.0\",\"method\":\"$1\",\"params\":$2}"
+}
+
+position() {
+    echo "{\"textDocument\":{\"uri\":\"$1\"},\"position\":{\"line\":$2,\"character\":$3}}"
+}
+
+initialize() {
+    request 1 "initialize" "{\"processId\":null,\"rootUri\":\"file://${TEST_DIR}\",\"capabilities\":{}}"
+    notify "initialized" "{}"
+}
+
+shutdown() {
+    request 99 "shutdown" "null"
+    notify "exit" "null"
+}
+
+# Strips the headers so the messages can be read as a JSON stream
+messages() {
+    sed -E 's/Content-Length: [0-9]+\r?//g' | jq -s '.'
+}
+
+result() {
+    echo "$MESSAGES" | jq -c ".[] | select(.id == $1) | .result"
+}
+
+# Test 1: Navigation, completion, hover and rename on a valid model
+TEXT=$(jq -Rs '.' < "${TEST_DIR}/specifications/Requirements.md")
+set +e
+OUTPUT=$(cd "$TEST_DIR" && {
+    initialize
+    notify "textDocument/didOpen" "{\"textDocument\":{\"uri\":\"${REQUIREMENTS_URI}\",\"languageId\":\"markdown\",\"version\":1,\"text\":${TEXT}}}"
+    request 2 "textDocument/hover" "$(position "$REQUIREMENTS_URI" 14 6)"
+    request 3 "textDocument/hover" "$(position "$REQUIREMENTS_URI" 23 6)"
+    request 4 "textDocument/definition" "$(position "$REQUIREMENTS_URI" 19 20)"
+    request 5 "textDocument/definition" "$(position "$REQUIREMENTS_URI" 20 20)"
+    request 6 "textDocument/references" "{\"textDocument\":{\"uri\":\"${REQUIREMENTS_URI}\"},\"position\":{\"line\":4,\"character\":6},\"context\":{\"includeDeclaration\":true}}"
+    request 7 "textDocument/completion" "$(position "$REQUIREMENTS_URI" 20 4)"
+    request 8 "textDocument/completion" "$(position "$REQUIREMENTS_URI" 19 17)"
+    request 9 "textDocument/prepareRename" "$(position "$REQUIREMENTS_URI" 4 6)"
+    request 10 "textDocument/rename" "{\"textDocument\":{\"uri\":\"${REQUIREMENTS_URI}\"},\"position\":{\"line\":4,\"character\":6},\"newName\":\"Remote Pump Operation\"}"
+    shutdown
+} | "$REQVIRE_BIN" lsp 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: lsp exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MESSAGES=$(echo "$OUTPUT" | messages)
+
+if [ "$(result 1 | jq -r '.capabilities.renameProvider.prepareProvider')" != "true" ] \
+    || [ "$(result 1 | jq -r '.capabilities.hoverProvider')" != "true" ]; then
+    echo "❌ FAILED: Missing server capabilities"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if [ "$(echo "$MESSAGES" | jq '[.[] | select(.method == "textDocument/publishDiagnostics")] | length')" != "0" ]; then
+    echo "❌ FAILED: Diagnostics published for a valid model"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if ! result 2 | jq -r '.contents.value' | grep -qF "Verified by: Remote Start Test" \
+    || ! result 2 | jq -r '.contents.value' | grep -qF "The controller shall start the pump" \
+    || ! result 3 | jq -r '.contents.value' | grep -qF "Not verified"; then
+    echo "❌ FAILED: Hover misses content or verification status"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if [ "$(result 4 | jq -r '.uri')" != "$REQUIREMENTS_URI" ] \
+    || [ "$(result 4 | jq -r '.range.start.line')" != "4" ] \
+    || [ "$(result 5 | jq -r '.uri')" != "file://${TEST_DIR}/specifications/src/main.rs" ]; then
+    echo "❌ FAILED: Definition does not resolve relation targets"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if [ "$(result 6 | jq -c '[.[] | .range.start.line]')" != "[4,19,28]" ]; then
+    echo "❌ FAILED: References do not list the declaration and both relations"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if ! result 7 | jq -r '.[].label' | grep -qx "verifiedBy" \
+    || ! result 7 | jq -r '.[].label' | grep -qx "derivedFrom"; then
+    echo "❌ FAILED: Relation types not completed"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if [ "$(result 8 | jq -r '.[] | select(.label == "Remote Operation") | .textEdit.newText')" != "[Remote Operation](#remote-operation)" ] \
+    || [ "$(result 8 | jq -r '.[] | select(.label == "Remote Start Test") | .textEdit.newText')" != "[Remote Start Test](Verifications.md#remote-start-test)" ] \
+    || result 8 | jq -r '.[].label' | grep -qx "Remote Start"; then
+    echo "❌ FAILED: Element links not completed"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if [ "$(result 9 | jq -r '.placeholder')" != "Remote Operation" ]; then
+    echo "❌ FAILED: prepareRename does not return the element name"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+RENAME_EDITS=$(result 10 | jq -c ".changes[\"${REQUIREMENTS_URI}\"]")
+if [ "$(echo "$RENAME_EDITS" | jq -c '[.[] | .range.start.line]')" != "[4,19,28]" ] \
+    || [ "$(echo "$RENAME_EDITS" | jq -r '.[0].newText')" != "Pump " ]; then
+    echo "❌ FAILED: Rename does not return the expected workspace edit"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet); then
+    echo "❌ FAILED: Language server changed files"
+    exit 1
+fi
+
+# Test 2: Diagnostics are published on save and cleared when fixed
+sed -i 's/Requirements.md#remote-start/Requirements.md#remote-begin/' "${TEST_DIR}/specifications/Verifications.md"
+set +e
+OUTPUT=$(cd "$TEST_DIR" && {
+    initialize
+    sleep 1
+    git checkout -q specifications/Verifications.md
+    notify "textDocument/didSave" "{\"textDocument\":{\"uri\":\"${VERIFICATIONS_URI}\"}}"
+    sleep 1
+    shutdown
+} | "$REQVIRE_BIN" lsp 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" >> "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: lsp exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MESSAGES=$(echo "$OUTPUT" | messages)
+DIAGNOSTICS=$(echo "$MESSAGES" | jq -c "[.[] | select(.method == \"textDocument/publishDiagnostics\") | .params | select(.uri == \"${VERIFICATIONS_URI}\") | .diagnostics]")
+
+if [ "$(echo "$DIAGNOSTICS" | jq 'length')" != "2" ] \
+    || [ "$(echo "$DIAGNOSTICS" | jq '.[0][0].range.start.line')" != "12" ] \
+    || ! echo "$DIAGNOSTICS" | jq -r '.[0][0].message' | grep -q "missing target" \
+    || [ "$(echo "$DIAGNOSTICS" | jq '.[1] | length')" != "0" ]; then
+    echo "❌ FAILED: Diagnostics not published on the relation line and cleared on save"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+# Test 3: Parse errors point at their line and element errors at the element
+sed -i '29s/.*/  derived from Remote Operation/' "${TEST_DIR}/specifications/Requirements.md"
+set +e
+OUTPUT=$(cd "$TEST_DIR" && {
+    initialize
+    sleep 1
+    sed -i '29d' specifications/Requirements.md
+    notify "textDocument/didSave" "{\"textDocument\":{\"uri\":\"${REQUIREMENTS_URI}\"}}"
+    sleep 1
+    git checkout -q specifications/Requirements.md
+    notify "textDocument/didSave" "{\"textDocument\":{\"uri\":\"${REQUIREMENTS_URI}\"}}"
+    sleep 1
+    shutdown
+} | "$REQVIRE_BIN" lsp 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" >> "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: lsp exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MESSAGES=$(echo "$OUTPUT" | messages)
+DIAGNOSTICS=$(echo "$MESSAGES" | jq -c "[.[] | select(.method == \"textDocument/publishDiagnostics\") | .params | select(.uri == \"${REQUIREMENTS_URI}\") | .diagnostics]")
+
+if [ "$(echo "$DIAGNOSTICS" | jq -c '[.[] | [.[] | .range.start.line]]')" != "[[28],[23],[]]" ] \
+    || ! echo "$DIAGNOSTICS" | jq -r '.[0][0].message' | grep -q "invalid relations format" \
+    || ! echo "$DIAGNOSTICS" | jq -r '.[1][0].message' | grep -q "has no parent relation"; then
+    echo "❌ FAILED: Diagnostics not published on the error line and the element"
+    echo "$DIAGNOSTICS"
+    exit 1
+fi
+
+# Test 4: Documents opened through a symlinked checkout
+LINK_DIR=$(mktemp -d)
+trap 'rm -rf "$LINK_DIR"' EXIT
+ln -s "$TEST_DIR" "${LINK_DIR}/checkout"
+LINKED_URI="file://${LINK_DIR}/checkout/specifications/Requirements.md"
+set +e
+OUTPUT=$(cd "${LINK_DIR}/checkout" && {
+    initialize
+    request 2 "textDocument/hover" "$(position "$LINKED_URI" 14 6)"
+    request 3 "textDocument/definition" "$(position "$LINKED_URI" 19 20)"
+    shutdown
+} | "$REQVIRE_BIN" lsp 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" >> "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: lsp exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+MESSAGES=$(echo "$OUTPUT" | messages)
+
+if ! result 2 | jq -r '.contents.value' | grep -qF "Verified by: Remote Start Test" \
+    || [ "$(result 3 | jq -r '.uri' | xargs basename)" != "Requirements.md" ] \
+    || [ "$(result 3 | jq -r '.range.start.line')" != "4" ]; then
+    echo "❌ FAILED: Symlinked documents do not map to model files"
+    echo "$MESSAGES"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,135 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Configurable Traceability Matrix
+# --------------------------------------
+# Satisfies: specifications/Verifications/TreacibilityMatrix.md#configurable-traceability-matrix-test
+#
+# Acceptance Criteria:
+# - Source types, target types and relations can be selected on the command line
+# - Rows can be filtered by file path and section name
+# - Matrix definitions saved in reqvire.yaml can be selected by name
+# - HTML export emits every saved matrix definition
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - JSON output contains only the selected sources and targets
+# - Unknown relation types, element types and definitions exit with non-zero code
+# - Exported index links to an SVG and HTML page per definition
+
+# Test 1: Explicit source, target and relation types
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --source-type requirement --target-type test-verification --relation verifiedBy --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: matrix with explicit types exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+SOURCES=$(echo "$OUTPUT" | jq -r '[.sources[].name] | join(",")')
+TARGETS=$(echo "$OUTPUT" | jq -r '[.targets[].name] | join(",")')
+if [ "$SOURCES" != "Alpha,Beta,Gamma" ] || [ "$TARGETS" != "Alpha Test,Beta Test" ]; then
+    echo "❌ FAILED: Unexpected sources '$SOURCES' or targets '$TARGETS'"
+    exit 1
+fi
+
+# Test 2: Custom relation between requirements
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --source-type user-requirement --target-type requirement --relation derive 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: matrix with derive relation exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "| Requirement | Traced |"; then
+    echo "❌ FAILED: Generic matrix is missing the 'Traced' status column"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 3: File and section filters
+FILE_SOURCES=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --filter-file "specifications/Sub/*.md" --json | jq -r '[.sources[].name] | join(",")')
+SECTION_SOURCES=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --filter-section "System*" --json | jq -r '[.sources[].name] | join(",")')
+if [ "$FILE_SOURCES" != "Gamma" ] || [ "$SECTION_SOURCES" != "Alpha,Beta" ]; then
+    echo "❌ FAILED: Unexpected filtered sources '$FILE_SOURCES' and '$SECTION_SOURCES'"
+    exit 1
+fi
+
+# Test 4: Saved definition selected by name
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --definition "Component Inspections" --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: matrix --definition exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+SOURCES=$(echo "$OUTPUT" | jq -r '[.sources[].name] | join(",")')
+TARGETS=$(echo "$OUTPUT" | jq -r '[.targets[].name] | join(",")')
+if [ "$SOURCES" != "Gamma" ] || [ "$TARGETS" != "Gamma Inspection" ]; then
+    echo "❌ FAILED: Unexpected definition sources '$SOURCES' or targets '$TARGETS'"
+    exit 1
+fi
+
+# Test 5: Invalid input is rejected
+set +e
+(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --relation testedBy > /dev/null 2>&1)
+RELATION_EXIT=$?
+(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --definition "Unknown" > /dev/null 2>&1)
+DEFINITION_EXIT=$?
+TYPE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --target-type test-verfication 2>&1)
+TYPE_EXIT=$?
+set -e
+
+if [ $RELATION_EXIT -eq 0 ] || [ $DEFINITION_EXIT -eq 0 ]; then
+    echo "❌ FAILED: Unknown relation or definition should fail"
+    exit 1
+fi
+
+if [ $TYPE_EXIT -eq 0 ] || ! echo "$TYPE_OUTPUT" | grep -q "Unknown element type 'test-verfication'"; then
+    echo "❌ FAILED: Unknown element type should fail"
+    echo "$TYPE_OUTPUT"
+    exit 1
+fi
+
+# Test 6: HTML export emits every saved definition
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --output html 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+for SLUG in system-tests component-inspections; do
+    if [ ! -f "${TEST_DIR}/html/matrices/${SLUG}.svg" ] || [ ! -f "${TEST_DIR}/html/matrices/${SLUG}.html" ]; then
+        echo "❌ FAILED: Missing exported matrix files for ${SLUG}"
+        exit 1
+    fi
+    if ! grep -q "matrices/${SLUG}.html" "${TEST_DIR}/html/index.html"; then
+        echo "❌ FAILED: index.html does not link to matrices/${SLUG}.html"
+        exit 1
+    fi
+done
+
+if [ ! -f "${TEST_DIR}/html/matrix.svg" ]; then
+    echo "❌ FAILED: Default matrix.svg is no longer exported"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,142 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: MCP Server
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#mcp-server-verification
+#
+# Acceptance Criteria:
+# - mcp serves the Model Context Protocol over stdio with newline-delimited JSON-RPC
+# - Query tools return summaries, elements, impact trees, traces, coverage and lint as JSON
+# - Edit tools write files only when the edited model is valid
+#
+# Test Criteria:
+# - Server exits with success (0) return code when stdin closes
+# - Tool results carry structured JSON content
+# - A rejected edit changes no file, accepted edits validate and need no formatting
+
+call() {
+    echo "{\"jsonrpc\":\"2.0\",\"id\":$1,\"method\":\"tools/call\",\"params\":{\"name\":\"$2\",\"arguments\":$3}}"
+}
+
+result() {
+    echo "$OUTPUT" | jq -c "select(.id == $1) | .result"
+}
+
+structured() {
+    echo "$OUTPUT" | jq -c "select(.id == $1) | .result.structuredContent"
+}
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && {
+    echo '{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"1"}}}'
+    echo '{"jsonrpc":"2.0","method":"notifications/initialized"}'
+    echo '{"jsonrpc":"2.0","id":2,"method":"tools/list"}'
+    call 3 "summary" '{"filter_is_not_verified":true}'
+    call 4 "element" '{"element":"Remote Start"}'
+    call 5 "impact_tree" '{"element":"Remote Operation"}'
+    call 6 "traces" '{}'
+    call 7 "coverage" '{}'
+    call 8 "lint" '{}'
+    call 9 "add_relation" '{"source":"specifications/Requirements.md#remote-stop","type":"verifiedBy","target":"specifications/Verifications.md#remote-halt-test"}'
+    call 10 "add_element" '{"name":"Remote Stop Test","file":"specifications/Verifications.md","section":"Tests","type":"test-verification","content":"Sends a stop command and checks the pump state."}'
+    call 11 "add_relation" '{"source":"specifications/Verifications.md#remote-stop-test","type":"verify","target":"specifications/Requirements.md#remote-stop"}'
+    call 12 "move_element" '{"element":"Remote Stop","file":"specifications/Control.md","section":"Stop","dry_run":true}'
+    call 13 "summary" '{"filter_name":"("}'
+    echo '{"jsonrpc":"2.0","id":14,"method":"resources/list"}'
+} | "$REQVIRE_BIN" mcp 2>/dev/null)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: mcp exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(result 1 | jq -r '.protocolVersion')" != "2025-06-18" ] \
+    || [ "$(result 1 | jq -r '.serverInfo.name')" != "reqvire" ] \
+    || [ "$(result 2 | jq -c '[.tools[].name] | sort')" != '["add_element","add_relation","coverage","element","impact_tree","lint","move_element","summary","traces","validate"]' ]; then
+    echo "❌ FAILED: Initialization or tool list"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+SUMMARY_NAMES=$(structured 3 | jq -r '[.files[].sections[].elements[].name] | sort | join(",")')
+if [ "$SUMMARY_NAMES" != "Remote Operation,Remote Stop" ]; then
+    echo "❌ FAILED: Summary filters not applied: $SUMMARY_NAMES"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(structured 4 | jq -r '.identifier')" != "specifications/Requirements.md#remote-start" ] \
+    || [ "$(structured 4 | jq -r '.relations[] | select(.type == "derivedFrom") | .target')" != "specifications/Requirements.md#remote-operation" ] \
+    || [ "$(structured 4 | jq -r '.opposite_relations[] | select(.type == "verifiedBy") | .target')" != "specifications/Verifications.md#remote-start-test" ]; then
+    echo "❌ FAILED: Element lookup"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(structured 5 | jq -r '[.relations[].element.name] | sort | join(",")')" != "Remote Start,Remote Stop" ]; then
+    echo "❌ FAILED: Impact tree"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(result 6 | jq -r '.isError')" != "false" ] \
+    || [ "$(result 7 | jq -r '.isError')" != "false" ] \
+    || ! structured 7 | jq -e '.summary' > /dev/null \
+    || ! structured 8 | jq -e 'has("auto_fixable") and has("needs_manual_review")' > /dev/null \
+    || ! result 8 | jq -r '.content[0].text' | jq -e '.auto_fixable' > /dev/null; then
+    echo "❌ FAILED: Traces, coverage or lint results"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(result 9 | jq -r '.isError')" != "true" ] \
+    || ! structured 9 | jq -r '.operations[].error // empty' | grep -q "remote-halt-test"; then
+    echo "❌ FAILED: Invalid relation not rejected"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(result 10 | jq -r '.isError')" != "false" ] \
+    || [ "$(structured 10 | jq -r '.applied')" != "true" ] \
+    || [ "$(structured 11 | jq -r '.applied')" != "true" ]; then
+    echo "❌ FAILED: Edits not applied"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! grep -qF "### Remote Stop Test" "${TEST_DIR}/specifications/Verifications.md" \
+    || ! grep -qF "  * verify: [Remote Stop](Requirements.md#remote-stop)" "${TEST_DIR}/specifications/Verifications.md" \
+    || grep -q "remote-halt-test" "${TEST_DIR}/specifications/Requirements.md"; then
+    echo "❌ FAILED: Files do not match the applied edits"
+    cat "${TEST_DIR}/specifications/Verifications.md"
+    exit 1
+fi
+
+if [ "$(structured 12 | jq -r '.applied')" != "false" ] \
+    || [ "$(structured 12 | jq -r '.new_element')" != "specifications/Control.md#remote-stop" ] \
+    || [ -e "${TEST_DIR}/specifications/Control.md" ]; then
+    echo "❌ FAILED: Dry run move"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(result 13 | jq -r '.isError')" != "true" ] \
+    || [ "$(echo "$OUTPUT" | jq -r 'select(.id == 14) | .error.code')" != "-32601" ]; then
+    echo "❌ FAILED: Errors not reported"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" validate > /dev/null) \
+    || ! (cd "$TEST_DIR" && "$REQVIRE_BIN" format | grep -q "No formatting changes needed"); then
+    echo "❌ FAILED: Edited model is invalid or needs formatting"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,155 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Move and Rename Elements
+# --------------------------------------
+# Satisfies: specifications/Verifications/ModelEditingTests.md#move-and-rename-commands-test
+#
+# Acceptance Criteria:
+# - mv moves an element to another file and section and rename changes its name
+# - Relations referring to the element are rewritten in place in all files
+# - The elements with relations to the element are listed
+# - --dry-run shows the changes without writing files
+# - Commands run from a subdirectory change the model files at their own paths
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - The changed model validates and needs no formatting
+# - Renaming to an existing element fails and changes no file
+
+# Test 1: Dry run lists impact and writes nothing
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" mv "Remote Start" --to "specifications/system/Control.md#Start and Stop" --dry-run 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: mv --dry-run exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -qF "specifications/Requirements.md#remote-start -> specifications/system/Control.md#remote-start" \
+    || ! echo "$OUTPUT" | grep -qF "  - specifications/Verifications.md#remote-start-test" \
+    || ! echo "$OUTPUT" | grep -q "Found 3 file(s) with changes" \
+    || ! echo "$OUTPUT" | grep -q "Run without --dry-run to apply these changes"; then
+    echo "❌ FAILED: Missing impact list or diff preview"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Links to and from the file that does not exist yet are relative to its folder
+if ! echo "$OUTPUT" | grep -qF "derivedFrom: [Remote Operation](../Requirements.md#remote-operation)" \
+    || ! echo "$OUTPUT" | grep -qF "verify: [Remote Start](system/Control.md#remote-start)"; then
+    echo "❌ FAILED: Wrong relative links in the preview"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet) || [ -e "${TEST_DIR}/specifications/system" ]; then
+    echo "❌ FAILED: Dry run changed files"
+    exit 1
+fi
+
+# Test 2: Move to a section of a new file
+set +e
+MV_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" mv "Remote Start" --to "specifications/system/Control.md#Start and Stop" 2>&1)
+MV_EXIT=$?
+set -e
+
+if [ $MV_EXIT -ne 0 ]; then
+    echo "❌ FAILED: mv exited with code $MV_EXIT"
+    echo "$MV_OUTPUT"
+    exit 1
+fi
+
+CONTROL="${TEST_DIR}/specifications/system/Control.md"
+if ! grep -qF "## Start and Stop" "$CONTROL" \
+    || ! grep -qF "  * derivedFrom: [Remote Operation](../Requirements.md#remote-operation)" "$CONTROL" \
+    || ! grep -qF "  * satisfiedBy: [main.rs](../src/main.rs)" "$CONTROL" \
+    || grep -qF "### Remote Start" "${TEST_DIR}/specifications/Requirements.md" \
+    || ! grep -qF "  * verify: [Remote Start](system/Control.md#remote-start)" "${TEST_DIR}/specifications/Verifications.md"; then
+    echo "❌ FAILED: Element not moved or relations not rewritten"
+    cat "$CONTROL" "${TEST_DIR}/specifications/Verifications.md"
+    exit 1
+fi
+
+# Test 3: Rename by identifier with JSON output
+set +e
+RENAME_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" rename specifications/system/Control.md#remote-start "Remote Pump Start" --json)
+RENAME_EXIT=$?
+set -e
+
+if [ $RENAME_EXIT -ne 0 ] \
+    || [ "$(echo "$RENAME_OUTPUT" | jq -r '.new_element')" != "specifications/system/Control.md#remote-pump-start" ] \
+    || [ "$(echo "$RENAME_OUTPUT" | jq -r '.impact | join(",")')" != "specifications/Requirements.md#remote-operation,specifications/Verifications.md#remote-start-test" ] \
+    || [ "$(echo "$RENAME_OUTPUT" | jq -r '"\(.applied) \(.files_changed)"')" != "true 2" ]; then
+    echo "❌ FAILED: Unexpected rename result"
+    echo "$RENAME_OUTPUT"
+    exit 1
+fi
+
+if ! grep -qF "### Remote Pump Start" "$CONTROL" \
+    || ! grep -qF "  * verify: [Remote Pump Start](system/Control.md#remote-pump-start)" "${TEST_DIR}/specifications/Verifications.md"; then
+    echo "❌ FAILED: Rename not written"
+    cat "$CONTROL" "${TEST_DIR}/specifications/Verifications.md"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" validate > /dev/null 2>&1); then
+    echo "❌ FAILED: Changed model does not validate"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" format | grep -q "No formatting changes needed"); then
+    echo "❌ FAILED: Changed files need formatting"
+    exit 1
+fi
+
+# Test 4: Invalid targets fail without changing files
+(cd "$TEST_DIR" && git add -A && git commit -q -m "Moved and renamed")
+set +e
+INVALID_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" mv "Remote Operation" --to specifications/notes.txt 2>&1)
+INVALID_EXIT=$?
+MISSING_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" rename "Remote Stop" "Remote Halt" 2>&1)
+MISSING_EXIT=$?
+set -e
+
+if [ $INVALID_EXIT -eq 0 ] || ! echo "$INVALID_OUTPUT" | grep -q "is not a markdown file"; then
+    echo "❌ FAILED: Move to a non-markdown file should fail"
+    echo "$INVALID_OUTPUT"
+    exit 1
+fi
+
+if [ $MISSING_EXIT -eq 0 ] || ! echo "$MISSING_OUTPUT" | grep -q "Element 'Remote Stop' not found"; then
+    echo "❌ FAILED: Rename of a missing element should fail"
+    echo "$MISSING_OUTPUT"
+    exit 1
+fi
+
+if ! (cd "$TEST_DIR" && git diff --quiet && [ -z "$(git status --porcelain)" ]); then
+    echo "❌ FAILED: Failed commands changed files"
+    exit 1
+fi
+
+# Test 5: Paths are relative to the model root when run from a subdirectory
+if ! (cd "$TEST_DIR/specifications" && "$REQVIRE_BIN" rename "Remote Pump Start" "Remote Start" > /dev/null 2>&1); then
+    echo "❌ FAILED: rename from a subdirectory failed"
+    exit 1
+fi
+
+if [ -e "$TEST_DIR/specifications/specifications" ]; then
+    echo "❌ FAILED: rename from a subdirectory wrote files relative to the subdirectory"
+    exit 1
+fi
+
+if ! grep -q "^### Remote Start$" "$TEST_DIR/specifications/system/Control.md" \
+    || ! grep -q "verify: \[Remote Start\](system/Control.md#remote-start)" "$TEST_DIR/specifications/Verifications.md"; then
+    echo "❌ FAILED: rename from a subdirectory did not change the model files"
+    cat "$TEST_DIR/specifications/Verifications.md"
+    exit 1
+fi
+
+exit 0
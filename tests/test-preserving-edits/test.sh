@@ -0,0 +1,136 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Format Preserving Edits
+# --------------------------------------
+# Satisfies: specifications/Verifications/ModelEditingTests.md#format-preserving-edits-test
+#
+# Acceptance Criteria:
+# - apply, rename and mv change only the lines of the edited elements and relations
+# - Comments, HTML, tables, blank lines and metadata order elsewhere are kept
+# - format still normalises whole files
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - git diff shows only the expected lines
+# - The changed model validates
+
+REQUIREMENTS="${TEST_DIR}/specifications/Requirements.md"
+VERIFICATIONS="${TEST_DIR}/specifications/Verifications.md"
+
+# The fixture is not formatted, so a regenerated file would differ everywhere
+if (cd "$TEST_DIR" && "$REQVIRE_BIN" format | grep -q "No formatting changes needed"); then
+    echo "❌ FAILED: Fixture should need formatting"
+    exit 1
+fi
+
+# Test 1: Metadata and relation edits touch only their lines
+set +e
+APPLY_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" apply patch.json --fix 2>&1)
+APPLY_EXIT=$?
+set -e
+
+printf "%s\n" "$APPLY_OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $APPLY_EXIT -ne 0 ]; then
+    echo "❌ FAILED: apply exited with code $APPLY_EXIT"
+    echo "$APPLY_OUTPUT"
+    exit 1
+fi
+
+DIFF=$(cd "$TEST_DIR" && git diff --numstat)
+if [ "$DIFF" != "$(printf '2\t2\tspecifications/Requirements.md')" ] \
+    || ! grep -qF "  * priority: medium" "$REQUIREMENTS" \
+    || ! grep -qF "  * satisfiedBy: [main.rs](src/main.rs)" "$REQUIREMENTS" \
+    || grep -qF "  * derivedFrom: #remote-start" "$REQUIREMENTS"; then
+    echo "❌ FAILED: apply changed more than the edited lines"
+    (cd "$TEST_DIR" && git diff)
+    exit 1
+fi
+
+# Test 2: Rename rewrites the header and the referring relation only
+(cd "$TEST_DIR" && git add -A && git commit -q -m "Applied patch")
+set +e
+RENAME_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" rename "Remote Start" "Remote Pump Start" 2>&1)
+RENAME_EXIT=$?
+set -e
+
+if [ $RENAME_EXIT -ne 0 ]; then
+    echo "❌ FAILED: rename exited with code $RENAME_EXIT"
+    echo "$RENAME_OUTPUT"
+    exit 1
+fi
+
+DIFF=$(cd "$TEST_DIR" && git diff --numstat)
+if [ "$DIFF" != "$(printf '1\t1\tspecifications/Requirements.md\n1\t1\tspecifications/Verifications.md')" ] \
+    || ! grep -qF "### Remote Pump Start" "$REQUIREMENTS" \
+    || ! grep -qF "  * verify: [Remote Pump Start](Requirements.md#remote-pump-start)" "$VERIFICATIONS" \
+    || ! grep -qF "  * satisfiedBy: src/main.rs" "$VERIFICATIONS"; then
+    echo "❌ FAILED: rename changed more than the header and the referring relation"
+    (cd "$TEST_DIR" && git diff)
+    exit 1
+fi
+
+# Test 3: Moving an element moves its text and leaves the rest in place
+(cd "$TEST_DIR" && git add -A && git commit -q -m "Renamed")
+set +e
+MV_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" mv "Remote Stop" --to "specifications/Requirements.md#Stakeholder Needs" 2>&1)
+MV_EXIT=$?
+set -e
+
+if [ $MV_EXIT -ne 0 ]; then
+    echo "❌ FAILED: mv exited with code $MV_EXIT"
+    echo "$MV_OUTPUT"
+    exit 1
+fi
+
+NEEDS_LINE=$(grep -n "^## Stakeholder Needs" "$REQUIREMENTS" | cut -d: -f1)
+SYSTEM_LINE=$(grep -n "^## System Requirements" "$REQUIREMENTS" | cut -d: -f1)
+STOP_LINE=$(grep -n "^### Remote Stop" "$REQUIREMENTS" | cut -d: -f1)
+if [ "$STOP_LINE" -le "$NEEDS_LINE" ] || [ "$STOP_LINE" -ge "$SYSTEM_LINE" ]; then
+    echo "❌ FAILED: Element not moved to the Stakeholder Needs section"
+    cat "$REQUIREMENTS"
+    exit 1
+fi
+
+# Only the lines of the moved element are removed
+UNEXPECTED=$(cd "$TEST_DIR" && git diff -U0 | grep '^-' | grep -v -e '^--- a/' -e '^-$' -e '^----$' \
+    | grep -vF -e "### Remote Stop" -e "The controller shall stop" -e "#### Relations" \
+        -e "derivedFrom: [Remote Operation]" -e "satisfiedBy: [main.rs]" || true)
+if [ -n "$UNEXPECTED" ]; then
+    echo "❌ FAILED: mv removed lines outside the moved element"
+    (cd "$TEST_DIR" && git diff)
+    exit 1
+fi
+
+for KEPT in "<!-- Owned by the systems team; keep the review table below. -->" \
+    "| Dana     | 2024-05-02 |" \
+    '<div class="note">Agreed in the 2024 workshop.</div>' \
+    "  * derivedFrom: #remote-operation" \
+    "  * satisfiedBy: src/main.rs"; do
+    if ! grep -qF -- "$KEPT" "$REQUIREMENTS"; then
+        echo "❌ FAILED: Layout not preserved, missing: $KEPT"
+        cat "$REQUIREMENTS"
+        exit 1
+    fi
+done
+
+if ! (cd "$TEST_DIR" && "$REQVIRE_BIN" validate > /dev/null 2>&1); then
+    echo "❌ FAILED: Changed model does not validate"
+    exit 1
+fi
+
+# Test 4: format remains the operation that normalises whole files
+set +e
+FORMAT_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" format --fix 2>&1)
+FORMAT_EXIT=$?
+set -e
+
+if [ $FORMAT_EXIT -ne 0 ] || ! grep -qF "  * derivedFrom: [Remote Operation](#remote-operation)" <(sed -n '/^### Remote Pump Start/,/^---/p' "$REQUIREMENTS"); then
+    echo "❌ FAILED: format did not normalise the files"
+    echo "$FORMAT_OUTPUT"
+    cat "$REQUIREMENTS"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,144 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: ReqIF Export
+# --------------------------------------
+# Satisfies: specifications/Verifications/InterchangeTests.md#reqif-export-test
+#
+# Acceptance Criteria:
+# - export --format reqif writes a .reqifz archive or a plain .reqif document
+# - Elements, relations, files and sections map to SPEC-OBJECTs, SPEC-RELATIONs and SPECIFICATIONs
+# - Repeated exports are byte-identical
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - The archive holds the same document as the plain export
+# - Objects carry name, XHTML text, type and metadata attributes
+# - Relations reference relation types, including relations to files
+# - ReqIF export without an output file exits with non-zero code
+# - An output file named html is checked like any other output file
+
+# Test 1: Zipped export
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif -o out/model.reqifz 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export --format reqif exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(unzip -Z1 "${TEST_DIR}/out/model.reqifz")" != "model.reqif" ]; then
+    echo "❌ FAILED: Archive should contain only model.reqif"
+    unzip -l "${TEST_DIR}/out/model.reqifz"
+    exit 1
+fi
+
+# Test 2: Plain export matches the archived document
+(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif -o out/model.reqif > /dev/null 2>&1)
+if ! unzip -p "${TEST_DIR}/out/model.reqifz" model.reqif | cmp -s - "${TEST_DIR}/out/model.reqif"; then
+    echo "❌ FAILED: Archived document differs from plain export"
+    exit 1
+fi
+REQIF="${TEST_DIR}/out/model.reqif"
+
+# Test 3: Repeated exports are byte-identical
+cp "${TEST_DIR}/out/model.reqifz" "${TEST_DIR}/first.reqifz"
+sleep 1
+(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif -o out/model.reqifz > /dev/null 2>&1)
+if ! cmp -s "${TEST_DIR}/first.reqifz" "${TEST_DIR}/out/model.reqifz"; then
+    echo "❌ FAILED: Repeated exports differ"
+    exit 1
+fi
+
+# Test 4: Spec objects and attributes
+for NAME in "ReqIF.ForeignID" "ReqIF.Name" "ReqIF.Text" "Type" "owner" "priority" "ReqIF.ChapterName"; do
+    if ! grep -q "LONG-NAME=\"${NAME}\"" "$REQIF"; then
+        echo "❌ FAILED: Missing attribute definition ${NAME}"
+        exit 1
+    fi
+done
+
+if [ "$(grep -c '<SPEC-OBJECT-REF>element-' "$REQIF")" -lt 3 ]; then
+    echo "❌ FAILED: Elements missing from hierarchy"
+    exit 1
+fi
+
+if ! grep -q 'THE-VALUE="Systems &amp; Integration"' "$REQIF"; then
+    echo "❌ FAILED: Escaped metadata value missing"
+    exit 1
+fi
+
+if ! grep -q '<xhtml:strong>compressed</xhtml:strong>' "$REQIF" || ! grep -q '<xhtml:li><xhtml:code>stable</xhtml:code> output</xhtml:li>' "$REQIF"; then
+    echo "❌ FAILED: Element text not rendered as XHTML"
+    exit 1
+fi
+
+if ! grep -q 'THE-VALUE="specifications/src/archive.rs"' "$REQIF"; then
+    echo "❌ FAILED: File object missing"
+    exit 1
+fi
+
+# Test 5: Relations and relation types
+if [ "$(grep -c '<SPEC-RELATION-TYPE ' "$REQIF")" -ne 7 ]; then
+    echo "❌ FAILED: Relation types missing"
+    exit 1
+fi
+
+for TYPE in derive derivedFrom satisfiedBy verify; do
+    if ! grep -q "<SPEC-RELATION-TYPE-REF>spec-relation-type-${TYPE}</SPEC-RELATION-TYPE-REF>" "$REQIF"; then
+        echo "❌ FAILED: Missing ${TYPE} relation"
+        exit 1
+    fi
+done
+
+if [ "$(grep -c '<SPEC-RELATION ' "$REQIF")" -ne 5 ]; then
+    echo "❌ FAILED: Expected 5 relations, got $(grep -c '<SPEC-RELATION ' "$REQIF")"
+    exit 1
+fi
+
+# Test 6: Specifications and chapters
+if ! grep -q '<SPECIFICATION IDENTIFIER="specification-specifications_Requirements.md"' "$REQIF"; then
+    echo "❌ FAILED: Specification for Requirements.md missing"
+    exit 1
+fi
+
+for SECTION in "Customer Requirements" "System Requirements"; do
+    if ! grep -q "THE-VALUE=\"${SECTION}\"" "$REQIF"; then
+        echo "❌ FAILED: Chapter ${SECTION} missing"
+        exit 1
+    fi
+done
+
+if command -v xmllint > /dev/null 2>&1 && ! xmllint --noout "$REQIF"; then
+    echo "❌ FAILED: ReqIF document is not well-formed XML"
+    exit 1
+fi
+
+# Test 7: Output file is required
+set +e
+(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif > /dev/null 2>&1)
+MISSING_EXIT=$?
+set -e
+
+if [ $MISSING_EXIT -eq 0 ]; then
+    echo "❌ FAILED: ReqIF export without output file should fail"
+    exit 1
+fi
+
+# Test 8: An output named html is not taken for a missing output file
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif -o html 2>&1)
+set -e
+
+if ! echo "$OUTPUT" | grep -q "must end with .reqifz or .reqif" || echo "$OUTPUT" | grep -q "requires an output file"; then
+    echo "❌ FAILED: export --format reqif -o html should be checked as an output file"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+exit 0
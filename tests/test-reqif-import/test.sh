@@ -0,0 +1,134 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: ReqIF Import
+# --------------------------------------
+# Satisfies: specifications/Verifications/InterchangeTests.md#reqif-import-test
+#
+# Acceptance Criteria:
+# - import reqif turns SPECIFICATIONs into markdown files and chapter objects into sections
+# - A mapping file selects name, content, type and metadata attributes and maps relation types
+# - XHTML content is converted to markdown
+# - Imported files validate and need no formatting changes
+# - Documents exported by reqvire import back into an equivalent model
+# - Relations to file objects link to the files again and leave them untouched
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - Elements carry mapped metadata and relations
+# - Relations with unmapped types are reported as skipped
+# - Existing files are never overwritten
+
+# Test 1: Import a supplier document with a mapping file
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import reqif supplier/brakes.reqif --mapping supplier/mapping.yaml --output specifications/supplier --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: import reqif exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | jq -r '.elements')" -ne 3 ] || [ "$(echo "$OUTPUT" | jq -r '.relations')" -ne 2 ]; then
+    echo "❌ FAILED: Expected 3 elements and 2 relations in import summary"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | jq -r '.skipped_relations[0]')" != "obj-3 Generic Link obj-5 (unsupported relation type)" ]; then
+    echo "❌ FAILED: Relation with unmapped type not reported as skipped"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+IMPORTED="${TEST_DIR}/specifications/supplier/Brake-Module.md"
+if [ ! -f "$IMPORTED" ]; then
+    echo "❌ FAILED: Specification not imported into Brake-Module.md"
+    exit 1
+fi
+
+for EXPECTED in "# Brake Module" "## Braking Functions" "Functions of the brake controller." "## Verification" \
+    "### Emergency Braking" "The controller **shall** apply full braking within 100 ms & report it." "* on driver request" \
+    "  * type: user-requirement" "  * doors-id: BRK-2" "  * priority: High" "  * type: test-verification" \
+    "  * verifiedBy: [Emergency Braking Test](#emergency-braking-test)" \
+    "  * derivedFrom: [Emergency Braking](#emergency-braking)"; do
+    if ! grep -qF -- "$EXPECTED" "$IMPORTED"; then
+        echo "❌ FAILED: Imported markdown is missing: $EXPECTED"
+        cat "$IMPORTED"
+        exit 1
+    fi
+done
+
+if grep -q "do not export\|Object Heading\|Category" "$IMPORTED"; then
+    echo "❌ FAILED: Unmapped attributes should not be imported"
+    cat "$IMPORTED"
+    exit 1
+fi
+
+# Test 2: The imported model validates and is already formatted
+set +e
+VALIDATE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" validate 2>&1)
+VALIDATE_EXIT=$?
+FORMAT_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" format --json 2>&1)
+set -e
+
+if [ $VALIDATE_EXIT -ne 0 ]; then
+    echo "❌ FAILED: Imported model does not validate"
+    echo "$VALIDATE_OUTPUT"
+    exit 1
+fi
+
+if echo "$FORMAT_OUTPUT" | jq -e '.diffs[] | select(.file_path | startswith("specifications/supplier/"))' > /dev/null; then
+    echo "❌ FAILED: Imported files need formatting changes"
+    echo "$FORMAT_OUTPUT"
+    exit 1
+fi
+
+# Test 3: Existing files are not overwritten
+set +e
+OVERWRITE_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import reqif supplier/brakes.reqif --mapping supplier/mapping.yaml --output specifications/supplier 2>&1)
+OVERWRITE_EXIT=$?
+set -e
+
+if [ $OVERWRITE_EXIT -eq 0 ] || ! echo "$OVERWRITE_OUTPUT" | grep -q "would overwrite existing file"; then
+    echo "❌ FAILED: Import should refuse to overwrite existing files"
+    echo "$OVERWRITE_OUTPUT"
+    exit 1
+fi
+
+# Test 4: Round trip through the ReqIF export
+rm -rf "${TEST_DIR}/specifications/supplier"
+(cd "$TEST_DIR" && "$REQVIRE_BIN" export --format reqif --output output/model.reqifz > /dev/null 2>&1)
+set +e
+ROUNDTRIP_OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" import reqif output/model.reqifz --output roundtrip 2>&1)
+ROUNDTRIP_EXIT=$?
+set -e
+
+if [ $ROUNDTRIP_EXIT -ne 0 ]; then
+    echo "❌ FAILED: Importing an exported archive failed"
+    echo "$ROUNDTRIP_OUTPUT"
+    exit 1
+fi
+
+ROUNDTRIP="${TEST_DIR}/roundtrip/specifications/Requirements.md"
+for EXPECTED in "## Customer Requirements" "### Exchange Data" "  * owner: Systems & Integration" \
+    "  * derive: [Export Archive](#export-archive)" "* \`stable\` output" "  * priority: high" \
+    "  * verify: [Export Archive](#export-archive)" "  * satisfiedBy: [archive.rs](../../specifications/src/archive.rs)"; do
+    if ! grep -qF -- "$EXPECTED" "$ROUNDTRIP"; then
+        echo "❌ FAILED: Round trip lost: $EXPECTED"
+        cat "$ROUNDTRIP"
+        exit 1
+    fi
+done
+
+if [ "$(grep -c "satisfiedBy: \[archive.rs\]" "$ROUNDTRIP")" -ne 2 ] || ! cmp -s "${TEST_DIR}/specifications/src/archive.rs" <(git -C "$TEST_DIR" show HEAD:specifications/src/archive.rs); then
+    echo "❌ FAILED: Round trip lost file relations or modified the linked file"
+    cat "$ROUNDTRIP"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,102 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: HTML Export Search Index
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#export-search-index-verification
+#
+# Acceptance Criteria:
+# - export writes search-index.json with the element types and files listed once
+# - Every element entry holds name, identifier, type and file positions, page link and text
+# - The text holds element content and metadata with whitespace collapsed
+# - search-index.js wraps the same index for pages opened from disk
+# - search.js is written to the export root
+# - Every exported page holds the search box with the relative path to the export root
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - Index and page contents match the model
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" export --output output 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: export exited with $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+OUT="$TEST_DIR/output"
+INDEX="$OUT/search-index.json"
+
+for FILE in search-index.json search-index.js search.js; do
+    if [ ! -f "$OUT/$FILE" ]; then
+        echo "❌ FAILED: $FILE was not exported"
+        exit 1
+    fi
+done
+
+if ! jq -e '.version == 1
+    and .types == ["requirement", "test-verification", "user-requirement"]
+    and .files == ["specifications/Requirements.md", "specifications/Tests/Verifications.md"]
+    and (.elements | length) == 3' "$INDEX" > /dev/null; then
+    echo "❌ FAILED: Unexpected index header"
+    jq -c '{version, types, files}' "$INDEX"
+    exit 1
+fi
+
+ENTRY=$(jq -c '.elements[] | select(.[1] == "specifications/Requirements.md#remote-start-pump")' "$INDEX")
+EXPECTED='["Remote Start (Pump)","specifications/Requirements.md#remote-start-pump",0,0,"specifications/Requirements.html#remote-start-(pump)","The controller shall start the pump on a remote command. priority: high"]'
+if [ "$ENTRY" != "$EXPECTED" ]; then
+    echo "❌ FAILED: Unexpected index entry"
+    echo "Expected: $EXPECTED"
+    echo "Got: $ENTRY"
+    exit 1
+fi
+
+ENTRY=$(jq -c '.elements[] | select(.[0] == "Remote Start Test")' "$INDEX")
+if ! echo "$ENTRY" | jq -e '.[2] == 1 and .[3] == 1 and .[4] == "specifications/Tests/Verifications.html#remote-start-test"' > /dev/null; then
+    echo "❌ FAILED: Verification entry does not reference its type, file and page"
+    echo "$ENTRY"
+    exit 1
+fi
+
+# The anchors of the index must exist on the exported pages
+if ! grep -q 'id="remote-start-(pump)"' "$OUT/specifications/Requirements.html"; then
+    echo "❌ FAILED: Page anchor of the index entry is missing"
+    exit 1
+fi
+
+SCRIPT=$(cat "$OUT/search-index.js")
+if [[ "$SCRIPT" != "window.reqvireSearchIndex = "* ]]; then
+    echo "❌ FAILED: search-index.js does not set window.reqvireSearchIndex"
+    exit 1
+fi
+if [ "$(echo "${SCRIPT#window.reqvireSearchIndex = }" | sed 's/;$//' | jq -c .)" != "$(jq -c . "$INDEX")" ]; then
+    echo "❌ FAILED: search-index.js differs from search-index.json"
+    exit 1
+fi
+
+check_page() {
+    local page="$1" root="$2"
+    if ! grep -q "<div id=\"reqvire-search\" data-root=\"$root\"" "$OUT/$page"; then
+        echo "❌ FAILED: $page has no search box with root '$root'"
+        exit 1
+    fi
+    if ! grep -q "<script src=\"${root}search.js\"></script>" "$OUT/$page"; then
+        echo "❌ FAILED: $page does not load ${root}search.js"
+        exit 1
+    fi
+}
+
+check_page "index.html" ""
+check_page "model.html" ""
+check_page "traces.html" ""
+check_page "coverage.html" ""
+check_page "matrix.html" ""
+check_page "specifications/Requirements.html" "../"
+check_page "specifications/Tests/Verifications.html" "../../"
+
+exit 0
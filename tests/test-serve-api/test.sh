@@ -0,0 +1,128 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Serve JSON API
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#serve-json-api-verification
+#
+# Acceptance Criteria:
+# - serve answers /api/ routes with JSON from the in-memory model
+# - /api/elements accepts the summary filters
+# - /api/elements/{id} lists outgoing and incoming relations of an element
+# - /api/impact/{id}, /api/traces, /api/coverage, /api/matrix and /api/lint return their reports
+# - Unknown elements and routes answer 404, unknown query parameters 400
+# - serve --watch answers 503 with the validation errors while the model is invalid
+#
+# Test Criteria:
+# - Responses are JSON with the expected status codes
+
+TEST_HOST="127.0.0.1"
+TEST_PORT=$((9000 + RANDOM % 1000))
+BASE_URL="http://$TEST_HOST:$TEST_PORT"
+SERVE_PID=""
+
+cleanup() {
+    if [ -n "$SERVE_PID" ]; then
+        kill "$SERVE_PID" 2>/dev/null || true
+        wait "$SERVE_PID" 2>/dev/null || true
+    fi
+}
+trap cleanup EXIT
+
+start_serve() {
+    "$REQVIRE_BIN" serve "$@" --host "$TEST_HOST" --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1 &
+    SERVE_PID=$!
+    for _ in $(seq 1 100); do
+        if curl -s -o /dev/null "$BASE_URL/"; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Server did not start"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+}
+
+stop_serve() {
+    kill "$SERVE_PID" 2>/dev/null || true
+    wait "$SERVE_PID" 2>/dev/null || true
+    SERVE_PID=""
+}
+
+# Waits until the route answers the status code
+wait_for_status() {
+    for _ in $(seq 1 100); do
+        if [ "$(curl -s -o /dev/null -w '%{http_code}' "$BASE_URL$1")" = "$2" ]; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Timed out waiting for $1 to answer $2"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+}
+
+# Fails unless the route answers the status code and the jq expression holds
+expect() {
+    local route="$1" status="$2" check="$3"
+    local response code body
+    response=$(curl -s -w '\n%{http_code}' "$BASE_URL$route")
+    code=$(echo "$response" | tail -n 1)
+    body=$(echo "$response" | sed '$d')
+    if [ "$code" != "$status" ] || ! echo "$body" | jq -e "$check" > /dev/null; then
+        echo "❌ FAILED: $route answered $code, expected $status with $check"
+        echo "$body"
+        exit 1
+    fi
+}
+
+cd "$TEST_DIR"
+start_serve
+
+# Test 1: Elements with summary filters
+expect "/api/elements" 200 '.global_counters.total_elements == 4'
+expect "/api/elements?filter_name=Stop" 200 '[.files[].sections[].elements[].name] == ["Remote Stop"]'
+expect "/api/elements?filter_is_not_verified" 200 '[.files[].sections[].elements[].name] | index("Remote Start") == null'
+
+# Test 2: Element with outgoing and incoming relations
+expect "/api/elements/specifications%2FRequirements.md%23remote-start" 200 \
+    '.name == "Remote Start"
+     and ([.outgoing[].target] == ["specifications/Requirements.md#remote-operation", "specifications/src/main.rs"])
+     and (.incoming == [{"type": "verify", "source": "specifications/Verifications.md#remote-start-test", "source_name": "Remote Start Test"}])'
+
+# Test 3: Reports
+expect "/api/impact/specifications%2FRequirements.md%23remote-operation" 200 '[.relations[].element.name] | sort == ["Remote Start", "Remote Stop"]'
+expect "/api/traces?filter_name=Remote%20Start%20Test" 200 '.files | has("specifications/Verifications.md")'
+expect "/api/coverage" 200 '.summary.total_verifications == 1'
+expect "/api/coverage?satisfaction" 200 'has("satisfied_leaf_requirements")'
+expect "/api/matrix?source_type=requirement&relation=verifiedBy" 200 \
+    '.matrix["specifications/Requirements.md#remote-start"] == ["specifications/Verifications.md#remote-start-test"]'
+expect "/api/lint" 200 '.auto_fixable == [] and .needs_manual_review == []'
+
+# Test 4: Errors
+expect "/api/elements/specifications%2FRequirements.md%23remote-begin" 404 '.error | contains("not found")'
+expect "/api/unknown" 404 '.error | contains("Unknown API route")'
+expect "/api/elements?filter=x" 400 '.error | contains("Unknown query parameter")'
+
+# The static site is still served next to the API
+PAGE=$(curl -s "$BASE_URL/index.html")
+if ! echo "$PAGE" | grep -q "<!DOCTYPE html>"; then
+    echo "❌ FAILED: Static site not served"
+    exit 1
+fi
+stop_serve
+
+# Test 5: In watch mode the API follows the model and reports validation errors
+start_serve --watch
+sed -i 's/Requirements.md#remote-start/Requirements.md#remote-begin/' specifications/Verifications.md
+wait_for_status "/api/lint" 503
+expect "/api/lint" 503 '.errors[0] | contains("specifications/Requirements.md#remote-begin")'
+
+git checkout -q specifications/Verifications.md
+wait_for_status "/api/lint" 200
+expect "/api/elements/specifications%2FRequirements.md%23remote-start" 200 '.incoming | length == 1'
+
+cat "${TEST_DIR}/serve_output.log" > "${TEST_DIR}/test_results.log"
+stop_serve
+
+exit 0
@@ -0,0 +1,210 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Serve Element Editor
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#serve-element-editor-verification
+#
+# Acceptance Criteria:
+# - serve --edit adds edit links to element pages and a link to the new element form
+# - The edit form shows the element's content, metadata and relations with identifier suggestions
+# - Saving updates content, metadata and relations in the file and redirects to the element page
+# - New elements are created in the chosen file and section
+# - Edits leaving the model invalid are refused with the validation errors and nothing is written
+# - Saving an element whose file changed since the form was loaded is refused with 409
+# - Submissions from other origins are refused with 403
+# - Editing works when serve runs from a subdirectory of the model
+#
+# Test Criteria:
+# - Responses carry the expected status codes and the files the expected content
+
+TEST_HOST="127.0.0.1"
+TEST_PORT=$((9000 + RANDOM % 1000))
+BASE_URL="http://$TEST_HOST:$TEST_PORT"
+ORIGIN="http://$TEST_HOST:$TEST_PORT"
+SERVE_PID=""
+
+cleanup() {
+    if [ -n "$SERVE_PID" ]; then
+        kill "$SERVE_PID" 2>/dev/null || true
+        wait "$SERVE_PID" 2>/dev/null || true
+    fi
+}
+trap cleanup EXIT
+
+start_serve() {
+    "$REQVIRE_BIN" serve "$@" --host "$TEST_HOST" --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1 &
+    SERVE_PID=$!
+    for _ in $(seq 1 100); do
+        if curl -s -o /dev/null "$BASE_URL/"; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Server did not start"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+}
+
+# Fails unless the text contains the expected string
+expect_contains() {
+    if ! grep -qF -- "$2" <<< "$1"; then
+        echo "❌ FAILED: $3"
+        echo "$1"
+        exit 1
+    fi
+}
+
+# Version of the element's file as loaded by the edit form
+form_version() {
+    curl -s "$BASE_URL/edit?element=$1" | grep -o 'name="version" value="[^"]*"' | sed 's/.*value="//;s/"$//'
+}
+
+# Posts a form and prints the status code and the Location header
+submit() {
+    local path="$1"
+    shift
+    local args=()
+    for field in "$@"; do
+        args+=(--data-urlencode "$field")
+    done
+    curl -s -o "${TEST_DIR}/response.html" -D "${TEST_DIR}/headers.txt" -w '%{http_code}' \
+        -H "Origin: $ORIGIN" "${args[@]}" "$BASE_URL$path"
+}
+
+cd "$TEST_DIR"
+start_serve --edit
+
+# Test 1: Element pages link to the editor
+PAGE=$(curl -s "$BASE_URL/specifications/Requirements.html")
+expect_contains "$PAGE" 'href="/edit/new?file=specifications%2FRequirements.md"' "New element link missing"
+expect_contains "$PAGE" '?element=' "Edit links missing"
+PAGE=$(curl -s "$BASE_URL/traces.html")
+if grep -q '?element=' <<< "$PAGE"; then
+    echo "❌ FAILED: Edit links added to a report page"
+    exit 1
+fi
+
+# Test 2: The edit form shows the element with suggestions
+REMOTE_START="specifications%2FRequirements.md%23remote-start"
+PAGE=$(curl -s "$BASE_URL/edit?element=$REMOTE_START")
+expect_contains "$PAGE" "The controller shall start the pump on a remote command.</textarea>" "Content missing from the form"
+expect_contains "$PAGE" 'value="derivedFrom specifications/Requirements.md#remote-operation"' "Relation missing from the form"
+expect_contains "$PAGE" '<option value="specifications/Requirements.md#remote-stop">Remote Stop</option>' "Identifier suggestions missing"
+
+# Test 3: Saving updates the element in its file
+VERSION=$(form_version "$REMOTE_START")
+STATUS=$(submit /edit \
+    "element=specifications/Requirements.md#remote-start" "version=$VERSION" \
+    "name=Remote Start" "type=requirement" \
+    $'content=The controller shall start the pump within 2 seconds.\r\n' \
+    "meta_key=priority" "meta_value=high" \
+    "relation=derivedFrom specifications/Requirements.md#remote-operation" \
+    "relation=satisfiedBy specifications/src/main.rs" \
+    "remove=satisfiedBy specifications/src/main.rs" \
+    "relation_type=trace" "relation_target=Remote Stop")
+if [ "$STATUS" != "303" ] || ! grep -q "Location: /specifications/Requirements.html#remote-start" "${TEST_DIR}/headers.txt"; then
+    echo "❌ FAILED: Save answered $STATUS"
+    cat "${TEST_DIR}/headers.txt" "${TEST_DIR}/response.html"
+    exit 1
+fi
+FILE=$(cat specifications/Requirements.md)
+expect_contains "$FILE" "The controller shall start the pump within 2 seconds." "Content not saved"
+expect_contains "$FILE" "  * priority: high" "Metadata not saved"
+expect_contains "$FILE" "  * trace: [Remote Stop](#remote-stop)" "Relation not added"
+if grep -q "satisfiedBy" <<< "$FILE"; then
+    echo "❌ FAILED: Relation not removed"
+    exit 1
+fi
+PAGE=$(curl -s "$BASE_URL/specifications/Requirements.html")
+expect_contains "$PAGE" "within 2 seconds" "Served page not rebuilt after saving"
+
+# Test 4: New elements are created in the chosen file and section
+STATUS=$(submit /edit/new \
+    "file=specifications/Requirements.md" "section=System Requirements" \
+    "name=Remote Reset" "type=requirement" \
+    "content=The controller shall reset the pump on a remote command." \
+    "relation_type=derivedFrom" "relation_target=specifications/Requirements.md#remote-operation")
+if [ "$STATUS" != "303" ] || ! grep -q "Location: /specifications/Requirements.html#remote-reset" "${TEST_DIR}/headers.txt"; then
+    echo "❌ FAILED: Create answered $STATUS"
+    cat "${TEST_DIR}/headers.txt" "${TEST_DIR}/response.html"
+    exit 1
+fi
+OUTPUT=$("$REQVIRE_BIN" summary --json)
+if ! echo "$OUTPUT" | jq -e '.files["specifications/Requirements.md"].sections["System Requirements"].elements
+        | any(.identifier == "specifications/Requirements.md#remote-reset" and .name == "Remote Reset")' > /dev/null; then
+    echo "❌ FAILED: New element not created in its section"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 5: Invalid edits are refused and nothing is written
+cp specifications/Requirements.md "${TEST_DIR}/before.md"
+VERSION=$(form_version "$REMOTE_START")
+STATUS=$(submit /edit \
+    "element=specifications/Requirements.md#remote-start" "version=$VERSION" \
+    "name=Remote Start" "type=requirement" "content=Changed." \
+    "relation_type=verifiedBy" "relation_target=specifications/Missing.md#nothing")
+if [ "$STATUS" != "422" ]; then
+    echo "❌ FAILED: Invalid edit answered $STATUS"
+    exit 1
+fi
+expect_contains "$(cat "${TEST_DIR}/response.html")" 'class="errors"' "Validation errors not shown"
+expect_contains "$(cat "${TEST_DIR}/response.html")" "Changed.</textarea>" "Submitted values not kept"
+if ! cmp -s specifications/Requirements.md "${TEST_DIR}/before.md"; then
+    echo "❌ FAILED: Invalid edit was written"
+    exit 1
+fi
+
+# Test 6: Files changed since the form was loaded are not overwritten
+VERSION=$(form_version "$REMOTE_START")
+sed -i 's/within 2 seconds/within 3 seconds/' specifications/Requirements.md
+STATUS=$(submit /edit \
+    "element=specifications/Requirements.md#remote-start" "version=$VERSION" \
+    "name=Remote Start" "type=requirement" "content=Overwritten.")
+if [ "$STATUS" != "409" ] || grep -q "Overwritten." specifications/Requirements.md; then
+    echo "❌ FAILED: Conflicting edit answered $STATUS"
+    exit 1
+fi
+expect_contains "$(cat "${TEST_DIR}/response.html")" "changed since this form was loaded" "Conflict not explained"
+
+# Test 7: Submissions from other origins are refused
+STATUS=$(curl -s -o /dev/null -w '%{http_code}' -H "Origin: http://attacker.example" \
+    --data-urlencode "file=specifications/Requirements.md" --data-urlencode "section=System Requirements" \
+    --data-urlencode "name=Injected" "$BASE_URL/edit/new")
+if [ "$STATUS" != "403" ] || grep -q "Injected" specifications/Requirements.md; then
+    echo "❌ FAILED: Cross-origin submission answered $STATUS"
+    exit 1
+fi
+
+# Test 8: Editing from a subdirectory reads and writes the model files
+cleanup
+SERVE_PID=""
+cd "$TEST_DIR/specifications"
+start_serve --edit
+cd "$TEST_DIR"
+VERSION=$(form_version "$REMOTE_START")
+STATUS=$(submit /edit \
+    "element=specifications/Requirements.md#remote-start" "version=$VERSION" \
+    "name=Remote Start" "type=requirement" "content=Started from a subdirectory.")
+if [ "$STATUS" != "303" ] || ! grep -q "Started from a subdirectory." specifications/Requirements.md; then
+    echo "❌ FAILED: Save from a subdirectory answered $STATUS"
+    cat "${TEST_DIR}/response.html"
+    exit 1
+fi
+if [ -e specifications/specifications ]; then
+    echo "❌ FAILED: Save from a subdirectory wrote outside the model files"
+    exit 1
+fi
+VERSION=$(form_version "$REMOTE_START")
+sed -i 's/Started from a subdirectory./Changed meanwhile./' specifications/Requirements.md
+STATUS=$(submit /edit \
+    "element=specifications/Requirements.md#remote-start" "version=$VERSION" \
+    "name=Remote Start" "type=requirement" "content=Overwritten.")
+if [ "$STATUS" != "409" ] || grep -q "Overwritten." specifications/Requirements.md; then
+    echo "❌ FAILED: Conflicting edit from a subdirectory answered $STATUS"
+    exit 1
+fi
+
+cat "${TEST_DIR}/serve_output.log" > "${TEST_DIR}/test_results.log"
+exit 0
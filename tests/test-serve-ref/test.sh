@@ -0,0 +1,169 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Serve Git Refs
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#serve-git-refs-verification
+#
+# Acceptance Criteria:
+# - serve answers /ref/<name>/ with a redirect to the resolved commit
+# - /ref/<commit>/ serves the HTML export of the model at that commit
+# - Served pages carry a ref switcher listing the working tree, branches and tags
+# - /compare shows changed elements side by side with their impact
+# - Unknown refs answer 404
+# - serve --ref serves the model at the ref and redirects other paths under it
+# - Ref exports live in unique temporary directories, at most 8 at a time
+#
+# Test Criteria:
+# - Responses carry the expected status codes and content
+
+TEST_HOST="127.0.0.1"
+TEST_PORT=$((9000 + RANDOM % 1000))
+BASE_URL="http://$TEST_HOST:$TEST_PORT"
+SERVE_PID=""
+
+cleanup() {
+    if [ -n "$SERVE_PID" ]; then
+        kill "$SERVE_PID" 2>/dev/null || true
+        wait "$SERVE_PID" 2>/dev/null || true
+    fi
+}
+trap cleanup EXIT
+
+start_serve() {
+    "$REQVIRE_BIN" serve "$@" --host "$TEST_HOST" --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1 &
+    SERVE_PID=$!
+    for _ in $(seq 1 100); do
+        if curl -s -o /dev/null "$BASE_URL/"; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Server did not start"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+}
+
+stop_serve() {
+    kill "$SERVE_PID" 2>/dev/null || true
+    wait "$SERVE_PID" 2>/dev/null || true
+    SERVE_PID=""
+}
+
+# Fails unless the route answers the status code, printing the body
+fetch() {
+    local route="$1" status="$2"
+    local response code
+    response=$(curl -s -w '\n%{http_code}' "$BASE_URL$route")
+    code=$(echo "$response" | tail -n 1)
+    if [ "$code" != "$status" ]; then
+        echo "❌ FAILED: $route answered $code, expected $status" >&2
+        echo "$response" >&2
+        exit 1
+    fi
+    echo "$response" | sed '$d'
+}
+
+# Fails unless the text contains the expected string
+expect_contains() {
+    if ! grep -qF -- "$2" <<< "$1"; then
+        echo "❌ FAILED: $3"
+        echo "$1"
+        exit 1
+    fi
+}
+
+cd "$TEST_DIR"
+git tag v1
+COMMIT=$(git rev-parse v1)
+sed -i 's/start the pump on a remote command\./start the pump on a remote command within 2 seconds./' specifications/Requirements.md
+
+start_serve
+
+# Test 1: Ref names redirect to the resolved commit
+LOCATION=$(curl -s -o /dev/null -w '%{http_code} %{redirect_url}' "$BASE_URL/ref/v1/specifications/Requirements.html")
+if [ "$LOCATION" != "302 $BASE_URL/ref/$COMMIT/specifications/Requirements.html" ]; then
+    echo "❌ FAILED: Ref name not redirected to its commit: $LOCATION"
+    exit 1
+fi
+
+# Test 2: The export at the commit is served with the ref switcher
+PAGE=$(fetch "/ref/$COMMIT/specifications/Requirements.html" 200)
+expect_contains "$PAGE" "start the pump on a remote command." "Model at the ref not served"
+if grep -q "within 2 seconds" <<< "$PAGE"; then
+    echo "❌ FAILED: Ref page shows working tree content"
+    exit 1
+fi
+expect_contains "$PAGE" 'id="reqvire-refs"' "Ref switcher missing on ref pages"
+expect_contains "$PAGE" "<option value=\"/ref/$COMMIT/\" selected>" "Served ref not selected in the switcher"
+expect_contains "$PAGE" "v1 · ${COMMIT:0:7}" "Tag missing from the switcher"
+
+# Test 3: Working tree pages carry the switcher too
+PAGE=$(fetch "/specifications/Requirements.html" 200)
+expect_contains "$PAGE" "within 2 seconds" "Working tree not served at the root"
+expect_contains "$PAGE" '<option value="/" selected>Working tree</option>' "Working tree not selected in the switcher"
+
+# Test 4: Change impact between the ref and the working tree
+PAGE=$(fetch "/compare?from=v1" 200)
+expect_contains "$PAGE" "1 changed, 0 added, 0 removed, 1 invalidated verification(s)" "Compare summary wrong"
+expect_contains "$PAGE" "<th>v1</th><th>Working tree</th>" "Compare columns wrong"
+expect_contains "$PAGE" "<pre>The controller shall start the pump on a remote command.</pre>" "Reference content missing"
+expect_contains "$PAGE" "<pre>The controller shall start the pump on a remote command within 2 seconds.</pre>" "Current content missing"
+expect_contains "$PAGE" "Remote Start Test" "Invalidated verification missing"
+
+PAGE=$(fetch "/compare?from=v1&to=v1" 200)
+expect_contains "$PAGE" "0 changed, 0 added, 0 removed" "Comparing a ref with itself reports changes"
+
+# Test 5: Unknown refs
+fetch "/ref/unknown-ref/" 404 > /dev/null
+fetch "/compare?from=unknown-ref" 404 > /dev/null
+stop_serve
+
+# Test 6: serve --ref serves the model at the ref
+start_serve --ref v1
+LOCATION=$(curl -s -o /dev/null -w '%{http_code} %{redirect_url}' "$BASE_URL/index.html")
+if [ "$LOCATION" != "302 $BASE_URL/ref/$COMMIT/index.html" ]; then
+    echo "❌ FAILED: serve --ref did not redirect to the ref: $LOCATION"
+    exit 1
+fi
+PAGE=$(fetch "/ref/$COMMIT/specifications/Requirements.html" 200)
+expect_contains "$PAGE" "start the pump on a remote command." "serve --ref did not serve the ref"
+if grep -q "Working tree" <<< "$PAGE"; then
+    echo "❌ FAILED: serve --ref offers the working tree"
+    exit 1
+fi
+cat "${TEST_DIR}/serve_output.log" > "${TEST_DIR}/test_results.log"
+stop_serve
+
+# Test 7: Unknown refs fail at startup
+set +e
+OUTPUT=$("$REQVIRE_BIN" serve --ref unknown-ref --host "$TEST_HOST" --port "$TEST_PORT" 2>&1)
+EXIT_CODE=$?
+set -e
+if [ $EXIT_CODE -eq 0 ] || ! grep -q "Unknown git reference 'unknown-ref'" <<< "$OUTPUT"; then
+    echo "❌ FAILED: serve --ref accepted an unknown ref"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 8: Least recently used ref exports are evicted, the served ref is kept
+REFS_TMP=$(mktemp -d)
+for i in $(seq 1 9); do
+    sed -i "s/within [0-9]* seconds/within $i seconds/" specifications/Requirements.md
+    git commit -q -am "Change $i"
+done
+TMPDIR="$REFS_TMP" start_serve --ref v1
+for commit in $(git rev-list -n 9 HEAD); do
+    fetch "/ref/$commit/index.html" 200 > /dev/null
+done
+EXPORTS=$(find "$REFS_TMP" -maxdepth 1 -name 'reqvire-ref-*' | wc -l)
+if [ "$EXPORTS" -ne 8 ]; then
+    echo "❌ FAILED: Expected 8 ref exports to be kept, found $EXPORTS"
+    exit 1
+fi
+PAGE=$(fetch "/ref/$COMMIT/specifications/Requirements.html" 200)
+expect_contains "$PAGE" "start the pump on a remote command." "Served ref evicted"
+stop_serve
+rm -rf "$REFS_TMP"
+
+exit 0
@@ -0,0 +1,129 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Serve Static Files
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#serve-static-files-verification
+#
+# Acceptance Criteria:
+# - Paths leaving the served directory, raw or percent-encoded, are refused
+# - Hidden files such as the export's .git directory are refused
+# - Content types follow the file extension, source files are served as text
+# - Responses carry an ETag and answer 304 to a matching If-None-Match
+# - Text responses are gzip compressed for clients accepting gzip
+# - Directories redirect to their slash-terminated URL, query strings are ignored
+# - HEAD requests get the headers of GET without a body, other methods 405
+#
+# Test Criteria:
+# - Status codes and headers match for each request
+
+TEST_HOST="127.0.0.1"
+TEST_PORT=$((9000 + RANDOM % 1000))
+BASE_URL="http://$TEST_HOST:$TEST_PORT"
+SERVE_PID=""
+
+cleanup() {
+    if [ -n "$SERVE_PID" ]; then
+        kill "$SERVE_PID" 2>/dev/null || true
+        wait "$SERVE_PID" 2>/dev/null || true
+    fi
+}
+trap cleanup EXIT
+
+# Fails unless the request answers the status code
+expect_status() {
+    local expected="$1"
+    shift
+    local code
+    code=$(curl -s -o /dev/null -w '%{http_code}' "$@")
+    if [ "$code" != "$expected" ]; then
+        echo "❌ FAILED: curl $* answered $code instead of $expected"
+        exit 1
+    fi
+}
+
+# Prints the value of a response header
+header_of() {
+    local name="$1"
+    shift
+    curl -s -o /dev/null -D - "$@" | grep -i "^$name:" | cut -d: -f2- | tr -d ' \r'
+}
+
+cd "$TEST_DIR"
+"$REQVIRE_BIN" serve --host "$TEST_HOST" --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1 &
+SERVE_PID=$!
+for _ in $(seq 1 100); do
+    if curl -s -o /dev/null "$BASE_URL/"; then
+        break
+    fi
+    sleep 0.1
+done
+
+# Test 1: Paths outside the served directory are refused
+expect_status 403 --path-as-is "$BASE_URL/../../../../etc/passwd"
+expect_status 403 "$BASE_URL/%2e%2e/%2e%2e/%2e%2e/etc/passwd"
+expect_status 403 "$BASE_URL/specifications/..%2F..%2F..%2Fetc%2Fpasswd"
+expect_status 403 "$BASE_URL/.git/config"
+expect_status 404 "$BASE_URL/missing.html"
+
+# Test 2: Content types
+for check in "index.html text/html;charset=utf-8" \
+             "matrix.svg image/svg+xml" \
+             "specifications/src/main.rs text/plain;charset=utf-8"; do
+    set -- $check
+    TYPE=$(header_of "Content-Type" "$BASE_URL/$1")
+    if [ "$TYPE" != "$2" ]; then
+        echo "❌ FAILED: $1 served as '$TYPE' instead of '$2'"
+        exit 1
+    fi
+done
+
+# Test 3: ETag revalidation
+ETAG=$(header_of "ETag" "$BASE_URL/index.html")
+if [ -z "$ETAG" ]; then
+    echo "❌ FAILED: No ETag header"
+    exit 1
+fi
+expect_status 304 -H "If-None-Match: $ETAG" "$BASE_URL/index.html"
+expect_status 200 -H 'If-None-Match: "other"' "$BASE_URL/index.html"
+
+# Test 4: gzip compression
+ENCODING=$(header_of "Content-Encoding" -H "Accept-Encoding: gzip" "$BASE_URL/index.html")
+if [ "$ENCODING" != "gzip" ]; then
+    echo "❌ FAILED: Response not gzip compressed"
+    exit 1
+fi
+PAGE=$(curl -s --compressed "$BASE_URL/index.html")
+if ! echo "$PAGE" | grep -q "<!DOCTYPE html>"; then
+    echo "❌ FAILED: Compressed page does not decompress to HTML"
+    exit 1
+fi
+if [ -n "$(header_of "Content-Encoding" "$BASE_URL/index.html")" ]; then
+    echo "❌ FAILED: Response compressed for a client not accepting gzip"
+    exit 1
+fi
+
+# Test 5: Directories and query strings
+expect_status 301 "$BASE_URL/specifications"
+LOCATION=$(header_of "Location" "$BASE_URL/specifications?x=1")
+if [ "$LOCATION" != "/specifications/?x=1" ]; then
+    echo "❌ FAILED: Directory redirected to '$LOCATION'"
+    exit 1
+fi
+expect_status 200 "$BASE_URL/?x=1"
+expect_status 200 "$BASE_URL/specifications/Requirements.html?x=1#remote-start"
+
+# Test 6: HEAD and other methods
+GET_LENGTH=$(header_of "Content-Length" "$BASE_URL/index.html")
+HEAD_RESPONSE=$(curl -s -I "$BASE_URL/index.html")
+if ! echo "$HEAD_RESPONSE" | grep -q "200 OK" \
+    || ! echo "$HEAD_RESPONSE" | grep -qi "^Content-Length: $GET_LENGTH"; then
+    echo "❌ FAILED: HEAD response does not match GET"
+    echo "$HEAD_RESPONSE"
+    exit 1
+fi
+expect_status 405 -X POST "$BASE_URL/index.html"
+
+cat "${TEST_DIR}/serve_output.log" > "${TEST_DIR}/test_results.log"
+
+exit 0
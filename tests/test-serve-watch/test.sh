@@ -0,0 +1,101 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Serve Watch Mode
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#serve-watch-verification
+#
+# Acceptance Criteria:
+# - serve --watch serves pages with a live reload script
+# - Connected pages receive a reload event through Server-Sent Events after each change
+# - Edited content is served after the rebuild
+# - Validation errors are shown as an overlay page while the server keeps running
+# - The overlay disappears once the model is valid again
+#
+# Test Criteria:
+# - Server stays up across invalid and valid model states
+# - Event stream carries one reload event per change
+
+TEST_HOST="127.0.0.1"
+TEST_PORT=$((9000 + RANDOM % 1000))
+BASE_URL="http://$TEST_HOST:$TEST_PORT"
+SERVE_PID=""
+EVENTS_PID=""
+
+cleanup() {
+    for pid in $EVENTS_PID $SERVE_PID; do
+        kill "$pid" 2>/dev/null || true
+        wait "$pid" 2>/dev/null || true
+    done
+}
+trap cleanup EXIT
+
+# Waits until the file contains the text the given number of times
+wait_for() {
+    for _ in $(seq 1 100); do
+        if [ "$(grep -cF "$2" "$1" || true)" -ge "$3" ]; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Timed out waiting for '$2' (x$3) in $1"
+    cat "$1"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+}
+
+cd "$TEST_DIR"
+"$REQVIRE_BIN" serve --watch --host "$TEST_HOST" --port "$TEST_PORT" > "${TEST_DIR}/serve_output.log" 2>&1 &
+SERVE_PID=$!
+wait_for "${TEST_DIR}/serve_output.log" "Watching" 1
+
+# Test 1: Pages carry the live reload script
+if ! curl -s "$BASE_URL/" | grep -q 'new EventSource("/__reqvire/events")'; then
+    echo "❌ FAILED: Live reload script not injected"
+    exit 1
+fi
+
+curl -sN "$BASE_URL/__reqvire/events" > "${TEST_DIR}/events.log" &
+EVENTS_PID=$!
+wait_for "${TEST_DIR}/events.log" ": connected" 1
+
+# Test 2: Edited content is served after a reload event
+sed -i 's/start the pump on a remote command/start the main pump on a remote command/' "${TEST_DIR}/specifications/Requirements.md"
+wait_for "${TEST_DIR}/events.log" "event: reload" 1
+
+if ! curl -s "$BASE_URL/specifications/Requirements.html" | grep -q "start the main pump"; then
+    echo "❌ FAILED: Edited content not served after rebuild"
+    exit 1
+fi
+
+# Test 3: Validation errors are shown as an overlay
+sed -i 's/Requirements.md#remote-start/Requirements.md#remote-begin/' "${TEST_DIR}/specifications/Verifications.md"
+wait_for "${TEST_DIR}/events.log" "event: reload" 2
+
+PAGE=$(curl -s "$BASE_URL/specifications/Requirements.html")
+if ! echo "$PAGE" | grep -q 'id="reqvire-errors"' \
+    || ! echo "$PAGE" | grep -q "missing target 'specifications/Requirements.md#remote-begin'" \
+    || ! echo "$PAGE" | grep -q "start the main pump"; then
+    echo "❌ FAILED: Overlay with errors not shown over the last valid export"
+    echo "$PAGE"
+    exit 1
+fi
+
+if ! kill -0 "$SERVE_PID" 2>/dev/null; then
+    echo "❌ FAILED: Server stopped on validation errors"
+    cat "${TEST_DIR}/serve_output.log"
+    exit 1
+fi
+
+# Test 4: The overlay disappears when the model is fixed
+git checkout -q specifications/Verifications.md
+wait_for "${TEST_DIR}/events.log" "event: reload" 3
+
+if curl -s "$BASE_URL/specifications/Requirements.html" | grep -q 'id="reqvire-errors"'; then
+    echo "❌ FAILED: Overlay still shown after fixing the model"
+    exit 1
+fi
+
+cat "${TEST_DIR}/serve_output.log" > "${TEST_DIR}/test_results.log"
+
+exit 0
@@ -0,0 +1,141 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Tabular Output
+# --------------------------------------
+# Satisfies: specifications/Verifications/ReportsTests.md#tabular-output-test
+#
+# Acceptance Criteria:
+# - summary, matrix, coverage and traces support --format csv and --format tsv
+# - Rows are elements with identifier, name, file, section and type columns
+# - Summary tables include metadata columns and one column per relation type
+# - Fields are quoted according to RFC 4180 and records end with CRLF
+#
+# Test Criteria:
+# - Commands exit with success (0) return code
+# - Headers list columns in a stable order
+# - Fields containing delimiters or quotes are quoted with doubled quotes
+# - Unsupported formats and --format combined with --json are rejected
+
+strip_cr() {
+    tr -d '\r'
+}
+
+# Test 1: Summary as CSV with all metadata keys
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" summary --format csv 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: summary --format csv exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(printf "%s\n" "$OUTPUT" | grep -c $'\r$')" -ne 5 ]; then
+    echo "❌ FAILED: Expected 5 CRLF-terminated records in summary CSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+HEADER=$(printf "%s\n" "$OUTPUT" | head -1 | strip_cr)
+EXPECTED_HEADER="identifier,name,file,section,type,owner,priority,derive,derivedFrom,satisfiedBy,verifiedBy,verify"
+if [ "$HEADER" != "$EXPECTED_HEADER" ]; then
+    echo "❌ FAILED: Unexpected summary header: $HEADER"
+    exit 1
+fi
+
+if ! printf "%s\n" "$OUTPUT" | strip_cr | grep -qF 'specifications/Requirements.md#reporting-export,"Reporting, Export",'; then
+    echo "❌ FAILED: Name containing a comma is not quoted"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! printf "%s\n" "$OUTPUT" | strip_cr | grep -qF ',"Team ""Alpha""",high,'; then
+    echo "❌ FAILED: Metadata containing quotes is not escaped"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! printf "%s\n" "$OUTPUT" | strip_cr | grep -qF 'specifications/Requirements.md#quoted-metadata-handling; specifications/Requirements.md#plain-export'; then
+    echo "❌ FAILED: Relation list missing from summary CSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 2: Summary with selected metadata keys
+HEADER=$(cd "$TEST_DIR" && "$REQVIRE_BIN" summary --format csv --metadata-key priority | head -1 | strip_cr)
+if [ "$HEADER" != "identifier,name,file,section,type,priority,derive,derivedFrom,satisfiedBy,verifiedBy,verify" ]; then
+    echo "❌ FAILED: Unexpected header with selected metadata keys: $HEADER"
+    exit 1
+fi
+
+# Test 3: Matrix as CSV with cell markers
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --format csv 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: matrix --format csv exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(printf "%s\n" "$OUTPUT" | head -1 | strip_cr)" != "identifier,name,file,section,type,Verified,specifications/Requirements.md#export-test" ]; then
+    echo "❌ FAILED: Unexpected matrix header"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! printf "%s\n" "$OUTPUT" | strip_cr | grep -qx 'specifications/Requirements.md#plain-export,Plain Export,specifications/Requirements.md,Requirements,requirement,yes,X'; then
+    echo "❌ FAILED: Verified matrix row missing cell marker"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 4: Coverage as CSV
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --format csv | strip_cr)
+if ! echo "$OUTPUT" | grep -qx 'leaf-requirement,specifications/Requirements.md#quoted-metadata-handling,Quoted Metadata Handling,specifications/Requirements.md,Requirements,requirement,unverified,'; then
+    echo "❌ FAILED: Unverified leaf requirement row missing from coverage CSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q '^test-verification,specifications/Requirements.md#export-test,.*,satisfied,specifications/test.sh$'; then
+    echo "❌ FAILED: Satisfied test verification row missing from coverage CSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 5: Traces as TSV
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" traces --format tsv | strip_cr)
+if [ "$(echo "$OUTPUT" | head -1 | cut -f1,5,9)" != "$(printf 'identifier\ttype\ttotal_requirements_in_tree')" ]; then
+    echo "❌ FAILED: Unexpected traces TSV header"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if [ "$(echo "$OUTPUT" | sed -n 2p | cut -f7)" != "specifications/Requirements.md#plain-export; specifications/Requirements.md#reporting-export" ]; then
+    echo "❌ FAILED: Traced requirements missing from traces TSV"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 6: Invalid combinations are rejected
+set +e
+(cd "$TEST_DIR" && "$REQVIRE_BIN" summary --format xlsx > /dev/null 2>&1)
+UNSUPPORTED_EXIT=$?
+(cd "$TEST_DIR" && "$REQVIRE_BIN" matrix --format csv --json > /dev/null 2>&1)
+CONFLICT_EXIT=$?
+set -e
+
+if [ $UNSUPPORTED_EXIT -eq 0 ] || [ $CONFLICT_EXIT -eq 0 ]; then
+    echo "❌ FAILED: Unsupported format or --format with --json should fail"
+    exit 1
+fi
+
+exit 0
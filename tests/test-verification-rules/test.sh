@@ -0,0 +1,105 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Verification Method Rules
+# --------------------------------------
+# Satisfies: specifications/Verifications/ReportsTests.md#verification-method-rules-test
+#
+# Acceptance Criteria:
+# - Requirements matching a rule by metadata are checked against the required verification types
+# - Requirements matching a rule by element type are checked against the required verification types
+# - Requirements verified by all required verification types are not listed
+# - Unmet rules are listed per requirement with the missing verification types in text and JSON output
+# - Invalid rules cause the coverage command to fail
+#
+# Test Criteria:
+# - Command exits with success (0) return code
+# - Text output lists unmet rules per requirement
+# - JSON summary contains verification rule counts
+# - Invalid rule configuration exits with non-zero code
+
+# Test 1: Text output lists unmet rules
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage 2>&1)
+EXIT_CODE=$?
+set -e
+
+printf "%s\n" "$OUTPUT" > "${TEST_DIR}/test_results.log"
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage command exited with code $EXIT_CODE"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "## Unmet Verification Rules"; then
+    echo "❌ FAILED: Text output missing 'Unmet Verification Rules' section"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "high-criticality: missing analysis-verification"; then
+    echo "❌ FAILED: Missing unmet high-criticality rule for partially verified requirement"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+if ! echo "$OUTPUT" | grep -q "user-demonstration: missing demonstration-verification"; then
+    echo "❌ FAILED: Missing unmet user-demonstration rule for user requirement"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+# Test 2: JSON output contains unmet rules per requirement
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage --json 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -ne 0 ]; then
+    echo "❌ FAILED: coverage --json command exited with code $EXIT_CODE"
+    exit 1
+fi
+
+RULES=$(echo "$OUTPUT" | jq '.summary.verification_rules')
+CHECKED=$(echo "$OUTPUT" | jq '.summary.requirements_checked_by_rules')
+UNMET=$(echo "$OUTPUT" | jq '.summary.requirements_with_unmet_rules')
+
+if [ "$RULES" -ne 2 ] || [ "$CHECKED" -ne 4 ] || [ "$UNMET" -ne 2 ]; then
+    echo "❌ FAILED: Expected 2 rules, 4 checked and 2 unmet requirements, got $RULES, $CHECKED and $UNMET"
+    exit 1
+fi
+
+UNMET_IDS=$(echo "$OUTPUT" | jq -r '[.unmet_verification_rules.files[][] | .identifier] | sort | join(",")')
+EXPECTED_IDS="specifications/Requirements.md#critical-partially-verified,specifications/Requirements.md#reporting-workflow"
+if [ "$UNMET_IDS" != "$EXPECTED_IDS" ]; then
+    echo "❌ FAILED: Unexpected requirements with unmet rules: $UNMET_IDS"
+    exit 1
+fi
+
+MISSING=$(echo "$OUTPUT" | jq -r '.unmet_verification_rules.files[][] | select(.name == "Critical Partially Verified") | .unmet_rules[0].missing | join(",")')
+if [ "$MISSING" != "analysis-verification" ]; then
+    echo "❌ FAILED: Expected missing 'analysis-verification', got '$MISSING'"
+    exit 1
+fi
+
+# Test 3: Invalid rule configuration is rejected
+cat > "${TEST_DIR}/reqvire.yaml" << 'YAML'
+verification_rules:
+  - name: invalid
+    requires:
+      - unit-test
+YAML
+
+set +e
+OUTPUT=$(cd "$TEST_DIR" && "$REQVIRE_BIN" coverage 2>&1)
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -eq 0 ]; then
+    echo "❌ FAILED: coverage should fail for invalid verification rules"
+    echo "$OUTPUT"
+    exit 1
+fi
+
+exit 0
@@ -0,0 +1,135 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Test: Watch Mode
+# --------------------------------------
+# Satisfies: specifications/Verifications/Misc.md#watch-mode-verification
+#
+# Acceptance Criteria:
+# - watch validates the model once and then waits for changes
+# - Saving a markdown file re-validates it and reports the changed file
+# - Validation errors are printed when introduced and cleared when fixed
+# - Removing a file referenced by a relation is reported, other files are not
+# - New files in new directories are picked up
+# - watch lint prints the lint report after each change
+#
+# Test Criteria:
+# - Output lists each change with its elapsed time followed by the result
+
+OUTPUT_FILE="${TEST_DIR}/watch_output.log"
+WATCH_PID=""
+
+cleanup() {
+    if [ -n "$WATCH_PID" ]; then
+        kill "$WATCH_PID" 2>/dev/null || true
+        wait "$WATCH_PID" 2>/dev/null || true
+    fi
+}
+trap cleanup EXIT
+
+start_watch() {
+    : > "$OUTPUT_FILE"
+    (cd "$TEST_DIR" && exec "$REQVIRE_BIN" watch "$@" > "$OUTPUT_FILE" 2>&1) &
+    WATCH_PID=$!
+    wait_for "Watching" 1
+}
+
+stop_watch() {
+    kill "$WATCH_PID" 2>/dev/null || true
+    wait "$WATCH_PID" 2>/dev/null || true
+    WATCH_PID=""
+}
+
+# Waits until the output contains the text the given number of times
+wait_for() {
+    for _ in $(seq 1 50); do
+        if [ "$(grep -cF "$1" "$OUTPUT_FILE" || true)" -ge "$2" ]; then
+            return 0
+        fi
+        sleep 0.1
+    done
+    echo "❌ FAILED: Timed out waiting for '$1' (x$2)"
+    cat "$OUTPUT_FILE"
+    exit 1
+}
+
+# Test 1: Validation errors appear and clear as files change
+start_watch
+
+if ! grep -q "✅ No validation issues found" "$OUTPUT_FILE"; then
+    echo "❌ FAILED: Initial validation not printed"
+    cat "$OUTPUT_FILE"
+    exit 1
+fi
+
+sed -i 's/Requirements.md#remote-start/Requirements.md#remote-begin/' "${TEST_DIR}/specifications/Verifications.md"
+wait_for "Changed: specifications/Verifications.md" 1
+wait_for "missing target 'specifications/Requirements.md#remote-begin'" 1
+
+(cd "$TEST_DIR" && git checkout -q specifications/Verifications.md)
+wait_for "Changed: specifications/Verifications.md" 2
+wait_for "✅ No validation issues found" 2
+
+# A new file in a new directory derives from an existing requirement
+mkdir -p "${TEST_DIR}/specifications/Extra"
+cat > "${TEST_DIR}/specifications/Extra/Extra.md" << 'EOF'
+# Extra
+
+## Requirements
+
+### Remote Reset
+
+The controller shall reset the pump on a remote command.
+
+#### Relations
+  * derivedFrom: [Remote Operation](../Requirements.md#remote-operation)
+  * satisfiedBy: [main.rs](../src/main.rs)
+---
+EOF
+wait_for "Changed: specifications/Extra/Extra.md" 1
+wait_for "✅ No validation issues found" 3
+
+# Files that are not relation targets do not trigger re-validation
+echo "notes" > "${TEST_DIR}/specifications/notes.txt"
+mv "${TEST_DIR}/specifications/src/main.rs" "${TEST_DIR}/main.rs.bak"
+wait_for "Changed: specifications/src/main.rs" 1
+wait_for "missing target 'specifications/src/main.rs'" 1
+
+if grep -q "notes.txt" "$OUTPUT_FILE"; then
+    echo "❌ FAILED: Unrelated file reported as a change"
+    cat "$OUTPUT_FILE"
+    exit 1
+fi
+
+mv "${TEST_DIR}/main.rs.bak" "${TEST_DIR}/specifications/src/main.rs"
+wait_for "Changed: specifications/src/main.rs" 2
+wait_for "✅ No validation issues found" 4
+
+if ! grep -Eq "Changed: specifications/Verifications.md \([0-9]+ ms\)" "$OUTPUT_FILE"; then
+    echo "❌ FAILED: Elapsed time not reported"
+    cat "$OUTPUT_FILE"
+    exit 1
+fi
+
+cat "$OUTPUT_FILE" > "${TEST_DIR}/test_results.log"
+stop_watch
+
+# Test 2: Lint mode reports issues for the changed model
+start_watch lint
+
+if ! grep -q "✅ No lint issues found" "$OUTPUT_FILE"; then
+    echo "❌ FAILED: Initial lint report not printed"
+    cat "$OUTPUT_FILE"
+    exit 1
+fi
+
+# Deriving from the requirement and its parent makes the parent link redundant
+sed -i 's|  \* satisfiedBy: \[main.rs\](../src/main.rs)|  * derivedFrom: [Remote Start](../Requirements.md#remote-start)\n  * satisfiedBy: [main.rs](../src/main.rs)|' \
+    "${TEST_DIR}/specifications/Extra/Extra.md"
+wait_for "Changed: specifications/Extra/Extra.md" 1
+wait_for "Redundant" 1
+
+cat "$OUTPUT_FILE" >> "${TEST_DIR}/test_results.log"
+stop_watch
+
+exit 0